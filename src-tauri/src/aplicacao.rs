@@ -8,13 +8,29 @@ use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
+mod argumentos_lancamento;
+mod armazenamento_compartilhado;
+mod atualizacao_conteudo;
+mod atualizacao_modpack;
 mod bootstrap;
+mod clonagem_instancia;
+mod configuracao_lancamento;
 mod conteudo_instalado;
+mod exportacao_modpack;
+pub(crate) mod extracao_segura;
+mod identificacao_conteudo;
 pub(crate) mod importacao_exportacao;
+mod importacao_launchers;
+mod instalador_forge;
 mod instancias_basicas;
 mod instancias_criacao;
 mod lancamento_jogo;
 mod mods_conteudo;
+pub(crate) mod registro_conteudo;
+mod resolucao_dependencias;
+mod troca_versao;
+pub(crate) mod verificacao_arquivos;
+mod versoes_loader;
 
 // Constantes das APIs
 const CURSEFORGE_API_KEY_FALLBACK: &str =
@@ -164,5 +180,8 @@ fn detectar_caminho_jogo(base: &std::path::Path) -> std::path::PathBuf {
         .unwrap_or_else(|| base.to_path_buf())
 }
 
+pub(crate) use atualizacao_modpack::{
+    alinhar_loader_com_modpack, conferir_versao_minecraft, gravar_instancia,
+};
 pub use bootstrap::run;
 pub(crate) use lancamento_jogo::timestamp_atual_segundos;
@@ -0,0 +1,634 @@
+use super::*;
+use crate::plataforma::Plataforma;
+use base64::Engine;
+use std::collections::HashMap;
+
+/// Entrada de `arguments.jvm`/`arguments.game`: texto simples ou objeto
+/// condicionado por `rules`, com `value` único ou em lista.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ArgumentoManifesto {
+    Simples(String),
+    Condicional {
+        #[serde(default)]
+        rules: Vec<crate::launcher::Rule>,
+        value: ValorArgumento,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ValorArgumento {
+    Unico(String),
+    Lista(Vec<String>),
+}
+
+/// Flags usadas em `rules[].features` dos manifestos modernos.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RecursosLancamento {
+    pub usuario_demo: bool,
+    pub resolucao_personalizada: bool,
+    pub suporte_quick_play: bool,
+    pub quick_play_singleplayer: bool,
+    pub quick_play_multiplayer: bool,
+    pub quick_play_realms: bool,
+}
+
+impl RecursosLancamento {
+    fn ativo(&self, nome: &str) -> bool {
+        match nome {
+            "is_demo_user" => self.usuario_demo,
+            "has_custom_resolution" => self.resolucao_personalizada,
+            "has_quick_plays_support" => self.suporte_quick_play,
+            "is_quick_play_singleplayer" => self.quick_play_singleplayer,
+            "is_quick_play_multiplayer" => self.quick_play_multiplayer,
+            "is_quick_play_realms" => self.quick_play_realms,
+            _ => false,
+        }
+    }
+}
+
+/// Expande uma lista de argumentos do manifesto, mantendo só os objetos cujas
+/// `rules` valem para a plataforma e os recursos ativos.
+pub(crate) fn avaliar_argumentos(
+    lista: &serde_json::Value,
+    plataforma: &Plataforma,
+    recursos: &RecursosLancamento,
+) -> Vec<String> {
+    let Some(itens) = lista.as_array() else {
+        return Vec::new();
+    };
+
+    let mut argumentos = Vec::new();
+    for item in itens {
+        match serde_json::from_value::<ArgumentoManifesto>(item.clone()) {
+            Ok(ArgumentoManifesto::Simples(arg)) => argumentos.push(arg),
+            Ok(ArgumentoManifesto::Condicional { rules, value }) => {
+                if !plataforma
+                    .regras_permitem_com_recursos(Some(&rules), &|nome| recursos.ativo(nome))
+                {
+                    continue;
+                }
+                match value {
+                    ValorArgumento::Unico(arg) => argumentos.push(arg),
+                    ValorArgumento::Lista(args) => argumentos.extend(args),
+                }
+            }
+            Err(e) => eprintln!("[Launch] Argumento de manifesto ignorado ({}): {}", e, item),
+        }
+    }
+    argumentos
+}
+
+/// Argumentos do jogo ainda com placeholders: `arguments.game` nos manifestos
+/// modernos ou `minecraftArguments` nos legados (até 1.12.2).
+pub(crate) fn argumentos_jogo_brutos(
+    details: &VersionDetail,
+    plataforma: &Plataforma,
+    recursos: &RecursosLancamento,
+) -> Vec<String> {
+    if let Some(game) = details.arguments.as_ref().and_then(|args| args.get("game")) {
+        return avaliar_argumentos(game, plataforma, recursos);
+    }
+
+    let mut argumentos: Vec<String> = details
+        .minecraft_arguments
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|arg| arg.to_string())
+        .collect();
+
+    // Manifestos legados não declaram a resolução; o launcher oficial a acrescenta.
+    if recursos.resolucao_personalizada {
+        argumentos.extend(
+            [
+                "--width",
+                "${resolution_width}",
+                "--height",
+                "${resolution_height}",
+            ]
+            .map(String::from),
+        );
+    }
+    argumentos
+}
+
+/// Argumentos da JVM declarados pelo manifesto (vanilla + loader), sem placeholders.
+pub(crate) fn argumentos_jvm_brutos(
+    details: &VersionDetail,
+    plataforma: &Plataforma,
+    recursos: &RecursosLancamento,
+) -> Vec<String> {
+    details
+        .arguments
+        .as_ref()
+        .and_then(|args| args.get("jvm"))
+        .map(|jvm| avaliar_argumentos(jvm, plataforma, recursos))
+        .unwrap_or_default()
+}
+
+/// Valores dos placeholders `${...}` usados nos argumentos.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Placeholders {
+    valores: HashMap<&'static str, String>,
+}
+
+impl Placeholders {
+    pub(crate) fn definir(&mut self, nome: &'static str, valor: impl Into<String>) {
+        self.valores.insert(nome, valor.into());
+    }
+
+    /// Substitui os placeholders conhecidos em uma única passada, de forma que
+    /// valores contendo `${` (ex.: caminhos) não sejam reinterpretados.
+    /// Placeholders desconhecidos permanecem intactos.
+    pub(crate) fn substituir(&self, arg: &str) -> String {
+        let mut resultado = String::with_capacity(arg.len());
+        let mut restante = arg;
+
+        while let Some(inicio) = restante.find("${") {
+            resultado.push_str(&restante[..inicio]);
+            let depois = &restante[inicio + 2..];
+            let Some(fim) = depois.find('}') else {
+                resultado.push_str(&restante[inicio..]);
+                return resultado;
+            };
+
+            let nome = &depois[..fim];
+            match self.valores.get(nome) {
+                Some(valor) => resultado.push_str(valor),
+                None => resultado.push_str(&restante[inicio..inicio + 3 + fim]),
+            }
+            restante = &depois[fim + 1..];
+        }
+
+        resultado.push_str(restante);
+        resultado
+    }
+}
+
+/// Troca cada `${library_directory}/<biblioteca>` pelo caminho que o classpath
+/// usa (`resolver_biblioteca`): a cópia local da instância, se existir, ou a do
+/// armazenamento. O placeholder sozinho segue a substituição normal.
+pub(crate) fn resolver_bibliotecas_no_argumento(
+    arg: &str,
+    instance_path: &std::path::Path,
+) -> String {
+    const PREFIXO: &str = "${library_directory}/";
+    let mut resultado = String::with_capacity(arg.len());
+    let mut restante = arg;
+
+    while let Some(inicio) = restante.find(PREFIXO) {
+        resultado.push_str(&restante[..inicio]);
+        let depois = &restante[inicio + PREFIXO.len()..];
+        let fim = depois
+            .find(|c: char| matches!(c, '$' | ',' | ';' | ':') || c.is_whitespace())
+            .unwrap_or(depois.len());
+        let caminho =
+            super::armazenamento_compartilhado::resolver_biblioteca(instance_path, &depois[..fim]);
+        resultado.push_str(&caminho.to_string_lossy());
+        restante = &depois[fim..];
+    }
+
+    resultado.push_str(restante);
+    resultado
+}
+
+/// Dados da sessão usados para preencher os placeholders do launch.
+pub(crate) struct DadosLancamento<'a> {
+    pub conta: &'a crate::launcher::MinecraftAccount,
+    pub details: &'a VersionDetail,
+    pub pasta_jogo: &'a std::path::Path,
+    pub pasta_assets: &'a std::path::Path,
+    pub pasta_bibliotecas: &'a std::path::Path,
+    pub pasta_natives: &'a std::path::Path,
+    pub classpath: &'a str,
+    pub largura: u32,
+    pub altura: u32,
+    pub servidor_quick_play: Option<&'a str>,
+}
+
+/// XUID da conta Xbox, presente como claim `xuid` no token JWT do Minecraft.
+pub(crate) fn xuid_do_token(access_token: &str) -> Option<String> {
+    let payload = access_token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    match &claims["xuid"] {
+        serde_json::Value::String(xuid) => Some(xuid.clone()),
+        serde_json::Value::Number(xuid) => Some(xuid.to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn placeholders_lancamento(dados: &DadosLancamento<'_>) -> Placeholders {
+    let caminho = |path: &std::path::Path| path.to_string_lossy().to_string();
+    let conta = dados.conta;
+    let mut placeholders = Placeholders::default();
+
+    placeholders.definir("auth_player_name", &conta.name);
+    placeholders.definir("auth_uuid", &conta.uuid);
+    placeholders.definir("auth_access_token", &conta.access_token);
+    placeholders.definir(
+        "auth_session",
+        format!("token:{}:{}", conta.access_token, conta.uuid),
+    );
+    placeholders.definir(
+        "auth_xuid",
+        xuid_do_token(&conta.access_token).unwrap_or_else(|| "0".to_string()),
+    );
+    placeholders.definir("clientid", crate::auth_sisu::MICROSOFT_CLIENT_ID);
+    placeholders.definir("client_id", crate::auth_sisu::MICROSOFT_CLIENT_ID);
+    placeholders.definir("user_type", "msa");
+    placeholders.definir("user_properties", "{}");
+
+    placeholders.definir("version_name", &dados.details.id);
+    placeholders.definir("version_type", &dados.details.r#type);
+    placeholders.definir("game_directory", caminho(dados.pasta_jogo));
+    placeholders.definir("assets_root", caminho(dados.pasta_assets));
+    placeholders.definir("game_assets", caminho(dados.pasta_assets));
+    placeholders.definir("assets_index_name", &dados.details.asset_index.id);
+    placeholders.definir("resolution_width", dados.largura.to_string());
+    placeholders.definir("resolution_height", dados.altura.to_string());
+
+    placeholders.definir(
+        "quickPlayPath",
+        caminho(&dados.pasta_jogo.join("quickPlay").join("log.json")),
+    );
+    if let Some(servidor) = dados.servidor_quick_play {
+        placeholders.definir("quickPlayMultiplayer", servidor);
+    }
+
+    placeholders.definir("natives_directory", caminho(dados.pasta_natives));
+    placeholders.definir("library_directory", caminho(dados.pasta_bibliotecas));
+    placeholders.definir("classpath", dados.classpath);
+    placeholders.definir(
+        "classpath_separator",
+        crate::plataforma::SEPARADOR_CLASSPATH,
+    );
+    placeholders.definir("launcher_name", "DomeLauncher");
+    placeholders.definir("launcher_version", env!("CARGO_PKG_VERSION"));
+
+    placeholders
+}
+
+/// Argumentos da JVM do manifesto já resolvidos, sem os parâmetros que o
+/// launcher controla diretamente (memória, classpath e `java.library.path`).
+pub(crate) fn coletar_argumentos_jvm_manifesto(
+    brutos: &[String],
+    placeholders: &Placeholders,
+    classpath: &str,
+) -> Vec<String> {
+    let mut args_jvm = Vec::new();
+    let mut ignorar_proximo_classpath = false;
+
+    for arg_raw in brutos {
+        let arg_raw_trim = arg_raw.trim();
+        if ignorar_proximo_classpath {
+            ignorar_proximo_classpath = false;
+            continue;
+        }
+
+        // O classpath é montado manualmente pelo launcher.
+        if arg_raw_trim == "-cp" || arg_raw_trim == "-classpath" {
+            ignorar_proximo_classpath = true;
+            continue;
+        }
+        if arg_raw_trim == "${classpath}" {
+            continue;
+        }
+
+        let mut arg = placeholders.substituir(arg_raw).trim().to_string();
+
+        if arg.starts_with("-DFabricMcEmu=") {
+            let valor = arg["-DFabricMcEmu=".len()..].trim();
+            arg = format!("-DFabricMcEmu={}", valor);
+        }
+
+        // O Forge espera ignorar o jar vanilla do jogo pelo nome. Como usamos client.jar,
+        // precisamos adicioná-lo ao ignoreList para evitar colisão de módulos Java 17.
+        if arg.starts_with("-DignoreList=") && !arg.contains("client.jar") {
+            arg.push_str(",client.jar,client");
+        }
+
+        // Ignorar parâmetros já controlados pelo launcher.
+        if arg.is_empty()
+            || arg == "-cp"
+            || arg == "-classpath"
+            || arg == classpath
+            || arg.starts_with("-Djava.library.path=")
+            || arg.starts_with("-Xmx")
+            || arg.starts_with("-Xms")
+        {
+            continue;
+        }
+
+        // Se ainda restou placeholder não resolvido, evita quebrar o launch.
+        if arg.contains("${") {
+            continue;
+        }
+
+        args_jvm.push(arg);
+    }
+
+    args_jvm
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::plataforma::SistemaOperacional;
+
+    /// Trecho `arguments` do manifesto oficial da 1.20.4.
+    const ARGUMENTOS_1_20_4: &str = r#"{
+        "game": [
+            "--username", "${auth_player_name}", "--version", "${version_name}",
+            "--gameDir", "${game_directory}", "--assetsDir", "${assets_root}",
+            "--assetIndex", "${assets_index_name}", "--uuid", "${auth_uuid}",
+            "--accessToken", "${auth_access_token}", "--clientId", "${clientid}",
+            "--xuid", "${auth_xuid}", "--userType", "${user_type}",
+            "--versionType", "${version_type}",
+            {"rules": [{"action": "allow", "features": {"is_demo_user": true}}], "value": "--demo"},
+            {"rules": [{"action": "allow", "features": {"has_custom_resolution": true}}],
+             "value": ["--width", "${resolution_width}", "--height", "${resolution_height}"]},
+            {"rules": [{"action": "allow", "features": {"has_quick_plays_support": true}}],
+             "value": ["--quickPlayPath", "${quickPlayPath}"]},
+            {"rules": [{"action": "allow", "features": {"is_quick_play_singleplayer": true}}],
+             "value": ["--quickPlaySingleplayer", "${quickPlaySingleplayer}"]},
+            {"rules": [{"action": "allow", "features": {"is_quick_play_multiplayer": true}}],
+             "value": ["--quickPlayMultiplayer", "${quickPlayMultiplayer}"]},
+            {"rules": [{"action": "allow", "features": {"is_quick_play_realms": true}}],
+             "value": ["--quickPlayRealms", "${quickPlayRealms}"]}
+        ],
+        "jvm": [
+            {"rules": [{"action": "allow", "os": {"name": "osx"}}], "value": ["-XstartOnFirstThread"]},
+            {"rules": [{"action": "allow", "os": {"name": "windows"}}],
+             "value": "-XX:HeapDumpPath=MojangTricksIntelDriversForPerformance_javaw.exe_minecraft.exe.heapdump"},
+            {"rules": [{"action": "allow", "os": {"arch": "x86"}}], "value": "-Xss1M"},
+            "-Djava.library.path=${natives_directory}",
+            "-Djna.tmpdir=${natives_directory}",
+            "-Dorg.lwjgl.system.SharedLibraryExtractPath=${natives_directory}",
+            "-Dio.netty.native.workdir=${natives_directory}",
+            "-Dminecraft.launcher.brand=${launcher_name}",
+            "-Dminecraft.launcher.version=${launcher_version}",
+            "-cp", "${classpath}"
+        ]
+    }"#;
+
+    /// Regra de versão do SO presente nos manifestos 1.13–1.18.
+    const JVM_WINDOWS_10: &str = r#"[
+        {"rules": [{"action": "allow", "os": {"name": "windows", "version": "^10\\."}}],
+         "value": ["-Dos.name=Windows 10", "-Dos.version=10.0"]}
+    ]"#;
+
+    fn manifesto(arguments: Option<&str>, minecraft_arguments: Option<&str>) -> VersionDetail {
+        let mut json = serde_json::json!({
+            "id": "1.20.4",
+            "type": "release",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [],
+            "assets": "12",
+            "assetIndex": { "id": "12", "sha1": "", "size": 0, "url": "" },
+            "downloads": { "client": { "sha1": "", "size": 0, "url": "" } }
+        });
+        if let Some(arguments) = arguments {
+            json["arguments"] = serde_json::from_str(arguments).unwrap();
+        }
+        if let Some(legado) = minecraft_arguments {
+            json["minecraftArguments"] = serde_json::json!(legado);
+        }
+        serde_json::from_value(json).expect("manifesto de teste deve ser válido")
+    }
+
+    fn plataforma(os: SistemaOperacional, arch: &'static str, versao: &str) -> Plataforma {
+        Plataforma {
+            os,
+            arch,
+            versao_os: versao.to_string(),
+        }
+    }
+
+    fn token_com_xuid(xuid: &str) -> String {
+        let codificar = |json: serde_json::Value| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json.to_string())
+        };
+        format!(
+            "{}.{}.assinatura",
+            codificar(serde_json::json!({ "alg": "HS256" })),
+            codificar(serde_json::json!({ "xuid": xuid, "sub": "teste" }))
+        )
+    }
+
+    #[test]
+    fn avalia_features_e_expande_listas_de_valores() {
+        let details = manifesto(Some(ARGUMENTOS_1_20_4), None);
+        let linux = plataforma(SistemaOperacional::Linux, "x86_64", "6.1");
+        let recursos = RecursosLancamento {
+            resolucao_personalizada: true,
+            quick_play_multiplayer: true,
+            ..RecursosLancamento::default()
+        };
+
+        let game = argumentos_jogo_brutos(&details, &linux, &recursos);
+        assert!(!game.contains(&"--demo".to_string()));
+        assert!(!game.contains(&"--quickPlayPath".to_string()));
+        assert!(!game.contains(&"--quickPlayRealms".to_string()));
+        let largura = game.iter().position(|arg| arg == "--width").unwrap();
+        assert_eq!(game[largura + 1], "${resolution_width}");
+        let quick_play = game
+            .iter()
+            .position(|arg| arg == "--quickPlayMultiplayer")
+            .unwrap();
+        assert_eq!(game[quick_play + 1], "${quickPlayMultiplayer}");
+
+        let demo = RecursosLancamento {
+            usuario_demo: true,
+            ..RecursosLancamento::default()
+        };
+        let game_demo = argumentos_jogo_brutos(&details, &linux, &demo);
+        assert!(game_demo.contains(&"--demo".to_string()));
+        assert!(!game_demo.contains(&"--width".to_string()));
+    }
+
+    #[test]
+    fn avalia_argumentos_jvm_por_sistema_e_versao() {
+        let details = manifesto(Some(ARGUMENTOS_1_20_4), None);
+        let recursos = RecursosLancamento::default();
+
+        let mac = argumentos_jvm_brutos(
+            &details,
+            &plataforma(SistemaOperacional::MacOs, "arm64", "14.2"),
+            &recursos,
+        );
+        assert_eq!(mac[0], "-XstartOnFirstThread");
+        assert!(!mac.iter().any(|arg| arg.starts_with("-XX:HeapDumpPath")));
+
+        let windows = argumentos_jvm_brutos(
+            &details,
+            &plataforma(SistemaOperacional::Windows, "x86_64", "10.0.19045"),
+            &recursos,
+        );
+        assert!(windows[0].starts_with("-XX:HeapDumpPath="));
+        assert!(!windows.contains(&"-Xss1M".to_string()));
+
+        let lista_windows_10: serde_json::Value = serde_json::from_str(JVM_WINDOWS_10).unwrap();
+        let win10 = plataforma(SistemaOperacional::Windows, "x86_64", "10.0.19045");
+        let win7 = plataforma(SistemaOperacional::Windows, "x86_64", "6.1.7601");
+        assert_eq!(
+            avaliar_argumentos(&lista_windows_10, &win10, &recursos),
+            vec!["-Dos.name=Windows 10", "-Dos.version=10.0"]
+        );
+        assert!(avaliar_argumentos(&lista_windows_10, &win7, &recursos).is_empty());
+    }
+
+    #[test]
+    fn substitui_todos_os_placeholders_documentados() {
+        let details = manifesto(Some(ARGUMENTOS_1_20_4), None);
+        let conta = crate::launcher::MinecraftAccount {
+            id: "uuid-teste".to_string(),
+            uuid: "uuid-teste".to_string(),
+            name: "Jogador".to_string(),
+            access_token: token_com_xuid("2535405290000000"),
+            refresh_token: None,
+            expires_at: None,
+            token_type: "Bearer".to_string(),
+        };
+        let raiz = std::path::Path::new("/jogos/instancia");
+        let natives = raiz.join("bin").join("natives");
+        let assets = raiz.join("assets");
+        let bibliotecas = raiz.join("libraries");
+        let placeholders = placeholders_lancamento(&DadosLancamento {
+            conta: &conta,
+            details: &details,
+            pasta_jogo: raiz,
+            pasta_assets: &assets,
+            pasta_bibliotecas: &bibliotecas,
+            pasta_natives: &natives,
+            classpath: "a.jar:b.jar",
+            largura: 1280,
+            altura: 720,
+            servidor_quick_play: Some("mc.exemplo.com:25565"),
+        });
+        let recursos = RecursosLancamento {
+            resolucao_personalizada: true,
+            quick_play_multiplayer: true,
+            ..RecursosLancamento::default()
+        };
+        let linux = plataforma(SistemaOperacional::Linux, "x86_64", "6.1");
+
+        let game: Vec<String> = argumentos_jogo_brutos(&details, &linux, &recursos)
+            .iter()
+            .map(|arg| placeholders.substituir(arg))
+            .collect();
+        let jvm: Vec<String> = argumentos_jvm_brutos(&details, &linux, &recursos)
+            .iter()
+            .map(|arg| placeholders.substituir(arg))
+            .collect();
+
+        for arg in game.iter().chain(jvm.iter()) {
+            assert!(!arg.contains("${"), "placeholder não resolvido: {}", arg);
+        }
+        assert!(game.contains(&"2535405290000000".to_string()));
+        assert!(game.contains(&crate::auth_sisu::MICROSOFT_CLIENT_ID.to_string()));
+        assert!(game.contains(&"mc.exemplo.com:25565".to_string()));
+        assert!(game.contains(&"1280".to_string()));
+        assert!(jvm.contains(&"-Dminecraft.launcher.brand=DomeLauncher".to_string()));
+        assert!(jvm.contains(&format!("-Djna.tmpdir={}", natives.to_string_lossy())));
+        assert!(jvm.contains(&"a.jar:b.jar".to_string()));
+    }
+
+    #[test]
+    fn manifesto_legado_recebe_resolucao_e_preserva_desconhecidos() {
+        let details = manifesto(
+            None,
+            Some(
+                "--username ${auth_player_name} --session ${auth_session} --extra ${desconhecido}",
+            ),
+        );
+        let recursos = RecursosLancamento {
+            resolucao_personalizada: true,
+            ..RecursosLancamento::default()
+        };
+        let game = argumentos_jogo_brutos(
+            &details,
+            &plataforma(SistemaOperacional::Linux, "x86_64", ""),
+            &recursos,
+        );
+        assert_eq!(
+            &game[game.len() - 4..],
+            [
+                "--width",
+                "${resolution_width}",
+                "--height",
+                "${resolution_height}"
+            ]
+        );
+
+        let mut placeholders = Placeholders::default();
+        placeholders.definir("auth_player_name", "${classpath}");
+        assert_eq!(
+            placeholders.substituir("${auth_player_name}-${desconhecido}-${incompleto"),
+            "${classpath}-${desconhecido}-${incompleto"
+        );
+    }
+
+    #[test]
+    fn bibliotecas_do_argumento_seguem_a_copia_local_da_instancia() {
+        let instancia = std::env::temp_dir().join(format!("dome_args_{}", uuid::Uuid::new_v4()));
+        let local = instancia.join("libraries/cpw/mods/bootstraplauncher/1.1.2/b.jar");
+        std::fs::create_dir_all(local.parent().unwrap()).unwrap();
+        std::fs::write(&local, "jar").unwrap();
+        let store = super::super::armazenamento_compartilhado::pasta_bibliotecas_store();
+
+        let arg = resolver_bibliotecas_no_argumento(
+            "${library_directory}/cpw/mods/bootstraplauncher/1.1.2/b.jar${classpath_separator}${library_directory}/org/ow2/asm/asm/9.5/asm-9.5.jar",
+            &instancia,
+        );
+        assert_eq!(
+            arg,
+            format!(
+                "{}${{classpath_separator}}{}",
+                local.to_string_lossy(),
+                store
+                    .join("org/ow2/asm/asm/9.5/asm-9.5.jar")
+                    .to_string_lossy()
+            )
+        );
+        assert_eq!(
+            resolver_bibliotecas_no_argumento(
+                "-DlibraryDirectory=${library_directory}",
+                &instancia
+            ),
+            "-DlibraryDirectory=${library_directory}"
+        );
+
+        let _ = std::fs::remove_dir_all(&instancia);
+    }
+
+    #[test]
+    fn filtra_parametros_controlados_pelo_launcher() {
+        let brutos: Vec<String> = [
+            "-Djava.library.path=${natives_directory}",
+            "-cp",
+            "${classpath}",
+            "-DignoreList=bootstraplauncher,${version_name}.jar",
+            "-Dsem.valor=${inexistente}",
+            "-Xss2M",
+        ]
+        .map(String::from)
+        .to_vec();
+        let mut placeholders = Placeholders::default();
+        placeholders.definir("natives_directory", "/natives");
+        placeholders.definir("classpath", "a.jar");
+        placeholders.definir("version_name", "1.20.1");
+
+        assert_eq!(
+            coletar_argumentos_jvm_manifesto(&brutos, &placeholders, "a.jar"),
+            vec![
+                "-DignoreList=bootstraplauncher,1.20.1.jar,client.jar,client",
+                "-Xss2M"
+            ]
+        );
+    }
+}
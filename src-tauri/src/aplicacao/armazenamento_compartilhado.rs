@@ -0,0 +1,567 @@
+use super::*;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Arquivo em cada instância com o que ela usa do armazenamento compartilhado.
+pub(crate) const ARQUIVO_REFERENCIAS: &str = ".referencias_store.json";
+/// Marca a migração única das instâncias antigas (pastas próprias de libraries/assets).
+const MARCADOR_MIGRACAO: &str = ".migracao_instancias_v1";
+
+/// Serializa mudanças no armazenamento (adoção, migração e limpeza).
+static TRAVA_STORE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Raiz do armazenamento compartilhado:
+/// `libraries/` (layout Maven), `assets/objects` (por hash), `assets/indexes`
+/// e `clients/<sha1>/client.jar`.
+pub(crate) fn pasta_store() -> PathBuf {
+    crate::plataforma::pasta_dados_launcher()
+        .map(|pasta| pasta.join("store"))
+        .unwrap_or_else(|| PathBuf::from("store"))
+}
+
+pub(crate) fn pasta_bibliotecas_store() -> PathBuf {
+    pasta_store().join("libraries")
+}
+
+pub(crate) fn pasta_assets_store() -> PathBuf {
+    pasta_store().join("assets")
+}
+
+fn caminho_client_em(store: &Path, sha1: &str) -> PathBuf {
+    // O nome `client.jar` é mantido porque o Forge o ignora pelo nome (`-DignoreList`).
+    store.join("clients").join(sha1).join("client.jar")
+}
+
+/// Caminho efetivo de uma biblioteca: a cópia local da instância (gerada por
+/// instaladores de loader ainda não adotada) ou a do armazenamento.
+pub(crate) fn resolver_biblioteca(instance_path: &Path, relativo: &str) -> PathBuf {
+    let local = instance_path.join("libraries").join(relativo);
+    if local.is_file() {
+        return local;
+    }
+    pasta_bibliotecas_store().join(relativo)
+}
+
+/// `bin/client.jar` local (jar modificado ou sem hash) ou o client do armazenamento.
+pub(crate) fn resolver_client_jar(instance_path: &Path, details: &VersionDetail) -> PathBuf {
+    let local = instance_path.join("bin").join("client.jar");
+    let sha1 = details.downloads.client.sha1.trim();
+    if local.is_file() || sha1.is_empty() {
+        return local;
+    }
+    caminho_client_em(&pasta_store(), sha1)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+struct ReferenciasStore {
+    /// Bibliotecas do manifesto atual (substituídas a cada download).
+    bibliotecas: BTreeSet<String>,
+    /// Bibliotecas geradas por instaladores e adotadas pelo armazenamento.
+    adotadas: BTreeSet<String>,
+    indices_assets: BTreeSet<String>,
+    clients: BTreeSet<String>,
+}
+
+fn ler_referencias(instance_path: &Path) -> Option<ReferenciasStore> {
+    let conteudo = std::fs::read_to_string(instance_path.join(ARQUIVO_REFERENCIAS)).ok()?;
+    serde_json::from_str(&conteudo).ok()
+}
+
+fn salvar_referencias(instance_path: &Path, referencias: &ReferenciasStore) -> Result<(), String> {
+    let conteudo = serde_json::to_string_pretty(referencias)
+        .map_err(|e| format!("Erro ao serializar referências: {}", e))?;
+    super::verificacao_arquivos::escrever_atomico(
+        &instance_path.join(ARQUIVO_REFERENCIAS),
+        conteudo.as_bytes(),
+    )
+}
+
+fn relativo_a(base: &Path, caminho: &Path) -> Option<String> {
+    caminho
+        .strip_prefix(base)
+        .ok()
+        .map(|relativo| relativo.to_string_lossy().replace('\\', "/"))
+}
+
+/// Registra o que o manifesto atual da instância usa do armazenamento.
+pub(crate) fn registrar_referencias_manifesto(
+    instance_path: &Path,
+    details: &VersionDetail,
+) -> Result<(), String> {
+    let store_bibliotecas = pasta_bibliotecas_store();
+    let mut referencias = ler_referencias(instance_path).unwrap_or_default();
+
+    referencias.bibliotecas =
+        super::verificacao_arquivos::arquivos_client_e_bibliotecas(instance_path, details)
+            .iter()
+            .filter_map(|arquivo| relativo_a(&store_bibliotecas, &arquivo.caminho))
+            .collect();
+    referencias.indices_assets = BTreeSet::from([details.asset_index.id.clone()]);
+    referencias.clients = Some(details.downloads.client.sha1.trim().to_string())
+        .filter(|sha1| !sha1.is_empty())
+        .into_iter()
+        .collect();
+
+    salvar_referencias(instance_path, &referencias)
+}
+
+fn mesmo_conteudo(a: &Path, b: &Path) -> bool {
+    let tamanho = |caminho: &Path| std::fs::metadata(caminho).map(|m| m.len()).ok();
+    if tamanho(a).is_none() || tamanho(a) != tamanho(b) {
+        return false;
+    }
+    match (
+        super::verificacao_arquivos::sha1_arquivo(a),
+        super::verificacao_arquivos::sha1_arquivo(b),
+    ) {
+        (Ok(hash_a), Ok(hash_b)) => hash_a == hash_b,
+        _ => false,
+    }
+}
+
+/// Move o arquivo para o armazenamento. Se o destino já existir, a cópia local
+/// é descartada quando `conteudo_confiavel` (nome derivado do hash) ou quando
+/// o conteúdo é idêntico; do contrário ela é mantida e `false` é retornado.
+fn mover_para_store(origem: &Path, destino: &Path, conteudo_confiavel: bool) -> bool {
+    if destino.is_file() {
+        if conteudo_confiavel || mesmo_conteudo(origem, destino) {
+            let _ = std::fs::remove_file(origem);
+            return true;
+        }
+        return false;
+    }
+
+    if let Some(pasta) = destino.parent() {
+        if std::fs::create_dir_all(pasta).is_err() {
+            return false;
+        }
+    }
+    if std::fs::rename(origem, destino).is_ok() {
+        return true;
+    }
+    // Pastas em discos diferentes: copiar e remover a origem.
+    let temporario = destino.with_extension(format!("{}.part", uuid::Uuid::new_v4().simple()));
+    let copiado = std::fs::copy(origem, &temporario)
+        .and_then(|_| std::fs::rename(&temporario, destino))
+        .is_ok();
+    if copiado {
+        let _ = std::fs::remove_file(origem);
+    } else {
+        let _ = std::fs::remove_file(&temporario);
+    }
+    copiado
+}
+
+fn arquivos_recursivos(pasta: &Path) -> Vec<PathBuf> {
+    let mut arquivos = Vec::new();
+    let mut pendentes = vec![pasta.to_path_buf()];
+    while let Some(atual) = pendentes.pop() {
+        let Ok(entradas) = std::fs::read_dir(&atual) else {
+            continue;
+        };
+        for entrada in entradas.flatten() {
+            let Ok(tipo) = entrada.file_type() else {
+                continue;
+            };
+            if tipo.is_dir() {
+                pendentes.push(entrada.path());
+            } else if tipo.is_file() {
+                arquivos.push(entrada.path());
+            }
+        }
+    }
+    arquivos
+}
+
+/// Remove pastas vazias deixadas para trás, incluindo a própria raiz.
+fn remover_pastas_vazias(pasta: &Path) {
+    if let Ok(entradas) = std::fs::read_dir(pasta) {
+        for entrada in entradas.flatten() {
+            if entrada
+                .file_type()
+                .map(|tipo| tipo.is_dir())
+                .unwrap_or(false)
+            {
+                remover_pastas_vazias(&entrada.path());
+            }
+        }
+    }
+    let _ = std::fs::remove_dir(pasta);
+}
+
+/// Adota as bibliotecas locais da instância (ex.: geradas pelo instalador do
+/// Forge) no armazenamento e as registra nas referências da instância.
+fn adotar_bibliotecas_em(instance_path: &Path, store: &Path) -> Vec<String> {
+    let pasta_local = instance_path.join("libraries");
+    let mut adotadas = Vec::new();
+
+    for arquivo in arquivos_recursivos(&pasta_local) {
+        let Some(relativo) = relativo_a(&pasta_local, &arquivo) else {
+            continue;
+        };
+        if relativo.ends_with(".part") {
+            continue;
+        }
+        if mover_para_store(&arquivo, &store.join("libraries").join(&relativo), false) {
+            adotadas.push(relativo);
+        }
+    }
+
+    remover_pastas_vazias(&pasta_local);
+    adotadas
+}
+
+/// Move assets e client jar de uma instância antiga para o armazenamento.
+fn migrar_assets_e_client_em(instance_path: &Path, store: &Path, sha1_client: Option<&str>) {
+    let assets_local = instance_path.join("assets");
+
+    let objetos_local = assets_local.join("objects");
+    for arquivo in arquivos_recursivos(&objetos_local) {
+        if let Some(relativo) = relativo_a(&objetos_local, &arquivo) {
+            mover_para_store(
+                &arquivo,
+                &store.join("assets").join("objects").join(relativo),
+                true,
+            );
+        }
+    }
+
+    let indices_local = assets_local.join("indexes");
+    for arquivo in arquivos_recursivos(&indices_local) {
+        if let Some(relativo) = relativo_a(&indices_local, &arquivo) {
+            mover_para_store(
+                &arquivo,
+                &store.join("assets").join("indexes").join(relativo),
+                true,
+            );
+        }
+    }
+    remover_pastas_vazias(&objetos_local);
+    remover_pastas_vazias(&indices_local);
+    let _ = std::fs::remove_dir(&assets_local);
+
+    // Só o client original (hash do manifesto) vai para o armazenamento.
+    let client_local = instance_path.join("bin").join("client.jar");
+    if let Some(sha1) = sha1_client.filter(|sha1| !sha1.is_empty()) {
+        let hash_local = super::verificacao_arquivos::sha1_arquivo(&client_local).ok();
+        if hash_local.as_deref() == Some(sha1) {
+            mover_para_store(&client_local, &caminho_client_em(store, sha1), true);
+        }
+    }
+}
+
+fn registrar_adotadas(instance_path: &Path, adotadas: Vec<String>) -> Result<(), String> {
+    if adotadas.is_empty() {
+        return Ok(());
+    }
+    let mut referencias = ler_referencias(instance_path).unwrap_or_default();
+    referencias.adotadas.extend(adotadas);
+    salvar_referencias(instance_path, &referencias)
+}
+
+/// Adota bibliotecas que instaladores de loader deixaram na pasta da instância.
+pub(crate) fn adotar_bibliotecas_locais(instance_path: &Path) -> Result<(), String> {
+    let _trava = TRAVA_STORE
+        .lock()
+        .map_err(|_| "Armazenamento ocupado.".to_string())?;
+    let adotadas = adotar_bibliotecas_em(instance_path, &pasta_store());
+    if !adotadas.is_empty() {
+        println!(
+            "[Store] {} biblioteca(s) local(is) movida(s) para o armazenamento compartilhado.",
+            adotadas.len()
+        );
+    }
+    registrar_adotadas(instance_path, adotadas)
+}
+
+fn migrar_instancia_em(instance_path: &Path, store: &Path) -> Result<(), String> {
+    let details = std::fs::read_to_string(instance_path.join("version_manifest.json"))
+        .ok()
+        .and_then(|conteudo| serde_json::from_str::<VersionDetail>(&conteudo).ok());
+
+    let adotadas = adotar_bibliotecas_em(instance_path, store);
+    migrar_assets_e_client_em(
+        instance_path,
+        store,
+        details
+            .as_ref()
+            .map(|details| details.downloads.client.sha1.as_str()),
+    );
+    registrar_adotadas(instance_path, adotadas)?;
+
+    if let Some(details) = details {
+        let mut referencias = ler_referencias(instance_path).unwrap_or_default();
+        referencias
+            .indices_assets
+            .insert(details.asset_index.id.clone());
+        referencias
+            .clients
+            .insert(details.downloads.client.sha1.clone());
+        salvar_referencias(instance_path, &referencias)?;
+    }
+    Ok(())
+}
+
+/// Migração única: deduplica libraries, assets e client jar das instâncias
+/// criadas antes do armazenamento compartilhado.
+pub(crate) fn migrar_instancias_para_store(instances_path: &Path) {
+    let store = pasta_store();
+    let marcador = store.join(MARCADOR_MIGRACAO);
+    if marcador.exists() {
+        return;
+    }
+    let Ok(_trava) = TRAVA_STORE.lock() else {
+        return;
+    };
+
+    let Ok(entradas) = std::fs::read_dir(instances_path) else {
+        return;
+    };
+    let mut falhas = 0;
+    for entrada in entradas.flatten() {
+        let instance_path = entrada.path();
+        if !instance_path.join("instance.json").exists() {
+            continue;
+        }
+        if let Err(erro) = migrar_instancia_em(&instance_path, &store) {
+            falhas += 1;
+            eprintln!(
+                "[Store] Aviso ao migrar {}: {}",
+                instance_path.display(),
+                erro
+            );
+        }
+    }
+
+    if falhas == 0 {
+        let _ = std::fs::create_dir_all(&store);
+        let _ = std::fs::write(&marcador, chrono::Utc::now().to_rfc3339());
+        println!("[Store] Migração das instâncias para o armazenamento compartilhado concluída.");
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioLimpezaStore {
+    pub arquivos_removidos: usize,
+    pub bytes_liberados: u64,
+    pub instancias_consideradas: usize,
+}
+
+/// Caminhos (relativos ao armazenamento) referenciados pelas instâncias.
+fn caminhos_referenciados(
+    instancias: &[PathBuf],
+    store: &Path,
+) -> Result<std::collections::HashSet<String>, String> {
+    let mut referenciados = std::collections::HashSet::new();
+
+    for instance_path in instancias {
+        let referencias = ler_referencias(instance_path).ok_or_else(|| {
+            format!(
+                "A instância {} ainda não registrou o que usa do armazenamento. Abra ou verifique a instância antes de limpar.",
+                instance_path.display()
+            )
+        })?;
+        // O backup de uma troca de versão precisa continuar restaurável.
+        let backup_versao =
+            ler_referencias(&instance_path.join(super::troca_versao::PASTA_BACKUP_VERSAO))
+                .into_iter();
+
+        for referencias in std::iter::once(referencias).chain(backup_versao) {
+            for relativo in referencias.bibliotecas.iter().chain(&referencias.adotadas) {
+                referenciados.insert(format!("libraries/{}", relativo));
+            }
+            for sha1 in &referencias.clients {
+                referenciados.insert(format!("clients/{}/client.jar", sha1));
+            }
+            for indice in &referencias.indices_assets {
+                let relativo_indice = format!("assets/indexes/{}.json", indice);
+                let Ok(conteudo) = std::fs::read_to_string(store.join(&relativo_indice)) else {
+                    continue;
+                };
+                referenciados.insert(relativo_indice);
+                for objeto in super::verificacao_arquivos::arquivos_assets_em(
+                    &store.join("assets").join("objects"),
+                    &conteudo,
+                )? {
+                    if let Some(relativo) = relativo_a(store, &objeto.caminho) {
+                        referenciados.insert(relativo);
+                    }
+                }
+            }
+        }
+    }
+    Ok(referenciados)
+}
+
+fn coletar_lixo_em(instancias: &[PathBuf], store: &Path) -> Result<RelatorioLimpezaStore, String> {
+    let referenciados = caminhos_referenciados(instancias, store)?;
+    let mut relatorio = RelatorioLimpezaStore {
+        instancias_consideradas: instancias.len(),
+        ..Default::default()
+    };
+
+    for subpasta in ["libraries", "assets", "clients"] {
+        let raiz = store.join(subpasta);
+        for arquivo in arquivos_recursivos(&raiz) {
+            let Some(relativo) = relativo_a(store, &arquivo) else {
+                continue;
+            };
+            if referenciados.contains(&relativo) {
+                continue;
+            }
+            let tamanho = std::fs::metadata(&arquivo).map(|m| m.len()).unwrap_or(0);
+            if std::fs::remove_file(&arquivo).is_ok() {
+                relatorio.arquivos_removidos += 1;
+                relatorio.bytes_liberados += tamanho;
+            }
+        }
+        remover_pastas_vazias(&raiz);
+    }
+    Ok(relatorio)
+}
+
+/// Remove do armazenamento compartilhado os arquivos que nenhuma instância usa.
+/// Instalações em andamento baixam para o armazenamento antes de gravar
+/// `instance.json` e as referências, por isso a limpeza espera que terminem.
+#[tauri::command]
+pub(crate) async fn limpar_armazenamento_compartilhado(
+    state: State<'_, LauncherState>,
+) -> Result<RelatorioLimpezaStore, String> {
+    let em_andamento = state
+        .tarefas
+        .listar()
+        .iter()
+        .any(|tarefa| tarefa.estado == crate::comandos::tarefas::EstadoTarefa::EmAndamento);
+    if em_andamento {
+        return Err(
+            "Aguarde as instalações em andamento terminarem antes de limpar o armazenamento."
+                .to_string(),
+        );
+    }
+
+    let instancias: Vec<PathBuf> = std::fs::read_dir(&state.instances_path)
+        .map_err(|e| format!("Erro ao listar instâncias: {}", e))?
+        .flatten()
+        .map(|entrada| entrada.path())
+        .filter(|caminho| {
+            caminho.join("instance.json").exists() || caminho.join(ARQUIVO_REFERENCIAS).exists()
+        })
+        .collect();
+
+    tokio::task::spawn_blocking(move || {
+        let _trava = TRAVA_STORE
+            .lock()
+            .map_err(|_| "Armazenamento ocupado.".to_string())?;
+        let relatorio = coletar_lixo_em(&instancias, &pasta_store())?;
+        println!(
+            "[Store] Limpeza concluída: {} arquivo(s), {} bytes liberados.",
+            relatorio.arquivos_removidos, relatorio.bytes_liberados
+        );
+        Ok(relatorio)
+    })
+    .await
+    .map_err(|e| format!("Erro ao limpar armazenamento: {}", e))?
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn pasta_temporaria(nome: &str) -> PathBuf {
+        let pasta = std::env::temp_dir().join(format!("dome-{}-{}", nome, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        pasta
+    }
+
+    fn escrever(caminho: &Path, conteudo: &str) {
+        std::fs::create_dir_all(caminho.parent().unwrap()).unwrap();
+        std::fs::write(caminho, conteudo).unwrap();
+    }
+
+    #[test]
+    fn adota_bibliotecas_e_mantem_conflitos_locais() {
+        let raiz = pasta_temporaria("store-adocao");
+        let store = raiz.join("store");
+        let instancia_a = raiz.join("a");
+        let instancia_b = raiz.join("b");
+
+        escrever(&instancia_a.join("libraries/net/forge/forge.jar"), "forge");
+        escrever(&instancia_b.join("libraries/net/forge/forge.jar"), "forge");
+        escrever(&instancia_b.join("libraries/org/lib/lib.jar"), "diferente");
+        escrever(&store.join("libraries/org/lib/lib.jar"), "original");
+
+        assert_eq!(
+            adotar_bibliotecas_em(&instancia_a, &store),
+            vec!["net/forge/forge.jar"]
+        );
+        let mut adotadas_b = adotar_bibliotecas_em(&instancia_b, &store);
+        adotadas_b.sort();
+        assert_eq!(adotadas_b, vec!["net/forge/forge.jar"]);
+
+        assert!(store.join("libraries/net/forge/forge.jar").is_file());
+        assert!(!instancia_a.join("libraries").exists());
+        // Conteúdo divergente continua na instância.
+        assert_eq!(
+            std::fs::read_to_string(instancia_b.join("libraries/org/lib/lib.jar")).unwrap(),
+            "diferente"
+        );
+
+        let _ = std::fs::remove_dir_all(raiz);
+    }
+
+    #[test]
+    fn limpeza_remove_apenas_arquivos_sem_referencia() {
+        let raiz = pasta_temporaria("store-limpeza");
+        let store = raiz.join("store");
+        let instancia = raiz.join("instancia");
+        std::fs::create_dir_all(&instancia).unwrap();
+
+        let hash_usado = "aa11111111111111111111111111111111111111";
+        let hash_orfao = "bb22222222222222222222222222222222222222";
+        escrever(
+            &store.join("assets/indexes/12.json"),
+            &serde_json::json!({ "objects": { "som.ogg": { "hash": hash_usado, "size": 3 } } })
+                .to_string(),
+        );
+        escrever(
+            &store.join(format!("assets/objects/aa/{}", hash_usado)),
+            "som",
+        );
+        escrever(
+            &store.join(format!("assets/objects/bb/{}", hash_orfao)),
+            "velho",
+        );
+        escrever(&store.join("libraries/usada/a.jar"), "a");
+        escrever(&store.join("libraries/adotada/b.jar"), "b");
+        escrever(&store.join("libraries/orfa/c.jar"), "c");
+        escrever(&store.join("clients/abc/client.jar"), "client");
+
+        let referencias = ReferenciasStore {
+            bibliotecas: BTreeSet::from(["usada/a.jar".to_string()]),
+            adotadas: BTreeSet::from(["adotada/b.jar".to_string()]),
+            indices_assets: BTreeSet::from(["12".to_string()]),
+            clients: BTreeSet::new(),
+        };
+        salvar_referencias(&instancia, &referencias).unwrap();
+
+        let relatorio = coletar_lixo_em(std::slice::from_ref(&instancia), &store).unwrap();
+        assert_eq!(relatorio.arquivos_removidos, 3);
+        assert!(store.join("libraries/usada/a.jar").is_file());
+        assert!(store.join("libraries/adotada/b.jar").is_file());
+        assert!(!store.join("libraries/orfa").exists());
+        assert!(!store.join("clients").exists());
+        assert!(store
+            .join(format!("assets/objects/aa/{}", hash_usado))
+            .is_file());
+        assert!(!store.join("assets/objects/bb").exists());
+
+        // Sem referências registradas, a limpeza é recusada.
+        let sem_referencias = raiz.join("nova");
+        std::fs::create_dir_all(&sem_referencias).unwrap();
+        assert!(coletar_lixo_em(&[instancia, sem_referencias], &store).is_err());
+
+        let _ = std::fs::remove_dir_all(raiz);
+    }
+}
@@ -0,0 +1,862 @@
+use super::mods_conteudo::{
+    arquivo_principal_modrinth, baixar_arquivo_para_pasta, loaders_compativeis,
+    pasta_destino_conteudo, versao_modrinth_compativel,
+};
+use super::registro_conteudo::{
+    alterar_lockfile, carregar_lockfile, nome_habilitado, EntradaConteudo,
+};
+use super::resolucao_dependencias::ContextoResolucao;
+use super::*;
+use crate::comandos::tarefas::{Tarefa, MENSAGEM_TAREFA_CANCELADA};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub(crate) const PASTA_BACKUPS_CONTEUDO: &str = ".backups_conteudo";
+pub(crate) const PASTA_PREPARACAO_ATUALIZACAO: &str = ".atualizacao_conteudo";
+const ARQUIVO_MANIFESTO_BACKUP: &str = "backup.json";
+/// Backups de atualização mantidos por instância; os mais antigos são descartados.
+const LIMITE_BACKUPS_CONTEUDO: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AtualizacaoDisponivel {
+    /// Nome do arquivo atual em disco (pode terminar em `.disabled`).
+    pub arquivo: String,
+    pub tipo_conteudo: String,
+    pub plataforma: ModPlatform,
+    pub project_id: String,
+    pub nome: String,
+    pub versao_atual_id: Option<String>,
+    pub versao_atual: Option<String>,
+    pub nova_versao_id: String,
+    pub nova_versao: String,
+    pub novo_arquivo: String,
+    pub download_url: String,
+    pub sha1: Option<String>,
+    pub tamanho: Option<u64>,
+    pub changelog: Option<String>,
+    pub publicado_em: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioAtualizacoes {
+    pub atualizacoes: Vec<AtualizacaoDisponivel>,
+    pub fixados: Vec<String>,
+    pub sem_origem: usize,
+    pub erros: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ItemBackup {
+    tipo_conteudo: String,
+    /// Pasta relativa à instância (`mods`, `resourcepacks`, `shaderpacks`).
+    pasta: String,
+    arquivo_antigo: String,
+    arquivo_novo: String,
+    entrada_antiga: Option<EntradaConteudo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ManifestoBackup {
+    id: String,
+    criado_em: String,
+    itens: Vec<ItemBackup>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResumoBackupConteudo {
+    pub id: String,
+    pub criado_em: String,
+    pub arquivos: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResultadoAtualizacaoConteudo {
+    pub backup_id: String,
+    pub atualizados: Vec<String>,
+}
+
+/// Arquivo novo já baixado e verificado, pronto para substituir o atual.
+struct TrocaPreparada {
+    tipo_conteudo: String,
+    pasta: String,
+    arquivo_antigo: String,
+    arquivo_novo: String,
+    preparado: PathBuf,
+    entrada_nova: EntradaConteudo,
+}
+
+fn id_numerico(id: Option<&str>) -> u64 {
+    id.and_then(|id| id.parse().ok()).unwrap_or(0)
+}
+
+fn pasta_backups(instance_path: &Path) -> PathBuf {
+    instance_path.join(PASTA_BACKUPS_CONTEUDO)
+}
+
+async fn atualizacoes_modrinth(
+    contexto: &ContextoResolucao,
+    entradas: &[&EntradaConteudo],
+    relatorio: &mut RelatorioAtualizacoes,
+) {
+    let mut por_tipo: HashMap<&str, Vec<&EntradaConteudo>> = HashMap::new();
+    for entrada in entradas {
+        if entrada.hashes.sha1.is_some() {
+            por_tipo
+                .entry(entrada.tipo_conteudo.as_str())
+                .or_default()
+                .push(entrada);
+        }
+    }
+
+    let mut candidatas: Vec<AtualizacaoDisponivel> = Vec::new();
+    for (tipo, grupo) in por_tipo {
+        let loader = contexto.loader_para(tipo);
+        let mut corpo = serde_json::json!({
+            "hashes": grupo.iter().filter_map(|e| e.hashes.sha1.as_deref()).collect::<Vec<_>>(),
+            "algorithm": "sha1",
+            "game_versions": [&contexto.versao_minecraft],
+        });
+        if let Some(loader) = &loader {
+            corpo["loaders"] = serde_json::json!(loaders_compativeis(loader));
+        }
+
+        let resposta = contexto
+            .client
+            .post(format!("{}/version_files/update", MODRINTH_API_BASE))
+            .json(&corpo)
+            .send()
+            .await;
+        let versoes: serde_json::Value = match resposta {
+            Ok(resposta) if resposta.status().is_success() => {
+                resposta.json().await.unwrap_or_default()
+            }
+            Ok(resposta) => {
+                relatorio.erros.push(format!(
+                    "Modrinth retornou HTTP {} ao verificar atualizações",
+                    resposta.status().as_u16()
+                ));
+                continue;
+            }
+            Err(e) => {
+                relatorio
+                    .erros
+                    .push(format!("Erro ao verificar atualizações no Modrinth: {}", e));
+                continue;
+            }
+        };
+
+        for entrada in grupo {
+            let Some(versao) = entrada
+                .hashes
+                .sha1
+                .as_deref()
+                .and_then(|sha1| versoes.get(sha1))
+            else {
+                continue;
+            };
+            let Some(nova_versao_id) = versao["id"].as_str() else {
+                continue;
+            };
+            if entrada.versao_id.as_deref() == Some(nova_versao_id)
+                || !versao_modrinth_compativel(versao, &contexto.versao_minecraft, &loader)
+            {
+                continue;
+            }
+            let Some(arquivo) = arquivo_principal_modrinth(versao) else {
+                continue;
+            };
+            if arquivo["hashes"]["sha1"].as_str() == entrada.hashes.sha1.as_deref() {
+                continue;
+            }
+
+            candidatas.push(AtualizacaoDisponivel {
+                arquivo: entrada.arquivo.clone(),
+                tipo_conteudo: entrada.tipo_conteudo.clone(),
+                plataforma: ModPlatform::Modrinth,
+                project_id: entrada.project_id.clone().unwrap_or_default(),
+                nome: entrada
+                    .nome
+                    .clone()
+                    .unwrap_or_else(|| entrada.arquivo.clone()),
+                versao_atual_id: entrada.versao_id.clone(),
+                versao_atual: None,
+                nova_versao_id: nova_versao_id.to_string(),
+                nova_versao: versao["version_number"].as_str().unwrap_or("").to_string(),
+                novo_arquivo: arquivo["filename"].as_str().unwrap_or("").to_string(),
+                download_url: arquivo["url"].as_str().unwrap_or("").to_string(),
+                sha1: arquivo["hashes"]["sha1"].as_str().map(|s| s.to_lowercase()),
+                tamanho: arquivo["size"].as_u64(),
+                changelog: versao["changelog"].as_str().map(|s| s.to_string()),
+                publicado_em: versao["date_published"].as_str().map(|s| s.to_string()),
+            });
+        }
+    }
+
+    let ids_atuais: Vec<&str> = candidatas
+        .iter()
+        .filter_map(|c| c.versao_atual_id.as_deref())
+        .collect();
+    if !ids_atuais.is_empty() {
+        let url = format!(
+            "{}/versions?ids={}",
+            MODRINTH_API_BASE,
+            urlencoding::encode(&serde_json::json!(ids_atuais).to_string())
+        );
+        if let Ok(versoes) = contexto.get_json(&url).await {
+            let nomes: HashMap<&str, &str> = versoes
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|v| Some((v["id"].as_str()?, v["version_number"].as_str()?)))
+                .collect();
+            for candidata in &mut candidatas {
+                candidata.versao_atual = candidata
+                    .versao_atual_id
+                    .as_deref()
+                    .and_then(|id| nomes.get(id))
+                    .map(|nome| nome.to_string());
+            }
+        }
+    }
+
+    relatorio.atualizacoes.extend(candidatas);
+}
+
+async fn atualizacoes_curseforge(
+    contexto: &ContextoResolucao,
+    entradas: &[&EntradaConteudo],
+    relatorio: &mut RelatorioAtualizacoes,
+) {
+    use futures::{stream, StreamExt};
+
+    let consultas: Vec<_> = entradas
+        .iter()
+        .map(|entrada| {
+            let entrada: EntradaConteudo = (*entrada).clone();
+            async move {
+                let project_id = entrada.project_id.clone().unwrap_or_default();
+                let arquivo = contexto
+                    .arquivo_curseforge(&project_id, &entrada.tipo_conteudo)
+                    .await;
+                (entrada, arquivo)
+            }
+        })
+        .collect();
+    let resultados: Vec<_> = stream::iter(consultas).buffer_unordered(6).collect().await;
+
+    let mut candidatas: Vec<AtualizacaoDisponivel> = Vec::new();
+    for (entrada, arquivo) in resultados {
+        let arquivo = match arquivo {
+            Ok(arquivo) => arquivo,
+            Err(e) => {
+                relatorio.erros.push(e);
+                continue;
+            }
+        };
+        let novo_id = arquivo["id"].as_u64().unwrap_or(0);
+        // Só oferece arquivos mais novos que o instalado, nunca um downgrade.
+        if novo_id <= id_numerico(entrada.versao_id.as_deref()) {
+            continue;
+        }
+        let Some(download_url) = arquivo["downloadUrl"].as_str() else {
+            continue;
+        };
+
+        let sha1 = arquivo["hashes"].as_array().and_then(|hashes| {
+            hashes
+                .iter()
+                .find(|h| h["algo"].as_u64() == Some(1))
+                .and_then(|h| h["value"].as_str())
+                .map(|s| s.to_lowercase())
+        });
+        let project_id = entrada.project_id.clone().unwrap_or_default();
+        let changelog = contexto
+            .get_json_curseforge(&format!(
+                "{}/mods/{}/files/{}/changelog",
+                CURSEFORGE_API_BASE, project_id, novo_id
+            ))
+            .await
+            .ok()
+            .and_then(|payload| payload["data"].as_str().map(|s| s.to_string()));
+
+        candidatas.push(AtualizacaoDisponivel {
+            arquivo: entrada.arquivo.clone(),
+            tipo_conteudo: entrada.tipo_conteudo.clone(),
+            plataforma: ModPlatform::CurseForge,
+            project_id,
+            nome: entrada
+                .nome
+                .clone()
+                .unwrap_or_else(|| entrada.arquivo.clone()),
+            versao_atual_id: entrada.versao_id.clone(),
+            versao_atual: None,
+            nova_versao_id: novo_id.to_string(),
+            nova_versao: arquivo["displayName"].as_str().unwrap_or("").to_string(),
+            novo_arquivo: arquivo["fileName"].as_str().unwrap_or("").to_string(),
+            download_url: download_url.to_string(),
+            sha1,
+            tamanho: arquivo["fileLength"].as_u64(),
+            changelog,
+            publicado_em: arquivo["fileDate"].as_str().map(|s| s.to_string()),
+        });
+    }
+
+    let ids_atuais: Vec<u64> = candidatas
+        .iter()
+        .filter_map(|c| c.versao_atual_id.as_deref()?.parse().ok())
+        .collect();
+    if !ids_atuais.is_empty() {
+        let requisicao = anexar_headers_curseforge(
+            contexto
+                .client
+                .post(format!("{}/mods/files", CURSEFORGE_API_BASE))
+                .json(&serde_json::json!({ "fileIds": ids_atuais })),
+        );
+        if let Ok(requisicao) = requisicao {
+            if let Ok(resposta) = requisicao.send().await {
+                let payload: serde_json::Value = resposta.json().await.unwrap_or_default();
+                let nomes: HashMap<String, String> = payload["data"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|f| {
+                        Some((
+                            f["id"].as_u64()?.to_string(),
+                            f["displayName"].as_str()?.to_string(),
+                        ))
+                    })
+                    .collect();
+                for candidata in &mut candidatas {
+                    candidata.versao_atual = candidata
+                        .versao_atual_id
+                        .as_ref()
+                        .and_then(|id| nomes.get(id))
+                        .cloned();
+                }
+            }
+        }
+    }
+
+    relatorio.atualizacoes.extend(candidatas);
+}
+
+/// Procura versões mais novas, compatíveis com a versão do Minecraft e o loader
+/// da instância, para todo conteúdo com origem conhecida no lockfile.
+#[tauri::command]
+pub(crate) async fn verificar_atualizacoes_conteudo(
+    instance_id: String,
+    state: State<'_, LauncherState>,
+) -> Result<RelatorioAtualizacoes, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let contexto = ContextoResolucao::para_instancia(&instance)?;
+    let lockfile = carregar_lockfile(&instance.path);
+    let mut relatorio = RelatorioAtualizacoes::default();
+
+    let mut modrinth = Vec::new();
+    let mut curseforge = Vec::new();
+    for entrada in &lockfile.entradas {
+        if entrada.project_id.is_none() {
+            relatorio.sem_origem += 1;
+            continue;
+        }
+        if entrada.fixado {
+            relatorio.fixados.push(entrada.arquivo.clone());
+            continue;
+        }
+        match entrada.plataforma {
+            Some(ModPlatform::Modrinth) => modrinth.push(entrada),
+            Some(ModPlatform::CurseForge) => curseforge.push(entrada),
+            _ => relatorio.sem_origem += 1,
+        }
+    }
+
+    atualizacoes_modrinth(&contexto, &modrinth, &mut relatorio).await;
+    atualizacoes_curseforge(&contexto, &curseforge, &mut relatorio).await;
+
+    // O lockfile guarda o nome sem `.disabled`; a interface precisa do nome em disco.
+    for atualizacao in &mut relatorio.atualizacoes {
+        if let Ok(pasta) = pasta_destino_conteudo(&instance, &atualizacao.tipo_conteudo) {
+            let desativado = format!("{}.disabled", atualizacao.arquivo);
+            if !pasta.join(&atualizacao.arquivo).exists() && pasta.join(&desativado).exists() {
+                atualizacao.arquivo = desativado;
+            }
+        }
+    }
+    relatorio
+        .atualizacoes
+        .sort_by_key(|atualizacao| atualizacao.nome.to_lowercase());
+
+    println!(
+        "[Atualizacoes] {}: {} atualização(ões) disponível(is)",
+        instance_id,
+        relatorio.atualizacoes.len()
+    );
+    Ok(relatorio)
+}
+
+/// Troca os arquivos movendo os antigos para o backup. Se qualquer passo
+/// falhar, as trocas já feitas são desfeitas e nada muda na instância.
+fn aplicar_trocas(
+    instance_path: &Path,
+    pasta_backup: &Path,
+    trocas: &[TrocaPreparada],
+) -> Result<(), String> {
+    let mut concluidas: Vec<(&TrocaPreparada, bool)> = Vec::new();
+
+    let desfazer = |concluidas: &[(&TrocaPreparada, bool)]| {
+        for (troca, novo_no_lugar) in concluidas.iter().rev() {
+            let pasta = instance_path.join(&troca.pasta);
+            if *novo_no_lugar {
+                let _ = std::fs::rename(pasta.join(&troca.arquivo_novo), &troca.preparado);
+            }
+            let _ = std::fs::rename(
+                pasta_backup.join(&troca.pasta).join(&troca.arquivo_antigo),
+                pasta.join(&troca.arquivo_antigo),
+            );
+        }
+    };
+
+    for troca in trocas {
+        let pasta = instance_path.join(&troca.pasta);
+        let destino_backup = pasta_backup.join(&troca.pasta);
+        let resultado = std::fs::create_dir_all(&destino_backup).and_then(|_| {
+            std::fs::rename(
+                pasta.join(&troca.arquivo_antigo),
+                destino_backup.join(&troca.arquivo_antigo),
+            )
+        });
+        if let Err(e) = resultado {
+            desfazer(&concluidas);
+            return Err(format!(
+                "Erro ao mover {} para o backup: {}",
+                troca.arquivo_antigo, e
+            ));
+        }
+        concluidas.push((troca, false));
+
+        if let Err(e) = std::fs::rename(&troca.preparado, pasta.join(&troca.arquivo_novo)) {
+            desfazer(&concluidas);
+            return Err(format!("Erro ao instalar {}: {}", troca.arquivo_novo, e));
+        }
+        if let Some(ultima) = concluidas.last_mut() {
+            ultima.1 = true;
+        }
+    }
+
+    Ok(())
+}
+
+fn salvar_manifesto_backup(pasta_backup: &Path, manifesto: &ManifestoBackup) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifesto)
+        .map_err(|e| format!("Erro ao serializar backup: {}", e))?;
+    std::fs::write(pasta_backup.join(ARQUIVO_MANIFESTO_BACKUP), json)
+        .map_err(|e| format!("Erro ao salvar manifesto do backup: {}", e))
+}
+
+fn ler_manifesto_backup(pasta_backup: &Path) -> Result<ManifestoBackup, String> {
+    let conteudo = std::fs::read_to_string(pasta_backup.join(ARQUIVO_MANIFESTO_BACKUP))
+        .map_err(|e| format!("Erro ao ler manifesto do backup: {}", e))?;
+    serde_json::from_str(&conteudo).map_err(|e| format!("Manifesto de backup inválido: {}", e))
+}
+
+fn listar_manifestos(instance_path: &Path) -> Vec<ManifestoBackup> {
+    let mut manifestos: Vec<ManifestoBackup> = std::fs::read_dir(pasta_backups(instance_path))
+        .map(|entradas| {
+            entradas
+                .flatten()
+                .filter_map(|entrada| ler_manifesto_backup(&entrada.path()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    manifestos.sort_by(|a, b| b.criado_em.cmp(&a.criado_em));
+    manifestos
+}
+
+fn podar_backups_antigos(instance_path: &Path) {
+    for manifesto in listar_manifestos(instance_path)
+        .into_iter()
+        .skip(LIMITE_BACKUPS_CONTEUDO)
+    {
+        let _ = std::fs::remove_dir_all(pasta_backups(instance_path).join(&manifesto.id));
+    }
+}
+
+/// Aplica as trocas já preparadas, grava o backup e atualiza o lockfile.
+fn concluir_atualizacao(
+    instance_path: &Path,
+    backup_id: &str,
+    trocas: &[TrocaPreparada],
+) -> Result<(), String> {
+    let pasta_backup = pasta_backups(instance_path).join(backup_id);
+    std::fs::create_dir_all(&pasta_backup)
+        .map_err(|e| format!("Erro ao criar pasta de backup: {}", e))?;
+
+    let lockfile = carregar_lockfile(instance_path);
+    let manifesto = ManifestoBackup {
+        id: backup_id.to_string(),
+        criado_em: chrono::Utc::now().to_rfc3339(),
+        itens: trocas
+            .iter()
+            .map(|troca| ItemBackup {
+                tipo_conteudo: troca.tipo_conteudo.clone(),
+                pasta: troca.pasta.clone(),
+                arquivo_antigo: troca.arquivo_antigo.clone(),
+                arquivo_novo: troca.arquivo_novo.clone(),
+                entrada_antiga: lockfile
+                    .entradas
+                    .iter()
+                    .find(|e| {
+                        e.tipo_conteudo == troca.tipo_conteudo
+                            && e.arquivo == nome_habilitado(&troca.arquivo_antigo)
+                    })
+                    .cloned(),
+            })
+            .collect(),
+    };
+    salvar_manifesto_backup(&pasta_backup, &manifesto)?;
+
+    if let Err(e) = aplicar_trocas(instance_path, &pasta_backup, trocas) {
+        let _ = std::fs::remove_dir_all(&pasta_backup);
+        return Err(e);
+    }
+
+    alterar_lockfile(instance_path, |lockfile| {
+        for troca in trocas {
+            lockfile.remover(&troca.tipo_conteudo, &troca.arquivo_antigo);
+            lockfile.registrar(troca.entrada_nova.clone());
+        }
+    })
+}
+
+/// Desfaz uma atualização: remove os arquivos novos, devolve os antigos do
+/// backup e restaura as entradas do lockfile.
+fn reverter_backup(instance_path: &Path, backup_id: &str) -> Result<Vec<String>, String> {
+    let pasta_backup = pasta_backups(instance_path).join(backup_id);
+    let manifesto = ler_manifesto_backup(&pasta_backup)?;
+    let mut restaurados = Vec::new();
+
+    for item in &manifesto.itens {
+        let pasta = instance_path.join(&item.pasta);
+        let novo = pasta.join(&item.arquivo_novo);
+        if novo.is_file() {
+            std::fs::remove_file(&novo)
+                .map_err(|e| format!("Erro ao remover {}: {}", item.arquivo_novo, e))?;
+        }
+        let antigo = pasta_backup.join(&item.pasta).join(&item.arquivo_antigo);
+        if antigo.is_file() {
+            std::fs::create_dir_all(&pasta)
+                .map_err(|e| format!("Erro ao criar pasta {}: {}", item.pasta, e))?;
+            std::fs::rename(&antigo, pasta.join(&item.arquivo_antigo))
+                .map_err(|e| format!("Erro ao restaurar {}: {}", item.arquivo_antigo, e))?;
+            restaurados.push(item.arquivo_antigo.clone());
+        }
+    }
+
+    alterar_lockfile(instance_path, |lockfile| {
+        for item in &manifesto.itens {
+            lockfile.remover(&item.tipo_conteudo, &item.arquivo_novo);
+            if let Some(entrada) = &item.entrada_antiga {
+                lockfile.registrar(entrada.clone());
+            }
+        }
+    })?;
+
+    let _ = std::fs::remove_dir_all(&pasta_backup);
+    Ok(restaurados)
+}
+
+async fn preparar_trocas(
+    instance: &Instance,
+    atualizacoes: &[AtualizacaoDisponivel],
+    pasta_preparacao: &Path,
+    tarefa: &Tarefa,
+) -> Result<Vec<TrocaPreparada>, String> {
+    let lockfile = carregar_lockfile(&instance.path);
+    let mut trocas = Vec::new();
+
+    for atualizacao in atualizacoes {
+        tarefa.verificar_cancelamento()?;
+        let pasta = pasta_destino_conteudo(instance, &atualizacao.tipo_conteudo)?;
+        let nome_pasta = pasta
+            .file_name()
+            .map(|nome| nome.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let arquivo_antigo = std::path::Path::new(&atualizacao.arquivo)
+            .file_name()
+            .and_then(|nome| nome.to_str())
+            .filter(|nome| !nome.is_empty())
+            .ok_or("Nome de arquivo inválido para atualização.")?
+            .to_string();
+        if !pasta.join(&arquivo_antigo).is_file() {
+            return Err(format!("'{}' não está mais instalado.", arquivo_antigo));
+        }
+
+        let pasta_preparo_tipo = pasta_preparacao.join(&nome_pasta);
+        let preparado = tokio::select! {
+            resultado = baixar_arquivo_para_pasta(
+                &pasta_preparo_tipo,
+                &atualizacao.tipo_conteudo,
+                atualizacao.download_url.clone(),
+                atualizacao.novo_arquivo.clone(),
+                "atualizacao",
+                atualizacao.sha1.as_deref(),
+            ) => resultado.map_err(|e| format!("Erro ao baixar {}: {}", atualizacao.nome, e))?,
+            _ = tarefa.cancelamento().cancelled() => return Err(MENSAGEM_TAREFA_CANCELADA.to_string()),
+        };
+        tarefa.avancar(1, atualizacao.tamanho.unwrap_or(0));
+
+        let mut nome_novo = preparado
+            .file_name()
+            .map(|nome| nome.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Conteúdo desativado continua desativado depois de atualizado.
+        if arquivo_antigo.ends_with(".disabled") {
+            nome_novo.push_str(".disabled");
+        }
+
+        let anterior = lockfile.entradas.iter().find(|e| {
+            e.tipo_conteudo == atualizacao.tipo_conteudo
+                && e.arquivo == nome_habilitado(&arquivo_antigo)
+        });
+        let mut entrada_nova = EntradaConteudo::de_arquivo(
+            &preparado,
+            &atualizacao.tipo_conteudo,
+            Default::default(),
+        )?
+        .com_projeto(
+            atualizacao.plataforma.clone(),
+            Some(atualizacao.project_id.clone()),
+            Some(atualizacao.nova_versao_id.clone()),
+        )
+        .com_url_download(&atualizacao.download_url);
+        entrada_nova.habilitado = !nome_novo.ends_with(".disabled");
+        if let Some(anterior) = anterior {
+            entrada_nova.origem = anterior.origem;
+            entrada_nova.nome = anterior.nome.clone();
+            entrada_nova.icone_url = anterior.icone_url.clone();
+        }
+
+        trocas.push(TrocaPreparada {
+            tipo_conteudo: atualizacao.tipo_conteudo.clone(),
+            pasta: nome_pasta,
+            arquivo_antigo,
+            arquivo_novo: nome_novo,
+            preparado,
+            entrada_nova,
+        });
+    }
+
+    Ok(trocas)
+}
+
+/// Baixa e verifica todas as atualizações selecionadas antes de tocar na
+/// instância; só então troca os arquivos, guardando os antigos em um backup.
+#[tauri::command]
+pub(crate) async fn aplicar_atualizacoes_conteudo(
+    app: tauri::AppHandle,
+    instance_id: String,
+    atualizacoes: Vec<AtualizacaoDisponivel>,
+    task_id: Option<String>,
+    state: State<'_, LauncherState>,
+) -> Result<ResultadoAtualizacaoConteudo, String> {
+    if atualizacoes.is_empty() {
+        return Err("Nenhuma atualização selecionada.".to_string());
+    }
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "atualizar_conteudo",
+        &format!(
+            "Atualizando {} arquivo(s) em {}",
+            atualizacoes.len(),
+            instance.name
+        ),
+        Some(&instance_id),
+    )?;
+
+    let backup_id = chrono::Utc::now().format("%Y%m%d-%H%M%S-%3f").to_string();
+    let pasta_preparacao = instance
+        .path
+        .join(PASTA_PREPARACAO_ATUALIZACAO)
+        .join(&backup_id);
+    tarefa.limpar_ao_cancelar(pasta_preparacao.clone());
+    tarefa.fase(
+        "baixando",
+        atualizacoes.len(),
+        atualizacoes.iter().filter_map(|a| a.tamanho).sum(),
+    );
+
+    let resultado = async {
+        let trocas = preparar_trocas(&instance, &atualizacoes, &pasta_preparacao, &tarefa).await?;
+        tarefa.verificar_cancelamento()?;
+        tarefa.fase("aplicando", trocas.len(), 0);
+        concluir_atualizacao(&instance.path, &backup_id, &trocas)?;
+        podar_backups_antigos(&instance.path);
+        Ok(ResultadoAtualizacaoConteudo {
+            backup_id: backup_id.clone(),
+            atualizados: trocas.iter().map(|t| t.arquivo_novo.clone()).collect(),
+        })
+    }
+    .await;
+
+    let _ = std::fs::remove_dir_all(&pasta_preparacao);
+    if resultado.is_ok() {
+        println!(
+            "[Atualizacoes] {} arquivo(s) atualizados em {} (backup {})",
+            atualizacoes.len(),
+            instance_id,
+            backup_id
+        );
+    }
+    tarefa.finalizar(resultado)
+}
+
+#[tauri::command]
+pub(crate) fn listar_backups_atualizacao(
+    instance_id: String,
+    state: State<LauncherState>,
+) -> Result<Vec<ResumoBackupConteudo>, String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    Ok(listar_manifestos(&instance_path)
+        .into_iter()
+        .map(|manifesto| ResumoBackupConteudo {
+            id: manifesto.id,
+            criado_em: manifesto.criado_em,
+            arquivos: manifesto
+                .itens
+                .into_iter()
+                .map(|item| item.arquivo_antigo)
+                .collect(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub(crate) fn reverter_atualizacao_conteudo(
+    instance_id: String,
+    backup_id: String,
+    state: State<LauncherState>,
+) -> Result<Vec<String>, String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    let backup_id = backup_id.trim();
+    if backup_id.is_empty()
+        || !backup_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err("Identificador de backup inválido.".to_string());
+    }
+    reverter_backup(&instance_path, backup_id)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::super::registro_conteudo::{registrar_conteudo, HashesConteudo, OrigemInstalacao};
+    use super::*;
+
+    fn preparar_instancia() -> PathBuf {
+        let pasta = std::env::temp_dir().join(format!("dome_atualizacao_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(pasta.join("mods")).unwrap();
+        std::fs::create_dir_all(pasta.join("preparo/mods")).unwrap();
+        pasta
+    }
+
+    fn troca(pasta: &Path, antigo: &str, novo: &str) -> TrocaPreparada {
+        let preparado = pasta.join("preparo/mods").join(nome_habilitado(novo));
+        std::fs::write(&preparado, format!("novo {}", novo)).unwrap();
+        TrocaPreparada {
+            tipo_conteudo: "mod".to_string(),
+            pasta: "mods".to_string(),
+            arquivo_antigo: antigo.to_string(),
+            arquivo_novo: novo.to_string(),
+            preparado,
+            entrada_nova: EntradaConteudo::nova(
+                novo,
+                "mod",
+                HashesConteudo::de_bytes(novo.as_bytes()),
+                OrigemInstalacao::Usuario,
+            ),
+        }
+    }
+
+    #[test]
+    fn atualiza_e_reverte_com_backup() {
+        let pasta = preparar_instancia();
+        std::fs::write(pasta.join("mods/sodium-0.5.jar"), "antigo").unwrap();
+        std::fs::write(pasta.join("mods/lithium-0.11.jar.disabled"), "antigo").unwrap();
+        let mut antiga = EntradaConteudo::nova(
+            "sodium-0.5.jar",
+            "mod",
+            HashesConteudo::default(),
+            OrigemInstalacao::Dependencia,
+        );
+        antiga.project_id = Some("AANobbMI".to_string());
+        registrar_conteudo(&pasta, vec![antiga]).unwrap();
+
+        let trocas = vec![
+            troca(&pasta, "sodium-0.5.jar", "sodium-0.6.jar"),
+            troca(
+                &pasta,
+                "lithium-0.11.jar.disabled",
+                "lithium-0.12.jar.disabled",
+            ),
+        ];
+        concluir_atualizacao(&pasta, "b1", &trocas).unwrap();
+
+        assert!(pasta.join("mods/sodium-0.6.jar").is_file());
+        assert!(pasta.join("mods/lithium-0.12.jar.disabled").is_file());
+        assert!(!pasta.join("mods/sodium-0.5.jar").exists());
+        let arquivos: Vec<String> = carregar_lockfile(&pasta)
+            .entradas
+            .into_iter()
+            .map(|e| e.arquivo)
+            .collect();
+        assert_eq!(arquivos, vec!["sodium-0.6.jar", "lithium-0.12.jar"]);
+
+        let restaurados = reverter_backup(&pasta, "b1").unwrap();
+        assert_eq!(restaurados.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(pasta.join("mods/sodium-0.5.jar")).unwrap(),
+            "antigo"
+        );
+        assert!(pasta.join("mods/lithium-0.11.jar.disabled").is_file());
+        assert!(!pasta.join("mods/sodium-0.6.jar").exists());
+        let lockfile = carregar_lockfile(&pasta);
+        assert_eq!(lockfile.entradas.len(), 1);
+        assert_eq!(lockfile.entradas[0].arquivo, "sodium-0.5.jar");
+        assert_eq!(lockfile.entradas[0].origem, OrigemInstalacao::Dependencia);
+        assert!(!pasta_backups(&pasta).join("b1").exists());
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn falha_no_meio_desfaz_trocas_anteriores() {
+        let pasta = preparar_instancia();
+        std::fs::write(pasta.join("mods/a-1.jar"), "a1").unwrap();
+        // `b-1.jar` não existe: a segunda troca falha ao mover para o backup.
+        let trocas = vec![
+            troca(&pasta, "a-1.jar", "a-2.jar"),
+            troca(&pasta, "b-1.jar", "b-2.jar"),
+        ];
+
+        assert!(concluir_atualizacao(&pasta, "b2", &trocas).is_err());
+        assert_eq!(
+            std::fs::read_to_string(pasta.join("mods/a-1.jar")).unwrap(),
+            "a1"
+        );
+        assert!(!pasta.join("mods/a-2.jar").exists());
+        assert!(trocas[0].preparado.is_file());
+        assert!(!pasta_backups(&pasta).join("b2").exists());
+        assert!(carregar_lockfile(&pasta).entradas.is_empty());
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+}
@@ -0,0 +1,1010 @@
+use super::instancias_criacao::{instalar_loader, nome_loader_instancia};
+use super::mods_conteudo::loaders_compativeis;
+use super::registro_conteudo::{
+    alterar_lockfile, carregar_lockfile, tipo_conteudo_por_caminho, EntradaConteudo,
+    OrigemInstalacao,
+};
+use super::resolucao_dependencias::ContextoResolucao;
+use super::verificacao_arquivos::sha1_arquivo;
+use super::*;
+use crate::comandos::modpacks::{
+    baixar_arquivos_modpack, baixar_pacote_modpack, carregar_arquivos_modpack,
+    cliente_http_modpack, extrair_overrides, gravar_modpack_info, ler_indice_modpack,
+    ler_modpack_info, resolver_arquivos_curseforge, salvar_arquivos_modpack,
+    url_download_curseforge, ArquivosModpackInstalados, IndiceModpack, ModpackInfo,
+};
+use crate::comandos::tarefas::Tarefa;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub(crate) const PASTA_PREPARACAO_MODPACK: &str = ".atualizacao_modpack";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VersaoModpackDisponivel {
+    /// Id da versão no Modrinth ou do arquivo no CurseForge.
+    pub versao_id: String,
+    pub versao: String,
+    pub download_url: String,
+    pub file_name: String,
+    pub publicado_em: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioAtualizacaoModpack {
+    pub atualizado: bool,
+    pub versao_anterior: String,
+    pub versao_nova: String,
+    pub adicionados: Vec<String>,
+    pub atualizados: Vec<String>,
+    pub removidos: Vec<String>,
+    /// Arquivos mantidos como estão porque o usuário os adicionou ou modificou.
+    pub preservados: Vec<String>,
+    pub loader_anterior: Option<String>,
+    pub loader_novo: Option<String>,
+    pub erros: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AcaoArquivoModpack {
+    Manter,
+    Adicionar,
+    Atualizar,
+    Remover,
+    Preservar,
+}
+
+/// Situação de um caminho da instância frente ao pacote antigo e ao novo.
+struct EstadoArquivoModpack<'a> {
+    /// SHA-1 entregue pelo pacote instalado, se o arquivo pertencia a ele.
+    anterior: Option<&'a str>,
+    /// Presença no pacote novo, com o SHA-1 quando o índice o declara.
+    novo: Option<Option<&'a str>>,
+    /// SHA-1 do arquivo em disco.
+    atual: Option<&'a str>,
+    /// Instalado ou fixado pelo usuário segundo o lockfile.
+    do_usuario: bool,
+}
+
+fn decidir_acao(estado: &EstadoArquivoModpack) -> Option<AcaoArquivoModpack> {
+    match (estado.novo, estado.atual) {
+        (Some(_), None) => Some(AcaoArquivoModpack::Adicionar),
+        (Some(novo), Some(atual)) if novo == Some(atual) => Some(AcaoArquivoModpack::Manter),
+        (Some(_), Some(atual)) => {
+            if !estado.do_usuario && estado.anterior == Some(atual) {
+                Some(AcaoArquivoModpack::Atualizar)
+            } else {
+                Some(AcaoArquivoModpack::Preservar)
+            }
+        }
+        (None, Some(atual)) if estado.anterior.is_some() => {
+            if !estado.do_usuario && estado.anterior == Some(atual) {
+                Some(AcaoArquivoModpack::Remover)
+            } else {
+                Some(AcaoArquivoModpack::Preservar)
+            }
+        }
+        (None, _) => None,
+    }
+}
+
+/// Caminho em disco de um arquivo do pacote, considerando a versão desativada.
+fn caminho_em_disco(instance_path: &Path, relativo: &str) -> Option<String> {
+    if instance_path.join(relativo).is_file() {
+        return Some(relativo.to_string());
+    }
+    let desativado = format!("{}.disabled", relativo);
+    instance_path
+        .join(&desativado)
+        .is_file()
+        .then_some(desativado)
+}
+
+fn pasta_tipo_conteudo(tipo_conteudo: &str) -> &'static str {
+    match tipo_conteudo {
+        "resourcepack" => "resourcepacks",
+        "shader" => "shaderpacks",
+        _ => "mods",
+    }
+}
+
+/// Arquivos do pacote instalado. Instalações anteriores ao registro usam as
+/// entradas do lockfile com origem no modpack; sem nenhum dos dois, a lista
+/// fica vazia e é reconstruída em `reconstruir_pacote_anterior`.
+fn arquivos_pacote_anterior(instance_path: &Path) -> BTreeMap<String, String> {
+    if let Some(registro) = carregar_arquivos_modpack(instance_path) {
+        return registro.arquivos;
+    }
+    carregar_lockfile(instance_path)
+        .entradas
+        .into_iter()
+        .filter(|entrada| entrada.origem == OrigemInstalacao::Modpack)
+        .filter_map(|entrada| {
+            let sha1 = entrada.hashes.sha1?;
+            Some((
+                format!(
+                    "{}/{}",
+                    pasta_tipo_conteudo(&entrada.tipo_conteudo),
+                    entrada.arquivo
+                ),
+                sha1,
+            ))
+        })
+        .collect()
+}
+
+/// Caminhos instalados pelo usuário (ou como dependência do que ele instalou)
+/// e os fixados. Arquivos só identificados por hash podem ter vindo do pacote
+/// e seguem a comparação com o índice anterior.
+fn caminhos_do_usuario(instance_path: &Path) -> HashSet<String> {
+    carregar_lockfile(instance_path)
+        .entradas
+        .into_iter()
+        .filter(|entrada| {
+            entrada.fixado
+                || matches!(
+                    entrada.origem,
+                    OrigemInstalacao::Usuario | OrigemInstalacao::Dependencia
+                )
+        })
+        .map(|entrada| {
+            format!(
+                "{}/{}",
+                pasta_tipo_conteudo(&entrada.tipo_conteudo),
+                entrada.arquivo
+            )
+        })
+        .collect()
+}
+
+fn id_numerico(valor: &str) -> Option<u64> {
+    valor.trim().parse().ok()
+}
+
+async fn versao_mais_recente_modrinth(
+    contexto: &ContextoResolucao,
+    info: &ModpackInfo,
+) -> Result<Option<VersaoModpackDisponivel>, String> {
+    let mut url = format!(
+        "{}/project/{}/version?game_versions={}",
+        MODRINTH_API_BASE,
+        info.project_id,
+        urlencoding::encode(&format!("[\"{}\"]", contexto.versao_minecraft))
+    );
+    // Sem o filtro, a versão mais recente pode ser de outro loader do mesmo pack.
+    if let Some(loader) = &contexto.loader {
+        url.push_str(&format!(
+            "&loaders={}",
+            urlencoding::encode(&serde_json::json!(loaders_compativeis(loader)).to_string())
+        ));
+    }
+    let versoes = contexto.get_json(&url).await?;
+    let Some(versao) = versoes.as_array().and_then(|lista| lista.first()) else {
+        return Ok(None);
+    };
+    let versao_id = versao["id"].as_str().unwrap_or("").to_string();
+    if versao_id.is_empty() || versao_id == info.version_id {
+        return Ok(None);
+    }
+
+    let arquivo =
+        arquivo_pacote_modrinth(versao).ok_or("Versão do modpack sem arquivos para download.")?;
+    Ok(Some(VersaoModpackDisponivel {
+        versao_id,
+        versao: versao["version_number"].as_str().unwrap_or("").to_string(),
+        download_url: arquivo["url"]
+            .as_str()
+            .ok_or("Versão do modpack sem URL de download.")?
+            .to_string(),
+        file_name: arquivo["filename"]
+            .as_str()
+            .unwrap_or("modpack.mrpack")
+            .to_string(),
+        publicado_em: versao["date_published"].as_str().map(|s| s.to_string()),
+    }))
+}
+
+/// Arquivo `.mrpack` de uma versão Modrinth: o primário ou o primeiro listado.
+fn arquivo_pacote_modrinth(versao: &serde_json::Value) -> Option<&serde_json::Value> {
+    let arquivos = versao["files"].as_array()?;
+    arquivos
+        .iter()
+        .find(|f| f["primary"].as_bool().unwrap_or(false))
+        .or_else(|| arquivos.first())
+}
+
+async fn versao_mais_recente_curseforge(
+    contexto: &ContextoResolucao,
+    info: &ModpackInfo,
+) -> Result<Option<VersaoModpackDisponivel>, String> {
+    let url = format!(
+        "{}/mods/{}/files?gameVersion={}&pageSize=50",
+        CURSEFORGE_API_BASE,
+        info.project_id,
+        urlencoding::encode(&contexto.versao_minecraft)
+    );
+    let payload = contexto.get_json_curseforge(&url).await?;
+    let instalado = id_numerico(&info.version_id)
+        .or_else(|| info.file_id.as_deref().and_then(id_numerico))
+        .unwrap_or(0);
+    let Some(arquivo) = payload["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|arquivo| {
+            arquivo["fileName"]
+                .as_str()
+                .is_some_and(|nome| nome.to_lowercase().ends_with(".zip"))
+                && arquivo["isAvailable"].as_bool().unwrap_or(true)
+        })
+        .filter(|arquivo| arquivo["id"].as_u64().is_some_and(|id| id > instalado))
+        .max_by_key(|arquivo| arquivo["id"].as_u64().unwrap_or(0))
+    else {
+        return Ok(None);
+    };
+
+    let file_id = arquivo["id"].as_u64().unwrap_or(0);
+    let mut download_url = arquivo["downloadUrl"]
+        .as_str()
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    if download_url.is_empty() {
+        let project_id =
+            id_numerico(&info.project_id).ok_or("Id do modpack CurseForge inválido.")?;
+        download_url = url_download_curseforge(&contexto.client, project_id, file_id).await?;
+    }
+    if download_url.is_empty() {
+        return Err("Nova versão do modpack sem URL de download.".to_string());
+    }
+
+    Ok(Some(VersaoModpackDisponivel {
+        versao_id: file_id.to_string(),
+        versao: arquivo["displayName"].as_str().unwrap_or("").to_string(),
+        download_url,
+        file_name: arquivo["fileName"]
+            .as_str()
+            .unwrap_or("modpack.zip")
+            .to_string(),
+        publicado_em: arquivo["fileDate"].as_str().map(|s| s.to_string()),
+    }))
+}
+
+async fn versao_mais_recente(
+    instance: &Instance,
+    info: &ModpackInfo,
+) -> Result<Option<VersaoModpackDisponivel>, String> {
+    let contexto = ContextoResolucao::para_instancia(instance)?;
+    match info.source.as_str() {
+        "modrinth" => versao_mais_recente_modrinth(&contexto, info).await,
+        "curseforge" => versao_mais_recente_curseforge(&contexto, info).await,
+        outra => Err(format!("Origem de modpack não suportada: {}", outra)),
+    }
+}
+
+/// URL do pacote da versão registrada em `modpack.json`.
+async fn url_pacote_instalado(
+    client: &reqwest::Client,
+    info: &ModpackInfo,
+) -> Result<String, String> {
+    match info.source.as_str() {
+        "modrinth" => {
+            let url = format!("{}/version/{}", MODRINTH_API_BASE, info.version_id);
+            let versao: serde_json::Value = client
+                .get(&url)
+                .send()
+                .await
+                .and_then(|resposta| resposta.error_for_status())
+                .map_err(|e| format!("Erro ao consultar versão instalada: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Erro ao parsear versão instalada: {}", e))?;
+            arquivo_pacote_modrinth(&versao)
+                .and_then(|arquivo| arquivo["url"].as_str())
+                .map(|url| url.to_string())
+                .ok_or_else(|| "Versão instalada sem arquivo para download.".to_string())
+        }
+        "curseforge" => {
+            let project_id =
+                id_numerico(&info.project_id).ok_or("Id do modpack CurseForge inválido.")?;
+            let file_id = info
+                .file_id
+                .as_deref()
+                .and_then(id_numerico)
+                .or_else(|| id_numerico(&info.version_id))
+                .ok_or("Arquivo instalado do modpack desconhecido.")?;
+            url_download_curseforge(client, project_id, file_id).await
+        }
+        outra => Err(format!("Origem de modpack não suportada: {}", outra)),
+    }
+}
+
+/// Para instalações sem registro nem lockfile, baixa de novo o pacote da versão
+/// instalada e monta a lista de arquivos a partir do índice e dos overrides.
+/// Opcionais só entram se estiverem em disco, pois podem não ter sido escolhidos.
+async fn reconstruir_pacote_anterior(
+    client: &reqwest::Client,
+    instance_path: &Path,
+    info: &ModpackInfo,
+    preparacao: &Path,
+    tarefa: &Tarefa,
+) -> Result<BTreeMap<String, String>, String> {
+    let erro = |motivo: String| {
+        format!(
+            "Não foi possível identificar os arquivos da versão instalada do modpack ({}). Reinstale o modpack para poder atualizá-lo.",
+            motivo
+        )
+    };
+
+    let url = url_pacote_instalado(client, info).await.map_err(erro)?;
+    if url.is_empty() {
+        return Err(erro("sem URL de download".to_string()));
+    }
+    let pasta = preparacao.join("anterior");
+    std::fs::create_dir_all(&pasta)
+        .map_err(|e| format!("Erro ao criar pasta temporária: {}", e))?;
+    let caminho_pacote = pasta.join("pacote.zip");
+    tarefa.fase("baixando_modpack_anterior", 1, 0);
+    baixar_pacote_modpack(client, &url, &caminho_pacote, tarefa)
+        .await
+        .map_err(erro)?;
+    let arquivo_pacote = std::fs::File::open(&caminho_pacote)
+        .map_err(|e| format!("Erro ao abrir modpack anterior: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(arquivo_pacote).map_err(|e| erro(format!("ZIP inválido: {}", e)))?;
+    let indice = ler_indice_modpack(&mut archive).map_err(erro)?;
+    let (arquivos_curseforge, _) =
+        resolver_arquivos_curseforge(client, &indice.arquivos_curseforge, tarefa)
+            .await
+            .map_err(erro)?;
+    let overrides = extrair_overrides(
+        &mut archive,
+        &indice.pastas_overrides,
+        &pasta.join("overrides"),
+        tarefa,
+    )
+    .map_err(erro)?;
+
+    let mut arquivos: BTreeMap<String, String> = overrides.into_iter().collect();
+    for arquivo in indice.arquivos.iter().chain(&arquivos_curseforge) {
+        let em_disco = caminho_em_disco(instance_path, &arquivo.caminho);
+        if arquivo.opcional && em_disco.is_none() {
+            continue;
+        }
+        let sha1 = arquivo.sha1.clone().or_else(|| {
+            em_disco.and_then(|relativo| sha1_arquivo(&instance_path.join(relativo)).ok())
+        });
+        if let Some(sha1) = sha1 {
+            arquivos.insert(arquivo.caminho.clone(), sha1);
+        }
+    }
+    let _ = std::fs::remove_dir_all(&pasta);
+
+    if arquivos.is_empty() {
+        return Err(erro("o pacote instalado não lista arquivos".to_string()));
+    }
+    println!(
+        "[Modpack] Lista do pacote instalado reconstruída: {} arquivo(s)",
+        arquivos.len()
+    );
+    Ok(arquivos)
+}
+
+fn modpack_da_instancia(instance: &Instance) -> Result<ModpackInfo, String> {
+    ler_modpack_info(&instance.path)?
+        .ok_or_else(|| "Esta instância não foi instalada a partir de um modpack.".to_string())
+}
+
+/// Procura uma versão mais nova do modpack da instância para a mesma versão
+/// do Minecraft.
+#[tauri::command]
+pub(crate) async fn check_modpack_updates(
+    state: State<'_, LauncherState>,
+    instance_id: String,
+) -> Result<Option<VersaoModpackDisponivel>, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let info = modpack_da_instancia(&instance)?;
+    versao_mais_recente(&instance, &info).await
+}
+
+/// Compara versões de loader aceitando o formato `<mc>-<versão>` do Forge.
+fn mesma_versao_loader(atual: Option<&str>, nova: &str, versao_minecraft: &str) -> bool {
+    let sem_prefixo = |versao: &str| {
+        versao
+            .strip_prefix(&format!("{}-", versao_minecraft))
+            .unwrap_or(versao)
+            .to_string()
+    };
+    atual.is_some_and(|atual| sem_prefixo(atual) == sem_prefixo(nova))
+}
+
+/// Recusa modpacks feitos para outra versão do Minecraft que não a da instância.
+pub(crate) fn conferir_versao_minecraft(
+    instance: &Instance,
+    indice: &IndiceModpack,
+) -> Result<(), String> {
+    match &indice.versao_minecraft {
+        Some(versao_mc) if versao_mc != &instance.version => Err(format!(
+            "O modpack usa o Minecraft {} e a instância está no {}. Atualize a versão da instância antes.",
+            versao_mc, instance.version
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn descricao_loader(instance: &Instance) -> String {
+    format!(
+        "{} {}",
+        instance.loader_type.as_deref().unwrap_or("Vanilla"),
+        instance.loader_version.as_deref().unwrap_or("")
+    )
+    .trim()
+    .to_string()
+}
+
+/// Instala o loader declarado nas dependências do modpack quando ele difere
+/// do da instância, devolvendo a instância com o loader novo (ainda não gravada).
+pub(crate) async fn alinhar_loader_com_modpack(
+    instance: &Instance,
+    indice: &IndiceModpack,
+    tarefa: &Tarefa,
+) -> Result<Option<Instance>, String> {
+    let (Some(loader), Some(versao_loader)) = (&indice.loader, &indice.versao_loader) else {
+        return Ok(None);
+    };
+    let loader_atual = detectar_loader_normalizado(instance.loader_type.as_deref());
+    if loader_atual.as_deref() == Some(loader.as_str())
+        && mesma_versao_loader(
+            instance.loader_version.as_deref(),
+            versao_loader,
+            &instance.version,
+        )
+    {
+        return Ok(None);
+    }
+
+    println!(
+        "[Modpack] Instalando {} {} em {}",
+        loader, versao_loader, instance.id
+    );
+    tarefa.fase("loader", 0, 0);
+    let tipo = instalar_loader(
+        &instance.path,
+        &instance.version,
+        loader,
+        Some(versao_loader),
+        Some(tarefa),
+    )
+    .await?;
+    let mut nova = instance.clone();
+    nova.loader_type = Some(nome_loader_instancia(tipo.as_ref()));
+    nova.loader_version = Some(versao_loader.clone());
+    if tipo.is_some() {
+        nova.mc_type = loader.clone();
+    }
+    Ok(Some(nova))
+}
+
+pub(crate) fn gravar_instancia(instance: &Instance) -> Result<(), String> {
+    let conteudo = serde_json::to_string_pretty(instance)
+        .map_err(|e| format!("Erro ao serializar instance.json: {}", e))?;
+    std::fs::write(instance.path.join("instance.json"), conteudo)
+        .map_err(|e| format!("Erro ao atualizar instance.json: {}", e))
+}
+
+/// Origem de um arquivo do pacote novo já preparado na pasta temporária.
+struct ArquivoPreparado {
+    origem: PathBuf,
+    entrada: Option<EntradaConteudo>,
+}
+
+/// Caminho relativo do arquivo antigo a retirar e, se houver, o destino
+/// relativo do arquivo novo com sua origem na pasta temporária.
+type OperacaoArquivo = (Option<String>, Option<(String, PathBuf)>);
+
+/// Operação aplicada em disco, guardada para desfazer em caso de falha.
+struct OperacaoAplicada {
+    /// Caminho relativo do arquivo antigo e onde ele foi guardado.
+    antigo: Option<(String, PathBuf)>,
+    /// Caminho relativo do arquivo novo e de onde ele veio.
+    novo: Option<(String, PathBuf)>,
+}
+
+fn desfazer_operacoes(instance_path: &Path, operacoes: &[OperacaoAplicada]) {
+    for operacao in operacoes.iter().rev() {
+        if let Some((relativo, origem)) = &operacao.novo {
+            let _ = std::fs::rename(instance_path.join(relativo), origem);
+        }
+        if let Some((relativo, guardado)) = &operacao.antigo {
+            let _ = std::fs::rename(guardado, instance_path.join(relativo));
+        }
+    }
+}
+
+/// Move os arquivos antigos para a pasta temporária e coloca os novos no
+/// lugar. Se algum passo falhar, tudo o que já foi feito é desfeito; em caso de
+/// sucesso, devolve as operações para que o chamador ainda possa desfazê-las.
+fn aplicar_operacoes(
+    instance_path: &Path,
+    pasta_antigos: &Path,
+    operacoes: Vec<OperacaoArquivo>,
+) -> Result<Vec<OperacaoAplicada>, String> {
+    let mut aplicadas: Vec<OperacaoAplicada> = Vec::new();
+    for (antigo, novo) in operacoes {
+        let mut aplicada = OperacaoAplicada {
+            antigo: None,
+            novo: None,
+        };
+        if let Some(relativo) = antigo {
+            let guardado = pasta_antigos.join(&relativo);
+            let resultado = guardado
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::rename(instance_path.join(&relativo), &guardado));
+            if let Err(e) = resultado {
+                desfazer_operacoes(instance_path, &aplicadas);
+                return Err(format!("Erro ao mover {}: {}", relativo, e));
+            }
+            aplicada.antigo = Some((relativo, guardado));
+        }
+        if let Some((relativo, origem)) = novo {
+            let destino = instance_path.join(&relativo);
+            let resultado = destino
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::rename(&origem, &destino));
+            if let Err(e) = resultado {
+                aplicadas.push(aplicada);
+                desfazer_operacoes(instance_path, &aplicadas);
+                return Err(format!("Erro ao instalar {}: {}", relativo, e));
+            }
+            aplicada.novo = Some((relativo, origem));
+        }
+        aplicadas.push(aplicada);
+    }
+    Ok(aplicadas)
+}
+
+async fn executar_atualizacao_modpack(
+    instance: Instance,
+    mut info: ModpackInfo,
+    versao: VersaoModpackDisponivel,
+    tarefa: &Tarefa,
+) -> Result<RelatorioAtualizacaoModpack, String> {
+    let instance_path = instance.path.clone();
+    let mut relatorio = RelatorioAtualizacaoModpack {
+        versao_anterior: info.installed_version.clone(),
+        versao_nova: versao.versao.clone(),
+        ..Default::default()
+    };
+
+    let preparacao = instance_path.join(PASTA_PREPARACAO_MODPACK);
+    let _ = std::fs::remove_dir_all(&preparacao);
+    std::fs::create_dir_all(&preparacao)
+        .map_err(|e| format!("Erro ao criar pasta temporária: {}", e))?;
+    tarefa.limpar_ao_cancelar(preparacao.clone());
+
+    let resultado = preparar_e_aplicar(
+        &instance,
+        &mut info,
+        &versao,
+        &preparacao,
+        &mut relatorio,
+        tarefa,
+    )
+    .await;
+    let _ = std::fs::remove_dir_all(&preparacao);
+    resultado.map(|_| relatorio)
+}
+
+async fn preparar_e_aplicar(
+    instance: &Instance,
+    info: &mut ModpackInfo,
+    versao: &VersaoModpackDisponivel,
+    preparacao: &Path,
+    relatorio: &mut RelatorioAtualizacaoModpack,
+    tarefa: &Tarefa,
+) -> Result<(), String> {
+    let instance_path = &instance.path;
+    let client = cliente_http_modpack()?;
+
+    // 1. Baixar e ler o pacote novo
+    tarefa.fase("baixando_modpack", 1, 0);
+    let caminho_pacote = preparacao.join("pacote.zip");
+    baixar_pacote_modpack(&client, &versao.download_url, &caminho_pacote, tarefa).await?;
+    let arquivo_pacote = std::fs::File::open(&caminho_pacote)
+        .map_err(|e| format!("Erro ao abrir modpack: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(arquivo_pacote).map_err(|e| format!("Erro ao ler ZIP: {}", e))?;
+    let indice = ler_indice_modpack(&mut archive)?;
+
+    conferir_versao_minecraft(instance, &indice)?;
+
+    let (arquivos_curseforge, erros_curseforge) =
+        resolver_arquivos_curseforge(&client, &indice.arquivos_curseforge, tarefa).await?;
+    if indice.arquivos_curseforge.len() > arquivos_curseforge.len() {
+        return Err(format!(
+            "Não foi possível resolver todos os arquivos do modpack no CurseForge. {}",
+            erros_curseforge.join(" | ")
+        ));
+    }
+    // Opcionais seguem a escolha feita na instalação: só entram os que o
+    // pacote anterior já tinha.
+    let mut anteriores = arquivos_pacote_anterior(instance_path);
+    if anteriores.is_empty() {
+        anteriores =
+            reconstruir_pacote_anterior(&client, instance_path, info, preparacao, tarefa).await?;
+    }
+    let mut arquivos_indice: Vec<_> = indice
+        .arquivos
+        .iter()
+        .filter(|arquivo| !arquivo.opcional || anteriores.contains_key(&arquivo.caminho))
+        .cloned()
+        .collect();
+    arquivos_indice.extend(arquivos_curseforge);
+
+    let pasta_overrides = preparacao.join("overrides");
+    let overrides = extrair_overrides(
+        &mut archive,
+        &indice.pastas_overrides,
+        &pasta_overrides,
+        tarefa,
+    )?;
+
+    // 2. Comparar com o pacote instalado. Arquivos do índice sobrescrevem
+    //    overrides no mesmo caminho, como na instalação.
+    let mut novos: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for (relativo, sha1) in &overrides {
+        novos.insert(relativo.clone(), Some(sha1.clone()));
+    }
+    let indice_por_caminho: HashMap<String, usize> = arquivos_indice
+        .iter()
+        .enumerate()
+        .map(|(posicao, arquivo)| (arquivo.caminho.clone(), posicao))
+        .collect();
+    for (caminho, posicao) in &indice_por_caminho {
+        novos.insert(caminho.clone(), arquivos_indice[*posicao].sha1.clone());
+    }
+
+    let do_usuario = caminhos_do_usuario(instance_path);
+    let caminhos: HashSet<&String> = novos.keys().chain(anteriores.keys()).collect();
+    let mut acoes: BTreeMap<String, (AcaoArquivoModpack, Option<String>)> = BTreeMap::new();
+    for caminho in caminhos {
+        let em_disco = caminho_em_disco(instance_path, caminho);
+        let atual = em_disco
+            .as_ref()
+            .and_then(|relativo| sha1_arquivo(&instance_path.join(relativo)).ok());
+        let estado = EstadoArquivoModpack {
+            anterior: anteriores.get(caminho).map(String::as_str),
+            novo: novos.get(caminho).map(Option::as_deref),
+            atual: atual.as_deref(),
+            do_usuario: do_usuario.contains(caminho),
+        };
+        if let Some(acao) = decidir_acao(&estado) {
+            acoes.insert(caminho.clone(), (acao, em_disco));
+        }
+    }
+
+    // 3. Baixar só o que mudou
+    let precisa_arquivo = |caminho: &String| {
+        matches!(
+            acoes.get(caminho),
+            Some((
+                AcaoArquivoModpack::Adicionar | AcaoArquivoModpack::Atualizar,
+                _
+            ))
+        )
+    };
+    let pendentes: Vec<_> = arquivos_indice
+        .iter()
+        .filter(|arquivo| precisa_arquivo(&arquivo.caminho))
+        .cloned()
+        .collect();
+    let pasta_baixados = preparacao.join("arquivos");
+    tarefa.verificar_cancelamento()?;
+    let mut preparados: HashMap<String, ArquivoPreparado> = HashMap::new();
+    for (relativo, _) in &overrides {
+        if precisa_arquivo(relativo) && !indice_por_caminho.contains_key(relativo) {
+            preparados.insert(
+                relativo.clone(),
+                ArquivoPreparado {
+                    origem: pasta_overrides.join(relativo),
+                    entrada: None,
+                },
+            );
+        }
+    }
+    for resultado in baixar_arquivos_modpack(&client, &pasta_baixados, &pendentes, tarefa).await {
+        let baixado = resultado?;
+        if let Some(esperado) = novos.get(&baixado.caminho).cloned().flatten() {
+            if esperado != baixado.sha1 {
+                return Err(format!(
+                    "Hash de {} não confere com o índice do modpack.",
+                    baixado.caminho
+                ));
+            }
+        }
+        preparados.insert(
+            baixado.caminho.clone(),
+            ArquivoPreparado {
+                origem: pasta_baixados.join(&baixado.caminho),
+                entrada: baixado.entrada,
+            },
+        );
+    }
+    tarefa.verificar_cancelamento()?;
+
+    // 4. Trocar os arquivos
+    tarefa.fase("aplicando", acoes.len(), 0);
+    let mut operacoes = Vec::new();
+    let mut entradas_novas = Vec::new();
+    let mut removidos_lockfile = Vec::new();
+    for (caminho, (acao, em_disco)) in &acoes {
+        match acao {
+            AcaoArquivoModpack::Manter => {}
+            AcaoArquivoModpack::Preservar => relatorio.preservados.push(caminho.clone()),
+            AcaoArquivoModpack::Remover => {
+                operacoes.push((em_disco.clone(), None));
+                if let Some(tipo) = tipo_conteudo_por_caminho(caminho) {
+                    let nome = caminho.rsplit('/').next().unwrap_or(caminho);
+                    removidos_lockfile.push((tipo, nome.to_string()));
+                }
+                relatorio.removidos.push(caminho.clone());
+            }
+            AcaoArquivoModpack::Adicionar | AcaoArquivoModpack::Atualizar => {
+                let Some(preparado) = preparados.remove(caminho) else {
+                    relatorio.erros.push(format!(
+                        "{} não foi preparado e ficou como estava.",
+                        caminho
+                    ));
+                    continue;
+                };
+                // Arquivos desativados pelo usuário continuam desativados.
+                let destino = em_disco.clone().unwrap_or_else(|| caminho.clone());
+                if let Some(tipo) = tipo_conteudo_por_caminho(caminho) {
+                    let entrada = match preparado.entrada {
+                        Some(entrada) => Ok(entrada),
+                        None => EntradaConteudo::de_arquivo(
+                            &preparado.origem,
+                            tipo,
+                            OrigemInstalacao::Modpack,
+                        ),
+                    };
+                    match entrada {
+                        Ok(mut entrada) => {
+                            entrada.habilitado = !destino.ends_with(".disabled");
+                            entradas_novas.push(entrada);
+                        }
+                        Err(e) => eprintln!("[Modpack] {}", e),
+                    }
+                }
+                operacoes.push((em_disco.clone(), Some((destino, preparado.origem))));
+                if *acao == AcaoArquivoModpack::Adicionar {
+                    relatorio.adicionados.push(caminho.clone());
+                } else {
+                    relatorio.atualizados.push(caminho.clone());
+                }
+            }
+        }
+        tarefa.avancar(1, 0);
+    }
+    tarefa.verificar_cancelamento()?;
+    let aplicadas = aplicar_operacoes(instance_path, &preparacao.join("antigos"), operacoes)?;
+
+    // 5. Atualizar o loader; se falhar, os arquivos antigos voltam para o lugar
+    let instancia_atualizada = match alinhar_loader_com_modpack(instance, &indice, tarefa).await {
+        Ok(instancia) => instancia,
+        Err(e) => {
+            desfazer_operacoes(instance_path, &aplicadas);
+            return Err(e);
+        }
+    };
+    if let Some(nova) = &instancia_atualizada {
+        relatorio.loader_anterior = Some(descricao_loader(instance));
+        relatorio.loader_novo = Some(descricao_loader(nova));
+    }
+
+    // 6. Gravar registros
+    if let Some(nova) = &instancia_atualizada {
+        gravar_instancia(nova)?;
+    }
+
+    if let Err(e) = alterar_lockfile(instance_path, |lockfile| {
+        for (tipo, nome) in &removidos_lockfile {
+            lockfile.remover(tipo, nome);
+        }
+        for entrada in entradas_novas {
+            lockfile.registrar(entrada);
+        }
+    }) {
+        relatorio.erros.push(e);
+    }
+
+    let registro = ArquivosModpackInstalados {
+        versao_id: Some(versao.versao_id.clone()),
+        arquivos: novos
+            .iter()
+            .filter_map(|(caminho, sha1)| {
+                let sha1 = sha1.clone().or_else(|| {
+                    let em_disco = caminho_em_disco(instance_path, caminho)?;
+                    sha1_arquivo(&instance_path.join(em_disco)).ok()
+                })?;
+                Some((caminho.clone(), sha1))
+            })
+            .collect(),
+    };
+    if let Err(e) = salvar_arquivos_modpack(instance_path, &registro) {
+        relatorio.erros.push(e);
+    }
+
+    info.version_id = versao.versao_id.clone();
+    if info.source == "curseforge" {
+        info.file_id = Some(versao.versao_id.clone());
+    }
+    info.installed_version = versao.versao.clone();
+    gravar_modpack_info(instance_path, info)?;
+
+    relatorio.atualizado = true;
+    Ok(())
+}
+
+/// Atualiza o modpack da instância para a versão mais recente: baixa o pacote
+/// novo, compara com o instalado, mantém o conteúdo adicionado ou modificado
+/// pelo usuário, remove o que o pacote antigo trazia e atualiza o loader.
+#[tauri::command]
+pub(crate) async fn atualizar_modpack(
+    app: tauri::AppHandle,
+    state: State<'_, LauncherState>,
+    instance_id: String,
+    task_id: Option<String>,
+) -> Result<RelatorioAtualizacaoModpack, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let info = modpack_da_instancia(&instance)?;
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "atualizar_modpack",
+        &format!("Atualizando modpack {}", info.name),
+        Some(&instance_id),
+    )?;
+
+    let resultado = async {
+        tarefa.fase("verificando", 1, 0);
+        let Some(versao) = versao_mais_recente(&instance, &info).await? else {
+            return Ok(RelatorioAtualizacaoModpack {
+                versao_anterior: info.installed_version.clone(),
+                versao_nova: info.installed_version.clone(),
+                ..Default::default()
+            });
+        };
+        tarefa.avancar(1, 0);
+        println!(
+            "[Modpack] Atualizando {} de {} para {}",
+            instance_id, info.installed_version, versao.versao
+        );
+        executar_atualizacao_modpack(instance.clone(), info.clone(), versao, &tarefa).await
+    }
+    .await;
+
+    if let Ok(relatorio) = &resultado {
+        println!(
+            "[Modpack] {}: {} adicionado(s), {} atualizado(s), {} removido(s), {} preservado(s)",
+            instance_id,
+            relatorio.adicionados.len(),
+            relatorio.atualizados.len(),
+            relatorio.removidos.len(),
+            relatorio.preservados.len()
+        );
+    }
+    tarefa.finalizar(resultado)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn estado<'a>(
+        anterior: Option<&'a str>,
+        novo: Option<Option<&'a str>>,
+        atual: Option<&'a str>,
+    ) -> EstadoArquivoModpack<'a> {
+        EstadoArquivoModpack {
+            anterior,
+            novo,
+            atual,
+            do_usuario: false,
+        }
+    }
+
+    #[test]
+    fn atualiza_e_remove_apenas_arquivos_intactos_do_pacote() {
+        use AcaoArquivoModpack::*;
+        assert_eq!(
+            decidir_acao(&estado(Some("a"), Some(Some("b")), Some("a"))),
+            Some(Atualizar)
+        );
+        assert_eq!(
+            decidir_acao(&estado(Some("a"), Some(Some("b")), Some("b"))),
+            Some(Manter)
+        );
+        assert_eq!(
+            decidir_acao(&estado(None, Some(Some("b")), None)),
+            Some(Adicionar)
+        );
+        assert_eq!(
+            decidir_acao(&estado(Some("a"), None, Some("a"))),
+            Some(Remover)
+        );
+        assert_eq!(decidir_acao(&estado(Some("a"), None, None)), None);
+    }
+
+    #[test]
+    fn preserva_configs_modificadas_e_conteudo_do_usuario() {
+        use AcaoArquivoModpack::*;
+        // Config alterada pelo usuário: não é sobrescrita nem removida.
+        assert_eq!(
+            decidir_acao(&estado(Some("a"), Some(Some("b")), Some("x"))),
+            Some(Preservar)
+        );
+        assert_eq!(
+            decidir_acao(&estado(Some("a"), None, Some("x"))),
+            Some(Preservar)
+        );
+        // Arquivo que existia antes de qualquer registro do pacote.
+        assert_eq!(
+            decidir_acao(&estado(None, Some(Some("b")), Some("x"))),
+            Some(Preservar)
+        );
+        // Mod do usuário fora do pacote nunca entra na comparação.
+        assert_eq!(decidir_acao(&estado(None, None, Some("x"))), None);
+
+        let mut do_usuario = estado(Some("a"), None, Some("a"));
+        do_usuario.do_usuario = true;
+        assert_eq!(decidir_acao(&do_usuario), Some(Preservar));
+    }
+
+    #[test]
+    fn arquivos_identificados_nao_contam_como_do_usuario() {
+        let pasta = std::env::temp_dir().join(format!("dome_pack_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        let entrada = |arquivo: &str, origem: OrigemInstalacao| {
+            EntradaConteudo::nova(arquivo, "mod", Default::default(), origem)
+        };
+        let mut fixado = entrada("fixado.jar", OrigemInstalacao::Modpack);
+        fixado.fixado = true;
+        super::super::registro_conteudo::registrar_conteudo(
+            &pasta,
+            vec![
+                entrada("sodium.jar", OrigemInstalacao::Usuario),
+                entrada("fabric-api.jar", OrigemInstalacao::Dependencia),
+                entrada("create.jar", OrigemInstalacao::Modpack),
+                entrada("jei.jar", OrigemInstalacao::Desconhecida),
+                fixado,
+            ],
+        )
+        .unwrap();
+
+        let do_usuario = caminhos_do_usuario(&pasta);
+        let mut caminhos: Vec<&str> = do_usuario.iter().map(String::as_str).collect();
+        caminhos.sort();
+        assert_eq!(
+            caminhos,
+            vec!["mods/fabric-api.jar", "mods/fixado.jar", "mods/sodium.jar"]
+        );
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn compara_versao_do_forge_com_e_sem_prefixo_do_minecraft() {
+        assert!(mesma_versao_loader(
+            Some("1.20.1-47.2.0"),
+            "47.2.0",
+            "1.20.1"
+        ));
+        assert!(mesma_versao_loader(Some("0.15.11"), "0.15.11", "1.20.1"));
+        assert!(!mesma_versao_loader(Some("47.1.0"), "47.2.0", "1.20.1"));
+        assert!(!mesma_versao_loader(None, "47.2.0", "1.20.1"));
+    }
+}
@@ -11,6 +11,12 @@ pub fn run() {
                     janela.open_devtools();
                 }
             }
+
+            // Migração única das instâncias antigas para o armazenamento compartilhado
+            let instances_path = app.state::<LauncherState>().instances_path.clone();
+            std::thread::spawn(move || {
+                super::armazenamento_compartilhado::migrar_instancias_para_store(&instances_path);
+            });
             Ok(())
         })
         .manage(LauncherState::new())
@@ -26,7 +32,7 @@ pub fn run() {
             super::lancamento_jogo::launch_instance,
             super::lancamento_jogo::launch_instance_to_server,
             super::instancias_basicas::get_minecraft_versions,
-            super::instancias_criacao::get_loader_versions, // Busca versões de loaders (Fabric, Forge, NeoForge)
+            super::instancias_criacao::get_loader_versions, // Busca versões de loaders (Fabric, Quilt, Forge, NeoForge)
             super::instancias_basicas::delete_instance,
             super::instancias_basicas::abrir_pasta_instancia,
             super::instancias_basicas::open_browser,
@@ -49,18 +55,37 @@ pub fn run() {
             super::mods_conteudo::install_mod,
             super::mods_conteudo::install_project_file,
             super::mods_conteudo::install_curseforge_project_file,
+            super::resolucao_dependencias::resolver_plano_instalacao,
+            super::resolucao_dependencias::executar_plano_instalacao,
+            super::registro_conteudo::obter_lockfile_conteudo,
+            super::registro_conteudo::definir_fixacao_conteudo,
             super::mods_conteudo::resolver_modpack_curseforge,
             super::mods_conteudo::get_installed_mods,
             super::mods_conteudo::get_installed_resourcepacks,
             super::mods_conteudo::get_installed_shaders,
             super::conteudo_instalado::obter_conteudo_instalado_detalhado,
+            super::identificacao_conteudo::identificar_conteudo_instalado,
+            super::atualizacao_conteudo::verificar_atualizacoes_conteudo,
+            super::atualizacao_conteudo::aplicar_atualizacoes_conteudo,
+            super::atualizacao_conteudo::listar_backups_atualizacao,
+            super::atualizacao_conteudo::reverter_atualizacao_conteudo,
             // Gerenciamento de instâncias
             super::instancias_basicas::get_instance_details,
             super::instancias_basicas::update_instance_name,
             super::instancias_basicas::update_instance_settings,
+            super::troca_versao::update_instance_version,
+            super::troca_versao::obter_backup_versao_instancia,
+            super::troca_versao::reverter_versao_instancia,
+            super::clonagem_instancia::clone_instance,
+            super::configuracao_lancamento::obter_configuracao_lancamento_efetiva,
             super::instancias_basicas::rename_instance_folder,
+            super::verificacao_arquivos::verify_instance_files,
+            super::armazenamento_compartilhado::limpar_armazenamento_compartilhado,
             // Exportação / Importação de instâncias
             super::importacao_exportacao::exportar_instancia,
+            super::exportacao_modpack::listar_itens_exportacao,
+            super::exportacao_modpack::exportar_mrpack,
+            super::exportacao_modpack::exportar_modpack_curseforge,
             super::importacao_exportacao::importar_instancia_arquivo,
             // Gerenciamento de mundos
             crate::comandos::instancia_sistema::get_worlds,
@@ -73,6 +98,7 @@ pub fn run() {
             // Gerenciamento de logs
             crate::comandos::logs_instancia::get_log_files,
             crate::comandos::logs_instancia::get_log_content,
+            crate::comandos::logs_instancia::read_log_page,
             crate::comandos::logs_instancia::delete_log_file,
             // Remoção de mods
             super::mods_conteudo::remove_mod,
@@ -85,8 +111,14 @@ pub fn run() {
             // Modpacks
             crate::comandos::modpacks::save_modpack_info,
             crate::comandos::modpacks::install_modpack_files,
+            crate::comandos::modpacks::ler_modpack,
+            crate::comandos::tarefas::list_tasks,
+            crate::comandos::tarefas::cancel_task,
+            crate::comandos::saida_jogo::get_game_output,
+            crate::comandos::analise_crash::get_last_run_result,
             crate::comandos::modpacks::get_modpack_info,
-            crate::comandos::modpacks::check_modpack_updates,
+            super::atualizacao_modpack::check_modpack_updates,
+            super::atualizacao_modpack::atualizar_modpack,
             crate::comandos::noticias_minecraft::get_minecraft_news,
             crate::comandos::social_launcher::get_launcher_friends,
             crate::comandos::social_launcher::search_launcher_friend_by_handle,
@@ -0,0 +1,345 @@
+use super::armazenamento_compartilhado::ARQUIVO_REFERENCIAS;
+use super::atualizacao_modpack::gravar_instancia;
+use super::importacao_exportacao::{
+    copiar_arquivo_com_modo, copiar_diretorio_com_modo, gerar_nome_instancia_unico, ModoCopia,
+    ResumoCopia,
+};
+use super::instancias_basicas::normalizar_nome_pasta_instancia;
+use super::registro_conteudo::{alterar_lockfile, ARQUIVO_LOCKFILE_CONTEUDO};
+use super::*;
+use crate::comandos::instancia_sistema::obter_mapa_instancias_em_execucao;
+use crate::comandos::modpacks::ARQUIVO_REGISTRO_MODPACK;
+use crate::comandos::tarefas::Tarefa;
+use std::path::Path;
+
+/// Itens sempre copiados: sem eles o clone não abre a mesma versão e loader.
+const ITENS_BASE: &[(&str, ModoCopia)] = &[
+    ("version_manifest.json", ModoCopia::Copiar),
+    ("forge_manifest.json", ModoCopia::Copiar),
+    ("neoforge_manifest.json", ModoCopia::Copiar),
+    ("fabric_manifest.json", ModoCopia::Copiar),
+    ("quilt_manifest.json", ModoCopia::Copiar),
+    (ARQUIVO_REFERENCIAS, ModoCopia::Copiar),
+    ("libraries", ModoCopia::Vincular),
+];
+
+/// Partes da instância que entram no clone. Todas vêm marcadas por padrão.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct OpcoesClonagem {
+    pub mundos: bool,
+    /// Configurações de mods e do jogo (`config`, `options.txt`, servidores).
+    pub configs: bool,
+    pub mods: bool,
+    /// Resource packs e shaders.
+    pub pacotes_recursos: bool,
+    pub capturas: bool,
+    /// Memória, argumentos, Java e janela definidos na instância.
+    pub configuracoes: bool,
+}
+
+impl Default for OpcoesClonagem {
+    fn default() -> Self {
+        Self {
+            mundos: true,
+            configs: true,
+            mods: true,
+            pacotes_recursos: true,
+            capturas: true,
+            configuracoes: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResultadoClonagem {
+    pub instancia_id: String,
+    pub nome: String,
+    pub arquivos_copiados: usize,
+    pub arquivos_vinculados: usize,
+}
+
+/// Itens da raiz da instância a copiar, com o modo de cópia de cada um.
+fn itens_clonagem(opcoes: &OpcoesClonagem) -> Vec<(&'static str, ModoCopia)> {
+    let mut itens = ITENS_BASE.to_vec();
+    if opcoes.mundos {
+        itens.push(("saves", ModoCopia::Copiar));
+    }
+    if opcoes.configs {
+        for item in [
+            "config",
+            "defaultconfigs",
+            "kubejs",
+            "scripts",
+            "options.txt",
+            "optionsof.txt",
+            "optionsshaders.txt",
+            "servers.dat",
+        ] {
+            itens.push((item, ModoCopia::Copiar));
+        }
+    }
+    if opcoes.mods {
+        itens.push(("mods", ModoCopia::Vincular));
+        itens.push(("modpack.json", ModoCopia::Copiar));
+        itens.push((ARQUIVO_REGISTRO_MODPACK, ModoCopia::Copiar));
+    }
+    if opcoes.pacotes_recursos {
+        itens.push(("resourcepacks", ModoCopia::Vincular));
+        itens.push(("shaderpacks", ModoCopia::Vincular));
+    }
+    if opcoes.mods || opcoes.pacotes_recursos {
+        itens.push((ARQUIVO_LOCKFILE_CONTEUDO, ModoCopia::Copiar));
+    }
+    if opcoes.capturas {
+        itens.push(("screenshots", ModoCopia::Vincular));
+    }
+    itens
+}
+
+/// Copia as partes escolhidas e tira do lockfile o conteúdo que ficou de fora.
+fn copiar_conteudo_clonado(
+    origem: &Path,
+    destino: &Path,
+    opcoes: &OpcoesClonagem,
+    tarefa: Option<&Tarefa>,
+) -> Result<ResumoCopia, String> {
+    let itens = itens_clonagem(opcoes);
+    if let Some(tarefa) = tarefa {
+        tarefa.fase("copiando", itens.len(), 0);
+    }
+
+    let mut resumo = ResumoCopia::default();
+    for (item, modo) in itens {
+        if let Some(tarefa) = tarefa {
+            tarefa.verificar_cancelamento()?;
+        }
+        let origem_item = origem.join(item);
+        let destino_item = destino.join(item);
+        let copiado = if origem_item.is_dir() {
+            copiar_diretorio_com_modo(&origem_item, &destino_item, modo)?
+        } else {
+            copiar_arquivo_com_modo(&origem_item, &destino_item, modo)?
+        };
+        resumo.somar(copiado);
+        if let Some(tarefa) = tarefa {
+            tarefa.avancar(1, 0);
+        }
+    }
+
+    if destino.join(ARQUIVO_LOCKFILE_CONTEUDO).exists() && !(opcoes.mods && opcoes.pacotes_recursos)
+    {
+        alterar_lockfile(destino, |lockfile| {
+            lockfile.entradas.retain(|entrada| {
+                if entrada.tipo_conteudo == "mod" {
+                    opcoes.mods
+                } else {
+                    opcoes.pacotes_recursos
+                }
+            })
+        })?;
+    }
+    Ok(resumo)
+}
+
+/// Registro do clone: caminho e id próprios, tempo de jogo zerado e, se
+/// pedido, sem as configurações de lançamento da original.
+fn instancia_clonada(
+    original: &Instance,
+    id: String,
+    nome: String,
+    path: std::path::PathBuf,
+    opcoes: &OpcoesClonagem,
+) -> Instance {
+    let mut clone = original.clone();
+    clone.id = id;
+    clone.name = nome;
+    clone.path = path;
+    clone.created = chrono::Utc::now().to_rfc3339();
+    clone.last_played = None;
+    clone.tempo_total_jogado_segundos = 0;
+    clone.sessao_iniciada_em = None;
+    if !opcoes.configuracoes {
+        clone.java_args = None;
+        clone.mc_args = None;
+        clone.java_path = None;
+        clone.memory = None;
+        clone.width = None;
+        clone.height = None;
+    }
+    clone
+}
+
+/// Duplica uma instância copiando só as partes escolhidas. Jars, pacotes e
+/// capturas viram hardlinks quando possível; o restante é copiado, com
+/// reflink nos sistemas de arquivos que suportam.
+#[tauri::command]
+pub(crate) async fn clone_instance(
+    app: tauri::AppHandle,
+    state: State<'_, LauncherState>,
+    instance_id: String,
+    nome: Option<String>,
+    opcoes: Option<OpcoesClonagem>,
+    task_id: Option<String>,
+) -> Result<ResultadoClonagem, String> {
+    let original = obter_instancia_por_id(&state, &instance_id)?;
+    let opcoes = opcoes.unwrap_or_default();
+    if opcoes.mundos {
+        let em_execucao =
+            obter_mapa_instancias_em_execucao(&state, std::slice::from_ref(&instance_id))?;
+        if em_execucao.get(&instance_id).copied().unwrap_or(false) {
+            return Err("Feche o jogo antes de clonar os mundos da instância.".to_string());
+        }
+    }
+
+    let nome_base = nome
+        .map(|nome| nome.trim().to_string())
+        .filter(|nome| !nome.is_empty())
+        .unwrap_or_else(|| format!("{} (cópia)", original.name));
+    let nome_unico = gerar_nome_instancia_unico(&state, &nome_base);
+    let id = normalizar_nome_pasta_instancia(&nome_unico);
+    let destino = caminho_instancia_por_id(&state, &id)?;
+    if destino.exists() {
+        return Err(format!("Já existe uma instância em '{}'.", id));
+    }
+
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "clonar_instancia",
+        &format!("Clonando {}", original.name),
+        Some(&instance_id),
+    )?;
+    tarefa.limpar_ao_cancelar(destino.clone());
+
+    let resultado = std::fs::create_dir_all(&destino)
+        .map_err(|e| format!("Erro ao criar pasta da instância: {}", e))
+        .and_then(|_| copiar_conteudo_clonado(&original.path, &destino, &opcoes, Some(&tarefa)))
+        .and_then(|resumo| {
+            let clone =
+                instancia_clonada(&original, id.clone(), nome_unico, destino.clone(), &opcoes);
+            gravar_instancia(&clone)?;
+            println!(
+                "[Clonagem] {} -> {}: {} arquivo(s) copiado(s), {} vinculado(s)",
+                instance_id, id, resumo.copiados, resumo.vinculados
+            );
+            Ok(ResultadoClonagem {
+                instancia_id: clone.id,
+                nome: clone.name,
+                arquivos_copiados: resumo.copiados,
+                arquivos_vinculados: resumo.vinculados,
+            })
+        });
+    if resultado.is_err() {
+        let _ = std::fs::remove_dir_all(&destino);
+    }
+    tarefa.finalizar(resultado)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::super::registro_conteudo::{
+        carregar_lockfile, registrar_conteudo, EntradaConteudo, HashesConteudo, OrigemInstalacao,
+    };
+    use super::*;
+
+    fn pasta_temporaria() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("dome-clonagem-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn escrever(caminho: &Path, conteudo: &str) {
+        std::fs::create_dir_all(caminho.parent().unwrap()).unwrap();
+        std::fs::write(caminho, conteudo).unwrap();
+    }
+
+    #[test]
+    fn copia_so_as_partes_escolhidas_e_filtra_o_lockfile() {
+        let origem = pasta_temporaria();
+        let destino = pasta_temporaria();
+        escrever(&origem.join("version_manifest.json"), "{}");
+        escrever(&origem.join("saves/Mundo/level.dat"), "mundo");
+        escrever(&origem.join("mods/sodium.jar"), "jar");
+        escrever(&origem.join("resourcepacks/faithful.zip"), "pack");
+        escrever(&origem.join("screenshots/tela.png"), "png");
+        registrar_conteudo(
+            &origem,
+            vec![
+                EntradaConteudo::nova(
+                    "sodium.jar",
+                    "mod",
+                    HashesConteudo::default(),
+                    OrigemInstalacao::Usuario,
+                ),
+                EntradaConteudo::nova(
+                    "faithful.zip",
+                    "resourcepack",
+                    HashesConteudo::default(),
+                    OrigemInstalacao::Usuario,
+                ),
+            ],
+        )
+        .unwrap();
+
+        let opcoes = OpcoesClonagem {
+            mundos: false,
+            pacotes_recursos: false,
+            ..Default::default()
+        };
+        let resumo = copiar_conteudo_clonado(&origem, &destino, &opcoes, None).unwrap();
+
+        assert!(destino.join("version_manifest.json").exists());
+        assert!(destino.join("mods/sodium.jar").exists());
+        assert!(destino.join("screenshots/tela.png").exists());
+        assert!(!destino.join("saves").exists());
+        assert!(!destino.join("resourcepacks").exists());
+        assert_eq!(resumo.copiados + resumo.vinculados, 4);
+        let entradas = carregar_lockfile(&destino).entradas;
+        assert_eq!(entradas.len(), 1);
+        assert_eq!(entradas[0].arquivo, "sodium.jar");
+        assert_eq!(carregar_lockfile(&origem).entradas.len(), 2);
+
+        let _ = std::fs::remove_dir_all(&origem);
+        let _ = std::fs::remove_dir_all(&destino);
+    }
+
+    #[test]
+    fn clone_tem_caminho_proprio_e_tempo_zerado() {
+        let original: Instance = serde_json::from_value(serde_json::json!({
+            "id": "original",
+            "name": "Original",
+            "version": "1.20.1",
+            "mcType": "fabric",
+            "loaderType": "Fabric",
+            "loaderVersion": "0.15.11",
+            "created": "2024-01-01T00:00:00Z",
+            "lastPlayed": "2024-02-01T00:00:00Z",
+            "tempoTotalJogadoSegundos": 3600,
+            "path": "/instancias/original",
+            "memory": 6144,
+            "javaArgs": "-XX:+UseG1GC",
+        }))
+        .unwrap();
+        let opcoes = OpcoesClonagem {
+            configuracoes: false,
+            ..Default::default()
+        };
+
+        let clone = instancia_clonada(
+            &original,
+            "original_copia".to_string(),
+            "Original (cópia)".to_string(),
+            std::path::PathBuf::from("/instancias/original_copia"),
+            &opcoes,
+        );
+        assert_eq!(clone.id, "original_copia");
+        assert_eq!(
+            clone.path,
+            std::path::PathBuf::from("/instancias/original_copia")
+        );
+        assert_eq!(clone.tempo_total_jogado_segundos, 0);
+        assert!(clone.last_played.is_none());
+        assert!(clone.memory.is_none() && clone.java_args.is_none());
+        assert_eq!(clone.loader_version.as_deref(), Some("0.15.11"));
+    }
+}
@@ -0,0 +1,231 @@
+use super::*;
+use crate::comandos::configuracoes_java::GlobalSettings;
+
+/// Camada de onde veio cada valor efetivo usado no launch.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OrigemConfiguracao {
+    Instancia,
+    Global,
+    Padrao,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ValorConfigurado<T> {
+    pub valor: T,
+    pub origem: OrigemConfiguracao,
+}
+
+impl<T> ValorConfigurado<T> {
+    fn novo(valor: T, origem: OrigemConfiguracao) -> Self {
+        Self { valor, origem }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfiguracaoLancamentoEfetiva {
+    pub memoria_mb: ValorConfigurado<u32>,
+    pub java_args: ValorConfigurado<Vec<String>>,
+    pub game_args: ValorConfigurado<Vec<String>>,
+    pub largura: ValorConfigurado<u32>,
+    pub altura: ValorConfigurado<u32>,
+    /// `None` significa seleção automática do Java pelo launcher.
+    pub java_path: ValorConfigurado<Option<String>>,
+}
+
+impl ConfiguracaoLancamentoEfetiva {
+    /// Argumentos de memória da JVM (`-Xmx`/`-Xms`).
+    pub(crate) fn argumentos_memoria(&self) -> Vec<String> {
+        let ram_mb = self.memoria_mb.valor;
+        vec![
+            format!("-Xmx{}M", ram_mb),
+            format!("-Xms{}M", (ram_mb / 2).max(512).min(ram_mb)),
+        ]
+    }
+}
+
+fn texto_configurado(valor: Option<&str>) -> Option<&str> {
+    valor.map(str::trim).filter(|texto| !texto.is_empty())
+}
+
+fn separar_argumentos(texto: &str) -> Vec<String> {
+    texto
+        .split_whitespace()
+        .map(|arg| arg.to_string())
+        .collect()
+}
+
+/// Resolve as configurações de launch em camadas: instância, depois globais
+/// salvas e, por fim, os padrões do launcher.
+///
+/// `globais` é `None` quando o usuário nunca salvou as configurações globais.
+pub(crate) fn resolver_configuracao_lancamento(
+    instancia: &Instance,
+    globais: Option<&GlobalSettings>,
+    padrao: &GlobalSettings,
+) -> ConfiguracaoLancamentoEfetiva {
+    use OrigemConfiguracao::{Global, Instancia, Padrao};
+
+    let (base, origem_base) = match globais {
+        Some(globais) => (globais, Global),
+        None => (padrao, Padrao),
+    };
+
+    let memoria_mb = match instancia.memory {
+        Some(memoria) => ValorConfigurado::novo(memoria, Instancia),
+        None => ValorConfigurado::novo(base.ram_mb, origem_base),
+    };
+
+    let java_args = if let Some(args) = texto_configurado(instancia.java_args.as_deref()) {
+        ValorConfigurado::novo(separar_argumentos(args), Instancia)
+    } else if let Some(args) = texto_configurado(Some(base.java_args.as_str())) {
+        ValorConfigurado::novo(separar_argumentos(args), origem_base)
+    } else {
+        ValorConfigurado::novo(Vec::new(), Padrao)
+    };
+
+    let game_args = match texto_configurado(instancia.mc_args.as_deref()) {
+        Some(args) => ValorConfigurado::novo(separar_argumentos(args), Instancia),
+        None => ValorConfigurado::novo(Vec::new(), Padrao),
+    };
+
+    let largura = match instancia.width {
+        Some(largura) => ValorConfigurado::novo(largura, Instancia),
+        None => ValorConfigurado::novo(base.width, origem_base),
+    };
+    let altura = match instancia.height {
+        Some(altura) => ValorConfigurado::novo(altura, Instancia),
+        None => ValorConfigurado::novo(base.height, origem_base),
+    };
+
+    // O caminho global só vale quando o gerenciamento automático está desligado.
+    let java_path_global = globais
+        .filter(|globais| !globais.auto_java)
+        .and_then(|globais| texto_configurado(globais.java_path.as_deref()));
+    let java_path = if let Some(caminho) = texto_configurado(instancia.java_path.as_deref()) {
+        ValorConfigurado::novo(Some(caminho.to_string()), Instancia)
+    } else if let Some(caminho) = java_path_global {
+        ValorConfigurado::novo(Some(caminho.to_string()), Global)
+    } else {
+        ValorConfigurado::novo(None, Padrao)
+    };
+
+    ConfiguracaoLancamentoEfetiva {
+        memoria_mb,
+        java_args,
+        game_args,
+        largura,
+        altura,
+        java_path,
+    }
+}
+
+pub(super) fn carregar_configuracao_lancamento(
+    instancia: &Instance,
+) -> ConfiguracaoLancamentoEfetiva {
+    let globais = crate::comandos::configuracoes_java::carregar_settings_salvas();
+    let padrao = GlobalSettings::default();
+    resolver_configuracao_lancamento(instancia, globais.as_ref(), &padrao)
+}
+
+#[tauri::command]
+pub(crate) async fn obter_configuracao_lancamento_efetiva(
+    state: State<'_, LauncherState>,
+    instance_id: String,
+) -> Result<ConfiguracaoLancamentoEfetiva, String> {
+    let instancia = obter_instancia_por_id(&state, &instance_id)?;
+    Ok(carregar_configuracao_lancamento(&instancia))
+}
+
+#[cfg(test)]
+mod testes {
+    use super::{resolver_configuracao_lancamento, OrigemConfiguracao};
+    use crate::comandos::configuracoes_java::GlobalSettings;
+    use crate::launcher::Instance;
+
+    fn instancia_vazia() -> Instance {
+        serde_json::from_value(serde_json::json!({
+            "id": "teste",
+            "name": "Teste",
+            "version": "1.20.1",
+            "mcType": "vanilla",
+            "path": "/tmp/teste",
+            "created": "2024-01-01T00:00:00Z"
+        }))
+        .expect("instância mínima deve ser válida")
+    }
+
+    fn settings(ram_mb: u32, java_args: &str) -> GlobalSettings {
+        GlobalSettings {
+            ram_mb,
+            java_args: java_args.to_string(),
+            ..GlobalSettings::default()
+        }
+    }
+
+    #[test]
+    fn instancia_sobrepoe_globais() {
+        let mut instancia = instancia_vazia();
+        instancia.memory = Some(10240);
+        instancia.java_args = Some("-XX:+UseZGC".to_string());
+        instancia.mc_args = Some("--demo  --fullscreen".to_string());
+        instancia.width = Some(1920);
+
+        let globais = settings(4096, "-XX:+UseG1GC");
+        let efetiva =
+            resolver_configuracao_lancamento(&instancia, Some(&globais), &settings(2048, ""));
+
+        assert_eq!(efetiva.memoria_mb.valor, 10240);
+        assert_eq!(efetiva.memoria_mb.origem, OrigemConfiguracao::Instancia);
+        assert_eq!(efetiva.java_args.valor, vec!["-XX:+UseZGC"]);
+        assert_eq!(efetiva.game_args.valor, vec!["--demo", "--fullscreen"]);
+        assert_eq!(efetiva.largura.valor, 1920);
+        assert_eq!(efetiva.altura.origem, OrigemConfiguracao::Global);
+        assert_eq!(
+            efetiva.argumentos_memoria(),
+            vec!["-Xmx10240M", "-Xms5120M"]
+        );
+    }
+
+    #[test]
+    fn usa_globais_e_depois_padroes() {
+        let instancia = instancia_vazia();
+        let padrao = settings(2048, "-XX:+UseG1GC");
+
+        let com_globais =
+            resolver_configuracao_lancamento(&instancia, Some(&settings(6144, "")), &padrao);
+        assert_eq!(com_globais.memoria_mb.valor, 6144);
+        assert_eq!(com_globais.memoria_mb.origem, OrigemConfiguracao::Global);
+        assert!(com_globais.java_args.valor.is_empty());
+        assert_eq!(com_globais.java_args.origem, OrigemConfiguracao::Padrao);
+
+        let sem_globais = resolver_configuracao_lancamento(&instancia, None, &padrao);
+        assert_eq!(sem_globais.memoria_mb.valor, 2048);
+        assert_eq!(sem_globais.memoria_mb.origem, OrigemConfiguracao::Padrao);
+        assert_eq!(sem_globais.java_args.valor, vec!["-XX:+UseG1GC"]);
+    }
+
+    #[test]
+    fn java_global_so_vale_sem_gerenciamento_automatico() {
+        let mut instancia = instancia_vazia();
+        let mut globais = settings(4096, "");
+        globais.java_path = Some("/opt/java17".to_string());
+        globais.auto_java = true;
+        let padrao = settings(2048, "");
+
+        let automatico = resolver_configuracao_lancamento(&instancia, Some(&globais), &padrao);
+        assert_eq!(automatico.java_path.valor, None);
+
+        globais.auto_java = false;
+        let manual = resolver_configuracao_lancamento(&instancia, Some(&globais), &padrao);
+        assert_eq!(manual.java_path.valor.as_deref(), Some("/opt/java17"));
+        assert_eq!(manual.java_path.origem, OrigemConfiguracao::Global);
+
+        instancia.java_path = Some("/opt/java21".to_string());
+        let da_instancia = resolver_configuracao_lancamento(&instancia, Some(&globais), &padrao);
+        assert_eq!(da_instancia.java_path.valor.as_deref(), Some("/opt/java21"));
+        assert_eq!(da_instancia.java_path.origem, OrigemConfiguracao::Instancia);
+    }
+}
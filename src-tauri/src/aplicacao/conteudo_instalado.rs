@@ -1,3 +1,4 @@
+use super::registro_conteudo::{carregar_lockfile, nome_habilitado, EntradaConteudo};
 use super::*;
 use base64::Engine as _;
 use std::io::{Read, Seek};
@@ -14,6 +15,11 @@ pub(crate) struct ConteudoInstaladoDetalhado {
     pub author: String,
     pub icon: Option<String>,
     pub enabled: bool,
+    /// Origem conhecida pelo lockfile (instalação ou identificação por hash).
+    pub platform: Option<ModPlatform>,
+    pub project_id: Option<String>,
+    pub version_id: Option<String>,
+    pub pinned: bool,
 }
 
 #[derive(Default)]
@@ -32,10 +38,10 @@ pub(crate) async fn obter_conteudo_instalado_detalhado(
 ) -> Result<Vec<ConteudoInstaladoDetalhado>, String> {
     let instancia = obter_instancia_por_id(&state, &instance_id)?;
     let tipo = content_type.trim().to_lowercase();
-    let (pasta, aceita_diretorio) = match tipo.as_str() {
-        "mods" => ("mods", false),
-        "resourcepacks" => ("resourcepacks", true),
-        "shaders" => ("shaderpacks", true),
+    let (pasta, aceita_diretorio, tipo_lockfile) = match tipo.as_str() {
+        "mods" => ("mods", false, "mod"),
+        "resourcepacks" => ("resourcepacks", true, "resourcepack"),
+        "shaders" => ("shaderpacks", true, "shader"),
         _ => return Err("Tipo de conteúdo inválido.".to_string()),
     };
 
@@ -44,8 +50,14 @@ pub(crate) async fn obter_conteudo_instalado_detalhado(
         return Ok(Vec::new());
     }
 
+    let instance_path = instancia.path.clone();
     tauri::async_runtime::spawn_blocking(move || {
-        listar_conteudo_da_pasta(&caminho_pasta, &tipo, aceita_diretorio)
+        let entradas: Vec<EntradaConteudo> = carregar_lockfile(&instance_path)
+            .entradas
+            .into_iter()
+            .filter(|entrada| entrada.tipo_conteudo == tipo_lockfile)
+            .collect();
+        listar_conteudo_da_pasta(&caminho_pasta, &tipo, aceita_diretorio, &entradas)
     })
     .await
     .map_err(|e| format!("Falha ao inspecionar conteúdo instalado: {}", e))?
@@ -55,6 +67,7 @@ fn listar_conteudo_da_pasta(
     caminho_pasta: &std::path::Path,
     tipo: &str,
     aceita_diretorio: bool,
+    lockfile: &[EntradaConteudo],
 ) -> Result<Vec<ConteudoInstaladoDetalhado>, String> {
     let mut conteudos = std::fs::read_dir(caminho_pasta)
         .map_err(|e| format!("Erro ao ler conteúdo instalado: {}", e))?
@@ -73,7 +86,15 @@ fn listar_conteudo_da_pasta(
                 return None;
             }
 
-            Some(inspecionar_conteudo(&caminho, &nome_arquivo, tipo))
+            let registro = lockfile
+                .iter()
+                .find(|entrada| entrada.arquivo == nome_habilitado(&nome_arquivo));
+            Some(inspecionar_conteudo(
+                &caminho,
+                &nome_arquivo,
+                tipo,
+                registro,
+            ))
         })
         .collect::<Vec<_>>();
 
@@ -85,6 +106,7 @@ fn inspecionar_conteudo(
     caminho: &std::path::Path,
     nome_arquivo: &str,
     tipo: &str,
+    registro: Option<&EntradaConteudo>,
 ) -> ConteudoInstaladoDetalhado {
     let habilitado = !nome_arquivo.to_lowercase().ends_with(".disabled");
     let nome_fallback = nome_legivel_arquivo(nome_arquivo);
@@ -94,13 +116,22 @@ fn inspecionar_conteudo(
         inspecionar_arquivo_compactado(caminho, tipo)
     };
 
+    // Metadados do próprio arquivo têm prioridade; o projeto identificado
+    // preenche nome e ícone de pacotes que não trazem os seus.
+    let nome_projeto = registro.and_then(|r| r.nome.clone());
+    let icone_projeto = registro.and_then(|r| r.icone_url.clone());
+
     ConteudoInstaladoDetalhado {
         file_name: nome_arquivo.to_string(),
-        name: metadados.nome.unwrap_or(nome_fallback),
+        name: metadados.nome.or(nome_projeto).unwrap_or(nome_fallback),
         version: metadados.versao.unwrap_or_default(),
         author: metadados.autor.unwrap_or_else(|| "Unknown".to_string()),
-        icon: icone,
+        icon: icone.or(icone_projeto),
         enabled: habilitado,
+        platform: registro.and_then(|r| r.plataforma.clone()),
+        project_id: registro.and_then(|r| r.project_id.clone()),
+        version_id: registro.and_then(|r| r.versao_id.clone()),
+        pinned: registro.is_some_and(|r| r.fixado),
     }
 }
 
@@ -537,7 +568,7 @@ mod testes {
         compactado.finish().expect("deve finalizar arquivo");
 
         for tipo in ["resourcepacks", "shaders"] {
-            let conteudo = inspecionar_conteudo(&caminho, "Pack Bonito.zip", tipo);
+            let conteudo = inspecionar_conteudo(&caminho, "Pack Bonito.zip", tipo, None);
             assert_eq!(conteudo.name, "Pack Bonito");
             assert!(conteudo
                 .icon
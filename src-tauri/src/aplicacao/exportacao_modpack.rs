@@ -0,0 +1,753 @@
+use super::extracao_segura::caminho_relativo_seguro;
+use super::importacao_exportacao::{
+    nome_arquivo_exportacao, resolver_pasta_destino_exportacao, ResultadoExportacao,
+};
+use super::registro_conteudo::{
+    carregar_lockfile, nome_habilitado, tipo_conteudo_por_caminho, EntradaConteudo, HashesConteudo,
+};
+use super::*;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Itens da instância incluídos quando a exportação não escolhe outros.
+const ITENS_EXPORTACAO_PADRAO: &[&str] = &[
+    "mods",
+    "resourcepacks",
+    "shaderpacks",
+    "config",
+    "defaultconfigs",
+    "kubejs",
+    "scripts",
+    "options.txt",
+    "optionsof.txt",
+    "optionsshaders.txt",
+    "servers.dat",
+];
+
+/// Arquivos e pastas internos do launcher que nunca vão para um modpack.
+const ITENS_INTERNOS: &[&str] = &[
+    "instance.json",
+    "modpack.json",
+    "temp",
+    crate::comandos::modpacks::ARQUIVO_REGISTRO_MODPACK,
+    crate::comandos::analise_crash::ARQUIVO_ULTIMA_EXECUCAO,
+    super::registro_conteudo::ARQUIVO_LOCKFILE_CONTEUDO,
+    super::armazenamento_compartilhado::ARQUIVO_REFERENCIAS,
+    super::atualizacao_conteudo::PASTA_BACKUPS_CONTEUDO,
+    super::atualizacao_conteudo::PASTA_PREPARACAO_ATUALIZACAO,
+    super::atualizacao_modpack::PASTA_PREPARACAO_MODPACK,
+    super::troca_versao::PASTA_BACKUP_VERSAO,
+];
+
+fn item_interno(caminho: &str) -> bool {
+    let lockfile_corrompido = format!(
+        "{}{}",
+        super::registro_conteudo::ARQUIVO_LOCKFILE_CONTEUDO,
+        super::registro_conteudo::SUFIXO_LOCKFILE_CORROMPIDO
+    );
+    caminho == lockfile_corrompido
+        || ITENS_INTERNOS.iter().any(|item| {
+            caminho == *item
+                || caminho
+                    .strip_prefix(item)
+                    .is_some_and(|resto| resto.starts_with('/'))
+        })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct OpcoesExportacaoModpack {
+    /// Pastas e arquivos relativos à instância; `None` usa a seleção padrão.
+    pub pastas: Option<Vec<String>>,
+    pub nome: Option<String>,
+    pub versao: Option<String>,
+    pub autor: Option<String>,
+    pub resumo: Option<String>,
+    pub destino: Option<String>,
+}
+
+/// Item da raiz da instância que pode ser escolhido na exportação.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemExportacao {
+    pub caminho: String,
+    pub pasta: bool,
+    pub padrao: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FormatoExportacao {
+    Modrinth,
+    CurseForge,
+}
+
+/// Arquivo da instância que o formato consegue referenciar pela plataforma.
+struct ArquivoReferenciado {
+    caminho: String,
+    entrada: EntradaConteudo,
+    hashes: HashesConteudo,
+    tamanho: u64,
+}
+
+#[derive(Default)]
+struct ConteudoExportacao {
+    referenciados: Vec<ArquivoReferenciado>,
+    overrides: Vec<String>,
+}
+
+/// Lista os arquivos de `itens` (recursivamente nas pastas), com caminhos
+/// relativos à instância. Links simbólicos são ignorados.
+fn coletar_arquivos(instance_path: &Path, itens: &[String]) -> Result<Vec<String>, String> {
+    fn visitar(base: &Path, relativo: &str, saida: &mut Vec<String>) {
+        let caminho = base.join(relativo);
+        let Ok(meta) = std::fs::symlink_metadata(&caminho) else {
+            return;
+        };
+        if meta.is_file() {
+            saida.push(relativo.to_string());
+        } else if meta.is_dir() {
+            let Ok(entradas) = std::fs::read_dir(&caminho) else {
+                return;
+            };
+            let mut nomes: Vec<String> = entradas
+                .flatten()
+                .map(|entrada| entrada.file_name().to_string_lossy().to_string())
+                .collect();
+            nomes.sort();
+            for nome in nomes {
+                visitar(base, &format!("{}/{}", relativo, nome), saida);
+            }
+        }
+    }
+
+    let mut arquivos = Vec::new();
+    for item in itens {
+        let relativo = caminho_relativo_seguro(item)?;
+        visitar(instance_path, &relativo, &mut arquivos);
+    }
+    arquivos.retain(|caminho| !item_interno(caminho));
+    arquivos.sort();
+    arquivos.dedup();
+    Ok(arquivos)
+}
+
+/// Entrada do lockfile que referencia o arquivo no formato pedido. O arquivo
+/// precisa estar habilitado, direto na pasta do tipo e igual ao registrado.
+fn entrada_referenciavel<'a>(
+    caminho: &str,
+    entradas: &'a HashMap<(String, String), EntradaConteudo>,
+    formato: FormatoExportacao,
+) -> Option<&'a EntradaConteudo> {
+    let tipo = tipo_conteudo_por_caminho(caminho)?;
+    let (pasta, nome) = caminho.split_once('/')?;
+    if nome.contains('/') || pasta.is_empty() || nome.ends_with(".disabled") {
+        return None;
+    }
+    let entrada = entradas.get(&(tipo.to_string(), nome_habilitado(nome).to_string()))?;
+    let project_id = entrada.project_id.as_deref()?;
+    let versao_id = entrada.versao_id.as_deref()?;
+    match formato {
+        FormatoExportacao::Modrinth => {
+            (entrada.plataforma == Some(ModPlatform::Modrinth)).then_some(entrada)
+        }
+        FormatoExportacao::CurseForge => (entrada.plataforma == Some(ModPlatform::CurseForge)
+            && project_id.parse::<u64>().is_ok()
+            && versao_id.parse::<u64>().is_ok())
+        .then_some(entrada),
+    }
+}
+
+/// Separa o que vai como referência à plataforma do que vai em `overrides`.
+/// No `.mrpack`, só é referenciado o arquivo com URL de download conhecida,
+/// registrada no lockfile ou encontrada pelo SHA-1 em `urls_por_sha1`.
+fn classificar_conteudo(
+    instance_path: &Path,
+    arquivos: Vec<String>,
+    formato: FormatoExportacao,
+    urls_por_sha1: &HashMap<String, String>,
+) -> ConteudoExportacao {
+    let entradas: HashMap<(String, String), EntradaConteudo> = carregar_lockfile(instance_path)
+        .entradas
+        .into_iter()
+        .map(|entrada| {
+            (
+                (entrada.tipo_conteudo.clone(), entrada.arquivo.clone()),
+                entrada,
+            )
+        })
+        .collect();
+
+    let mut conteudo = ConteudoExportacao::default();
+    for caminho in arquivos {
+        let Some(entrada) = entrada_referenciavel(&caminho, &entradas, formato) else {
+            conteudo.overrides.push(caminho);
+            continue;
+        };
+        let arquivo = instance_path.join(&caminho);
+        let (Ok(hashes), Ok(meta)) = (
+            HashesConteudo::de_arquivo(&arquivo),
+            std::fs::metadata(&arquivo),
+        ) else {
+            conteudo.overrides.push(caminho);
+            continue;
+        };
+        // Arquivo trocado depois da instalação: o registro não vale mais.
+        if entrada.hashes.sha1.is_some() && entrada.hashes.sha1 != hashes.sha1 {
+            conteudo.overrides.push(caminho);
+            continue;
+        }
+        let mut entrada = entrada.clone();
+        if formato == FormatoExportacao::Modrinth {
+            // O nome local pode ter sido trocado; a URL precisa ser a real.
+            let url = entrada.url_download.clone().or_else(|| {
+                hashes
+                    .sha1
+                    .as_ref()
+                    .and_then(|sha1| urls_por_sha1.get(sha1).cloned())
+            });
+            let Some(url) = url else {
+                conteudo.overrides.push(caminho);
+                continue;
+            };
+            entrada.url_download = Some(url);
+        }
+        conteudo.referenciados.push(ArquivoReferenciado {
+            caminho,
+            entrada,
+            hashes,
+            tamanho: meta.len(),
+        });
+    }
+    conteudo
+}
+
+/// Versão do loader sem o prefixo `<versão do Minecraft>-` usado pelo Forge.
+fn versao_loader_exportacao(instancia: &Instance) -> Option<(String, String)> {
+    let loader = detectar_loader_normalizado(instancia.loader_type.as_deref())
+        .filter(|loader| loader != "vanilla")?;
+    let versao = instancia.loader_version.as_deref()?.trim();
+    let versao = versao
+        .strip_prefix(&format!("{}-", instancia.version))
+        .unwrap_or(versao);
+    (!versao.is_empty()).then(|| (loader, versao.to_string()))
+}
+
+fn montar_indice_modrinth(
+    instancia: &Instance,
+    opcoes: &OpcoesExportacaoModpack,
+    nome: &str,
+    versao: &str,
+    referenciados: &[ArquivoReferenciado],
+) -> serde_json::Value {
+    let arquivos: Vec<serde_json::Value> = referenciados
+        .iter()
+        .map(|arquivo| {
+            let url = arquivo.entrada.url_download.clone().unwrap_or_default();
+            serde_json::json!({
+                "path": arquivo.caminho,
+                "hashes": {
+                    "sha1": arquivo.hashes.sha1,
+                    "sha512": arquivo.hashes.sha512,
+                },
+                "downloads": [url],
+                "fileSize": arquivo.tamanho,
+            })
+        })
+        .collect();
+
+    let mut dependencias = serde_json::json!({ "minecraft": instancia.version });
+    if let Some((loader, versao_loader)) = versao_loader_exportacao(instancia) {
+        let chave = match loader.as_str() {
+            "fabric" => "fabric-loader",
+            "quilt" => "quilt-loader",
+            outro => outro,
+        };
+        dependencias[chave] = serde_json::Value::String(versao_loader);
+    }
+
+    let mut indice = serde_json::json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": versao,
+        "name": nome,
+        "files": arquivos,
+        "dependencies": dependencias,
+    });
+    if let Some(resumo) = opcoes.resumo.as_deref().filter(|r| !r.trim().is_empty()) {
+        indice["summary"] = serde_json::Value::String(resumo.to_string());
+    }
+    indice
+}
+
+fn montar_manifesto_curseforge(
+    instancia: &Instance,
+    opcoes: &OpcoesExportacaoModpack,
+    nome: &str,
+    versao: &str,
+    referenciados: &[ArquivoReferenciado],
+) -> serde_json::Value {
+    let arquivos: Vec<serde_json::Value> = referenciados
+        .iter()
+        .filter_map(|arquivo| {
+            Some(serde_json::json!({
+                "projectID": arquivo.entrada.project_id.as_deref()?.parse::<u64>().ok()?,
+                "fileID": arquivo.entrada.versao_id.as_deref()?.parse::<u64>().ok()?,
+                "required": true,
+            }))
+        })
+        .collect();
+    let mod_loaders: Vec<serde_json::Value> = versao_loader_exportacao(instancia)
+        .map(|(loader, versao_loader)| {
+            serde_json::json!({ "id": format!("{}-{}", loader, versao_loader), "primary": true })
+        })
+        .into_iter()
+        .collect();
+
+    serde_json::json!({
+        "minecraft": {
+            "version": instancia.version,
+            "modLoaders": mod_loaders,
+        },
+        "manifestType": "minecraftModpack",
+        "manifestVersion": 1,
+        "name": nome,
+        "version": versao,
+        "author": opcoes.autor.clone().unwrap_or_default(),
+        "files": arquivos,
+        "overrides": "overrides",
+    })
+}
+
+fn exportar_modpack(
+    instancia: &Instance,
+    opcoes: &OpcoesExportacaoModpack,
+    formato: FormatoExportacao,
+    urls_por_sha1: &HashMap<String, String>,
+) -> Result<ResultadoExportacao, String> {
+    let itens: Vec<String> = match &opcoes.pastas {
+        Some(pastas) => pastas.clone(),
+        None => ITENS_EXPORTACAO_PADRAO
+            .iter()
+            .map(|item| item.to_string())
+            .collect(),
+    };
+    let arquivos = coletar_arquivos(&instancia.path, &itens)?;
+    let conteudo = classificar_conteudo(&instancia.path, arquivos, formato, urls_por_sha1);
+
+    let nome = opcoes
+        .nome
+        .clone()
+        .filter(|nome| !nome.trim().is_empty())
+        .unwrap_or_else(|| instancia.name.clone());
+    let versao = opcoes
+        .versao
+        .clone()
+        .filter(|versao| !versao.trim().is_empty())
+        .unwrap_or_else(|| "1.0.0".to_string());
+    let (arquivo_indice, indice, extensao) = match formato {
+        FormatoExportacao::Modrinth => (
+            "modrinth.index.json",
+            montar_indice_modrinth(instancia, opcoes, &nome, &versao, &conteudo.referenciados),
+            "mrpack",
+        ),
+        FormatoExportacao::CurseForge => (
+            "manifest.json",
+            montar_manifesto_curseforge(instancia, opcoes, &nome, &versao, &conteudo.referenciados),
+            "zip",
+        ),
+    };
+
+    let pasta_destino = resolver_pasta_destino_exportacao(opcoes.destino.as_deref());
+    std::fs::create_dir_all(&pasta_destino)
+        .map_err(|e| format!("Erro ao criar pasta de destino: {}", e))?;
+    let nome_arquivo = format!(
+        "{}-{}.{}",
+        nome_arquivo_exportacao(&nome),
+        nome_arquivo_exportacao(&versao),
+        extensao
+    );
+    let caminho_zip = pasta_destino.join(&nome_arquivo);
+
+    let arquivo_zip = std::fs::File::create(&caminho_zip)
+        .map_err(|e| format!("Erro ao criar arquivo zip: {}", e))?;
+    let mut zip = zip::ZipWriter::new(arquivo_zip);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let indice_json = serde_json::to_string_pretty(&indice)
+        .map_err(|e| format!("Erro ao serializar {}: {}", arquivo_indice, e))?;
+    zip.start_file(arquivo_indice, options)
+        .map_err(|e| format!("Erro ao criar {} no zip: {}", arquivo_indice, e))?;
+    zip.write_all(indice_json.as_bytes())
+        .map_err(|e| format!("Erro ao escrever {}: {}", arquivo_indice, e))?;
+
+    let mut total_overrides = 0;
+    for caminho in &conteudo.overrides {
+        // Arquivos presos pelo jogo aberto são ignorados, como na exportação .dome.
+        let mut origem = match std::fs::File::open(instancia.path.join(caminho)) {
+            Ok(arquivo) => arquivo,
+            Err(e) => {
+                eprintln!(
+                    "[Exportação] Aviso: ignorando arquivo inacessível '{}': {}",
+                    caminho, e
+                );
+                continue;
+            }
+        };
+        zip.start_file(format!("overrides/{}", caminho), options)
+            .map_err(|e| format!("Erro ao criar '{}' no zip: {}", caminho, e))?;
+        std::io::copy(&mut origem, &mut zip)
+            .map_err(|e| format!("Erro ao escrever '{}' no zip: {}", caminho, e))?;
+        total_overrides += 1;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Erro ao finalizar arquivo zip: {}", e))?;
+    println!(
+        "[Exportação] {} exportada como {} ({} referenciados, {} em overrides)",
+        instancia.id,
+        nome_arquivo,
+        conteudo.referenciados.len(),
+        total_overrides
+    );
+
+    Ok(ResultadoExportacao {
+        sucesso: true,
+        caminho_arquivo: Some(caminho_zip.to_string_lossy().to_string()),
+        mensagem: format!(
+            "Modpack exportado como {} ({} arquivos baixados da plataforma, {} em overrides).",
+            nome_arquivo,
+            conteudo.referenciados.len(),
+            total_overrides
+        ),
+    })
+}
+
+/// Itens da raiz da instância disponíveis para exportação, marcando os que
+/// entram por padrão.
+#[tauri::command]
+pub(crate) fn listar_itens_exportacao(
+    instance_id: String,
+    state: State<'_, LauncherState>,
+) -> Result<Vec<ItemExportacao>, String> {
+    let instancia = obter_instancia_por_id(&state, &instance_id)?;
+    let entradas = std::fs::read_dir(&instancia.path)
+        .map_err(|e| format!("Erro ao ler pasta da instância: {}", e))?;
+    let mut itens: Vec<ItemExportacao> = entradas
+        .flatten()
+        .filter_map(|entrada| {
+            let tipo = entrada.file_type().ok().filter(|tipo| !tipo.is_symlink())?;
+            let caminho = entrada.file_name().to_string_lossy().to_string();
+            (!ITENS_INTERNOS.contains(&caminho.as_str())).then(|| ItemExportacao {
+                padrao: ITENS_EXPORTACAO_PADRAO.contains(&caminho.as_str()),
+                pasta: tipo.is_dir(),
+                caminho,
+            })
+        })
+        .collect();
+    itens.sort_by(|a, b| b.pasta.cmp(&a.pasta).then(a.caminho.cmp(&b.caminho)));
+    Ok(itens)
+}
+
+/// URLs de download no Modrinth, por SHA-1, do conteúdo Modrinth do lockfile
+/// instalado antes de a URL ser registrada. Sem rede, o mapa fica vazio e esses
+/// arquivos vão em `overrides`.
+async fn urls_modrinth_por_sha1(instance_path: &Path) -> HashMap<String, String> {
+    let hashes: Vec<String> = carregar_lockfile(instance_path)
+        .entradas
+        .into_iter()
+        .filter(|entrada| {
+            entrada.plataforma == Some(ModPlatform::Modrinth) && entrada.url_download.is_none()
+        })
+        .filter_map(|entrada| entrada.hashes.sha1)
+        .collect();
+    if hashes.is_empty() {
+        return HashMap::new();
+    }
+
+    let resultado = async {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .user_agent("DomeLauncher/1.0 (+https://domestudios.com.br)")
+            .build()
+            .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+        client
+            .post(format!("{}/version_files", MODRINTH_API_BASE))
+            .json(&serde_json::json!({ "hashes": hashes, "algorithm": "sha1" }))
+            .send()
+            .await
+            .and_then(|resposta| resposta.error_for_status())
+            .map_err(|e| format!("Erro na requisição Modrinth: {}", e))?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("Erro ao parsear resposta Modrinth: {}", e))
+    }
+    .await;
+    let versoes = match resultado {
+        Ok(versoes) => versoes,
+        Err(e) => {
+            eprintln!("[Exportação] {}", e);
+            return HashMap::new();
+        }
+    };
+
+    let mut urls = HashMap::new();
+    for (sha1, versao) in versoes.as_object().into_iter().flatten() {
+        let url = versao["files"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|arquivo| arquivo["hashes"]["sha1"].as_str() == Some(sha1.as_str()))
+            .and_then(|arquivo| arquivo["url"].as_str());
+        if let Some(url) = url {
+            urls.insert(sha1.clone(), url.to_string());
+        }
+    }
+    urls
+}
+
+/// Exporta a instância como `.mrpack`. Conteúdo instalado pelo Modrinth e
+/// registrado no lockfile vira entrada de `files`; o resto vai em `overrides`.
+#[tauri::command]
+pub(crate) async fn exportar_mrpack(
+    instance_id: String,
+    opcoes: Option<OpcoesExportacaoModpack>,
+    state: State<'_, LauncherState>,
+) -> Result<ResultadoExportacao, String> {
+    let instancia = obter_instancia_por_id(&state, &instance_id)?;
+    let opcoes = opcoes.unwrap_or_default();
+    let urls = urls_modrinth_por_sha1(&instancia.path).await;
+    tokio::task::spawn_blocking(move || {
+        exportar_modpack(&instancia, &opcoes, FormatoExportacao::Modrinth, &urls)
+    })
+    .await
+    .map_err(|e| format!("Erro ao exportar modpack: {}", e))?
+}
+
+/// Exporta a instância como modpack do CurseForge (`manifest.json` com pares
+/// projectID/fileID e o restante em `overrides`).
+#[tauri::command]
+pub(crate) async fn exportar_modpack_curseforge(
+    instance_id: String,
+    opcoes: Option<OpcoesExportacaoModpack>,
+    state: State<'_, LauncherState>,
+) -> Result<ResultadoExportacao, String> {
+    let instancia = obter_instancia_por_id(&state, &instance_id)?;
+    let opcoes = opcoes.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        exportar_modpack(
+            &instancia,
+            &opcoes,
+            FormatoExportacao::CurseForge,
+            &HashMap::new(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Erro ao exportar modpack: {}", e))?
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::aplicacao::registro_conteudo::{registrar_conteudo, OrigemInstalacao};
+    use crate::comandos::modpacks::ler_indice_modpack;
+
+    fn instancia_teste() -> Instance {
+        let pasta = std::env::temp_dir().join(format!("dome-exportacao-{}", uuid::Uuid::new_v4()));
+        let escrever = |relativo: &str, conteudo: &[u8]| {
+            let caminho = pasta.join(relativo);
+            std::fs::create_dir_all(caminho.parent().unwrap()).unwrap();
+            std::fs::write(caminho, conteudo).unwrap();
+        };
+        escrever("mods/sodium.jar", b"sodium");
+        escrever("mods/iris.jar", b"iris");
+        escrever("mods/lithium.jar", b"lithium");
+        escrever("mods/jei.jar", b"jei");
+        escrever("mods/local.jar", b"local");
+        escrever("config/sodium.json", b"{}");
+        escrever("saves/mundo/level.dat", b"nbt");
+
+        let sodium = EntradaConteudo::de_arquivo(
+            &pasta.join("mods/sodium.jar"),
+            "mod",
+            OrigemInstalacao::Usuario,
+        )
+        .unwrap()
+        .com_projeto(
+            ModPlatform::Modrinth,
+            Some("AANobbMI".to_string()),
+            Some("abc123".to_string()),
+        )
+        .com_url_download(
+            "https://cdn.modrinth.com/data/AANobbMI/versions/abc123/sodium-forge-0.5.3.jar",
+        );
+        // Instalados antes de a URL ser registrada no lockfile.
+        let sem_url = |nome: &str, project_id: &str| {
+            EntradaConteudo::de_arquivo(
+                &pasta.join("mods").join(nome),
+                "mod",
+                OrigemInstalacao::Usuario,
+            )
+            .unwrap()
+            .com_projeto(
+                ModPlatform::Modrinth,
+                Some(project_id.to_string()),
+                Some("v1".to_string()),
+            )
+        };
+        let iris = sem_url("iris.jar", "YL57xq9U");
+        let lithium = sem_url("lithium.jar", "gvQqBUqZ");
+        let jei = EntradaConteudo::de_arquivo(
+            &pasta.join("mods/jei.jar"),
+            "mod",
+            OrigemInstalacao::Usuario,
+        )
+        .unwrap()
+        .com_projeto(
+            ModPlatform::CurseForge,
+            Some("238222".to_string()),
+            Some("4712866".to_string()),
+        );
+        registrar_conteudo(&pasta, vec![sodium, iris, lithium, jei]).unwrap();
+
+        let mut instancia: Instance = serde_json::from_value(serde_json::json!({
+            "id": "exportacao",
+            "name": "Meu Pack",
+            "version": "1.20.1",
+            "mcType": "forge",
+            "loaderType": "Forge",
+            "loaderVersion": "1.20.1-47.2.0",
+            "path": "",
+            "created": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        instancia.path = pasta;
+        instancia
+    }
+
+    fn opcoes_teste(instancia: &Instance) -> OpcoesExportacaoModpack {
+        OpcoesExportacaoModpack {
+            versao: Some("2.0".to_string()),
+            destino: Some(instancia.path.join("saida").to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn abrir(resultado: &ResultadoExportacao) -> zip::ZipArchive<std::fs::File> {
+        let caminho = resultado.caminho_arquivo.as_deref().unwrap();
+        zip::ZipArchive::new(std::fs::File::open(caminho).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn exporta_mrpack_com_mods_conhecidos_e_overrides() {
+        let instancia = instancia_teste();
+        let sha1_iris = HashesConteudo::de_bytes(b"iris").sha1.unwrap();
+        let url_iris = "https://cdn.modrinth.com/data/YL57xq9U/versions/v1/iris-1.6.jar";
+        let urls = HashMap::from([(sha1_iris, url_iris.to_string())]);
+        let resultado = exportar_modpack(
+            &instancia,
+            &opcoes_teste(&instancia),
+            FormatoExportacao::Modrinth,
+            &urls,
+        )
+        .unwrap();
+        assert!(resultado
+            .caminho_arquivo
+            .as_deref()
+            .unwrap()
+            .ends_with("Meu_Pack-2.0.mrpack"));
+        let mut zip = abrir(&resultado);
+        let indice = ler_indice_modpack(&mut zip).unwrap();
+
+        assert_eq!(indice.nome.as_deref(), Some("Meu Pack"));
+        assert_eq!(indice.versao_minecraft.as_deref(), Some("1.20.1"));
+        assert_eq!(indice.loader.as_deref(), Some("forge"));
+        assert_eq!(indice.versao_loader.as_deref(), Some("47.2.0"));
+        assert_eq!(indice.arquivos.len(), 2);
+        assert_eq!(indice.arquivos[0].caminho, "mods/iris.jar");
+        assert_eq!(indice.arquivos[0].urls, vec![url_iris]);
+        assert_eq!(indice.arquivos[1].caminho, "mods/sodium.jar");
+        assert_eq!(
+            indice.arquivos[1].urls,
+            vec!["https://cdn.modrinth.com/data/AANobbMI/versions/abc123/sodium-forge-0.5.3.jar"]
+        );
+        assert!(zip.by_name("overrides/mods/lithium.jar").is_ok());
+        assert!(zip.by_name("overrides/mods/jei.jar").is_ok());
+        assert!(zip.by_name("overrides/mods/local.jar").is_ok());
+        assert!(zip.by_name("overrides/config/sodium.json").is_ok());
+        assert!(zip.by_name("overrides/mods/sodium.jar").is_err());
+        assert!(zip.by_name("overrides/saves/mundo/level.dat").is_err());
+        let _ = std::fs::remove_dir_all(&instancia.path);
+    }
+
+    #[test]
+    fn ignora_arquivos_internos_do_launcher() {
+        let pasta = std::env::temp_dir().join(format!("dome_exportacao_{}", uuid::Uuid::new_v4()));
+        for arquivo in [
+            "config/a.toml",
+            ".referencias_store.json",
+            "conteudo.lock.json.corrompido",
+            ".backups_conteudo/mods/a.jar",
+            ".backup_versao/instance.json",
+            "temp/preparacao/mods/b.jar",
+        ] {
+            let caminho = pasta.join(arquivo);
+            std::fs::create_dir_all(caminho.parent().unwrap()).unwrap();
+            std::fs::write(caminho, b"x").unwrap();
+        }
+        let itens = [
+            "config",
+            ".referencias_store.json",
+            "conteudo.lock.json.corrompido",
+            ".backups_conteudo",
+            ".backup_versao",
+            "temp",
+        ]
+        .map(String::from);
+        assert_eq!(
+            coletar_arquivos(&pasta, &itens).unwrap(),
+            vec!["config/a.toml".to_string()]
+        );
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn exporta_manifesto_curseforge_com_pastas_escolhidas() {
+        let instancia = instancia_teste();
+        let opcoes = OpcoesExportacaoModpack {
+            pastas: Some(vec!["mods".to_string(), "saves".to_string()]),
+            ..opcoes_teste(&instancia)
+        };
+        let resultado = exportar_modpack(
+            &instancia,
+            &opcoes,
+            FormatoExportacao::CurseForge,
+            &HashMap::new(),
+        )
+        .unwrap();
+        let mut zip = abrir(&resultado);
+        let indice = ler_indice_modpack(&mut zip).unwrap();
+
+        assert_eq!(indice.arquivos_curseforge, vec![(238222, 4712866)]);
+        assert_eq!(indice.loader.as_deref(), Some("forge"));
+        assert_eq!(indice.pastas_overrides, vec!["overrides".to_string()]);
+        assert!(zip.by_name("overrides/mods/sodium.jar").is_ok());
+        assert!(zip.by_name("overrides/saves/mundo/level.dat").is_ok());
+        assert!(zip.by_name("overrides/config/sodium.json").is_err());
+        assert!(zip.by_name("overrides/mods/jei.jar").is_err());
+
+        let invalidas = OpcoesExportacaoModpack {
+            pastas: Some(vec!["../fora".to_string()]),
+            ..opcoes_teste(&instancia)
+        };
+        assert!(exportar_modpack(
+            &instancia,
+            &invalidas,
+            FormatoExportacao::CurseForge,
+            &HashMap::new()
+        )
+        .is_err());
+        let _ = std::fs::remove_dir_all(&instancia.path);
+    }
+}
@@ -0,0 +1,351 @@
+use super::*;
+use sha1::{Digest, Sha1};
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Limites aplicados a arquivos compactados vindos de fora (modpacks,
+/// instâncias importadas) para barrar zip bombs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LimitesExtracao {
+    pub max_arquivos: usize,
+    pub max_bytes_total: u64,
+    pub max_bytes_arquivo: u64,
+    /// Razão máxima entre o tamanho extraído e o compactado de uma entrada,
+    /// verificada só em entradas acima de 1 MiB.
+    pub max_taxa_compressao: u64,
+}
+
+impl Default for LimitesExtracao {
+    fn default() -> Self {
+        Self {
+            max_arquivos: 100_000,
+            max_bytes_total: 16 * 1024 * 1024 * 1024,
+            max_bytes_arquivo: 4 * 1024 * 1024 * 1024,
+            max_taxa_compressao: 1_000,
+        }
+    }
+}
+
+const TAMANHO_MINIMO_TAXA: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ArquivoExtraido {
+    /// Caminho relativo ao destino, sempre com `/`.
+    pub caminho: String,
+    pub sha1: String,
+}
+
+/// Normaliza um caminho relativo vindo de um arquivo compactado ou índice de
+/// modpack. Recusa caminhos vazios, absolutos, com letra de unidade, UNC ou
+/// que subam de pasta com `..`. `:` só é recusado no Windows, onde abriria um
+/// fluxo alternativo do arquivo.
+pub(crate) fn caminho_relativo_seguro(caminho: &str) -> Result<String, String> {
+    let normalizado = caminho.replace('\\', "/");
+    let invalido = || format!("Caminho inválido no arquivo: {}", caminho);
+    if normalizado.starts_with('/')
+        || normalizado.contains('\0')
+        || (cfg!(windows) && normalizado.contains(':'))
+    {
+        return Err(invalido());
+    }
+    let partes: Vec<&str> = normalizado
+        .split('/')
+        .filter(|parte| !parte.is_empty() && *parte != ".")
+        .collect();
+    let letra_unidade = partes.first().is_some_and(|primeira| {
+        let bytes = primeira.as_bytes();
+        bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+    });
+    if partes.is_empty() || letra_unidade || partes.contains(&"..") {
+        return Err(invalido());
+    }
+    Ok(partes.join("/"))
+}
+
+/// Normaliza o nome de uma entrada do zip para `mapear`, sem validá-lo: barras
+/// viram `/` e segmentos vazios ou `.` somem, mas uma barra inicial é mantida
+/// para que o caminho continue absoluto.
+fn normalizar_nome_entrada(nome: &str) -> String {
+    let nome = nome.replace('\\', "/");
+    let partes: Vec<&str> = nome
+        .split('/')
+        .filter(|parte| !parte.is_empty() && *parte != ".")
+        .collect();
+    let prefixo = if nome.starts_with('/') { "/" } else { "" };
+    format!("{}{}", prefixo, partes.join("/"))
+}
+
+/// Entrada selecionada para extração, já validada.
+struct EntradaPlanejada {
+    indice: usize,
+    caminho: String,
+    pasta: bool,
+}
+
+/// Extrai as entradas de `archive` para dentro de `destino`. `mapear` recebe o
+/// nome normalizado de cada entrada e devolve o caminho relativo de destino,
+/// ou `None` para ignorá-la. Só as entradas mantidas por `mapear` são
+/// validadas, mas todas antes de qualquer gravação: nomes inseguros, links
+/// simbólicos ou limites estourados recusam a extração inteira.
+pub(crate) fn extrair_zip<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    destino: &Path,
+    limites: &LimitesExtracao,
+    mut mapear: impl FnMut(&str) -> Option<String>,
+) -> Result<Vec<ArquivoExtraido>, String> {
+    let mut planejadas = Vec::new();
+    let mut bytes_declarados: u64 = 0;
+    for indice in 0..archive.len() {
+        let entrada = archive
+            .by_index_raw(indice)
+            .map_err(|e| format!("Erro ao ler arquivo do ZIP: {}", e))?;
+        let nome = entrada.name().replace('\\', "/");
+        if nome.trim_matches('/').is_empty() {
+            continue;
+        }
+        let Some(relativo) = mapear(&normalizar_nome_entrada(&nome)) else {
+            continue;
+        };
+        if relativo.trim_matches('/').is_empty() {
+            continue;
+        }
+        let relativo = caminho_relativo_seguro(&relativo)?;
+
+        if entrada.is_symlink() {
+            return Err(format!("Links simbólicos não são permitidos: {}", nome));
+        }
+        let tamanho = entrada.size();
+        if tamanho > limites.max_bytes_arquivo {
+            return Err(format!("{} excede o tamanho máximo permitido.", nome));
+        }
+        let compactado = entrada.compressed_size().max(1);
+        if tamanho > TAMANHO_MINIMO_TAXA && tamanho / compactado > limites.max_taxa_compressao {
+            return Err(format!("Taxa de compressão suspeita em {}.", nome));
+        }
+        bytes_declarados = bytes_declarados.saturating_add(tamanho);
+        if bytes_declarados > limites.max_bytes_total {
+            return Err("O conteúdo extraído excede o tamanho máximo permitido.".to_string());
+        }
+
+        planejadas.push(EntradaPlanejada {
+            indice,
+            caminho: relativo,
+            pasta: entrada.is_dir(),
+        });
+        if planejadas.len() > limites.max_arquivos {
+            return Err("O arquivo tem entradas demais para ser extraído.".to_string());
+        }
+    }
+
+    std::fs::create_dir_all(destino)
+        .map_err(|e| format!("Erro ao criar pasta {}: {}", destino.display(), e))?;
+    let mut extraidos = Vec::new();
+    let mut bytes_extraidos: u64 = 0;
+    for planejada in planejadas {
+        let caminho_final = destino.join(&planejada.caminho);
+        let pasta = if planejada.pasta {
+            caminho_final.clone()
+        } else {
+            caminho_final
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| destino.to_path_buf())
+        };
+        std::fs::create_dir_all(&pasta)
+            .map_err(|e| format!("Erro ao criar pasta {}: {}", planejada.caminho, e))?;
+        // Pastas que já existiam no destino podem ser links para fora dele.
+        validar_caminho_dentro_raiz(destino, &pasta)?;
+        if planejada.pasta {
+            continue;
+        }
+        if std::fs::symlink_metadata(&caminho_final).is_ok_and(|meta| meta.file_type().is_symlink())
+        {
+            return Err(format!(
+                "Destino {} é um link simbólico e não será sobrescrito.",
+                planejada.caminho
+            ));
+        }
+
+        let entrada = archive
+            .by_index(planejada.indice)
+            .map_err(|e| format!("Erro ao ler arquivo do ZIP: {}", e))?;
+        // O tamanho declarado pode mentir; o limite vale para os bytes reais.
+        let restante_total = limites.max_bytes_total.saturating_sub(bytes_extraidos);
+        let limite = limites.max_bytes_arquivo.min(restante_total);
+        let mut leitor = entrada.take(limite.saturating_add(1));
+        let mut arquivo = std::fs::File::create(&caminho_final)
+            .map_err(|e| format!("Erro ao criar arquivo {}: {}", planejada.caminho, e))?;
+        let mut hasher = Sha1::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut tamanho: u64 = 0;
+        loop {
+            let lidos = leitor
+                .read(&mut buffer)
+                .map_err(|e| format!("Erro ao extrair {}: {}", planejada.caminho, e))?;
+            if lidos == 0 {
+                break;
+            }
+            tamanho += lidos as u64;
+            if tamanho > limite {
+                drop(arquivo);
+                let _ = std::fs::remove_file(&caminho_final);
+                return Err(format!(
+                    "{} excede o tamanho máximo permitido.",
+                    planejada.caminho
+                ));
+            }
+            hasher.update(&buffer[..lidos]);
+            std::io::Write::write_all(&mut arquivo, &buffer[..lidos])
+                .map_err(|e| format!("Erro ao gravar {}: {}", planejada.caminho, e))?;
+        }
+        bytes_extraidos += tamanho;
+        extraidos.push(ArquivoExtraido {
+            caminho: planejada.caminho,
+            sha1: format!("{:x}", hasher.finalize()),
+        });
+    }
+
+    Ok(extraidos)
+}
+
+/// Extrai apenas o conteúdo de `pasta` (sem o prefixo) para `destino`.
+pub(crate) fn extrair_pasta_zip<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    pasta: &str,
+    destino: &Path,
+    limites: &LimitesExtracao,
+) -> Result<Vec<ArquivoExtraido>, String> {
+    let prefixo = format!("{}/", pasta.trim_matches('/'));
+    extrair_zip(archive, destino, limites, |nome| {
+        nome.strip_prefix(&prefixo)
+            .map(|relativo| relativo.to_string())
+    })
+}
+
+/// Monta um zip em memória com as entradas dadas, para testes.
+#[cfg(test)]
+pub(crate) fn zip_em_memoria(
+    entradas: &[(&str, &[u8])],
+) -> zip::ZipArchive<std::io::Cursor<Vec<u8>>> {
+    let mut escritor = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (nome, conteudo) in entradas {
+        escritor
+            .start_file(*nome, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(&mut escritor, conteudo).unwrap();
+    }
+    zip::ZipArchive::new(escritor.finish().unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn pasta_teste() -> PathBuf {
+        std::env::temp_dir().join(format!("dome-extracao-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn recusa_caminhos_que_saem_do_destino() {
+        for nome in [
+            "../fora.txt",
+            "overrides/../../fora.txt",
+            "/etc/passwd",
+            "\\\\servidor\\pasta\\a.txt",
+            "C:/Windows/a.txt",
+            "..\\fora.txt",
+        ] {
+            let destino = pasta_teste();
+            let mut archive = zip_em_memoria(&[("ok.txt", b"ok"), (nome, b"mal")]);
+            let resultado = extrair_zip(&mut archive, &destino, &LimitesExtracao::default(), |n| {
+                Some(n.to_string())
+            });
+            assert!(resultado.is_err(), "{} deveria ser recusado", nome);
+            // Nada é gravado quando uma entrada é recusada.
+            assert!(!destino.join("ok.txt").exists());
+            let _ = std::fs::remove_dir_all(&destino);
+        }
+        assert_eq!(
+            caminho_relativo_seguro("./config//a.toml").unwrap(),
+            "config/a.toml"
+        );
+        assert_eq!(
+            caminho_relativo_seguro("config/mod:opcao.toml").is_ok(),
+            !cfg!(windows)
+        );
+    }
+
+    #[test]
+    fn ignora_nomes_invalidos_fora_da_pasta_extraida() {
+        let destino = pasta_teste();
+        let mut archive = zip_em_memoria(&[
+            ("overrides/config/a.toml", b"a"),
+            ("../fora.txt", b"mal"),
+            ("C:/Windows/a.txt", b"mal"),
+        ]);
+        let extraidos = extrair_pasta_zip(
+            &mut archive,
+            "overrides",
+            &destino,
+            &LimitesExtracao::default(),
+        )
+        .unwrap();
+        assert_eq!(extraidos.len(), 1);
+        assert!(destino.join("config/a.toml").exists());
+        let _ = std::fs::remove_dir_all(&destino);
+    }
+
+    #[test]
+    fn recusa_links_simbolicos() {
+        let mut escritor = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        escritor
+            .add_symlink(
+                "overrides/mods",
+                "/home",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        let mut archive = zip::ZipArchive::new(escritor.finish().unwrap()).unwrap();
+        let destino = pasta_teste();
+        let resultado = extrair_pasta_zip(
+            &mut archive,
+            "overrides",
+            &destino,
+            &LimitesExtracao::default(),
+        );
+        assert!(resultado.unwrap_err().contains("simbólicos"));
+        let _ = std::fs::remove_dir_all(&destino);
+    }
+
+    #[test]
+    fn aplica_limites_de_quantidade_e_tamanho() {
+        let destino = pasta_teste();
+        let limites = LimitesExtracao {
+            max_arquivos: 2,
+            max_bytes_total: 1024,
+            max_bytes_arquivo: 1024,
+            max_taxa_compressao: 1_000,
+        };
+        let mut muitos = zip_em_memoria(&[("a", b"1"), ("b", b"2"), ("c", b"3")]);
+        assert!(extrair_zip(&mut muitos, &destino, &limites, |n| Some(n.to_string())).is_err());
+
+        let grande = vec![0u8; 2048];
+        let mut bomba = zip_em_memoria(&[("zeros.bin", &grande)]);
+        assert!(extrair_zip(&mut bomba, &destino, &limites, |n| Some(n.to_string())).is_err());
+
+        let mut valido = zip_em_memoria(&[
+            ("overrides/config/a.toml", b"a = 1"),
+            ("overrides/", b""),
+            ("manifest.json", b"{}"),
+        ]);
+        let extraidos = extrair_pasta_zip(&mut valido, "overrides", &destino, &limites).unwrap();
+        assert_eq!(extraidos.len(), 1);
+        assert_eq!(extraidos[0].caminho, "config/a.toml");
+        assert_eq!(
+            std::fs::read_to_string(destino.join("config/a.toml")).unwrap(),
+            "a = 1"
+        );
+        let _ = std::fs::remove_dir_all(&destino);
+    }
+}
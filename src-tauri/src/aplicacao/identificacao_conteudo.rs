@@ -0,0 +1,571 @@
+use super::registro_conteudo::{
+    alterar_lockfile, carregar_lockfile, nome_habilitado, EntradaConteudo, HashesConteudo,
+    OrigemInstalacao,
+};
+use super::*;
+use std::collections::HashMap;
+
+/// Pastas verificadas e o tipo de conteúdo correspondente no lockfile.
+const PASTAS_IDENTIFICACAO: [(&str, &str); 3] = [
+    ("mods", "mod"),
+    ("resourcepacks", "resourcepack"),
+    ("shaderpacks", "shader"),
+];
+
+/// Arquivo local com os hashes usados nas duas plataformas.
+#[derive(Debug, Clone)]
+struct ArquivoLocal {
+    nome: String,
+    /// `<pasta>/<arquivo>`; nomes iguais podem existir em pastas diferentes.
+    caminho: String,
+    tipo_conteudo: String,
+    hashes: HashesConteudo,
+    fingerprint: u32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConteudoIdentificado {
+    pub file_name: String,
+    pub tipo_conteudo: String,
+    pub plataforma: ModPlatform,
+    pub project_id: String,
+    pub versao_id: String,
+    pub nome: Option<String>,
+    pub icone_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioIdentificacao {
+    pub identificados: Vec<ConteudoIdentificado>,
+    pub nao_identificados: Vec<String>,
+    pub ja_rastreados: usize,
+}
+
+/// Fingerprint do CurseForge: MurmurHash2 (semente 1) sobre o arquivo sem
+/// os bytes de espaço em branco (tab, LF, CR e espaço).
+pub(crate) fn fingerprint_curseforge(bytes: &[u8]) -> u32 {
+    let normalizado: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+    murmur2(&normalizado, 1)
+}
+
+fn murmur2(dados: &[u8], semente: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = semente ^ dados.len() as u32;
+    let mut blocos = dados.chunks_exact(4);
+    for bloco in &mut blocos {
+        let mut k = u32::from_le_bytes([bloco[0], bloco[1], bloco[2], bloco[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    let resto = blocos.remainder();
+    if !resto.is_empty() {
+        for (indice, byte) in resto.iter().enumerate() {
+            h ^= (*byte as u32) << (8 * indice);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+fn ler_arquivo_local(
+    caminho: &std::path::Path,
+    pasta: &str,
+    tipo_conteudo: &str,
+) -> Option<ArquivoLocal> {
+    let bytes = std::fs::read(caminho).ok()?;
+    let nome = caminho.file_name()?.to_string_lossy().to_string();
+    Some(ArquivoLocal {
+        caminho: format!("{}/{}", pasta, nome),
+        nome,
+        tipo_conteudo: tipo_conteudo.to_string(),
+        hashes: HashesConteudo::de_bytes(&bytes),
+        fingerprint: fingerprint_curseforge(&bytes),
+    })
+}
+
+/// Lista os arquivos das pastas de conteúdo; com `apenas_sem_projeto`, pula
+/// os que o lockfile já associa a um projeto.
+fn coletar_arquivos_locais(
+    instance_path: &std::path::Path,
+    apenas_sem_projeto: bool,
+) -> (Vec<ArquivoLocal>, usize) {
+    let lockfile = carregar_lockfile(instance_path);
+    let mut arquivos = Vec::new();
+    let mut ja_rastreados = 0;
+
+    for (pasta, tipo) in PASTAS_IDENTIFICACAO {
+        let Ok(entradas) = std::fs::read_dir(instance_path.join(pasta)) else {
+            continue;
+        };
+        for entrada in entradas.flatten() {
+            let caminho = entrada.path();
+            if !caminho.is_file() {
+                continue;
+            }
+            let nome = entrada.file_name().to_string_lossy().to_string();
+            let rastreado = lockfile.entradas.iter().any(|e| {
+                e.tipo_conteudo == tipo
+                    && e.arquivo == nome_habilitado(&nome)
+                    && e.project_id.is_some()
+            });
+            if apenas_sem_projeto && rastreado {
+                ja_rastreados += 1;
+                continue;
+            }
+            if let Some(arquivo) = ler_arquivo_local(&caminho, pasta, tipo) {
+                arquivos.push(arquivo);
+            }
+        }
+    }
+
+    (arquivos, ja_rastreados)
+}
+
+async fn identificar_modrinth(
+    client: &reqwest::Client,
+    arquivos: &[ArquivoLocal],
+) -> Result<HashMap<String, ConteudoIdentificado>, String> {
+    let mut por_hash: HashMap<&str, Vec<&ArquivoLocal>> = HashMap::new();
+    for arquivo in arquivos {
+        if let Some(hash) = arquivo.hashes.sha512.as_deref() {
+            por_hash.entry(hash).or_default().push(arquivo);
+        }
+    }
+    if por_hash.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let corpo = serde_json::json!({
+        "hashes": por_hash.keys().collect::<Vec<_>>(),
+        "algorithm": "sha512",
+    });
+    let resposta = client
+        .post(format!("{}/version_files", MODRINTH_API_BASE))
+        .json(&corpo)
+        .send()
+        .await
+        .map_err(|e| format!("Erro na requisição Modrinth: {}", e))?;
+    if !resposta.status().is_success() {
+        return Err(format!(
+            "Modrinth retornou HTTP {} ao identificar arquivos",
+            resposta.status().as_u16()
+        ));
+    }
+    let versoes: serde_json::Value = resposta
+        .json()
+        .await
+        .map_err(|e| format!("Erro ao parsear resposta Modrinth: {}", e))?;
+
+    let mut identificados = HashMap::new();
+    for (hash, versao) in versoes.as_object().into_iter().flatten() {
+        let (Some(locais), Some(project_id), Some(versao_id)) = (
+            por_hash.get(hash.as_str()),
+            versao["project_id"].as_str(),
+            versao["id"].as_str(),
+        ) else {
+            continue;
+        };
+        for arquivo in locais {
+            identificados.insert(
+                arquivo.caminho.clone(),
+                ConteudoIdentificado {
+                    file_name: arquivo.nome.clone(),
+                    tipo_conteudo: arquivo.tipo_conteudo.clone(),
+                    plataforma: ModPlatform::Modrinth,
+                    project_id: project_id.to_string(),
+                    versao_id: versao_id.to_string(),
+                    nome: None,
+                    icone_url: None,
+                },
+            );
+        }
+    }
+
+    let ids: Vec<&str> = identificados
+        .values()
+        .map(|item| item.project_id.as_str())
+        .collect();
+    if !ids.is_empty() {
+        let url = format!(
+            "{}/projects?ids={}",
+            MODRINTH_API_BASE,
+            urlencoding::encode(&serde_json::json!(ids).to_string())
+        );
+        let projetos: serde_json::Value = match client.get(&url).send().await {
+            Ok(resposta) => resposta.json().await.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("[Identificacao] Erro ao buscar projetos Modrinth: {}", e);
+                serde_json::Value::Null
+            }
+        };
+        let projetos: HashMap<&str, &serde_json::Value> = projetos
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p["id"].as_str().map(|id| (id, p)))
+            .collect();
+        for item in identificados.values_mut() {
+            if let Some(projeto) = projetos.get(item.project_id.as_str()) {
+                item.nome = projeto["title"].as_str().map(|s| s.to_string());
+                item.icone_url = projeto["icon_url"].as_str().map(|s| s.to_string());
+            }
+        }
+    }
+
+    Ok(identificados)
+}
+
+async fn identificar_curseforge(
+    client: &reqwest::Client,
+    arquivos: &[ArquivoLocal],
+) -> Result<HashMap<String, ConteudoIdentificado>, String> {
+    if arquivos.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut por_fingerprint: HashMap<u32, Vec<&ArquivoLocal>> = HashMap::new();
+    for arquivo in arquivos {
+        por_fingerprint
+            .entry(arquivo.fingerprint)
+            .or_default()
+            .push(arquivo);
+    }
+
+    let requisicao = anexar_headers_curseforge(
+        client
+            .post(format!("{}/fingerprints/432", CURSEFORGE_API_BASE))
+            .json(&serde_json::json!({
+                "fingerprints": por_fingerprint.keys().collect::<Vec<_>>(),
+            })),
+    )?;
+    let resposta = requisicao
+        .send()
+        .await
+        .map_err(|e| format!("Erro na requisição CurseForge: {}", e))?;
+    if !resposta.status().is_success() {
+        return Err(format!(
+            "CurseForge retornou HTTP {} ao identificar arquivos",
+            resposta.status().as_u16()
+        ));
+    }
+    let payload: serde_json::Value = resposta
+        .json()
+        .await
+        .map_err(|e| format!("Erro ao parsear JSON CurseForge: {}", e))?;
+
+    let mut identificados = HashMap::new();
+    for correspondencia in payload["data"]["exactMatches"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let arquivo_remoto = &correspondencia["file"];
+        let Some(locais) = arquivo_remoto["fileFingerprint"]
+            .as_u64()
+            .and_then(|f| por_fingerprint.get(&(f as u32)))
+        else {
+            continue;
+        };
+        let (Some(mod_id), Some(file_id)) = (
+            correspondencia["id"]
+                .as_u64()
+                .or_else(|| arquivo_remoto["modId"].as_u64()),
+            arquivo_remoto["id"].as_u64(),
+        ) else {
+            continue;
+        };
+        for arquivo in locais {
+            identificados.insert(
+                arquivo.caminho.clone(),
+                ConteudoIdentificado {
+                    file_name: arquivo.nome.clone(),
+                    tipo_conteudo: arquivo.tipo_conteudo.clone(),
+                    plataforma: ModPlatform::CurseForge,
+                    project_id: mod_id.to_string(),
+                    versao_id: file_id.to_string(),
+                    nome: None,
+                    icone_url: None,
+                },
+            );
+        }
+    }
+
+    let ids: Vec<u64> = identificados
+        .values()
+        .filter_map(|item| item.project_id.parse().ok())
+        .collect();
+    if !ids.is_empty() {
+        let requisicao = anexar_headers_curseforge(
+            client
+                .post(format!("{}/mods", CURSEFORGE_API_BASE))
+                .json(&serde_json::json!({ "modIds": ids })),
+        )?;
+        let projetos: serde_json::Value = match requisicao.send().await {
+            Ok(resposta) => resposta.json().await.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("[Identificacao] Erro ao buscar projetos CurseForge: {}", e);
+                serde_json::Value::Null
+            }
+        };
+        let projetos: HashMap<String, &serde_json::Value> = projetos["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p["id"].as_u64().map(|id| (id.to_string(), p)))
+            .collect();
+        for item in identificados.values_mut() {
+            if let Some(projeto) = projetos.get(&item.project_id) {
+                item.nome = projeto["name"].as_str().map(|s| s.to_string());
+                item.icone_url = projeto["logo"]["thumbnailUrl"]
+                    .as_str()
+                    .or_else(|| projeto["logo"]["url"].as_str())
+                    .map(|s| s.to_string());
+            }
+        }
+    }
+
+    Ok(identificados)
+}
+
+/// Associa os arquivos identificados às entradas do lockfile, preservando a
+/// origem e a fixação das que já existiam. Arquivos novos no lockfile ficam com
+/// origem desconhecida.
+fn gravar_identificacoes(
+    instance_path: &std::path::Path,
+    arquivos: &[ArquivoLocal],
+    identificados: &[ConteudoIdentificado],
+) -> Result<(), String> {
+    let hashes: HashMap<(&str, &str), &HashesConteudo> = arquivos
+        .iter()
+        .map(|a| ((a.tipo_conteudo.as_str(), a.nome.as_str()), &a.hashes))
+        .collect();
+
+    alterar_lockfile(instance_path, |lockfile| {
+        for item in identificados {
+            let hashes = hashes
+                .get(&(item.tipo_conteudo.as_str(), item.file_name.as_str()))
+                .cloned()
+                .cloned();
+            match lockfile.entrada_mut(&item.tipo_conteudo, &item.file_name) {
+                Some(entrada) => {
+                    entrada.plataforma = Some(item.plataforma.clone());
+                    entrada.project_id = Some(item.project_id.clone());
+                    entrada.versao_id = Some(item.versao_id.clone());
+                    entrada.nome = item.nome.clone();
+                    entrada.icone_url = item.icone_url.clone();
+                    if let Some(hashes) = hashes {
+                        entrada.hashes = hashes;
+                    }
+                }
+                None => {
+                    let mut entrada = EntradaConteudo::nova(
+                        &item.file_name,
+                        &item.tipo_conteudo,
+                        hashes.unwrap_or_default(),
+                        OrigemInstalacao::Desconhecida,
+                    )
+                    .com_projeto(
+                        item.plataforma.clone(),
+                        Some(item.project_id.clone()),
+                        Some(item.versao_id.clone()),
+                    );
+                    entrada.nome = item.nome.clone();
+                    entrada.icone_url = item.icone_url.clone();
+                    lockfile.registrar(entrada);
+                }
+            }
+        }
+    })
+}
+
+/// Identifica pelo hash os arquivos sem origem conhecida: primeiro no Modrinth
+/// (SHA-512 em `version_files`) e o restante no CurseForge (fingerprint murmur2).
+/// As correspondências são gravadas no lockfile da instância.
+#[tauri::command]
+pub(crate) async fn identificar_conteudo_instalado(
+    instance_id: String,
+    reidentificar: Option<bool>,
+    state: State<'_, LauncherState>,
+) -> Result<RelatorioIdentificacao, String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    let apenas_sem_projeto = !reidentificar.unwrap_or(false);
+
+    let caminho = instance_path.clone();
+    let (arquivos, ja_rastreados) = tauri::async_runtime::spawn_blocking(move || {
+        coletar_arquivos_locais(&caminho, apenas_sem_projeto)
+    })
+    .await
+    .map_err(|e| format!("Erro ao calcular hashes do conteúdo: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("DomeLauncher/1.0 (+https://domestudios.com.br)")
+        .build()
+        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+
+    let mut identificados = identificar_modrinth(&client, &arquivos)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("[Identificacao] {}", e);
+            HashMap::new()
+        });
+
+    let restantes: Vec<ArquivoLocal> = arquivos
+        .iter()
+        .filter(|a| !identificados.contains_key(&a.caminho))
+        .cloned()
+        .collect();
+    match identificar_curseforge(&client, &restantes).await {
+        Ok(curseforge) => identificados.extend(curseforge),
+        Err(e) => eprintln!("[Identificacao] {}", e),
+    }
+
+    let nao_identificados = arquivos
+        .iter()
+        .filter(|a| !identificados.contains_key(&a.caminho))
+        .map(|a| a.caminho.clone())
+        .collect();
+    let mut relatorio = RelatorioIdentificacao {
+        identificados: identificados.into_values().collect(),
+        nao_identificados,
+        ja_rastreados,
+    };
+    relatorio
+        .identificados
+        .sort_by_key(|item| item.file_name.to_lowercase());
+
+    gravar_identificacoes(&instance_path, &arquivos, &relatorio.identificados)?;
+    println!(
+        "[Identificacao] {}: {} identificado(s), {} sem correspondência",
+        instance_id,
+        relatorio.identificados.len(),
+        relatorio.nao_identificados.len()
+    );
+    Ok(relatorio)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn fingerprint_ignora_espacos_em_branco() {
+        assert_eq!(murmur2(b"", 1), 0x5bd1_5e36);
+        assert_eq!(
+            fingerprint_curseforge(b"abc def\r\n\tghi"),
+            fingerprint_curseforge(b"abcdefghi")
+        );
+        assert_ne!(
+            fingerprint_curseforge(b"abcdefghi"),
+            fingerprint_curseforge(b"abcdefghj")
+        );
+    }
+
+    #[test]
+    fn identificacao_preserva_origem_e_fixacao_do_lockfile() {
+        let pasta = std::env::temp_dir().join(format!("dome_ident_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        let mut existente = EntradaConteudo::nova(
+            "jei.jar",
+            "mod",
+            HashesConteudo::default(),
+            OrigemInstalacao::Modpack,
+        );
+        existente.fixado = true;
+        super::super::registro_conteudo::registrar_conteudo(&pasta, vec![existente]).unwrap();
+
+        let arquivo = |nome: &str| ArquivoLocal {
+            nome: nome.to_string(),
+            caminho: format!("mods/{}", nome),
+            tipo_conteudo: "mod".to_string(),
+            hashes: HashesConteudo::de_bytes(nome.as_bytes()),
+            fingerprint: 7,
+        };
+        let identificado = |nome: &str, project_id: &str| ConteudoIdentificado {
+            file_name: nome.to_string(),
+            tipo_conteudo: "mod".to_string(),
+            plataforma: ModPlatform::CurseForge,
+            project_id: project_id.to_string(),
+            versao_id: "4712866".to_string(),
+            nome: Some("Just Enough Items".to_string()),
+            icone_url: None,
+        };
+        let arquivos = vec![arquivo("jei.jar"), arquivo("appleskin.jar")];
+        let identificados = vec![
+            identificado("jei.jar", "238222"),
+            identificado("appleskin.jar", "248787"),
+        ];
+        gravar_identificacoes(&pasta, &arquivos, &identificados).unwrap();
+
+        let lockfile = carregar_lockfile(&pasta);
+        let entrada = &lockfile.entradas[0];
+        assert_eq!(entrada.origem, OrigemInstalacao::Modpack);
+        assert!(entrada.fixado);
+        assert_eq!(entrada.project_id.as_deref(), Some("238222"));
+        assert_eq!(entrada.nome.as_deref(), Some("Just Enough Items"));
+        assert!(entrada.hashes.sha512.is_some());
+        // Sem entrada anterior, não dá para saber se o arquivo veio do usuário.
+        assert_eq!(lockfile.entradas[1].origem, OrigemInstalacao::Desconhecida);
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn arquivos_com_mesmo_nome_em_pastas_diferentes_nao_colidem() {
+        let pasta = std::env::temp_dir().join(format!("dome_ident_{}", uuid::Uuid::new_v4()));
+        for (subpasta, conteudo) in [("mods", "mod"), ("resourcepacks", "pack")] {
+            std::fs::create_dir_all(pasta.join(subpasta)).unwrap();
+            std::fs::write(pasta.join(subpasta).join("extra.zip"), conteudo).unwrap();
+        }
+
+        let (arquivos, _) = coletar_arquivos_locais(&pasta, false);
+        let mut caminhos: Vec<&str> = arquivos.iter().map(|a| a.caminho.as_str()).collect();
+        caminhos.sort();
+        assert_eq!(caminhos, vec!["mods/extra.zip", "resourcepacks/extra.zip"]);
+
+        let identificados: Vec<ConteudoIdentificado> = arquivos
+            .iter()
+            .map(|a| ConteudoIdentificado {
+                file_name: a.nome.clone(),
+                tipo_conteudo: a.tipo_conteudo.clone(),
+                plataforma: ModPlatform::Modrinth,
+                project_id: a.tipo_conteudo.clone(),
+                versao_id: "v1".to_string(),
+                nome: None,
+                icone_url: None,
+            })
+            .collect();
+        gravar_identificacoes(&pasta, &arquivos, &identificados).unwrap();
+
+        let lockfile = carregar_lockfile(&pasta);
+        let sha1_de = |tipo: &str| {
+            lockfile
+                .entradas
+                .iter()
+                .find(|e| e.tipo_conteudo == tipo)
+                .and_then(|e| e.hashes.sha1.clone())
+        };
+        assert_eq!(sha1_de("mod"), HashesConteudo::de_bytes(b"mod").sha1);
+        assert_eq!(
+            sha1_de("resourcepack"),
+            HashesConteudo::de_bytes(b"pack").sha1
+        );
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+}
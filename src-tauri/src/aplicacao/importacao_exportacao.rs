@@ -1,3 +1,4 @@
+use super::importacao_launchers::{listar_instancias_nas_raizes, RaizesImportacao};
 use super::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -13,6 +14,21 @@ pub(crate) struct InstanciaImportavelExterna {
     pub icone: Option<String>,
     pub caminho_origem: String,
     pub caminho_jogo: String,
+    #[serde(default)]
+    pub configuracao: ConfiguracaoImportada,
+}
+
+/// Ajustes da instância de origem que o Dome consegue reproduzir, e avisos
+/// sobre o que ficou de fora.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct ConfiguracaoImportada {
+    pub memoria_mb: Option<u32>,
+    pub java_args: Option<String>,
+    pub java_path: Option<String>,
+    pub largura: Option<u32>,
+    pub altura: Option<u32>,
+    pub avisos: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,19 +42,151 @@ pub(crate) struct ResultadoImportacaoInstancia {
     pub mensagem: String,
 }
 
-fn listar_instancias_prism() -> Vec<InstanciaImportavelExterna> {
-    let Some(app_data) = std::env::var("APPDATA").ok() else {
-        return Vec::new();
-    };
+/// Valor do `instance.cfg` sem as aspas que o Qt coloca em textos com espaços
+/// e sem os escapes (`\"`, `\\`) que ele grava dentro deles.
+fn valor_cfg(cfg: &std::collections::HashMap<String, String>, chave: &str) -> Option<String> {
+    let valor = cfg.get(chave)?.trim();
+    let valor = valor
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(valor)
+        .trim();
+    let mut texto = String::with_capacity(valor.len());
+    let mut caracteres = valor.chars();
+    while let Some(caractere) = caracteres.next() {
+        if caractere != '\\' {
+            texto.push(caractere);
+            continue;
+        }
+        match caracteres.next() {
+            Some('n') => texto.push('\n'),
+            Some('t') => texto.push('\t'),
+            Some('r') => texto.push('\r'),
+            Some(escapado) => texto.push(escapado),
+            None => texto.push('\\'),
+        }
+    }
+    (!texto.is_empty()).then_some(texto)
+}
+
+/// Ajustes por instância do Prism/MultiMC. Só valem quando a opção
+/// `Override*` correspondente está ligada (ou ausente, em versões antigas).
+fn configuracao_instancia_mmc(
+    cfg: &std::collections::HashMap<String, String>,
+    pasta_instancias: &std::path::Path,
+) -> ConfiguracaoImportada {
+    let sobrescrito = |chave: &str| valor_cfg(cfg, chave).is_none_or(|valor| valor == "true");
+    let numero = |chave: &str| valor_cfg(cfg, chave).and_then(|valor| valor.parse::<u32>().ok());
+    let mut configuracao = ConfiguracaoImportada::default();
+
+    if sobrescrito("OverrideMemory") {
+        configuracao.memoria_mb = numero("MaxMemAlloc").filter(|memoria| *memoria > 0);
+    }
+    if sobrescrito("OverrideJavaArgs") {
+        configuracao.java_args = valor_cfg(cfg, "JvmArgs");
+    }
+    if sobrescrito("OverrideJavaLocation") {
+        if let Some(java) = valor_cfg(cfg, "JavaPath") {
+            // Caminhos relativos são da pasta do launcher (Java baixado pelo Prism).
+            let caminho = std::path::Path::new(&java);
+            let caminho = if caminho.is_absolute() {
+                caminho.to_path_buf()
+            } else {
+                pasta_instancias
+                    .parent()
+                    .unwrap_or(pasta_instancias)
+                    .join(caminho)
+            };
+            if caminho.is_file() {
+                configuracao.java_path = Some(caminho.to_string_lossy().to_string());
+            } else {
+                configuracao
+                    .avisos
+                    .push(format!("Java personalizado não encontrado ({})", java));
+            }
+        }
+    }
+    if sobrescrito("OverrideWindow") {
+        if valor_cfg(cfg, "LaunchMaximized").as_deref() == Some("true") {
+            configuracao
+                .avisos
+                .push("opção de abrir o jogo maximizado".to_string());
+        } else {
+            configuracao.largura = numero("MinecraftWinWidth").filter(|valor| *valor > 0);
+            configuracao.altura = numero("MinecraftWinHeight").filter(|valor| *valor > 0);
+        }
+    }
+    configuracao
+}
+
+/// Versão do Minecraft e loader do `mmc-pack.json`. Componentes que o Dome não
+/// reproduz (LWJGL trocado, jar mods, patches próprios, um segundo loader)
+/// entram em `avisos`.
+fn componentes_mmc(
+    caminho_origem: &std::path::Path,
+    avisos: &mut Vec<String>,
+) -> (String, Option<String>, Option<String>) {
+    let mut versao_minecraft = String::new();
+    let mut loader_tipo: Option<String> = None;
+    let mut loader_versao: Option<String> = None;
+
+    let json = std::fs::read_to_string(caminho_origem.join("mmc-pack.json"))
+        .ok()
+        .and_then(|conteudo| serde_json::from_str::<serde_json::Value>(&conteudo).ok())
+        .unwrap_or_default();
+    for componente in json["components"].as_array().into_iter().flatten() {
+        let uid = texto_json_caminho(componente, &["uid"]).unwrap_or_default();
+        let versao = texto_json_caminho(componente, &["version"]);
+        let nome_componente =
+            texto_json_caminho(componente, &["cachedName"]).unwrap_or_else(|| uid.clone());
+        let descricao = match &versao {
+            Some(versao) => format!("{} {}", nome_componente, versao),
+            None => nome_componente.clone(),
+        };
+
+        let loader = match uid.as_str() {
+            "net.minecraft" => {
+                if versao_minecraft.is_empty() {
+                    versao_minecraft = versao.unwrap_or_default();
+                }
+                continue;
+            }
+            "net.minecraftforge" => "Forge",
+            "net.fabricmc.fabric-loader" => "Fabric",
+            "net.neoforged" => "NeoForge",
+            "org.quiltmc.quilt-loader" => "Quilt",
+            // Dependências implícitas dos loaders, instaladas junto com eles.
+            "net.fabricmc.intermediary" | "org.quiltmc.hashed" => continue,
+            "org.lwjgl" | "org.lwjgl3" => {
+                if !componente["dependencyOnly"].as_bool().unwrap_or(false) {
+                    avisos.push(format!("versão personalizada do {}", descricao));
+                }
+                continue;
+            }
+            _ if uid.starts_with("org.multimc.jarmod.") => {
+                avisos.push(format!("jar mod {}", nome_componente));
+                continue;
+            }
+            _ => {
+                avisos.push(format!("componente {}", descricao));
+                continue;
+            }
+        };
+        if loader_tipo.is_some() {
+            avisos.push(format!("loader adicional {}", descricao));
+            continue;
+        }
+        loader_tipo = Some(loader.to_string());
+        loader_versao = versao;
+    }
 
-    let pasta_instancias = std::path::PathBuf::from(app_data)
-        .join("PrismLauncher")
-        .join("instances");
-    listar_instancias_prism_em(&pasta_instancias)
+    (versao_minecraft, loader_tipo, loader_versao)
 }
 
-fn listar_instancias_prism_em(
+/// Instâncias no formato do Prism/MultiMC (`instance.cfg` + `mmc-pack.json`).
+pub(super) fn listar_instancias_mmc_em(
     pasta_instancias: &std::path::Path,
+    launcher: &str,
 ) -> Vec<InstanciaImportavelExterna> {
     if !pasta_instancias.exists() {
         return Vec::new();
@@ -67,57 +215,22 @@ fn listar_instancias_prism_em(
             .cloned()
             .unwrap_or_else(|| entrada.file_name().to_string_lossy().to_string());
 
-        let mut versao_minecraft = String::new();
-        let mut loader_tipo: Option<String> = None;
-        let mut loader_versao: Option<String> = None;
-
-        let caminho_mmc_pack = caminho_origem.join("mmc-pack.json");
-        if caminho_mmc_pack.exists() {
-            if let Ok(conteudo) = std::fs::read_to_string(&caminho_mmc_pack) {
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&conteudo) {
-                    if let Some(componentes) = json.get("components").and_then(|v| v.as_array()) {
-                        for componente in componentes {
-                            let uid = texto_json_caminho(componente, &["uid"]).unwrap_or_default();
-                            let versao = texto_json_caminho(componente, &["version"]);
-
-                            match uid.as_str() {
-                                "net.minecraft" => {
-                                    if versao_minecraft.is_empty() {
-                                        versao_minecraft = versao.unwrap_or_default();
-                                    }
-                                }
-                                "net.minecraftforge" => {
-                                    loader_tipo = Some("Forge".to_string());
-                                    loader_versao = versao;
-                                }
-                                "net.fabricmc.fabric-loader" => {
-                                    loader_tipo = Some("Fabric".to_string());
-                                    loader_versao = versao;
-                                }
-                                "net.neoforged" => {
-                                    loader_tipo = Some("NeoForge".to_string());
-                                    loader_versao = versao;
-                                }
-                                "org.quiltmc.quilt-loader" => {
-                                    loader_tipo = Some("Quilt".to_string());
-                                    loader_versao = versao;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let mut configuracao = configuracao_instancia_mmc(&cfg, pasta_instancias);
+        let (mut versao_minecraft, loader_tipo, loader_versao) =
+            componentes_mmc(&caminho_origem, &mut configuracao.avisos);
 
+        // Instâncias antigas do MultiMC não têm `mmc-pack.json`.
+        if versao_minecraft.trim().is_empty() {
+            versao_minecraft = cfg.get("IntendedVersion").cloned().unwrap_or_default();
+        }
         if versao_minecraft.trim().is_empty() {
             continue;
         }
 
         let caminho_jogo = detectar_caminho_jogo(&caminho_origem);
         resultados.push(InstanciaImportavelExterna {
-            id_externo: format!("prism:{}", caminho_origem.to_string_lossy()),
-            launcher: "prism".to_string(),
+            id_externo: format!("{}:{}", launcher, caminho_origem.to_string_lossy()),
+            launcher: launcher.to_string(),
             nome,
             versao_minecraft,
             loader_type: loader_tipo,
@@ -125,6 +238,7 @@ fn listar_instancias_prism_em(
             icone: None,
             caminho_origem: caminho_origem.to_string_lossy().to_string(),
             caminho_jogo: caminho_jogo.to_string_lossy().to_string(),
+            configuracao,
         });
     }
 
@@ -198,6 +312,7 @@ fn listar_instancias_modrinth_por_profile_json(
             icone,
             caminho_origem: caminho_origem.to_string_lossy().to_string(),
             caminho_jogo: caminho_jogo.to_string_lossy().to_string(),
+            configuracao: Default::default(),
         });
     }
 
@@ -260,6 +375,7 @@ fn montar_instancia_modrinth(
         caminho_jogo: detectar_caminho_jogo(&caminho_origem)
             .to_string_lossy()
             .to_string(),
+        configuracao: Default::default(),
     })
 }
 
@@ -325,31 +441,17 @@ fn listar_instancias_modrinth_por_banco(
     consultar_instancias_modrinth(&conexao, pasta_base, CONSULTA_LEGADA).unwrap_or_default()
 }
 
-fn listar_instancias_modrinth() -> Vec<InstanciaImportavelExterna> {
-    let Some(app_data) = std::env::var("APPDATA").ok() else {
-        return Vec::new();
-    };
-
-    let pastas_base = vec![
-        std::path::PathBuf::from(&app_data)
-            .join("com.modrinth.theseus")
-            .join("profiles"),
-        std::path::PathBuf::from(&app_data)
-            .join("ModrinthApp")
-            .join("profiles"),
-        std::path::PathBuf::from(&app_data)
-            .join("theseus")
-            .join("profiles"),
-    ];
-
+pub(super) fn listar_instancias_modrinth_em(
+    pastas_base: &[std::path::PathBuf],
+) -> Vec<InstanciaImportavelExterna> {
     let mut resultados = Vec::new();
     for pasta_base in pastas_base {
         if !pasta_base.exists() {
             continue;
         }
 
-        resultados.extend(listar_instancias_modrinth_por_profile_json(&pasta_base));
-        resultados.extend(listar_instancias_modrinth_por_banco(&pasta_base));
+        resultados.extend(listar_instancias_modrinth_por_profile_json(pasta_base));
+        resultados.extend(listar_instancias_modrinth_por_banco(pasta_base));
     }
 
     resultados
@@ -400,37 +502,7 @@ fn extrair_loader_curseforge(
     (loader_type, loader_version)
 }
 
-fn listar_instancias_curseforge() -> Vec<InstanciaImportavelExterna> {
-    let Some(user_profile) = std::env::var("USERPROFILE").ok() else {
-        return Vec::new();
-    };
-
-    let pastas_base = vec![
-        std::path::PathBuf::from(&user_profile)
-            .join("curseforge")
-            .join("minecraft")
-            .join("Instances"),
-        std::path::PathBuf::from(&user_profile)
-            .join("Documents")
-            .join("Curse")
-            .join("Minecraft")
-            .join("Instances"),
-    ];
-
-    let mut resultados = Vec::new();
-
-    for pasta_base in pastas_base {
-        if !pasta_base.exists() {
-            continue;
-        }
-
-        resultados.extend(listar_instancias_curseforge_em(&pasta_base));
-    }
-
-    resultados
-}
-
-fn listar_instancias_curseforge_em(
+pub(super) fn listar_instancias_curseforge_em(
     pasta_base: &std::path::Path,
 ) -> Vec<InstanciaImportavelExterna> {
     let mut resultados = Vec::new();
@@ -485,64 +557,31 @@ fn listar_instancias_curseforge_em(
                 .or_else(|| texto_json_caminho(&json, &["thumbnailUrl"])),
             caminho_origem: caminho_origem.to_string_lossy().to_string(),
             caminho_jogo: caminho_jogo.to_string_lossy().to_string(),
+            configuracao: Default::default(),
         });
     }
 
     resultados
 }
 
-fn listar_instancias_em_pasta_apontada(
-    pasta_apontada: &std::path::Path,
-) -> Vec<InstanciaImportavelExterna> {
-    let candidatos = [
-        pasta_apontada.to_path_buf(),
-        pasta_apontada.join("instances"),
-        pasta_apontada.join("Instances"),
-        pasta_apontada.join("profiles"),
-        pasta_apontada.join("minecraft").join("Instances"),
-        pasta_apontada.join("Minecraft").join("Instances"),
-    ];
-    let mut pastas_vistas = std::collections::HashSet::new();
-    let mut resultados = Vec::new();
-
-    for pasta_base in candidatos {
-        if !pasta_base.is_dir() {
-            continue;
-        }
-
-        let chave = pasta_base.to_string_lossy().to_lowercase();
-        if !pastas_vistas.insert(chave) {
-            continue;
-        }
-
-        resultados.extend(listar_instancias_prism_em(&pasta_base));
-        resultados.extend(listar_instancias_modrinth_por_profile_json(&pasta_base));
-        resultados.extend(listar_instancias_modrinth_por_banco(&pasta_base));
-        resultados.extend(listar_instancias_curseforge_em(&pasta_base));
-    }
-
-    resultados
-}
-
 #[tauri::command]
 pub(crate) fn listar_instancias_importaveis(
     caminhos_adicionais: Option<Vec<String>>,
 ) -> Result<Vec<InstanciaImportavelExterna>, String> {
-    let mut resultados = Vec::new();
-    resultados.extend(listar_instancias_prism());
-    resultados.extend(listar_instancias_modrinth());
-    resultados.extend(listar_instancias_curseforge());
+    let mut resultados = listar_instancias_nas_raizes(&RaizesImportacao::padrao());
 
     for caminho in caminhos_adicionais.unwrap_or_default() {
         let pasta_apontada = std::path::PathBuf::from(caminho.trim());
         if pasta_apontada.is_dir() {
-            resultados.extend(listar_instancias_em_pasta_apontada(&pasta_apontada));
+            resultados.extend(listar_instancias_nas_raizes(&RaizesImportacao::em_pasta(
+                &pasta_apontada,
+            )));
         }
     }
 
-    let mut caminhos_vistos = std::collections::HashSet::new();
-    resultados
-        .retain(|instancia| caminhos_vistos.insert(instancia.caminho_origem.trim().to_lowercase()));
+    // Perfis do launcher oficial podem dividir a mesma pasta; o id externo é único.
+    let mut ids_vistos = std::collections::HashSet::new();
+    resultados.retain(|instancia| ids_vistos.insert(instancia.id_externo.trim().to_lowercase()));
 
     resultados.sort_by(|a, b| {
         a.launcher
@@ -553,27 +592,78 @@ pub(crate) fn listar_instancias_importaveis(
     Ok(resultados)
 }
 
-fn copiar_arquivo_se_existir(
+/// Como um arquivo chega ao destino de uma cópia.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ModoCopia {
+    /// Cópia independente. `std::fs::copy` já clona os blocos (reflink)
+    /// quando o sistema de arquivos oferece: APFS, Btrfs, XFS e ReFS.
+    Copiar,
+    /// Hardlink, para arquivos que o launcher só substitui e nunca altera no
+    /// lugar (jars, pacotes, capturas). Cai para a cópia se não for possível.
+    Vincular,
+}
+
+/// Quantos arquivos foram copiados e quantos viraram hardlinks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct ResumoCopia {
+    pub copiados: usize,
+    pub vinculados: usize,
+}
+
+impl ResumoCopia {
+    pub(super) fn somar(&mut self, outro: ResumoCopia) {
+        self.copiados += outro.copiados;
+        self.vinculados += outro.vinculados;
+    }
+}
+
+pub(super) fn copiar_arquivo_com_modo(
     origem: &std::path::Path,
     destino: &std::path::Path,
-) -> Result<(), String> {
+    modo: ModoCopia,
+) -> Result<ResumoCopia, String> {
     if !origem.exists() || !origem.is_file() {
-        return Ok(());
+        return Ok(ResumoCopia::default());
     }
 
     if let Some(pai) = destino.parent() {
         std::fs::create_dir_all(pai).map_err(|e| format!("Erro ao criar pasta destino: {}", e))?;
     }
+    if modo == ModoCopia::Vincular && std::fs::hard_link(origem, destino).is_ok() {
+        return Ok(ResumoCopia {
+            copiados: 0,
+            vinculados: 1,
+        });
+    }
     std::fs::copy(origem, destino).map_err(|e| format!("Erro ao copiar arquivo: {}", e))?;
-    Ok(())
+    Ok(ResumoCopia {
+        copiados: 1,
+        vinculados: 0,
+    })
+}
+
+fn copiar_arquivo_se_existir(
+    origem: &std::path::Path,
+    destino: &std::path::Path,
+) -> Result<(), String> {
+    copiar_arquivo_com_modo(origem, destino, ModoCopia::Copiar).map(|_| ())
 }
 
 fn copiar_diretorio_recursivo(
     origem: &std::path::Path,
     destino: &std::path::Path,
 ) -> Result<(), String> {
+    copiar_diretorio_com_modo(origem, destino, ModoCopia::Copiar).map(|_| ())
+}
+
+pub(super) fn copiar_diretorio_com_modo(
+    origem: &std::path::Path,
+    destino: &std::path::Path,
+    modo: ModoCopia,
+) -> Result<ResumoCopia, String> {
+    let mut resumo = ResumoCopia::default();
     if !origem.exists() || !origem.is_dir() {
-        return Ok(());
+        return Ok(resumo);
     }
 
     std::fs::create_dir_all(destino).map_err(|e| format!("Erro ao criar pasta destino: {}", e))?;
@@ -593,13 +683,17 @@ fn copiar_diretorio_recursivo(
         let destino_item = destino.join(entrada.file_name());
 
         if tipo.is_dir() {
-            copiar_diretorio_recursivo(&origem_item, &destino_item)?;
+            resumo.somar(copiar_diretorio_com_modo(
+                &origem_item,
+                &destino_item,
+                modo,
+            )?);
         } else if tipo.is_file() {
-            copiar_arquivo_se_existir(&origem_item, &destino_item)?;
+            resumo.somar(copiar_arquivo_com_modo(&origem_item, &destino_item, modo)?);
         }
     }
 
-    Ok(())
+    Ok(resumo)
 }
 
 fn copiar_conteudo_instancia_importada(
@@ -646,7 +740,7 @@ fn copiar_conteudo_instancia_importada(
     Ok(())
 }
 
-fn gerar_nome_instancia_unico(state: &LauncherState, nome_base: &str) -> String {
+pub(super) fn gerar_nome_instancia_unico(state: &LauncherState, nome_base: &str) -> String {
     let nome_base = if nome_base.trim().is_empty() {
         "Instância importada".to_string()
     } else {
@@ -720,25 +814,7 @@ async fn resolver_versao_loader_importacao(
         return Ok(versao.to_string());
     }
 
-    let resposta =
-        super::instancias_criacao::get_loader_versions(loader_normalizado.to_string()).await?;
-    let versoes: Vec<String> = resposta.versions.into_iter().map(|v| v.version).collect();
-    if loader_normalizado == "forge" {
-        if let Some(versao) = versoes
-            .iter()
-            .find(|versao| versao.starts_with(&format!("{}-", versao_minecraft)))
-            .cloned()
-        {
-            return Ok(versao);
-        }
-    }
-
-    versoes.into_iter().next().ok_or_else(|| {
-        format!(
-            "Nenhuma versão disponível para loader {}.",
-            loader_normalizado
-        )
-    })
+    super::versoes_loader::versao_loader_recomendada(loader_normalizado, versao_minecraft).await
 }
 
 async fn buscar_versoes_fabric_compativeis(versao_minecraft: &str) -> Result<Vec<String>, String> {
@@ -904,7 +980,7 @@ async fn criar_instancia_base_importada(
         std::fs::create_dir_all(&instance_path).map_err(|e| e.to_string())?;
     }
 
-    super::instancias_criacao::download_instance_files(&instance_path, &details).await?;
+    super::instancias_criacao::download_instance_files(&instance_path, &details, None).await?;
 
     let loader_normalizado = detectar_loader_normalizado(loader_type);
     let (loader_type_salvo, loader_version_final, mc_type) = match loader_normalizado.as_deref() {
@@ -916,6 +992,7 @@ async fn criar_instancia_base_importada(
                 &instance_path,
                 versao_minecraft,
                 &versao_loader,
+                None,
             )
             .await?;
             (
@@ -932,6 +1009,7 @@ async fn criar_instancia_base_importada(
                 &instance_path,
                 versao_minecraft,
                 &versao_loader,
+                None,
             )
             .await?;
             (
@@ -948,6 +1026,7 @@ async fn criar_instancia_base_importada(
                 &instance_path,
                 versao_minecraft,
                 &versao_loader,
+                None,
             )
             .await?;
             (
@@ -957,7 +1036,21 @@ async fn criar_instancia_base_importada(
             )
         }
         Some("quilt") => {
-            return Err("Instâncias com Quilt ainda não são suportadas no importador.".to_string());
+            let versao_loader =
+                resolver_versao_loader_importacao("quilt", versao_minecraft, loader_version)
+                    .await?;
+            super::instancias_criacao::install_quilt_loader(
+                &instance_path,
+                versao_minecraft,
+                &versao_loader,
+                None,
+            )
+            .await?;
+            (
+                Some("Quilt".to_string()),
+                Some(versao_loader),
+                "quilt".to_string(),
+            )
         }
         Some("vanilla") | None => (Some("Vanilla".to_string()), None, "vanilla".to_string()),
         Some(outro) => {
@@ -985,6 +1078,7 @@ async fn criar_instancia_base_importada(
         path: instance_path.clone(),
         java_args: None,
         mc_args: None,
+        java_path: None,
         memory: None,
         width: None,
         height: None,
@@ -1057,7 +1151,7 @@ pub(crate) fn atualizar_icones_instancias_modrinth_existentes(
         return Ok(0);
     }
 
-    let icones_por_nome = listar_instancias_modrinth()
+    let icones_por_nome = listar_instancias_modrinth_em(&RaizesImportacao::padrao().modrinth)
         .into_iter()
         .filter_map(|instancia| {
             preparar_icone_importado(instancia.icone.as_deref())
@@ -1095,6 +1189,38 @@ pub(crate) fn atualizar_icones_instancias_modrinth_existentes(
     Ok(quantidade_atualizada)
 }
 
+/// Grava na instância criada os ajustes trazidos da origem, devolvendo o que
+/// não pôde ser reproduzido.
+fn aplicar_configuracao_importada(
+    mut instancia: Instance,
+    configuracao: &ConfiguracaoImportada,
+) -> Vec<String> {
+    let mut avisos = configuracao.avisos.clone();
+    if configuracao == &ConfiguracaoImportada::default() {
+        return avisos;
+    }
+    instancia.memory = configuracao.memoria_mb.or(instancia.memory);
+    instancia.java_args = configuracao.java_args.clone().or(instancia.java_args);
+    instancia.java_path = configuracao.java_path.clone().or(instancia.java_path);
+    instancia.width = configuracao.largura.or(instancia.width);
+    instancia.height = configuracao.altura.or(instancia.height);
+    if let Err(e) = gravar_instancia(&instancia) {
+        avisos.push(format!("ajustes de memória, Java e janela ({})", e));
+    }
+    avisos
+}
+
+fn mensagem_importacao(mensagem: &str, avisos: &[String]) -> String {
+    if avisos.is_empty() {
+        return mensagem.to_string();
+    }
+    format!(
+        "{} Não foi possível importar: {}.",
+        mensagem,
+        avisos.join("; ")
+    )
+}
+
 #[tauri::command]
 pub(crate) async fn importar_instancias_externas(
     instancias: Vec<InstanciaImportavelExterna>,
@@ -1119,6 +1245,10 @@ pub(crate) async fn importar_instancias_externas(
         .await
         {
             Ok(instancia_criada) => {
+                let avisos = aplicar_configuracao_importada(
+                    instancia_criada.clone(),
+                    &instancia.configuracao,
+                );
                 let caminho_jogo = std::path::PathBuf::from(instancia.caminho_jogo.trim());
                 let mensagem_copia =
                     copiar_conteudo_instancia_importada(&caminho_jogo, &instancia_criada.path);
@@ -1129,9 +1259,12 @@ pub(crate) async fn importar_instancias_externas(
                         nome_origem: instancia.nome.clone(),
                         sucesso: true,
                         instancia_id: Some(instancia_criada.id.clone()),
-                        mensagem: format!(
-                            "Instância importada, mas houve falha ao copiar parte dos arquivos: {}",
-                            erro_copia
+                        mensagem: mensagem_importacao(
+                            &format!(
+                                "Instância importada, mas houve falha ao copiar parte dos arquivos: {}",
+                                erro_copia
+                            ),
+                            &avisos,
                         ),
                     }
                 } else {
@@ -1141,7 +1274,7 @@ pub(crate) async fn importar_instancias_externas(
                         nome_origem: instancia.nome.clone(),
                         sucesso: true,
                         instancia_id: Some(instancia_criada.id.clone()),
-                        mensagem: "Instância importada com sucesso.".to_string(),
+                        mensagem: mensagem_importacao("Instância importada com sucesso.", &avisos),
                     }
                 }
             }
@@ -1261,7 +1394,17 @@ fn adicionar_diretorio_ao_zip(
     Ok(())
 }
 
-fn resolver_pasta_destino_exportacao(destino: Option<&str>) -> std::path::PathBuf {
+/// Nome da instância reduzido a caracteres seguros para nome de arquivo. O
+/// ponto é mantido (versões como `1.0.0`), exceto nas pontas.
+pub(super) fn nome_arquivo_exportacao(nome: &str) -> String {
+    nome.chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+        .collect::<String>()
+        .trim_matches(|c| c == ' ' || c == '.')
+        .replace(' ', "_")
+}
+
+pub(super) fn resolver_pasta_destino_exportacao(destino: Option<&str>) -> std::path::PathBuf {
     if let Some(dest) = destino {
         return std::path::PathBuf::from(dest);
     }
@@ -1286,16 +1429,7 @@ fn exportar_instancia_interno(
     }
 
     // Nome sanitizado para arquivo
-    let nome_arquivo = format!(
-        "{}.dome",
-        instancia
-            .name
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
-            .collect::<String>()
-            .trim()
-            .replace(' ', "_")
-    );
+    let nome_arquivo = format!("{}.dome", nome_arquivo_exportacao(&instancia.name));
 
     let caminho_zip = pasta_destino.join(&nome_arquivo);
 
@@ -1518,36 +1652,16 @@ pub(crate) async fn importar_instancia_arquivo(
         zip::ZipArchive::new(arquivo).map_err(|e| format!("Erro ao reler arquivo zip: {}", e))?;
 
     // Itens que devemos extrair (ignorando manifesto e instance.json pois já usamos)
-    for i in 0..zip.len() {
-        let mut entry = match zip.by_index(i) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let nome_entrada = match entry.enclosed_name() {
-            Some(n) => n.to_path_buf(),
-            None => continue,
-        };
-
-        let nome_str = nome_entrada.to_string_lossy().to_string();
-        // Pular manifesto e instance.json (já processados)
-        if nome_str == "dome_manifest.json" || nome_str == "instance.json" {
-            continue;
-        }
-
-        let destino = instancia_criada.path.join(&nome_entrada);
-
-        if entry.is_dir() {
-            std::fs::create_dir_all(&destino).ok();
-        } else {
-            if let Some(pai) = destino.parent() {
-                std::fs::create_dir_all(pai).ok();
-            }
-            let mut arquivo_destino = std::fs::File::create(&destino)
-                .map_err(|e| format!("Erro ao criar arquivo {}: {}", nome_str, e))?;
-            std::io::copy(&mut entry, &mut arquivo_destino)
-                .map_err(|e| format!("Erro ao extrair {}: {}", nome_str, e))?;
-        }
+    let extracao = super::extracao_segura::extrair_zip(
+        &mut zip,
+        &instancia_criada.path,
+        &super::extracao_segura::LimitesExtracao::default(),
+        |nome| (nome != "dome_manifest.json" && nome != "instance.json").then(|| nome.to_string()),
+    );
+    if let Err(e) = extracao {
+        // A instância acabou de ser criada; não deixar uma cópia pela metade.
+        let _ = std::fs::remove_dir_all(&instancia_criada.path);
+        return Err(format!("Arquivo de instância recusado: {}", e));
     }
 
     Ok(ResultadoImportacaoArquivo {
@@ -0,0 +1,645 @@
+use super::importacao_exportacao::{
+    listar_instancias_curseforge_em, listar_instancias_mmc_em, listar_instancias_modrinth_em,
+    InstanciaImportavelExterna,
+};
+use super::*;
+use std::path::{Path, PathBuf};
+
+/// Pastas onde cada launcher guarda suas instâncias. Os padrões vêm das pastas
+/// do usuário; testes e a pasta apontada usam raízes próprias.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RaizesImportacao {
+    pub prism: Vec<PathBuf>,
+    pub multimc: Vec<PathBuf>,
+    pub modrinth: Vec<PathBuf>,
+    pub curseforge: Vec<PathBuf>,
+    pub atlauncher: Vec<PathBuf>,
+    pub gdlauncher: Vec<PathBuf>,
+    /// Pastas `modpacks` do Technic.
+    pub technic: Vec<PathBuf>,
+    /// Pastas `.minecraft` com `launcher_profiles.json`.
+    pub vanilla: Vec<PathBuf>,
+}
+
+impl RaizesImportacao {
+    pub(crate) fn padrao() -> Self {
+        let base = directories::BaseDirs::new();
+        let dados = std::env::var("APPDATA")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| base.as_ref().map(|base| base.data_dir().to_path_buf()));
+        let home = std::env::var("USERPROFILE")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| base.as_ref().map(|base| base.home_dir().to_path_buf()));
+
+        let em = |raiz: &Option<PathBuf>, caminhos: &[&str]| -> Vec<PathBuf> {
+            raiz.as_ref()
+                .map(|raiz| caminhos.iter().map(|caminho| raiz.join(caminho)).collect())
+                .unwrap_or_default()
+        };
+
+        Self {
+            prism: em(&dados, &["PrismLauncher/instances"]),
+            multimc: em(&dados, &["multimc/instances", "MultiMC/instances"]),
+            modrinth: em(
+                &dados,
+                &[
+                    "com.modrinth.theseus/profiles",
+                    "ModrinthApp/profiles",
+                    "theseus/profiles",
+                ],
+            ),
+            curseforge: em(
+                &home,
+                &[
+                    "curseforge/minecraft/Instances",
+                    "Documents/Curse/Minecraft/Instances",
+                ],
+            ),
+            atlauncher: em(&dados, &["ATLauncher/instances"]),
+            gdlauncher: em(&dados, &["gdlauncher_next/instances"]),
+            technic: [
+                em(&dados, &[".technic/modpacks", "technic/modpacks"]),
+                em(&home, &[".technic/modpacks"]),
+            ]
+            .concat(),
+            vanilla: [
+                em(&dados, &[".minecraft", "minecraft"]),
+                em(&home, &[".minecraft"]),
+            ]
+            .concat(),
+        }
+    }
+
+    /// Raízes dentro de uma pasta escolhida pelo usuário, aceitando tanto a
+    /// pasta do launcher quanto a de instâncias.
+    pub(crate) fn em_pasta(pasta: &Path) -> Self {
+        let mut vistas = std::collections::HashSet::new();
+        let instancias: Vec<PathBuf> = [
+            pasta.to_path_buf(),
+            pasta.join("instances"),
+            pasta.join("Instances"),
+            pasta.join("profiles"),
+            pasta.join("minecraft").join("Instances"),
+            pasta.join("Minecraft").join("Instances"),
+        ]
+        .into_iter()
+        .filter(|caminho| caminho.is_dir())
+        .filter(|caminho| vistas.insert(caminho.to_string_lossy().to_lowercase()))
+        .collect();
+
+        Self {
+            prism: instancias.clone(),
+            multimc: Vec::new(),
+            modrinth: instancias.clone(),
+            curseforge: instancias.clone(),
+            atlauncher: instancias.clone(),
+            gdlauncher: instancias,
+            technic: vec![pasta.to_path_buf(), pasta.join("modpacks")],
+            vanilla: vec![pasta.to_path_buf(), pasta.join(".minecraft")],
+        }
+    }
+}
+
+/// Procura instâncias de todos os launchers conhecidos nas raízes dadas.
+pub(crate) fn listar_instancias_nas_raizes(
+    raizes: &RaizesImportacao,
+) -> Vec<InstanciaImportavelExterna> {
+    let mut resultados = Vec::new();
+    for pasta in &raizes.prism {
+        resultados.extend(listar_instancias_mmc_em(pasta, launcher_mmc(pasta)));
+    }
+    for pasta in &raizes.multimc {
+        resultados.extend(listar_instancias_mmc_em(pasta, "multimc"));
+    }
+    resultados.extend(listar_instancias_modrinth_em(&raizes.modrinth));
+    for pasta in &raizes.curseforge {
+        resultados.extend(listar_instancias_curseforge_em(pasta));
+    }
+    for pasta in &raizes.atlauncher {
+        resultados.extend(listar_instancias_atlauncher_em(pasta));
+    }
+    for pasta in &raizes.gdlauncher {
+        resultados.extend(listar_instancias_gdlauncher_em(pasta));
+    }
+    for pasta in &raizes.technic {
+        resultados.extend(listar_instancias_technic_em(pasta));
+    }
+    for pasta in &raizes.vanilla {
+        resultados.extend(listar_instalacoes_vanilla_em(pasta));
+    }
+    resultados
+}
+
+/// Prism e MultiMC usam o mesmo formato de instância; o arquivo de
+/// configuração na pasta do launcher diz qual dos dois é.
+fn launcher_mmc(pasta_instancias: &Path) -> &'static str {
+    let pasta_launcher = pasta_instancias.parent().unwrap_or(pasta_instancias);
+    if pasta_launcher.join("multimc.cfg").is_file()
+        && !pasta_launcher.join("prismlauncher.cfg").is_file()
+    {
+        "multimc"
+    } else {
+        "prism"
+    }
+}
+
+fn ler_json(caminho: &Path) -> Option<serde_json::Value> {
+    let conteudo = std::fs::read_to_string(caminho).ok()?;
+    serde_json::from_str(&conteudo).ok()
+}
+
+fn subpastas(pasta: &Path) -> Vec<PathBuf> {
+    let Ok(entradas) = std::fs::read_dir(pasta) else {
+        return Vec::new();
+    };
+    let mut pastas: Vec<PathBuf> = entradas
+        .flatten()
+        .map(|entrada| entrada.path())
+        .filter(|caminho| caminho.is_dir())
+        .collect();
+    pastas.sort();
+    pastas
+}
+
+fn nome_pasta(caminho: &Path) -> String {
+    caminho
+        .file_name()
+        .map(|nome| nome.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn icone_existente(pasta: &Path, nomes: &[&str]) -> Option<String> {
+    nomes
+        .iter()
+        .map(|nome| pasta.join(nome))
+        .find(|caminho| caminho.is_file())
+        .map(|caminho| caminho.to_string_lossy().to_string())
+}
+
+/// Rótulo e versão do loader a partir do nome usado por um launcher.
+fn loader_por_nome(nome: Option<&str>, versao: Option<String>) -> (Option<String>, Option<String>) {
+    match detectar_loader_normalizado(nome).filter(|loader| loader != "vanilla") {
+        Some(loader) => (rotulo_loader(Some(&loader)), versao),
+        None => (None, None),
+    }
+}
+
+fn listar_instancias_atlauncher_em(pasta_base: &Path) -> Vec<InstanciaImportavelExterna> {
+    let mut resultados = Vec::new();
+    for caminho_origem in subpastas(pasta_base) {
+        let Some(json) = ler_json(&caminho_origem.join("instance.json")) else {
+            continue;
+        };
+        // O `instance.json` do ATLauncher guarda a versão do Minecraft em `id`
+        // e os dados do pack em `launcher`.
+        let Some(launcher) = json.get("launcher").filter(|valor| valor.is_object()) else {
+            continue;
+        };
+        let Some(versao_minecraft) = texto_json_caminho(&json, &["id"])
+            .or_else(|| texto_json_caminho(launcher, &["minecraftVersion"]))
+        else {
+            continue;
+        };
+        let (loader_type, loader_version) = loader_por_nome(
+            texto_json_caminho(launcher, &["loaderVersion", "type"]).as_deref(),
+            texto_json_caminho(launcher, &["loaderVersion", "version"]),
+        );
+
+        resultados.push(InstanciaImportavelExterna {
+            id_externo: format!("atlauncher:{}", caminho_origem.to_string_lossy()),
+            launcher: "atlauncher".to_string(),
+            nome: texto_json_caminho(launcher, &["name"])
+                .unwrap_or_else(|| nome_pasta(&caminho_origem)),
+            versao_minecraft,
+            loader_type,
+            loader_version,
+            icone: icone_existente(&caminho_origem, &["instance.png"]),
+            caminho_origem: caminho_origem.to_string_lossy().to_string(),
+            caminho_jogo: caminho_origem.to_string_lossy().to_string(),
+            configuracao: Default::default(),
+        });
+    }
+    resultados
+}
+
+fn listar_instancias_gdlauncher_em(pasta_base: &Path) -> Vec<InstanciaImportavelExterna> {
+    let mut resultados = Vec::new();
+    for caminho_origem in subpastas(pasta_base) {
+        let Some(json) = ler_json(&caminho_origem.join("config.json")) else {
+            continue;
+        };
+        let loader = &json["loader"];
+        let Some(versao_minecraft) = texto_json_caminho(loader, &["mcVersion"]) else {
+            continue;
+        };
+        // O GDLauncher grava a versão do Forge como `<mc>-<forge>`.
+        let versao_loader = texto_json_caminho(loader, &["loaderVersion"]).map(|versao| {
+            versao
+                .strip_prefix(&format!("{}-", versao_minecraft))
+                .unwrap_or(&versao)
+                .to_string()
+        });
+        let (loader_type, loader_version) = loader_por_nome(
+            texto_json_caminho(loader, &["loaderType"]).as_deref(),
+            versao_loader,
+        );
+        let icone = texto_json_caminho(&json, &["background"])
+            .map(|fundo| caminho_origem.join(fundo))
+            .filter(|caminho| caminho.is_file())
+            .map(|caminho| caminho.to_string_lossy().to_string());
+
+        resultados.push(InstanciaImportavelExterna {
+            id_externo: format!("gdlauncher:{}", caminho_origem.to_string_lossy()),
+            launcher: "gdlauncher".to_string(),
+            nome: texto_json_caminho(&json, &["name"])
+                .unwrap_or_else(|| nome_pasta(&caminho_origem)),
+            versao_minecraft,
+            loader_type,
+            loader_version,
+            icone,
+            caminho_origem: caminho_origem.to_string_lossy().to_string(),
+            caminho_jogo: caminho_origem.to_string_lossy().to_string(),
+            configuracao: Default::default(),
+        });
+    }
+    resultados
+}
+
+/// Versão do Minecraft e loader de um id de versão do launcher oficial, como
+/// `fabric-loader-0.15.7-1.20.1`, `1.20.1-forge-47.2.0` ou `neoforge-20.4.237`.
+fn analisar_id_versao(id: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let id = id.trim();
+    for (prefixo, loader) in [("fabric-loader-", "Fabric"), ("quilt-loader-", "Quilt")] {
+        if let Some(resto) = id.strip_prefix(prefixo) {
+            if let Some((versao_loader, versao_mc)) = resto.split_once('-') {
+                return (
+                    Some(versao_mc.to_string()),
+                    Some(loader.to_string()),
+                    Some(versao_loader.to_string()),
+                );
+            }
+        }
+    }
+    if let Some(versao_loader) = id.strip_prefix("neoforge-") {
+        // NeoForge 20.4.x roda no Minecraft 1.20.4; 21.0.x no 1.21.
+        let mut partes = versao_loader.split('.');
+        let versao_mc = match (partes.next(), partes.next()) {
+            (Some(maior), Some("0")) => Some(format!("1.{}", maior)),
+            (Some(maior), Some(menor)) => Some(format!("1.{}.{}", maior, menor)),
+            _ => None,
+        };
+        return (
+            versao_mc,
+            Some("NeoForge".to_string()),
+            Some(versao_loader.to_string()),
+        );
+    }
+
+    let minusculo = id.to_lowercase();
+    for (marcador, loader) in [("-neoforge", "NeoForge"), ("-forge", "Forge")] {
+        if let Some(posicao) = minusculo.find(marcador) {
+            let versao_mc = &id[..posicao];
+            let resto = id[posicao + marcador.len()..].trim_start_matches('-');
+            // Formatos antigos repetem a versão do Minecraft: `1.12.2-forge1.12.2-14.23.5`.
+            let versao_loader = resto
+                .strip_prefix(&format!("{}-", versao_mc))
+                .unwrap_or(resto);
+            let versao_loader = versao_loader
+                .strip_suffix(&format!("-{}", versao_mc))
+                .unwrap_or(versao_loader);
+            return (
+                Some(versao_mc.to_string()),
+                Some(loader.to_string()),
+                (!versao_loader.is_empty()).then(|| versao_loader.to_string()),
+            );
+        }
+    }
+
+    ((!id.is_empty()).then(|| id.to_string()), None, None)
+}
+
+/// Versão do Minecraft e loader de um JSON de versão (`versions/<id>/<id>.json`
+/// ou o `bin/version.json` do Technic), usando `inheritsFrom` e as bibliotecas
+/// do loader quando existem.
+fn analisar_json_versao(
+    json: &serde_json::Value,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let (versao_id, mut loader_type, mut loader_version) =
+        analisar_id_versao(&texto_json_caminho(json, &["id"]).unwrap_or_default());
+    let versao_minecraft = texto_json_caminho(json, &["inheritsFrom"]).or(versao_id);
+
+    if loader_type.is_none() {
+        for biblioteca in json["libraries"].as_array().into_iter().flatten() {
+            let Some(nome) = texto_json_caminho(biblioteca, &["name"]) else {
+                continue;
+            };
+            let partes: Vec<&str> = nome.split(':').collect();
+            let [grupo, artefato, versao, ..] = partes.as_slice() else {
+                continue;
+            };
+            let loader = match (*grupo, *artefato) {
+                ("net.fabricmc", "fabric-loader") => "Fabric",
+                ("org.quiltmc", "quilt-loader") => "Quilt",
+                ("net.neoforged", "neoforge") => "NeoForge",
+                ("net.minecraftforge", "forge" | "minecraftforge") => "Forge",
+                _ => continue,
+            };
+            let versao = versao_minecraft
+                .as_deref()
+                .and_then(|mc| versao.strip_prefix(&format!("{}-", mc)))
+                .unwrap_or(versao);
+            let versao = versao_minecraft
+                .as_deref()
+                .and_then(|mc| versao.strip_suffix(&format!("-{}", mc)))
+                .unwrap_or(versao);
+            loader_type = Some(loader.to_string());
+            loader_version = Some(versao.to_string());
+            break;
+        }
+    }
+
+    (versao_minecraft, loader_type, loader_version)
+}
+
+fn listar_instancias_technic_em(pasta_modpacks: &Path) -> Vec<InstanciaImportavelExterna> {
+    // `installedPacks` fica ao lado da pasta `modpacks` e guarda os nomes exibidos.
+    let instalados = pasta_modpacks
+        .parent()
+        .and_then(|pasta| ler_json(&pasta.join("installedPacks")))
+        .unwrap_or_default();
+    let mut resultados = Vec::new();
+    for caminho_origem in subpastas(pasta_modpacks) {
+        let Some(json) = ler_json(&caminho_origem.join("bin").join("version.json")) else {
+            continue;
+        };
+        let (Some(versao_minecraft), loader_type, loader_version) = analisar_json_versao(&json)
+        else {
+            continue;
+        };
+        let slug = nome_pasta(&caminho_origem);
+        let pack = &instalados["installedPacks"][&slug];
+
+        resultados.push(InstanciaImportavelExterna {
+            id_externo: format!("technic:{}", caminho_origem.to_string_lossy()),
+            launcher: "technic".to_string(),
+            nome: texto_json_caminho(pack, &["displayName"])
+                .or_else(|| texto_json_caminho(pack, &["name"]))
+                .unwrap_or(slug),
+            versao_minecraft,
+            loader_type,
+            loader_version,
+            icone: None,
+            caminho_origem: caminho_origem.to_string_lossy().to_string(),
+            caminho_jogo: caminho_origem.to_string_lossy().to_string(),
+            configuracao: Default::default(),
+        });
+    }
+    resultados
+}
+
+/// Instalações do launcher oficial (`launcher_profiles.json`). Perfis
+/// `latest-release`/`latest-snapshot` não têm versão fixa e são ignorados.
+fn listar_instalacoes_vanilla_em(pasta_minecraft: &Path) -> Vec<InstanciaImportavelExterna> {
+    let Some(json) = ler_json(&pasta_minecraft.join("launcher_profiles.json")) else {
+        return Vec::new();
+    };
+    let Some(perfis) = json["profiles"].as_object() else {
+        return Vec::new();
+    };
+
+    let mut resultados = Vec::new();
+    for (id_perfil, perfil) in perfis {
+        let tipo = texto_json_caminho(perfil, &["type"]).unwrap_or_default();
+        let Some(id_versao) = texto_json_caminho(perfil, &["lastVersionId"]) else {
+            continue;
+        };
+        if tipo.starts_with("latest-") || id_versao.starts_with("latest-") {
+            continue;
+        }
+        let json_versao = pasta_minecraft
+            .join("versions")
+            .join(&id_versao)
+            .join(format!("{}.json", id_versao));
+        let (versao_minecraft, loader_type, loader_version) = match ler_json(&json_versao) {
+            Some(json_versao) => analisar_json_versao(&json_versao),
+            None => analisar_id_versao(&id_versao),
+        };
+        let Some(versao_minecraft) = versao_minecraft else {
+            continue;
+        };
+        let caminho_jogo = texto_json_caminho(perfil, &["gameDir"])
+            .map(PathBuf::from)
+            .filter(|caminho| caminho.is_dir())
+            .unwrap_or_else(|| pasta_minecraft.to_path_buf());
+        let icone =
+            texto_json_caminho(perfil, &["icon"]).filter(|icone| icone.starts_with("data:"));
+
+        resultados.push(InstanciaImportavelExterna {
+            id_externo: format!(
+                "vanilla:{}#{}",
+                pasta_minecraft.to_string_lossy(),
+                id_perfil
+            ),
+            launcher: "vanilla".to_string(),
+            nome: texto_json_caminho(perfil, &["name"]).unwrap_or_else(|| id_versao.clone()),
+            versao_minecraft,
+            loader_type,
+            loader_version,
+            icone,
+            caminho_origem: caminho_jogo.to_string_lossy().to_string(),
+            caminho_jogo: caminho_jogo.to_string_lossy().to_string(),
+            configuracao: Default::default(),
+        });
+    }
+    resultados.sort_by(|a, b| a.nome.cmp(&b.nome));
+    resultados
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn escrever(raiz: &Path, relativo: &str, conteudo: &str) {
+        let caminho = raiz.join(relativo);
+        std::fs::create_dir_all(caminho.parent().unwrap()).unwrap();
+        std::fs::write(caminho, conteudo).unwrap();
+    }
+
+    fn por_launcher<'a>(
+        instancias: &'a [InstanciaImportavelExterna],
+        launcher: &str,
+    ) -> Vec<&'a InstanciaImportavelExterna> {
+        instancias
+            .iter()
+            .filter(|instancia| instancia.launcher == launcher)
+            .collect()
+    }
+
+    #[test]
+    fn le_instancias_de_cada_launcher_nas_raizes_configuradas() {
+        let raiz = std::env::temp_dir().join(format!("dome-importacao-{}", uuid::Uuid::new_v4()));
+        escrever(&raiz, "MultiMC/multimc.cfg", "");
+        escrever(
+            &raiz,
+            "MultiMC/instances/Antiga/instance.cfg",
+            "name=Antiga\nIntendedVersion=1.7.10\n",
+        );
+        escrever(
+            &raiz,
+            "ATLauncher/instances/SkyFactory/instance.json",
+            r#"{"id":"1.16.5","launcher":{"name":"SkyFactory 4","loaderVersion":{"type":"Forge","version":"36.2.39"}}}"#,
+        );
+        escrever(
+            &raiz,
+            "gdlauncher_next/instances/Fabuloso/config.json",
+            r#"{"loader":{"loaderType":"fabric","mcVersion":"1.20.1","loaderVersion":"0.15.7"}}"#,
+        );
+        escrever(
+            &raiz,
+            ".technic/installedPacks",
+            r#"{"installedPacks":{"tekkit":{"name":"tekkit","displayName":"Tekkit Legends"}}}"#,
+        );
+        escrever(
+            &raiz,
+            ".technic/modpacks/tekkit/bin/version.json",
+            r#"{"id":"1.7.10","libraries":[{"name":"net.minecraftforge:forge:1.7.10-10.13.4.1614-1.7.10"}]}"#,
+        );
+
+        let raizes = RaizesImportacao {
+            multimc: vec![raiz.join("MultiMC/instances")],
+            atlauncher: vec![raiz.join("ATLauncher/instances")],
+            gdlauncher: vec![raiz.join("gdlauncher_next/instances")],
+            technic: vec![raiz.join(".technic/modpacks")],
+            ..Default::default()
+        };
+        let instancias = listar_instancias_nas_raizes(&raizes);
+
+        let multimc = por_launcher(&instancias, "multimc");
+        assert_eq!(multimc.len(), 1);
+        assert_eq!(multimc[0].versao_minecraft, "1.7.10");
+
+        let atlauncher = por_launcher(&instancias, "atlauncher");
+        assert_eq!(atlauncher[0].nome, "SkyFactory 4");
+        assert_eq!(atlauncher[0].loader_type.as_deref(), Some("Forge"));
+        assert_eq!(atlauncher[0].loader_version.as_deref(), Some("36.2.39"));
+
+        let gdlauncher = por_launcher(&instancias, "gdlauncher");
+        assert_eq!(gdlauncher[0].nome, "Fabuloso");
+        assert_eq!(gdlauncher[0].loader_type.as_deref(), Some("Fabric"));
+
+        let technic = por_launcher(&instancias, "technic");
+        assert_eq!(technic[0].nome, "Tekkit Legends");
+        assert_eq!(technic[0].versao_minecraft, "1.7.10");
+        assert_eq!(technic[0].loader_version.as_deref(), Some("10.13.4.1614"));
+        let _ = std::fs::remove_dir_all(&raiz);
+    }
+
+    #[test]
+    fn le_ajustes_e_componentes_do_prism() {
+        let raiz = std::env::temp_dir().join(format!("dome-importacao-{}", uuid::Uuid::new_v4()));
+        escrever(&raiz, "PrismLauncher/java/bin/java", "");
+        escrever(
+            &raiz,
+            "PrismLauncher/instances/Pack/instance.cfg",
+            "[General]\nname=Pack\nOverrideMemory=true\nMaxMemAlloc=6144\n\
+             OverrideJavaArgs=true\nJvmArgs=\"-XX:+UseG1GC -Dfoo=bar -Dnome=\\\"Meu Pack\\\" -Dpasta=C:\\\\mods\"\n\
+             OverrideJavaLocation=true\nJavaPath=java/bin/java\n\
+             OverrideWindow=false\nMinecraftWinWidth=1280\nMinecraftWinHeight=720\n",
+        );
+        escrever(
+            &raiz,
+            "PrismLauncher/instances/Pack/mmc-pack.json",
+            r#"{"components":[
+                {"uid":"org.lwjgl3","version":"3.3.3","cachedName":"LWJGL 3"},
+                {"uid":"net.minecraft","version":"1.20.1"},
+                {"uid":"org.quiltmc.hashed","version":"1.20.1","dependencyOnly":true},
+                {"uid":"org.quiltmc.quilt-loader","version":"0.23.1","cachedName":"Quilt Loader"},
+                {"uid":"org.multimc.jarmod.1234","cachedName":"OptiFine.jar"}
+            ]}"#,
+        );
+
+        let raizes = RaizesImportacao {
+            prism: vec![raiz.join("PrismLauncher/instances")],
+            ..Default::default()
+        };
+        let instancias = listar_instancias_nas_raizes(&raizes);
+        assert_eq!(instancias.len(), 1);
+        let instancia = &instancias[0];
+        assert_eq!(instancia.loader_type.as_deref(), Some("Quilt"));
+        assert_eq!(instancia.loader_version.as_deref(), Some("0.23.1"));
+
+        let configuracao = &instancia.configuracao;
+        assert_eq!(configuracao.memoria_mb, Some(6144));
+        assert_eq!(
+            configuracao.java_args.as_deref(),
+            Some(r#"-XX:+UseG1GC -Dfoo=bar -Dnome="Meu Pack" -Dpasta=C:\mods"#)
+        );
+        assert!(configuracao
+            .java_path
+            .as_deref()
+            .is_some_and(|java| java.ends_with("java")));
+        // Janela sem override fica com o padrão do Dome.
+        assert_eq!((configuracao.largura, configuracao.altura), (None, None));
+        assert_eq!(
+            configuracao.avisos,
+            vec![
+                "versão personalizada do LWJGL 3 3.3.3".to_string(),
+                "jar mod OptiFine.jar".to_string(),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&raiz);
+    }
+
+    #[test]
+    fn le_instalacoes_do_launcher_oficial() {
+        let raiz = std::env::temp_dir().join(format!("dome-importacao-{}", uuid::Uuid::new_v4()));
+        escrever(
+            &raiz,
+            "launcher_profiles.json",
+            r#"{"profiles":{
+                "a":{"name":"Fabric","type":"custom","lastVersionId":"fabric-loader-0.15.7-1.20.1"},
+                "b":{"name":"Mais recente","type":"latest-release","lastVersionId":"latest-release"},
+                "c":{"name":"NeoForge","type":"custom","lastVersionId":"neoforge-20.4.237"},
+                "d":{"name":"Forge","type":"custom","lastVersionId":"1.20.1-forge-47.2.0"}
+            }}"#,
+        );
+        escrever(
+            &raiz,
+            "versions/neoforge-20.4.237/neoforge-20.4.237.json",
+            r#"{"id":"neoforge-20.4.237","inheritsFrom":"1.20.4"}"#,
+        );
+
+        let instancias = listar_instalacoes_vanilla_em(&raiz);
+        assert_eq!(instancias.len(), 3);
+        let resumo: Vec<(&str, &str, Option<&str>, Option<&str>)> = instancias
+            .iter()
+            .map(|instancia| {
+                (
+                    instancia.nome.as_str(),
+                    instancia.versao_minecraft.as_str(),
+                    instancia.loader_type.as_deref(),
+                    instancia.loader_version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            resumo,
+            vec![
+                ("Fabric", "1.20.1", Some("Fabric"), Some("0.15.7")),
+                ("Forge", "1.20.1", Some("Forge"), Some("47.2.0")),
+                ("NeoForge", "1.20.4", Some("NeoForge"), Some("20.4.237")),
+            ]
+        );
+        assert_eq!(
+            analisar_id_versao("1.12.2-forge1.12.2-14.23.5.2859"),
+            (
+                Some("1.12.2".to_string()),
+                Some("Forge".to_string()),
+                Some("14.23.5.2859".to_string())
+            )
+        );
+        let _ = std::fs::remove_dir_all(&raiz);
+    }
+}
@@ -0,0 +1,662 @@
+use super::armazenamento_compartilhado::{pasta_bibliotecas_store, resolver_client_jar};
+use super::extracao_segura::caminho_relativo_seguro;
+use super::verificacao_arquivos::{
+    self as verificacao, escrever_atomico, sha1_arquivo, sha1_bytes, ArquivoEsperado,
+    CategoriaArquivo,
+};
+use super::*;
+use crate::comandos::tarefas::{executar_processo, Tarefa};
+use std::collections::HashMap;
+use std::io::Read;
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Tamanho máximo de um arquivo lido de dentro do instalador.
+const MAX_BYTES_ENTRADA: u64 = 512 * 1024 * 1024;
+
+/// Caminho Maven relativo de `grupo:artefato:versão[:classifier][@extensão]`.
+pub(super) fn caminho_maven(coordenada: &str) -> Option<String> {
+    let (coordenada, extensao) = coordenada
+        .trim()
+        .split_once('@')
+        .unwrap_or((coordenada.trim(), "jar"));
+    let partes: Vec<&str> = coordenada.split(':').collect();
+    if partes.len() < 3 || partes.len() > 4 || partes.iter().any(|parte| parte.is_empty()) {
+        return None;
+    }
+    let (grupo, artefato, versao) = (partes[0].replace('.', "/"), partes[1], partes[2]);
+    let nome = match partes.get(3) {
+        Some(classifier) => format!("{}-{}-{}.{}", artefato, versao, classifier, extensao),
+        None => format!("{}-{}.{}", artefato, versao, extensao),
+    };
+    Some(format!("{}/{}/{}/{}", grupo, artefato, versao, nome))
+}
+
+/// Biblioteca usada pelo instalador: a cópia local ou a do armazenamento, se
+/// existirem; do contrário a pasta local da instância, adotada pelo
+/// armazenamento no próximo download de arquivos.
+fn caminho_biblioteca(instance_path: &Path, relativo: &str) -> PathBuf {
+    let local = instance_path.join("libraries").join(relativo);
+    if local.is_file() {
+        return local;
+    }
+    let store = pasta_bibliotecas_store().join(relativo);
+    if store.is_file() {
+        return store;
+    }
+    local
+}
+
+fn ler_entrada_zip<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    nome: &str,
+) -> Result<Vec<u8>, String> {
+    let entrada = archive
+        .by_name(nome.trim_start_matches('/'))
+        .map_err(|e| format!("{} não encontrado no instalador: {}", nome, e))?;
+    let mut bytes = Vec::new();
+    entrada
+        .take(MAX_BYTES_ENTRADA + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Erro ao ler {} do instalador: {}", nome, e))?;
+    if bytes.len() as u64 > MAX_BYTES_ENTRADA {
+        return Err(format!("{} excede o tamanho máximo permitido.", nome));
+    }
+    Ok(bytes)
+}
+
+fn ler_json_zip<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    nome: &str,
+) -> Result<serde_json::Value, String> {
+    let bytes = ler_entrada_zip(archive, nome)?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Erro ao parsear {}: {}", nome, e))
+}
+
+/// Resolve um argumento ou valor de `data` do `install_profile.json`:
+/// `[coordenada]` vira o caminho da biblioteca, `{CHAVE}` o valor já
+/// resolvido e `'texto'` o literal sem aspas.
+fn resolver_valor(
+    valor: &str,
+    instance_path: &Path,
+    dados: &HashMap<String, String>,
+) -> Result<String, String> {
+    if let Some(coordenada) = valor.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        let relativo = caminho_maven(coordenada)
+            .ok_or_else(|| format!("Coordenada Maven inválida: {}", coordenada))?;
+        let relativo = caminho_relativo_seguro(&relativo)?;
+        return Ok(caminho_biblioteca(instance_path, &relativo)
+            .to_string_lossy()
+            .to_string());
+    }
+    if let Some(chave) = valor.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        return dados
+            .get(chave)
+            .cloned()
+            .ok_or_else(|| format!("Variável {{{}}} não definida no instalador.", chave));
+    }
+    if let Some(literal) = valor.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return Ok(literal.to_string());
+    }
+    Ok(valor.to_string())
+}
+
+fn main_class_manifesto(manifesto: &str) -> Option<String> {
+    manifesto.lines().find_map(|linha| {
+        linha
+            .strip_prefix("Main-Class:")
+            .map(|classe| classe.trim().to_string())
+            .filter(|classe| !classe.is_empty())
+    })
+}
+
+fn main_class_jar(jar: &Path) -> Result<String, String> {
+    let arquivo =
+        std::fs::File::open(jar).map_err(|e| format!("Erro ao abrir {}: {}", jar.display(), e))?;
+    let mut archive = zip::ZipArchive::new(arquivo)
+        .map_err(|e| format!("Erro ao ler {}: {}", jar.display(), e))?;
+    let manifesto = ler_entrada_zip(&mut archive, "META-INF/MANIFEST.MF")?;
+    main_class_manifesto(&String::from_utf8_lossy(&manifesto))
+        .ok_or_else(|| format!("{} não declara Main-Class.", jar.display()))
+}
+
+/// Tudo o que sai do instalador antes de qualquer download: perfis, dados do
+/// lado cliente e bibliotecas que precisam ser baixadas.
+struct PreparacaoInstalador {
+    perfil: serde_json::Value,
+    versao: serde_json::Value,
+    dados: HashMap<String, String>,
+    downloads: Vec<ArquivoEsperado>,
+}
+
+/// Lê o `install_profile.json`, extrai as bibliotecas embutidas em `maven/`
+/// e os arquivos de `data` para `pasta_temp`, conferindo os hashes declarados.
+fn preparar_instalacao(
+    instalador: &[u8],
+    instance_path: &Path,
+    pasta_temp: &Path,
+) -> Result<PreparacaoInstalador, String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(instalador))
+        .map_err(|e| format!("Instalador inválido: {}", e))?;
+    let perfil = ler_json_zip(&mut archive, "install_profile.json")?;
+
+    // Instaladores antigos (≤ 1.12.2) trazem o perfil e o jar universal prontos.
+    if let Some(versao) = perfil.get("versionInfo") {
+        let install = &perfil["install"];
+        let relativo = install["path"]
+            .as_str()
+            .and_then(caminho_maven)
+            .ok_or("Instalador antigo sem 'install.path'.")?;
+        let arquivo = install["filePath"]
+            .as_str()
+            .ok_or("Instalador antigo sem 'install.filePath'.")?;
+        let destino = instance_path
+            .join("libraries")
+            .join(caminho_relativo_seguro(&relativo)?);
+        escrever_atomico(&destino, &ler_entrada_zip(&mut archive, arquivo)?)?;
+        return Ok(PreparacaoInstalador {
+            perfil: perfil.clone(),
+            versao: versao.clone(),
+            dados: HashMap::new(),
+            downloads: Vec::new(),
+        });
+    }
+
+    let versao = ler_json_zip(
+        &mut archive,
+        perfil["json"].as_str().unwrap_or("/version.json"),
+    )?;
+
+    let mut downloads = Vec::new();
+    let bibliotecas = perfil["libraries"]
+        .as_array()
+        .into_iter()
+        .chain(versao["libraries"].as_array())
+        .flatten();
+    for biblioteca in bibliotecas {
+        let artifact = &biblioteca["downloads"]["artifact"];
+        let Some(relativo) = artifact["path"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| biblioteca["name"].as_str().and_then(caminho_maven))
+        else {
+            continue;
+        };
+        let relativo = caminho_relativo_seguro(&relativo)?;
+        let esperado = ArquivoEsperado {
+            url: artifact["url"].as_str().unwrap_or_default().to_string(),
+            caminho: caminho_biblioteca(instance_path, &relativo),
+            sha1: artifact["sha1"]
+                .as_str()
+                .filter(|sha1| !sha1.is_empty())
+                .map(str::to_string),
+            tamanho: artifact["size"].as_u64().filter(|tamanho| *tamanho > 0),
+            categoria: CategoriaArquivo::Biblioteca,
+        };
+        if verificacao::estado_arquivo(&esperado, verificacao::ModoVerificacao::Completa)
+            == verificacao::EstadoArquivo::Ok
+            || downloads
+                .iter()
+                .any(|d: &ArquivoEsperado| d.caminho == esperado.caminho)
+        {
+            continue;
+        }
+        if !esperado.url.trim().is_empty() {
+            downloads.push(esperado);
+            continue;
+        }
+
+        // Sem URL: o próprio instalador traz o arquivo em `maven/`.
+        let bytes = ler_entrada_zip(&mut archive, &format!("maven/{}", relativo))?;
+        if let Some(sha1) = esperado.sha1.as_deref() {
+            let obtido = sha1_bytes(&bytes);
+            if !obtido.eq_ignore_ascii_case(sha1) {
+                return Err(format!(
+                    "{} embutido no instalador com SHA-1 {} (esperado {}).",
+                    relativo, obtido, sha1
+                ));
+            }
+        }
+        escrever_atomico(&esperado.caminho, &bytes)?;
+    }
+
+    let mut dados = HashMap::new();
+    if let Some(entradas) = perfil["data"].as_object() {
+        for (chave, valor) in entradas {
+            let Some(valor) = valor["client"].as_str() else {
+                continue;
+            };
+            let resolvido = if let Some(interno) = valor.strip_prefix('/') {
+                let relativo = caminho_relativo_seguro(interno)?;
+                let destino = pasta_temp.join(&relativo);
+                escrever_atomico(&destino, &ler_entrada_zip(&mut archive, &relativo)?)?;
+                destino.to_string_lossy().to_string()
+            } else {
+                resolver_valor(valor, instance_path, &dados)?
+            };
+            dados.insert(chave.clone(), resolvido);
+        }
+    }
+
+    Ok(PreparacaoInstalador {
+        perfil,
+        versao,
+        dados,
+        downloads,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct Processador {
+    jar: String,
+    #[serde(default)]
+    classpath: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    outputs: HashMap<String, String>,
+    #[serde(default)]
+    sides: Option<Vec<String>>,
+}
+
+/// Saídas declaradas do processador com o SHA-1 esperado de cada uma.
+fn saidas_processador(
+    processador: &Processador,
+    instance_path: &Path,
+    dados: &HashMap<String, String>,
+) -> Result<Vec<(PathBuf, String)>, String> {
+    processador
+        .outputs
+        .iter()
+        .map(|(arquivo, sha1)| {
+            Ok((
+                PathBuf::from(resolver_valor(arquivo, instance_path, dados)?),
+                resolver_valor(sha1, instance_path, dados)?,
+            ))
+        })
+        .collect()
+}
+
+fn conferir_saidas(saidas: &[(PathBuf, String)]) -> Result<(), String> {
+    for (arquivo, esperado) in saidas {
+        let obtido = sha1_arquivo(arquivo)
+            .map_err(|e| format!("{} não foi gerado: {}", arquivo.display(), e))?;
+        if !obtido.eq_ignore_ascii_case(esperado) {
+            return Err(format!(
+                "{} com SHA-1 {} (esperado {})",
+                arquivo.display(),
+                obtido,
+                esperado
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn executar_processadores(
+    perfil: &serde_json::Value,
+    instance_path: &Path,
+    pasta_temp: &Path,
+    dados: &HashMap<String, String>,
+    java: &str,
+    tarefa: Option<&Tarefa>,
+) -> Result<(), String> {
+    let processadores: Vec<Processador> =
+        serde_json::from_value(perfil["processors"].clone()).unwrap_or_default();
+    let processadores: Vec<Processador> = processadores
+        .into_iter()
+        .filter(|p| {
+            p.sides
+                .as_ref()
+                .is_none_or(|sides| sides.iter().any(|lado| lado == "client"))
+        })
+        .collect();
+    if let Some(tarefa) = tarefa {
+        tarefa.fase("processadores", processadores.len(), 0);
+    }
+
+    let total = processadores.len();
+    for (indice, processador) in processadores.iter().enumerate() {
+        if let Some(tarefa) = tarefa {
+            tarefa.verificar_cancelamento()?;
+        }
+        let rotulo = format!("{}/{} ({})", indice + 1, total, processador.jar);
+        let erro = |detalhe: String| format!("Processador {} falhou: {}", rotulo, detalhe);
+
+        let saidas = saidas_processador(processador, instance_path, dados).map_err(erro)?;
+        if !saidas.is_empty() && conferir_saidas(&saidas).is_ok() {
+            println!("[Instalador] Processador {} já aplicado.", rotulo);
+            if let Some(tarefa) = tarefa {
+                tarefa.avancar(1, 0);
+            }
+            continue;
+        }
+
+        let jar = PathBuf::from(
+            resolver_valor(&format!("[{}]", processador.jar), instance_path, dados)
+                .map_err(erro)?,
+        );
+        let main_class = main_class_jar(&jar).map_err(erro)?;
+        let mut classpath = vec![jar.to_string_lossy().to_string()];
+        for coordenada in &processador.classpath {
+            classpath.push(
+                resolver_valor(&format!("[{}]", coordenada), instance_path, dados).map_err(erro)?,
+            );
+        }
+        let args = processador
+            .args
+            .iter()
+            .map(|arg| resolver_valor(arg, instance_path, dados))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(erro)?;
+
+        println!("[Instalador] Executando processador {}", rotulo);
+        let mut comando = std::process::Command::new(java);
+        #[cfg(target_os = "windows")]
+        comando.creation_flags(CREATE_NO_WINDOW);
+        comando
+            .arg("-cp")
+            .arg(classpath.join(crate::plataforma::SEPARADOR_CLASSPATH))
+            .arg(&main_class)
+            .args(&args)
+            .current_dir(pasta_temp);
+        let saida = executar_processo(comando, tarefa).await.map_err(erro)?;
+        if !saida.status.success() {
+            let stderr = String::from_utf8_lossy(&saida.stderr);
+            let stdout = String::from_utf8_lossy(&saida.stdout);
+            let texto = if stderr.trim().is_empty() {
+                stdout
+            } else {
+                stderr
+            };
+            let linhas: Vec<&str> = texto.trim().lines().collect();
+            let detalhe = linhas[linhas.len().saturating_sub(5)..].join("\n");
+            return Err(erro(format!("{} - {}", saida.status, detalhe)));
+        }
+
+        if let Err(detalhe) = conferir_saidas(&saidas) {
+            for (arquivo, _) in &saidas {
+                let _ = std::fs::remove_file(arquivo);
+            }
+            return Err(erro(format!("saída inválida: {}", detalhe)));
+        }
+        if let Some(tarefa) = tarefa {
+            tarefa.avancar(1, 0);
+        }
+    }
+    Ok(())
+}
+
+/// Client jar vanilla usado como entrada dos processadores, baixado e
+/// conferido pelo SHA-1 do manifesto se ainda não estiver em disco.
+async fn garantir_client_vanilla(
+    client: &reqwest::Client,
+    instance_path: &Path,
+    minecraft_version: &str,
+) -> Result<PathBuf, String> {
+    let local = std::fs::read_to_string(instance_path.join("version_manifest.json"))
+        .ok()
+        .and_then(|conteudo| serde_json::from_str::<VersionDetail>(&conteudo).ok())
+        .filter(|details| details.id == minecraft_version);
+    let details = match local {
+        Some(details) => details,
+        None => {
+            let manifest = client
+                .get("https://piston-meta.mojang.com/mc/game/version_manifest_v2.json")
+                .send()
+                .await
+                .map_err(|e| format!("Erro ao buscar manifesto Minecraft: {}", e))?
+                .json::<VersionManifest>()
+                .await
+                .map_err(|e| format!("Erro ao ler manifesto Minecraft: {}", e))?;
+            let entrada = manifest
+                .versions
+                .iter()
+                .find(|v| v.id == minecraft_version)
+                .ok_or_else(|| {
+                    format!("Versão Minecraft '{}' não encontrada.", minecraft_version)
+                })?;
+            client
+                .get(&entrada.url)
+                .send()
+                .await
+                .map_err(|e| format!("Erro ao buscar detalhes da versão: {}", e))?
+                .json::<VersionDetail>()
+                .await
+                .map_err(|e| format!("Erro ao ler detalhes da versão: {}", e))?
+        }
+    };
+
+    let esperado = ArquivoEsperado {
+        url: details.downloads.client.url.clone(),
+        caminho: resolver_client_jar(instance_path, &details),
+        sha1: Some(details.downloads.client.sha1.clone()).filter(|sha1| !sha1.is_empty()),
+        tamanho: Some(details.downloads.client.size).filter(|tamanho| *tamanho > 0),
+        categoria: CategoriaArquivo::ClientJar,
+    };
+    if verificacao::estado_arquivo(&esperado, verificacao::ModoVerificacao::Completa)
+        != verificacao::EstadoArquivo::Ok
+    {
+        verificacao::baixar_verificado(client, &esperado)
+            .await
+            .map_err(|e| format!("Erro ao baixar client do Minecraft: {}", e))?;
+    }
+    Ok(esperado.caminho)
+}
+
+/// Instala Forge/NeoForge a partir do jar do instalador sem executá-lo:
+/// baixa as bibliotecas do `install_profile.json` e roda cada processador do
+/// lado cliente com o Java gerenciado. Devolve o `version.json` do loader.
+pub(super) async fn instalar_pelo_perfil(
+    instance_path: &Path,
+    minecraft_version: &str,
+    instalador: &[u8],
+    nome_loader: &str,
+    tarefa: Option<&Tarefa>,
+) -> Result<serde_json::Value, String> {
+    let pasta_temp = std::env::temp_dir().join(format!("dome-instalador-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&pasta_temp)
+        .map_err(|e| format!("Erro ao criar pasta temporária: {}", e))?;
+    let resultado = async {
+        let preparacao = preparar_instalacao(instalador, instance_path, &pasta_temp)?;
+        if preparacao.perfil.get("versionInfo").is_some() {
+            println!("[{}] Instalador antigo: sem processadores.", nome_loader);
+            return Ok(preparacao.versao);
+        }
+
+        let client = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(20))
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+
+        println!(
+            "[{}] Baixando {} bibliotecas do instalador...",
+            nome_loader,
+            preparacao.downloads.len()
+        );
+        if let Some(tarefa) = tarefa {
+            tarefa.fase(
+                "loader",
+                preparacao.downloads.len(),
+                verificacao::tamanho_total(&preparacao.downloads),
+            );
+        }
+        let falhas =
+            verificacao::baixar_lote_verificado(&client, preparacao.downloads, 8, tarefa).await;
+        if let Some(tarefa) = tarefa {
+            tarefa.verificar_cancelamento()?;
+        }
+        if let Some((_, erro)) = falhas.first() {
+            return Err(format!(
+                "{} biblioteca(s) do {} não puderam ser baixadas. Primeiro erro: {}",
+                falhas.len(),
+                nome_loader,
+                erro
+            ));
+        }
+
+        let client_jar = garantir_client_vanilla(&client, instance_path, minecraft_version).await?;
+        let java = crate::comandos::configuracoes_java::ensure_java_for_version(
+            minecraft_version.to_string(),
+        )
+        .await?;
+
+        let mut dados = preparacao.dados;
+        let caminho = |p: &Path| p.to_string_lossy().to_string();
+        dados.insert("SIDE".to_string(), "client".to_string());
+        dados.insert("MINECRAFT_JAR".to_string(), caminho(&client_jar));
+        dados.insert(
+            "MINECRAFT_VERSION".to_string(),
+            minecraft_version.to_string(),
+        );
+        dados.insert("ROOT".to_string(), caminho(instance_path));
+        dados.insert(
+            "LIBRARY_DIR".to_string(),
+            caminho(&instance_path.join("libraries")),
+        );
+        let instalador_temp = pasta_temp.join("installer.jar");
+        escrever_atomico(&instalador_temp, instalador)?;
+        dados.insert("INSTALLER".to_string(), caminho(&instalador_temp));
+
+        executar_processadores(
+            &preparacao.perfil,
+            instance_path,
+            &pasta_temp,
+            &dados,
+            &java,
+            tarefa,
+        )
+        .await?;
+        println!("[{}] Instalação concluída!", nome_loader);
+        Ok(preparacao.versao)
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&pasta_temp);
+    resultado
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn resolve_coordenadas_maven_e_valores_do_perfil() {
+        assert_eq!(
+            caminho_maven("net.minecraftforge:forge:1.20.1-47.2.0:client").unwrap(),
+            "net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-client.jar"
+        );
+        assert_eq!(
+            caminho_maven("de.oceanlabs.mcp:mcp_config:1.20.1-20230612@zip").unwrap(),
+            "de/oceanlabs/mcp/mcp_config/1.20.1-20230612/mcp_config-1.20.1-20230612.zip"
+        );
+        assert!(caminho_maven("apenas:dois").is_none());
+
+        let instancia = std::env::temp_dir().join(format!("dome-forge-{}", uuid::Uuid::new_v4()));
+        let dados = HashMap::from([("MC_SLIM_SHA".to_string(), "abc".to_string())]);
+        assert_eq!(
+            resolver_valor("{MC_SLIM_SHA}", &instancia, &dados).unwrap(),
+            "abc"
+        );
+        assert_eq!(
+            resolver_valor("'deadbeef'", &instancia, &dados).unwrap(),
+            "deadbeef"
+        );
+        assert_eq!(
+            resolver_valor("--task", &instancia, &dados).unwrap(),
+            "--task"
+        );
+        assert!(resolver_valor("{FALTANDO}", &instancia, &dados).is_err());
+        assert!(resolver_valor("[a:../b:1]", &instancia, &dados).is_err());
+        assert_eq!(
+            main_class_manifesto("Manifest-Version: 1.0\r\nMain-Class: net.minecraftforge.Run\r\n")
+                .as_deref(),
+            Some("net.minecraftforge.Run")
+        );
+    }
+
+    #[test]
+    fn prepara_bibliotecas_embutidas_e_dados_do_cliente() {
+        let instancia = std::env::temp_dir().join(format!("dome-forge-{}", uuid::Uuid::new_v4()));
+        let temp = instancia.join("temp");
+        let jar_forge = b"forge universal";
+        let perfil = serde_json::json!({
+            "json": "/version.json",
+            "data": {
+                "BINPATCH": { "client": "/data/client.lzma", "server": "/data/server.lzma" },
+                "MC_SLIM_SHA": { "client": "'0123'", "server": "'4567'" },
+                "PATCHED": { "client": "[net.minecraftforge:forge:1.0:client]", "server": "" }
+            },
+            "libraries": [{
+                "name": "net.minecraftforge:forge:1.0:universal",
+                "downloads": { "artifact": {
+                    "path": "net/minecraftforge/forge/1.0/forge-1.0-universal.jar",
+                    "url": "",
+                    "sha1": sha1_bytes(jar_forge)
+                }}
+            }, {
+                "name": "org.ow2.asm:asm:9.5",
+                "downloads": { "artifact": {
+                    "path": "org/ow2/asm/asm/9.5/asm-9.5.jar",
+                    "url": "https://maven.minecraftforge.net/org/ow2/asm/asm/9.5/asm-9.5.jar",
+                    "sha1": "aaaa"
+                }}
+            }]
+        })
+        .to_string();
+        let versao = serde_json::json!({ "id": "1.20.1-forge-1.0", "libraries": [] }).to_string();
+        let mut escritor = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (nome, conteudo) in [
+            ("install_profile.json", perfil.as_bytes()),
+            ("version.json", versao.as_bytes()),
+            ("data/client.lzma", b"patch".as_slice()),
+            (
+                "maven/net/minecraftforge/forge/1.0/forge-1.0-universal.jar",
+                jar_forge.as_slice(),
+            ),
+        ] {
+            escritor
+                .start_file(nome, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            std::io::Write::write_all(&mut escritor, conteudo).unwrap();
+        }
+        let bytes = escritor.finish().unwrap().into_inner();
+
+        let preparacao = preparar_instalacao(&bytes, &instancia, &temp).unwrap();
+        assert_eq!(preparacao.versao["id"], "1.20.1-forge-1.0");
+        assert_eq!(
+            std::fs::read(
+                instancia.join("libraries/net/minecraftforge/forge/1.0/forge-1.0-universal.jar")
+            )
+            .unwrap(),
+            jar_forge
+        );
+        assert_eq!(preparacao.downloads.len(), 1);
+        assert!(preparacao.downloads[0].url.ends_with("asm-9.5.jar"));
+        assert_eq!(preparacao.dados["MC_SLIM_SHA"], "0123");
+        assert_eq!(
+            std::fs::read_to_string(&preparacao.dados["BINPATCH"]).unwrap(),
+            "patch"
+        );
+        assert!(preparacao.dados["PATCHED"].ends_with("forge-1.0-client.jar"));
+        let _ = std::fs::remove_dir_all(&instancia);
+    }
+
+    #[test]
+    fn saida_com_hash_divergente_identifica_o_arquivo() {
+        let pasta = std::env::temp_dir().join(format!("dome-forge-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        let arquivo = pasta.join("client-srg.jar");
+        std::fs::write(&arquivo, b"gerado").unwrap();
+
+        assert!(conferir_saidas(&[(arquivo.clone(), sha1_bytes(b"gerado"))]).is_ok());
+        let erro = conferir_saidas(&[(arquivo.clone(), "ffff".to_string())]).unwrap_err();
+        assert!(erro.contains("client-srg.jar") && erro.contains("ffff"));
+        assert!(conferir_saidas(&[(pasta.join("faltando.jar"), "ffff".to_string())]).is_err());
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+}
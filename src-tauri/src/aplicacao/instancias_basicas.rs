@@ -109,6 +109,7 @@ pub(crate) async fn update_instance_name(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn update_instance_settings(
     state: State<'_, LauncherState>,
     instance_id: String,
@@ -117,6 +118,7 @@ pub(crate) async fn update_instance_settings(
     mc_args: Option<String>,
     width: Option<u32>,
     height: Option<u32>,
+    java_path: Option<String>,
 ) -> Result<(), String> {
     let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
     let config_path = instance_path.join("instance.json");
@@ -156,6 +158,15 @@ pub(crate) async fn update_instance_settings(
         };
     }
 
+    if let Some(java_path_valor) = java_path {
+        let texto = java_path_valor.trim();
+        instance.java_path = if texto.is_empty() {
+            None
+        } else {
+            Some(texto.to_string())
+        };
+    }
+
     if let Some(largura) = width {
         if !(320..=7680).contains(&largura) {
             return Err("Largura da janela deve estar entre 320 e 7680.".to_string());
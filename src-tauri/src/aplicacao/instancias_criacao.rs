@@ -1,9 +1,11 @@
+use super::armazenamento_compartilhado::resolver_biblioteca;
+use super::verificacao_arquivos::{
+    baixar_verificado, estado_arquivo, ArquivoEsperado, CategoriaArquivo, EstadoArquivo,
+    ModoVerificacao,
+};
+use super::versoes_loader::MarcadorVersaoLoader;
 use super::*;
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
-#[cfg(target_os = "windows")]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::comandos::tarefas::Tarefa;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +13,8 @@ pub(crate) struct LoaderVersionInfo {
     pub version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marcadores: Vec<MarcadorVersaoLoader>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,142 +22,30 @@ pub(crate) struct LoaderVersionsResponse {
     pub versions: Vec<LoaderVersionInfo>,
 }
 
-/// Busca versões disponíveis dos loaders (Fabric, Forge, NeoForge)
+/// Busca versões disponíveis dos loaders (Fabric, Quilt, Forge, NeoForge).
+/// Com `minecraft_version`, lista apenas as compatíveis com essa versão.
 #[tauri::command]
 pub(crate) async fn get_loader_versions(
     loader_type: String,
+    minecraft_version: Option<String>,
 ) -> Result<LoaderVersionsResponse, String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
-
-    let versions = match loader_type.to_lowercase().as_str() {
-        "fabric" => {
-            // API do Fabric para versões do loader
-            let url = "https://meta.fabricmc.net/v2/versions/loader";
-            let response = client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| format!("Erro ao buscar versões do Fabric: {}", e))?;
-
-            if !response.status().is_success() {
-                return Err(format!(
-                    "API do Fabric retornou erro: {}",
-                    response.status()
-                ));
-            }
-
-            let fabric_versions: Vec<serde_json::Value> = response
-                .json()
-                .await
-                .map_err(|e| format!("Erro ao parsear resposta do Fabric: {}", e))?;
-
-            fabric_versions
-                .iter()
-                .filter_map(|v| {
-                    let version = v["version"].as_str()?.to_string();
-                    let stable = v["stable"].as_bool();
-                    Some(LoaderVersionInfo { version, stable })
-                })
-                .collect::<Vec<_>>()
-        }
-        "forge" => {
-            // API do Forge - usa o promotions endpoint para versões estáveis
-            let url =
-                "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
-            let response = client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| format!("Erro ao buscar versões do Forge: {}", e))?;
-
-            if !response.status().is_success() {
-                return Err(format!("API do Forge retornou erro: {}", response.status()));
-            }
-
-            let forge_data: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| format!("Erro ao parsear resposta do Forge: {}", e))?;
-
-            // Extrair versões únicas do Forge
-            let mut versions_set = std::collections::HashSet::new();
-            if let Some(promos) = forge_data["promos"].as_object() {
-                for (key, value) in promos {
-                    // Formato: "1.20.1-latest" ou "1.20.1-recommended" -> valor é a build do Forge
-                    if let Some(forge_version) = value.as_str() {
-                        // Extrair versão do MC a partir da chave
-                        if let Some(mc_version) = key.split('-').next() {
-                            let full_version = format!("{}-{}", mc_version, forge_version);
-                            versions_set.insert(full_version);
-                        }
-                    }
-                }
-            }
-
-            let mut versions: Vec<LoaderVersionInfo> = versions_set
-                .into_iter()
-                .map(|v| LoaderVersionInfo {
-                    version: v,
-                    stable: Some(true),
-                })
-                .collect();
-
-            // Ordenar por versão (mais recente primeiro)
-            versions.sort_by(|a, b| b.version.cmp(&a.version));
-            versions
-        }
-        "neoforge" => {
-            // API do NeoForge para listar versões
-            let url =
-                "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
-            let response = client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| format!("Erro ao buscar versões do NeoForge: {}", e))?;
-
-            if !response.status().is_success() {
-                return Err(format!(
-                    "API do NeoForge retornou erro: {}",
-                    response.status()
-                ));
-            }
-
-            let neoforge_data: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| format!("Erro ao parsear resposta do NeoForge: {}", e))?;
-
-            // O endpoint retorna um array de versões
-            if let Some(versions_arr) = neoforge_data["versions"].as_array() {
-                versions_arr
-                    .iter()
-                    .filter_map(|v| {
-                        let version = v.as_str()?.to_string();
-                        Some(LoaderVersionInfo {
-                            version,
-                            stable: Some(true),
-                        })
-                    })
-                    .rev() // Versões mais recentes primeiro
-                    .collect::<Vec<_>>()
-            } else {
-                Vec::new()
-            }
-        }
-        _ => {
-            return Err(format!("Tipo de loader desconhecido: {}", loader_type));
-        }
-    };
+    let minecraft_version = minecraft_version
+        .map(|versao| versao.trim().to_string())
+        .filter(|versao| !versao.is_empty());
+    let versions = super::versoes_loader::buscar_versoes_loader(
+        &loader_type.to_lowercase(),
+        minecraft_version.as_deref(),
+    )
+    .await?;
 
     if versions.is_empty() {
-        return Err(format!(
-            "Nenhuma versão encontrada para o loader: {}",
-            loader_type
-        ));
+        return Err(match minecraft_version {
+            Some(versao) => format!(
+                "Nenhuma versão do loader {} encontrada para Minecraft {}",
+                loader_type, versao
+            ),
+            None => format!("Nenhuma versão encontrada para o loader: {}", loader_type),
+        });
     }
 
     Ok(LoaderVersionsResponse { versions })
@@ -194,6 +86,7 @@ pub(super) async fn install_forge_loader(
     instance_path: &std::path::Path,
     minecraft_version: &str,
     forge_version: &str,
+    tarefa: Option<&Tarefa>,
 ) -> Result<(), String> {
     let versao_forge = versao_forge_completa(minecraft_version, forge_version);
     let client = reqwest::Client::builder()
@@ -207,11 +100,6 @@ pub(super) async fn install_forge_loader(
         versao_forge, versao_forge
     );
 
-    let temp_dir = std::env::temp_dir().join("dome_launcher_forge_installer");
-    std::fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
-
-    let installer_path = temp_dir.join("forge-installer.jar");
-
     // Download do installer
     let response = client
         .get(&installer_url)
@@ -225,111 +113,151 @@ pub(super) async fn install_forge_loader(
             installer_url
         ));
     }
-    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
-    std::fs::write(&installer_path, bytes).map_err(|e| e.to_string())?;
-
-    // Criar launcher_profiles.json se necessário (o instalador do Forge exige para --installClient)
-    let launcher_profiles = instance_path.join("launcher_profiles.json");
-    if !launcher_profiles.exists() {
-        let _ = std::fs::write(&launcher_profiles, "{\"profiles\":{}}");
-    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Erro ao baixar instalador Forge: {}", e))?;
 
-    let instance_str = instance_path
-        .to_str()
-        .ok_or_else(|| "Caminho da instância inválido.".to_string())?;
-
-    let instalador_str = installer_path
-        .to_str()
-        .ok_or_else(|| "Caminho do instalador Forge inválido.".to_string())?;
-
-    let mut tentativas: Vec<String> = Vec::new();
-    for modo in ["--installClient", "--installServer"] {
-        let mut comando_instalador = std::process::Command::new("java");
-        #[cfg(target_os = "windows")]
-        comando_instalador.creation_flags(CREATE_NO_WINDOW);
-        let output = comando_instalador
-            .args(["-jar", instalador_str, modo, instance_str])
-            .current_dir(instance_path)
-            .output()
-            .map_err(|e| format!("Erro ao executar instalador Forge: {}", e))?;
-
-        if output.status.success() {
-            // Limpar arquivos temporários
-            let _ = std::fs::remove_dir_all(&temp_dir);
-            return Ok(());
-        }
+    let versao = super::instalador_forge::instalar_pelo_perfil(
+        instance_path,
+        minecraft_version,
+        &bytes,
+        "Forge",
+        tarefa,
+    )
+    .await?;
 
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        let detalhe = if !stderr.is_empty() { stderr } else { stdout };
-        tentativas.push(format!("{} -> {}", modo, detalhe));
-    }
+    // Manifesto do Forge mesclado no lançamento (adjust_forge_manifest)
+    std::fs::write(
+        instance_path.join("forge_manifest.json"),
+        serde_json::to_string_pretty(&versao).unwrap_or_default(),
+    )
+    .map_err(|e| format!("Erro ao salvar manifesto Forge: {}", e))?;
+    Ok(())
+}
 
-    let _ = std::fs::remove_dir_all(temp_dir);
-    Err(format!(
-        "Falha ao instalar Forge (versão {}). {}",
-        versao_forge,
-        tentativas.join(" | ")
-    ))
+/// Loader distribuído como perfil JSON por um servidor meta (Fabric e Quilt
+/// usam o mesmo formato).
+pub(super) struct PerfilMeta {
+    nome: &'static str,
+    api_meta: &'static str,
+    arquivo_manifesto: &'static str,
+    maven_padrao: &'static str,
+    main_class_padrao: &'static str,
 }
 
+pub(super) const PERFIL_FABRIC: PerfilMeta = PerfilMeta {
+    nome: "Fabric",
+    api_meta: "https://meta.fabricmc.net/v2",
+    arquivo_manifesto: "fabric_manifest.json",
+    maven_padrao: "https://maven.fabricmc.net/",
+    main_class_padrao: "net.fabricmc.loader.impl.launch.knot.KnotClient",
+};
+
+pub(super) const PERFIL_QUILT: PerfilMeta = PerfilMeta {
+    nome: "Quilt",
+    api_meta: "https://meta.quiltmc.org/v3",
+    arquivo_manifesto: "quilt_manifest.json",
+    maven_padrao: "https://maven.quiltmc.org/repository/release/",
+    main_class_padrao: "org.quiltmc.loader.impl.launch.knot.KnotClient",
+};
+
 pub(super) async fn install_fabric_loader(
     instance_path: &std::path::Path,
     minecraft_version: &str,
     fabric_version: &str,
+    tarefa: Option<&Tarefa>,
+) -> Result<(), String> {
+    instalar_perfil_meta(
+        &PERFIL_FABRIC,
+        instance_path,
+        minecraft_version,
+        fabric_version,
+        tarefa,
+    )
+    .await
+}
+
+pub(super) async fn install_quilt_loader(
+    instance_path: &std::path::Path,
+    minecraft_version: &str,
+    quilt_version: &str,
+    tarefa: Option<&Tarefa>,
+) -> Result<(), String> {
+    instalar_perfil_meta(
+        &PERFIL_QUILT,
+        instance_path,
+        minecraft_version,
+        quilt_version,
+        tarefa,
+    )
+    .await
+}
+
+async fn instalar_perfil_meta(
+    perfil: &PerfilMeta,
+    instance_path: &std::path::Path,
+    minecraft_version: &str,
+    loader_version: &str,
+    tarefa: Option<&Tarefa>,
 ) -> Result<(), String> {
+    let nome = perfil.nome;
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(60))
         .build()
         .map_err(|e| e.to_string())?;
 
     println!(
-        "[Fabric] Instalando Fabric {} para MC {}",
-        fabric_version, minecraft_version
+        "[{}] Instalando {} {} para MC {}",
+        nome, nome, loader_version, minecraft_version
     );
 
-    // Usar a API Meta do Fabric para obter o perfil completo
+    // Usar a API Meta do loader para obter o perfil completo
     let profile_url = format!(
-        "https://meta.fabricmc.net/v2/versions/loader/{}/{}/profile/json",
-        minecraft_version, fabric_version
+        "{}/versions/loader/{}/{}/profile/json",
+        perfil.api_meta, minecraft_version, loader_version
     );
 
-    println!("[Fabric] Buscando perfil: {}", profile_url);
+    println!("[{}] Buscando perfil: {}", nome, profile_url);
 
     let response = client
         .get(&profile_url)
         .send()
         .await
-        .map_err(|e| format!("Erro ao buscar perfil Fabric: {}", e))?;
+        .map_err(|e| format!("Erro ao buscar perfil {}: {}", nome, e))?;
 
     if !response.status().is_success() {
         return Err(format!(
-            "Falha ao obter perfil Fabric: {}",
+            "Falha ao obter perfil {}: {}",
+            nome,
             response.status()
         ));
     }
 
-    let fabric_profile: serde_json::Value = response
+    let profile: serde_json::Value = response
         .json()
         .await
-        .map_err(|e| format!("Erro ao parsear perfil Fabric: {}", e))?;
+        .map_err(|e| format!("Erro ao parsear perfil {}: {}", nome, e))?;
 
-    // Salvar o perfil do Fabric para uso posterior
-    let fabric_manifest_path = instance_path.join("fabric_manifest.json");
+    // Salvar o perfil do loader para uso posterior
+    let manifest_path = instance_path.join(perfil.arquivo_manifesto);
     std::fs::write(
-        &fabric_manifest_path,
-        serde_json::to_string_pretty(&fabric_profile).unwrap(),
+        &manifest_path,
+        serde_json::to_string_pretty(&profile).unwrap(),
     )
-    .map_err(|e| format!("Erro ao salvar manifesto Fabric: {}", e))?;
-
-    // Baixar bibliotecas do Fabric
-    let libraries_path = instance_path.join("libraries");
-    std::fs::create_dir_all(&libraries_path).map_err(|e| e.to_string())?;
+    .map_err(|e| format!("Erro ao salvar manifesto {}: {}", nome, e))?;
 
-    if let Some(libs) = fabric_profile["libraries"].as_array() {
-        println!("[Fabric] Baixando {} bibliotecas...", libs.len());
+    // Baixar bibliotecas do loader (direto no armazenamento compartilhado)
+    if let Some(libs) = profile["libraries"].as_array() {
+        println!("[{}] Baixando {} bibliotecas...", nome, libs.len());
+        if let Some(tarefa) = tarefa {
+            tarefa.fase("loader", libs.len(), 0);
+        }
 
         for lib in libs {
+            if let Some(tarefa) = tarefa {
+                tarefa.verificar_cancelamento()?;
+            }
             if let Some(name) = lib["name"].as_str() {
                 // Formato Maven: group:artifact:version
                 // Exemplo: net.fabricmc:fabric-loader:0.16.14
@@ -339,61 +267,74 @@ pub(super) async fn install_fabric_loader(
                     let artifact = parts[1];
                     let version = parts[2];
 
-                    // URL base (pode vir de lib["url"] ou usar Maven Central/Fabric Maven)
-                    let base_url = lib["url"].as_str().unwrap_or("https://maven.fabricmc.net/");
+                    // URL base (pode vir de lib["url"] ou usar o Maven do loader)
+                    let base_url = lib["url"].as_str().unwrap_or(perfil.maven_padrao);
 
                     let jar_path = format!(
                         "{}/{}/{}/{}-{}.jar",
                         group, artifact, version, artifact, version
                     );
-                    let download_url = format!("{}{}", base_url, jar_path);
-
-                    let local_path = libraries_path.join(&jar_path);
+                    let mut esperado = ArquivoEsperado {
+                        url: format!("{}{}", base_url, jar_path),
+                        caminho: resolver_biblioteca(instance_path, &jar_path),
+                        sha1: lib["sha1"].as_str().map(str::to_string),
+                        tamanho: lib["size"].as_u64(),
+                        categoria: CategoriaArquivo::Biblioteca,
+                    };
 
-                    if !local_path.exists() {
-                        // Criar diretório pai
-                        if let Some(parent) = local_path.parent() {
-                            std::fs::create_dir_all(parent).ok();
+                    if estado_arquivo(&esperado, ModoVerificacao::Completa) != EstadoArquivo::Ok {
+                        println!("[{}] Baixando: {}", nome, artifact);
+                        // Perfis antigos não trazem o hash; o Maven publica o `.sha1` ao lado do jar.
+                        if esperado.sha1.is_none() {
+                            esperado.sha1 = sha1_publicado_maven(&client, &esperado.url).await;
                         }
-
-                        println!("[Fabric] Baixando: {}", artifact);
-                        match client.get(&download_url).send().await {
-                            Ok(res) => {
-                                if res.status().is_success() {
-                                    if let Ok(bytes) = res.bytes().await {
-                                        std::fs::write(&local_path, bytes).ok();
-                                    }
-                                } else {
-                                    // Tentar Maven Central como fallback
-                                    let maven_central_url =
-                                        format!("https://repo1.maven.org/maven2/{}", jar_path);
-                                    if let Ok(res2) = client.get(&maven_central_url).send().await {
-                                        if res2.status().is_success() {
-                                            if let Ok(bytes) = res2.bytes().await {
-                                                std::fs::write(&local_path, bytes).ok();
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("[Fabric] Falha ao baixar {}: {}", artifact, e);
-                            }
+                        if let Err(erro) = baixar_verificado(&client, &esperado).await {
+                            // Tentar Maven Central como fallback
+                            let maven_central = ArquivoEsperado {
+                                url: format!("https://repo1.maven.org/maven2/{}", jar_path),
+                                ..esperado.clone()
+                            };
+                            baixar_verificado(&client, &maven_central).await.map_err(
+                                |erro_central| {
+                                    format!(
+                                        "Erro ao baixar biblioteca {} do {}: {} | {}",
+                                        name, nome, erro, erro_central
+                                    )
+                                },
+                            )?;
                         }
                     }
                 }
             }
+            if let Some(tarefa) = tarefa {
+                tarefa.avancar(1, 0);
+            }
         }
     }
 
-    println!("[Fabric] Instalação concluída!");
+    println!("[{}] Instalação concluída!", nome);
     Ok(())
 }
 
+/// SHA-1 que o repositório Maven publica em `<artefato>.sha1`, quando existe.
+async fn sha1_publicado_maven(client: &reqwest::Client, url_artefato: &str) -> Option<String> {
+    let resposta = client
+        .get(format!("{}.sha1", url_artefato))
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
+    let texto = resposta.text().await.ok()?;
+    let sha1 = texto.split_whitespace().next()?.to_lowercase();
+    (sha1.len() == 40 && sha1.chars().all(|c| c.is_ascii_hexdigit())).then_some(sha1)
+}
+
 pub(super) async fn install_neoforge_loader(
     instance_path: &std::path::Path,
-    _minecraft_version: &str,
+    minecraft_version: &str,
     neoforge_version: &str,
+    tarefa: Option<&Tarefa>,
 ) -> Result<(), String> {
     let client = reqwest::Client::new();
     let installer_url = format!(
@@ -401,57 +342,131 @@ pub(super) async fn install_neoforge_loader(
         neoforge_version, neoforge_version
     );
 
-    let temp_dir = std::env::temp_dir().join("dome_launcher_neoforge_installer");
-    std::fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
-
-    let installer_path = temp_dir.join("neoforge-installer.jar");
-
     // Download do installer
     let response = client
         .get(&installer_url)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
-    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
-    std::fs::write(&installer_path, bytes).map_err(|e| e.to_string())?;
-
-    // Executar installer
-    let mut comando_instalador = std::process::Command::new("java");
-    #[cfg(target_os = "windows")]
-    comando_instalador.creation_flags(CREATE_NO_WINDOW);
-    let output = comando_instalador
-        .args(["-jar", installer_path.to_str().unwrap(), "--installServer"])
-        .current_dir(instance_path)
-        .output()
-        .map_err(|e| e.to_string())?;
-
-    if !output.status.success() {
-        return Err("Falha ao instalar NeoForge".to_string());
+        .map_err(|e| format!("Erro ao baixar instalador NeoForge: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Falha ao baixar instalador NeoForge ({}): {}",
+            response.status(),
+            installer_url
+        ));
     }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Erro ao baixar instalador NeoForge: {}", e))?;
+
+    let versao = super::instalador_forge::instalar_pelo_perfil(
+        instance_path,
+        minecraft_version,
+        &bytes,
+        "NeoForge",
+        tarefa,
+    )
+    .await?;
 
-    // Limpar arquivos temporários
-    let _ = std::fs::remove_dir_all(temp_dir);
-
+    // Manifesto do NeoForge mesclado no lançamento (adjust_neoforge_manifest)
+    std::fs::write(
+        instance_path.join("neoforge_manifest.json"),
+        serde_json::to_string_pretty(&versao).unwrap_or_default(),
+    )
+    .map_err(|e| format!("Erro ao salvar manifesto NeoForge: {}", e))?;
     Ok(())
 }
 
+/// Cria a instância como uma tarefa: o progresso sai no evento `tarefa-progresso`
+/// e o id (`task_id`, opcional) permite cancelar pela interface.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub(crate) async fn create_instance(
+    app: tauri::AppHandle,
     state: State<'_, LauncherState>,
     name: String,
     version: String,
     mc_type: String,
     loader_type: Option<String>,
     loader_version: Option<String>,
+    task_id: Option<String>,
 ) -> Result<(), String> {
-    println!("=== INICIANDO CRIAÇÃO DE INSTÂNCIA ===");
-    println!("Nome: {}, Versão: {}, Tipo: {}", name, version, mc_type);
-    // Check Auth
-    let account = state.account.lock().unwrap().clone();
-    if account.is_none() {
-        return Err("Você precisa estar logado para criar instâncias.".to_string());
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "criar_instancia",
+        &format!("Criando {}", name),
+        None,
+    )?;
+    let resultado = criar_instancia(
+        &state,
+        name,
+        version,
+        mc_type,
+        loader_type,
+        loader_version,
+        &tarefa,
+    )
+    .await;
+    tarefa.finalizar(resultado)
+}
+
+/// Instala o loader pelo nome normalizado (`forge`, `fabric`, `neoforge`,
+/// `quilt` ou `vanilla`), devolvendo o tipo instalado.
+pub(super) async fn instalar_loader(
+    instance_path: &std::path::Path,
+    mc_version: &str,
+    loader: &str,
+    loader_version: Option<&str>,
+    tarefa: Option<&Tarefa>,
+) -> Result<Option<LoaderType>, String> {
+    match loader {
+        "forge" => {
+            let lv = loader_version.ok_or("Versão do Forge é obrigatória")?;
+            install_forge_loader(instance_path, mc_version, lv, tarefa).await?;
+            Ok(Some(LoaderType::Forge))
+        }
+        "fabric" => {
+            let lv = loader_version.ok_or("Versão do Fabric é obrigatória")?;
+            install_fabric_loader(instance_path, mc_version, lv, tarefa).await?;
+            Ok(Some(LoaderType::Fabric))
+        }
+        "neoforge" => {
+            let lv = loader_version.ok_or("Versão do NeoForge é obrigatória")?;
+            install_neoforge_loader(instance_path, mc_version, lv, tarefa).await?;
+            Ok(Some(LoaderType::NeoForge))
+        }
+        "quilt" => {
+            let lv = loader_version.ok_or("Versão do Quilt é obrigatória")?;
+            install_quilt_loader(instance_path, mc_version, lv, tarefa).await?;
+            Ok(Some(LoaderType::Quilt))
+        }
+        "vanilla" => Ok(None),
+        _ => Err(format!(
+            "Loader '{}' não é suportado nesta versão do launcher.",
+            loader
+        )),
+    }
+}
+
+/// Nome do loader gravado em `instance.json`.
+pub(super) fn nome_loader_instancia(loader: Option<&LoaderType>) -> String {
+    match loader {
+        Some(LoaderType::Fabric) => "Fabric",
+        Some(LoaderType::Forge) => "Forge",
+        Some(LoaderType::NeoForge) => "NeoForge",
+        Some(LoaderType::Quilt) => "Quilt",
+        Some(LoaderType::Vanilla) | None => "Vanilla",
     }
+    .to_string()
+}
 
+/// Busca no manifesto da Mojang os detalhes (`VersionDetail`) de uma versão.
+pub(super) async fn buscar_detalhes_versao(
+    version: &str,
+    tarefa: Option<&Tarefa>,
+) -> Result<VersionDetail, String> {
     let client = reqwest::Client::new();
 
     // 1. Buscar o manifesto para encontrar a URL da versão
@@ -470,6 +485,9 @@ pub(crate) async fn create_instance(
         .iter()
         .find(|v| v.id == version)
         .ok_or_else(|| "Versão não encontrada no manifesto".to_string())?;
+    if let Some(tarefa) = tarefa {
+        tarefa.avancar(1, 0);
+    }
 
     // 2. Buscar detalhes da versão
     let res = client
@@ -481,55 +499,63 @@ pub(crate) async fn create_instance(
         .json::<VersionDetail>()
         .await
         .map_err(|e| e.to_string())?;
+    if let Some(tarefa) = tarefa {
+        tarefa.avancar(1, 0);
+    }
+    Ok(details)
+}
 
-    // 3. Preparar diretório
+async fn criar_instancia(
+    state: &LauncherState,
+    name: String,
+    version: String,
+    mc_type: String,
+    loader_type: Option<String>,
+    loader_version: Option<String>,
+    tarefa: &Tarefa,
+) -> Result<(), String> {
+    println!(
+        "=== INICIANDO CRIAÇÃO DE INSTÂNCIA (tarefa {}) ===",
+        tarefa.id()
+    );
+    println!("Nome: {}, Versão: {}, Tipo: {}", name, version, mc_type);
+    // Check Auth
+    let account = state.account.lock().unwrap().clone();
+    if account.is_none() {
+        return Err("Você precisa estar logado para criar instâncias.".to_string());
+    }
+
+    tarefa.fase("manifesto", 2, 0);
+    let details = buscar_detalhes_versao(&version, Some(tarefa)).await?;
+    tarefa.verificar_cancelamento()?;
+
+    // 3. Preparar diretório (removido se a criação for cancelada)
     let id = urlencoding::encode(&name.to_lowercase().replace(' ', "_")).to_string();
-    let instance_path = caminho_instancia_por_id(&state, &id)?;
+    let instance_path = caminho_instancia_por_id(state, &id)?;
     if !instance_path.exists() {
         std::fs::create_dir_all(&instance_path).map_err(|e| e.to_string())?;
+        tarefa.limpar_ao_cancelar(instance_path.clone());
     }
 
     // 4. Baixar arquivos essenciais do Minecraft primeiro
-    download_instance_files(&instance_path, &details).await?;
+    download_instance_files(&instance_path, &details, Some(tarefa)).await?;
+    tarefa.fase("loader", 0, 0);
 
     // 5. Instalar loader se especificado (agora que os arquivos já existem)
-    let (loader_type_enum, loader_version_final) = if let Some(loader) = &loader_type {
-        match loader.as_str() {
-            "forge" => {
-                if let Some(lv) = &loader_version {
-                    install_forge_loader(&instance_path, &version, lv).await?;
-                    (Some(LoaderType::Forge), Some(lv.clone()))
-                } else {
-                    return Err("Versão do Forge é obrigatória".to_string());
-                }
-            }
-            "fabric" => {
-                if let Some(lv) = &loader_version {
-                    install_fabric_loader(&instance_path, &version, lv).await?;
-                    (Some(LoaderType::Fabric), Some(lv.clone()))
-                } else {
-                    return Err("Versão do Fabric é obrigatória".to_string());
-                }
-            }
-            "neoforge" => {
-                if let Some(lv) = &loader_version {
-                    install_neoforge_loader(&instance_path, &version, lv).await?;
-                    (Some(LoaderType::NeoForge), Some(lv.clone()))
-                } else {
-                    return Err("Versão do NeoForge é obrigatória".to_string());
-                }
-            }
-            "vanilla" => (None, None),
-            _ => {
-                return Err(format!(
-                    "Loader '{}' não é suportado nesta versão do launcher.",
-                    loader
-                ))
-            }
+    let loader_type_enum = match &loader_type {
+        Some(loader) => {
+            instalar_loader(
+                &instance_path,
+                &version,
+                loader,
+                loader_version.as_deref(),
+                Some(tarefa),
+            )
+            .await?
         }
-    } else {
-        (None, None)
+        None => None,
     };
+    let loader_version_final = loader_type_enum.as_ref().and(loader_version);
 
     // 5. Salvar registro
     let instance = Instance {
@@ -537,16 +563,7 @@ pub(crate) async fn create_instance(
         name,
         version: version.clone(),
         mc_type,
-        loader_type: Some(loader_type_enum.map_or_else(
-            || "Vanilla".to_string(),
-            |lt| match lt {
-                LoaderType::Fabric => "Fabric".to_string(),
-                LoaderType::Forge => "Forge".to_string(),
-                LoaderType::NeoForge => "NeoForge".to_string(),
-                LoaderType::Quilt => "Quilt".to_string(),
-                LoaderType::Vanilla => "Vanilla".to_string(),
-            },
-        )),
+        loader_type: Some(nome_loader_instancia(loader_type_enum.as_ref())),
         loader_version: loader_version_final,
         icon: Some(format!(
             "https://api.dicebear.com/9.x/shapes/svg?seed={}",
@@ -559,11 +576,15 @@ pub(crate) async fn create_instance(
         path: instance_path.clone(),
         java_args: None,
         mc_args: None,
+        java_path: None,
         memory: None,
         width: None,
         height: None,
     };
 
+    tarefa.verificar_cancelamento()?;
+    tarefa.fase("finalizando", 1, 0);
+
     // Salvar instance.json
     let config_path = instance_path.join("instance.json");
     let content = serde_json::to_string_pretty(&instance).map_err(|e| e.to_string())?;
@@ -582,120 +603,73 @@ pub(crate) async fn create_instance(
 // ===== FUNÇÃO PARA BAIXAR ASSETS DE FORMA CONTROLADA =====
 
 pub(super) async fn download_assets_safely(
-    instance_path: &std::path::Path,
     details: &VersionDetail,
+    tarefa: Option<&Tarefa>,
 ) -> Result<(), String> {
+    use super::verificacao_arquivos as verificacao;
+
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .map_err(|e| e.to_string())?;
 
-    let assets_dir = instance_path.join("assets");
-    let indexes_dir = assets_dir.join("indexes");
-    let objects_dir = assets_dir.join("objects");
-
-    std::fs::create_dir_all(&indexes_dir).map_err(|e| e.to_string())?;
-    std::fs::create_dir_all(&objects_dir).map_err(|e| e.to_string())?;
-
-    // Download Asset Index
-    let index_path = indexes_dir.join(format!("{}.json", details.asset_index.id));
-    let index_content = if !index_path.exists() {
-        println!("Baixando asset index...");
-        let res = client
-            .get(&details.asset_index.url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let text = res.text().await.map_err(|e| e.to_string())?;
-        std::fs::write(&index_path, &text).map_err(|e| e.to_string())?;
-        text
-    } else {
-        println!("Asset index já existe");
-        std::fs::read_to_string(&index_path).map_err(|e| e.to_string())?
-    };
+    // Asset index conferido pelo SHA-1 do manifesto
+    let index_content = verificacao::garantir_asset_index(&client, details).await?;
 
-    // Parse e coletar assets para download
-    if let Ok(index_json) = serde_json::from_str::<serde_json::Value>(&index_content) {
-        if let Some(objects) = index_json["objects"].as_object() {
-            let total_objects = objects.len();
-            println!("Encontrados {} assets para verificar", total_objects);
+    let assets = verificacao::arquivos_assets(&index_content)?;
+    let total_objects = assets.len();
+    println!("Encontrados {} assets para verificar", total_objects);
 
-            // Estrutura para tarefa de download
-            struct AssetTask {
-                url: String,
-                path: std::path::PathBuf,
-            }
+    let download_tasks: Vec<_> = assets
+        .into_iter()
+        .filter(|asset| {
+            verificacao::estado_arquivo(asset, verificacao::ModoVerificacao::Rapida)
+                != verificacao::EstadoArquivo::Ok
+        })
+        .collect();
 
-            let mut download_tasks: Vec<AssetTask> = Vec::new();
+    let tasks_count = download_tasks.len();
+    let already_downloaded = total_objects - tasks_count;
+    if let Some(tarefa) = tarefa {
+        tarefa.fase(
+            "assets",
+            tasks_count,
+            verificacao::tamanho_total(&download_tasks),
+        );
+    }
 
-            for (_key, obj) in objects.iter() {
-                if let Some(hash) = obj["hash"].as_str() {
-                    let prefix = &hash[0..2];
-                    let object_path = objects_dir.join(prefix).join(hash);
+    if tasks_count == 0 {
+        println!("Todos os {} assets já estão baixados!", total_objects);
+        return Ok(());
+    }
 
-                    if !object_path.exists() {
-                        if let Some(parent) = object_path.parent() {
-                            std::fs::create_dir_all(parent).ok();
-                        }
+    println!(
+        "Iniciando download paralelo de {} assets ({} já existem)...",
+        tasks_count, already_downloaded
+    );
 
-                        let url = format!(
-                            "https://resources.download.minecraft.net/{}/{}",
-                            prefix, hash
-                        );
+    // Download paralelo com limite de 50 concorrências para assets (são pequenos)
+    let falhas = verificacao::baixar_lote_verificado(&client, download_tasks, 50, tarefa).await;
+    if let Some(tarefa) = tarefa {
+        tarefa.verificar_cancelamento()?;
+    }
+    for (_, erro) in falhas.iter().take(5) {
+        eprintln!("[Assets] Falha: {}", erro);
+    }
 
-                        download_tasks.push(AssetTask {
-                            url,
-                            path: object_path,
-                        });
-                    }
-                }
-            }
+    println!(
+        "Assets concluídos: {} de {} baixados",
+        total_objects - falhas.len(),
+        total_objects
+    );
 
-            let tasks_count = download_tasks.len();
-            let already_downloaded = total_objects - tasks_count;
-
-            if tasks_count == 0 {
-                println!("Todos os {} assets já estão baixados!", total_objects);
-            } else {
-                println!(
-                    "Iniciando download paralelo de {} assets ({} já existem)...",
-                    tasks_count, already_downloaded
-                );
-
-                // Download paralelo com limite de 50 concorrências para assets (são pequenos)
-                let concurrency_limit = 50;
-                let client_clone = client.clone();
-
-                let results: Vec<Result<(), String>> = stream::iter(download_tasks)
-                    .map(|task| {
-                        let client = client_clone.clone();
-                        async move {
-                            match client.get(&task.url).send().await {
-                                Ok(res) => match res.bytes().await {
-                                    Ok(bytes) => {
-                                        std::fs::write(&task.path, bytes).ok();
-                                        Ok(())
-                                    }
-                                    Err(e) => Err(format!("Erro ao ler bytes: {}", e)),
-                                },
-                                Err(e) => Err(format!("Erro ao baixar: {}", e)),
-                            }
-                        }
-                    })
-                    .buffer_unordered(concurrency_limit)
-                    .collect()
-                    .await;
-
-                let downloaded = results.iter().filter(|r| r.is_ok()).count();
-                println!(
-                    "Assets concluídos: {} de {} baixados",
-                    downloaded + already_downloaded,
-                    total_objects
-                );
-            }
-        }
+    if let Some((_, primeiro)) = falhas.first() {
+        return Err(format!(
+            "{} asset(s) não puderam ser baixados. Primeiro erro: {}",
+            falhas.len(),
+            primeiro
+        ));
     }
-
     Ok(())
 }
 
@@ -704,151 +678,191 @@ pub(super) async fn download_assets_safely(
 pub(super) async fn download_instance_files(
     instance_path: &std::path::Path,
     details: &VersionDetail,
+    tarefa: Option<&Tarefa>,
 ) -> Result<(), String> {
+    use super::verificacao_arquivos as verificacao;
+
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .map_err(|e| e.to_string())?;
 
-    // 1. Download do Client Jar
-    let client_download_url = &details.downloads.client.url;
-
-    let bin_path = instance_path.join("bin");
-    std::fs::create_dir_all(&bin_path).map_err(|e| e.to_string())?;
-
-    if !bin_path.join("client.jar").exists() {
-        let jar_res = client
-            .get(client_download_url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let mut file =
-            std::fs::File::create(bin_path.join("client.jar")).map_err(|e| e.to_string())?;
-        let content = jar_res.bytes().await.map_err(|e| e.to_string())?;
-        std::io::copy(&mut &content[..], &mut file).map_err(|e| e.to_string())?;
+    // Bibliotecas geradas por instaladores de loader passam para o armazenamento compartilhado
+    if let Err(erro) = super::armazenamento_compartilhado::adotar_bibliotecas_locais(instance_path)
+    {
+        eprintln!("[Store] Aviso: {}", erro);
     }
 
-    // 2. Download das Bibliotecas (PARALELO)
-    let libraries_path = instance_path.join("libraries");
-    let total_libs = details.libraries.len();
+    // 1. Client jar e bibliotecas (artifacts + natives da plataforma)
+    let arquivos = verificacao::arquivos_client_e_bibliotecas(instance_path, details);
+    let total_libs = arquivos.len();
 
-    // Coletar todos os downloads necessários
-    struct DownloadTask {
-        url: String,
-        path: std::path::PathBuf,
-    }
-
-    let mut download_tasks: Vec<DownloadTask> = Vec::new();
-
-    for lib in &details.libraries {
-        // Verificação de Regras (Basic Windows Check)
-        let mut allowed = true;
-        if let Some(rules) = &lib.rules {
-            for rule in rules {
-                if rule.action == "allow" {
-                    if let Some(os) = &rule.os {
-                        if os.name != "windows" {
-                            allowed = false;
-                        }
-                    }
-                } else if rule.action == "disallow" {
-                    if let Some(os) = &rule.os {
-                        if os.name == "windows" {
-                            allowed = false;
-                        }
-                    }
-                }
-            }
-        }
-
-        if allowed {
-            if let Some(downloads) = &lib.downloads {
-                // Artifact (Library comum)
-                if let Some(artifact) = &downloads.artifact {
-                    if let Some(path) = &artifact.path {
-                        let lib_file_path = libraries_path.join(path);
-                        if !lib_file_path.exists() {
-                            // Criar diretório pai se não existir
-                            if let Some(parent) = lib_file_path.parent() {
-                                std::fs::create_dir_all(parent).ok();
-                            }
-                            download_tasks.push(DownloadTask {
-                                url: artifact.url.clone(),
-                                path: lib_file_path,
-                            });
-                        }
-                    }
-                }
-
-                // Classifiers (Natives)
-                if let Some(classifiers) = &downloads.classifiers {
-                    let native_key = "natives-windows";
-                    if let Some(native_obj) = classifiers.get(native_key) {
-                        if let Some(url) = native_obj["url"].as_str() {
-                            if let Some(path) = native_obj["path"].as_str() {
-                                let native_path = libraries_path.join(path);
-                                if !native_path.exists() {
-                                    if let Some(parent) = native_path.parent() {
-                                        std::fs::create_dir_all(parent).ok();
-                                    }
-                                    download_tasks.push(DownloadTask {
-                                        url: url.to_string(),
-                                        path: native_path,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let download_tasks: Vec<_> = arquivos
+        .into_iter()
+        .filter(|arquivo| {
+            verificacao::estado_arquivo(arquivo, verificacao::ModoVerificacao::Rapida)
+                != verificacao::EstadoArquivo::Ok
+        })
+        .collect();
 
     let tasks_count = download_tasks.len();
     println!(
         "Iniciando download paralelo de {} bibliotecas (de {} total)...",
         tasks_count, total_libs
     );
+    if let Some(tarefa) = tarefa {
+        tarefa.fase(
+            "bibliotecas",
+            tasks_count,
+            verificacao::tamanho_total(&download_tasks),
+        );
+    }
 
-    // Download paralelo com limite de 20 concorrências
-    let concurrency_limit = 20;
-    let client_clone = client.clone();
-
-    let results: Vec<Result<(), String>> = stream::iter(download_tasks)
-        .map(|task| {
-            let client = client_clone.clone();
-            async move {
-                match client.get(&task.url).send().await {
-                    Ok(res) => match res.bytes().await {
-                        Ok(bytes) => {
-                            std::fs::write(&task.path, bytes).ok();
-                            Ok(())
-                        }
-                        Err(e) => Err(format!("Erro ao ler bytes: {}", e)),
-                    },
-                    Err(e) => Err(format!("Erro ao baixar: {}", e)),
-                }
-            }
-        })
-        .buffer_unordered(concurrency_limit)
-        .collect()
-        .await;
-
-    let downloaded_libs = results.iter().filter(|r| r.is_ok()).count();
+    // 2. Download paralelo com limite de 20 concorrências
+    let falhas = verificacao::baixar_lote_verificado(&client, download_tasks, 20, tarefa).await;
+    if let Some(tarefa) = tarefa {
+        tarefa.verificar_cancelamento()?;
+    }
     println!(
         "Libraries concluídas: {} de {} baixadas",
-        downloaded_libs, tasks_count
+        tasks_count - falhas.len(),
+        tasks_count
     );
 
+    // Arquivos com SHA-1 no manifesto são obrigatórios; bibliotecas sem hash
+    // (geradas por instaladores de loader) apenas geram aviso.
+    let mut falhas_obrigatorias = Vec::new();
+    for (arquivo, erro) in &falhas {
+        if arquivo.sha1.is_some() {
+            falhas_obrigatorias.push(erro.as_str());
+        } else {
+            eprintln!("[Download] Aviso: {}", erro);
+        }
+    }
+    if let Some(primeira) = falhas_obrigatorias.first() {
+        return Err(format!(
+            "{} arquivo(s) da instância não puderam ser baixados. Primeiro erro: {}",
+            falhas_obrigatorias.len(),
+            primeira
+        ));
+    }
+
     // 3. Assets - Download controlado para evitar travamentos
     println!("Iniciando download de assets...");
-    download_assets_safely(instance_path, details).await?;
+    download_assets_safely(details, tarefa).await?;
+
+    super::armazenamento_compartilhado::registrar_referencias_manifesto(instance_path, details)?;
 
     Ok(())
 }
 
 // ===== FUNÇÕES DE AJUSTE DE MANIFESTO PARA LOADERS =====
 
+/// Mescla o `version.json` de um loader (Forge/NeoForge) no manifesto vanilla:
+/// main class, bibliotecas ainda ausentes e argumentos JVM/jogo.
+fn mesclar_manifesto_loader(
+    details: &mut VersionDetail,
+    fj: &serde_json::Value,
+    nome: &str,
+    maven_padrao: &str,
+) {
+    if let Some(main_class) = fj.get("mainClass").and_then(|v| v.as_str()) {
+        println!(
+            "[{}] Aplicando manifesto do {} (main class: {})...",
+            nome, nome, main_class
+        );
+        details.main_class = main_class.to_string();
+    }
+
+    // Adicionar bibliotecas do loader
+    let mut nomes_existentes: std::collections::HashSet<String> = details
+        .libraries
+        .iter()
+        .map(|lib| lib.name.clone())
+        .collect();
+
+    if let Some(libs_arr) = fj.get("libraries").and_then(|v| v.as_array()) {
+        for lib_val in libs_arr {
+            let name = lib_val
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+
+            if name.is_empty() || nomes_existentes.contains(&name) {
+                continue;
+            }
+
+            if let Ok(mut lib) = serde_json::from_value::<crate::launcher::Library>(lib_val.clone())
+            {
+                // Garantir que a biblioteca tenha um download path associado
+                if lib
+                    .downloads
+                    .as_ref()
+                    .and_then(|d| d.artifact.as_ref())
+                    .and_then(|a| a.path.as_ref())
+                    .is_none()
+                {
+                    let partes: Vec<&str> = name.split(':').collect();
+                    if partes.len() >= 3 {
+                        let grupo = partes[0].replace('.', "/");
+                        let artefato = partes[1];
+                        let versao = partes[2];
+                        let caminho_jar = format!(
+                            "{}/{}/{}/{}-{}.jar",
+                            grupo, artefato, versao, artefato, versao
+                        );
+                        lib.downloads = Some(crate::launcher::LibraryDownloads {
+                            artifact: Some(crate::launcher::Artifact {
+                                path: Some(caminho_jar.clone()),
+                                sha1: None,
+                                size: None,
+                                url: format!("{}{}", maven_padrao, caminho_jar),
+                            }),
+                            classifiers: None,
+                        });
+                    }
+                }
+                details.libraries.push(lib);
+                nomes_existentes.insert(name);
+            }
+        }
+    }
+
+    // Mesclar argumentos JVM e Game do loader
+    if let Some(fd_args) = fj.get("arguments") {
+        let details_args = details
+            .arguments
+            .get_or_insert_with(|| serde_json::json!({}));
+
+        if let Some(obj) = details_args.as_object_mut() {
+            if let Some(fd_jvm) = fd_args.get("jvm").and_then(|v| v.as_array()) {
+                let jvm_array = obj
+                    .entry("jvm")
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+                    .as_array_mut();
+                if let Some(arr) = jvm_array {
+                    for arg in fd_jvm {
+                        arr.push(arg.clone());
+                    }
+                }
+            }
+
+            if let Some(fd_game) = fd_args.get("game").and_then(|v| v.as_array()) {
+                let game_array = obj
+                    .entry("game")
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+                    .as_array_mut();
+                if let Some(arr) = game_array {
+                    for arg in fd_game {
+                        arr.push(arg.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub(super) async fn adjust_forge_manifest(
     details: &mut VersionDetail,
     forge_version: &str,
@@ -932,118 +946,22 @@ pub(super) async fn adjust_forge_manifest(
 
     // 3. Se obtivemos o manifesto do Forge, mesclar com o manifesto vanilla
     if let Some(fj) = forge_json_opt {
-        if let Some(main_class) = fj.get("mainClass").and_then(|v| v.as_str()) {
-            println!(
-                "[Forge] Aplicando manifesto do Forge (main class: {})...",
-                main_class
-            );
-            details.main_class = main_class.to_string();
-        }
-
-        // Adicionar bibliotecas do Forge
-        let mut nomes_existentes: std::collections::HashSet<String> = details
-            .libraries
-            .iter()
-            .map(|lib| lib.name.clone())
-            .collect();
-
-        if let Some(libs_arr) = fj.get("libraries").and_then(|v| v.as_array()) {
-            for lib_val in libs_arr {
-                let name = lib_val
-                    .get("name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or_default()
-                    .to_string();
-
-                if name.is_empty() || nomes_existentes.contains(&name) {
-                    continue;
-                }
-
-                if let Ok(mut lib) =
-                    serde_json::from_value::<crate::launcher::Library>(lib_val.clone())
-                {
-                    // Garantir que a biblioteca tenha um download path associado
-                    if lib
-                        .downloads
-                        .as_ref()
-                        .and_then(|d| d.artifact.as_ref())
-                        .and_then(|a| a.path.as_ref())
-                        .is_none()
-                    {
-                        let partes: Vec<&str> = name.split(':').collect();
-                        if partes.len() >= 3 {
-                            let grupo = partes[0].replace('.', "/");
-                            let artefato = partes[1];
-                            let versao = partes[2];
-                            let caminho_jar = format!(
-                                "{}/{}/{}/{}-{}.jar",
-                                grupo, artefato, versao, artefato, versao
-                            );
-                            lib.downloads = Some(crate::launcher::LibraryDownloads {
-                                artifact: Some(crate::launcher::Artifact {
-                                    path: Some(caminho_jar.clone()),
-                                    sha1: None,
-                                    size: None,
-                                    url: format!(
-                                        "https://maven.minecraftforge.net/{}",
-                                        caminho_jar
-                                    ),
-                                }),
-                                classifiers: None,
-                            });
-                        }
-                    }
-                    details.libraries.push(lib);
-                    nomes_existentes.insert(name);
-                }
-            }
-        }
-
-        // Mesclar argumentos JVM e Game do Forge
-        if let Some(fd_args) = fj.get("arguments") {
-            let details_args = details
-                .arguments
-                .get_or_insert_with(|| serde_json::json!({}));
-
-            if let Some(obj) = details_args.as_object_mut() {
-                if let Some(fd_jvm) = fd_args.get("jvm").and_then(|v| v.as_array()) {
-                    let jvm_array = obj
-                        .entry("jvm")
-                        .or_insert_with(|| serde_json::Value::Array(Vec::new()))
-                        .as_array_mut();
-                    if let Some(arr) = jvm_array {
-                        for arg in fd_jvm {
-                            arr.push(arg.clone());
-                        }
-                    }
-                }
-
-                if let Some(fd_game) = fd_args.get("game").and_then(|v| v.as_array()) {
-                    let game_array = obj
-                        .entry("game")
-                        .or_insert_with(|| serde_json::Value::Array(Vec::new()))
-                        .as_array_mut();
-                    if let Some(arr) = game_array {
-                        for arg in fd_game {
-                            arr.push(arg.clone());
-                        }
-                    }
-                }
-            }
-        }
+        mesclar_manifesto_loader(details, &fj, "Forge", "https://maven.minecraftforge.net/");
 
         // Verificar se os jars de cliente do Forge foram gerados (forge-*-client.jar)
-        let forge_client_jar = instance_path
-            .join("libraries")
-            .join("net")
-            .join("minecraftforge")
-            .join("forge")
-            .join(&versao_forge)
-            .join(format!("forge-{}-client.jar", versao_forge));
+        let forge_client_jar = resolver_biblioteca(
+            instance_path,
+            &format!(
+                "net/minecraftforge/forge/{0}/forge-{0}-client.jar",
+                versao_forge
+            ),
+        );
 
         if !forge_client_jar.exists() {
             println!("[Forge] Jars do cliente Forge não encontrados. Executando instalação do cliente...");
-            if let Err(e) = install_forge_loader(instance_path, &details.id, forge_version).await {
+            if let Err(e) =
+                install_forge_loader(instance_path, &details.id, forge_version, None).await
+            {
                 eprintln!("[Forge] Aviso na instalação do loader: {}", e);
             }
         }
@@ -1089,59 +1007,78 @@ pub(super) async fn adjust_fabric_manifest(
     _fabric_version: &str,
     instance_path: &std::path::Path,
 ) -> Result<(), String> {
-    // Carregar manifesto do Fabric
-    let fabric_manifest_path = instance_path.join("fabric_manifest.json");
+    ajustar_manifesto_perfil_meta(&PERFIL_FABRIC, details, instance_path)
+}
+
+pub(super) async fn adjust_quilt_manifest(
+    details: &mut VersionDetail,
+    _quilt_version: &str,
+    instance_path: &std::path::Path,
+) -> Result<(), String> {
+    ajustar_manifesto_perfil_meta(&PERFIL_QUILT, details, instance_path)
+}
 
-    if !fabric_manifest_path.exists() {
-        return Err("Manifesto do Fabric não encontrado. Recrie a instância.".to_string());
+/// Mescla no manifesto do Minecraft o perfil salvo por `instalar_perfil_meta`.
+fn ajustar_manifesto_perfil_meta(
+    perfil: &PerfilMeta,
+    details: &mut VersionDetail,
+    instance_path: &std::path::Path,
+) -> Result<(), String> {
+    let nome = perfil.nome;
+    // Carregar manifesto do loader
+    let manifest_path = instance_path.join(perfil.arquivo_manifesto);
+
+    if !manifest_path.exists() {
+        return Err(format!(
+            "Manifesto do {} não encontrado. Recrie a instância.",
+            nome
+        ));
     }
 
-    let fabric_content = std::fs::read_to_string(&fabric_manifest_path)
-        .map_err(|e| format!("Erro ao ler manifesto Fabric: {}", e))?;
-    let fabric_profile: serde_json::Value = serde_json::from_str(&fabric_content)
-        .map_err(|e| format!("Erro ao parsear manifesto Fabric: {}", e))?;
+    let conteudo_manifesto = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Erro ao ler manifesto {}: {}", nome, e))?;
+    let profile: serde_json::Value = serde_json::from_str(&conteudo_manifesto)
+        .map_err(|e| format!("Erro ao parsear manifesto {}: {}", nome, e))?;
 
-    // Atualizar main class do Fabric
-    if let Some(main_class) = fabric_profile["mainClass"].as_str() {
+    // Atualizar main class do loader
+    if let Some(main_class) = profile["mainClass"].as_str() {
         details.main_class = main_class.to_string();
     } else {
-        details.main_class = "net.fabricmc.loader.impl.launch.knot.KnotClient".to_string();
+        details.main_class = perfil.main_class_padrao.to_string();
     }
 
-    // Adicionar bibliotecas do Fabric ao details
-    if let Some(fabric_libs) = fabric_profile["libraries"].as_array() {
-        let libraries_path = instance_path.join("libraries");
-
-        // Coletar nomes de artifacts do Fabric para remover duplicatas do Minecraft
-        let mut fabric_artifacts: std::collections::HashSet<String> =
+    // Adicionar bibliotecas do loader ao details
+    if let Some(loader_libs) = profile["libraries"].as_array() {
+        // Coletar nomes de artifacts do loader para remover duplicatas do Minecraft
+        let mut loader_artifacts: std::collections::HashSet<String> =
             std::collections::HashSet::new();
 
-        for lib in fabric_libs {
+        for lib in loader_libs {
             if let Some(name) = lib["name"].as_str() {
                 let parts: Vec<&str> = name.split(':').collect();
                 if parts.len() >= 3 {
                     // Chave: group:artifact (sem versão)
                     let artifact_key = format!("{}:{}", parts[0], parts[1]);
-                    fabric_artifacts.insert(artifact_key);
+                    loader_artifacts.insert(artifact_key);
                 }
             }
         }
 
-        // Remover bibliotecas do Minecraft que serão substituídas pelo Fabric
+        // Remover bibliotecas do Minecraft que serão substituídas pelo loader
         details.libraries.retain(|lib| {
             let parts: Vec<&str> = lib.name.split(':').collect();
             if parts.len() >= 2 {
                 let artifact_key = format!("{}:{}", parts[0], parts[1]);
-                if fabric_artifacts.contains(&artifact_key) {
-                    println!("[Fabric] Substituindo biblioteca: {}", lib.name);
+                if loader_artifacts.contains(&artifact_key) {
+                    println!("[{}] Substituindo biblioteca: {}", nome, lib.name);
                     return false; // Remover do Minecraft
                 }
             }
             true // Manter
         });
 
-        // Agora adicionar bibliotecas do Fabric
-        for lib in fabric_libs {
+        // Agora adicionar bibliotecas do loader
+        for lib in loader_libs {
             if let Some(name) = lib["name"].as_str() {
                 // Formato Maven: group:artifact:version
                 let parts: Vec<&str> = name.split(':').collect();
@@ -1155,7 +1092,7 @@ pub(super) async fn adjust_fabric_manifest(
                         group, artifact, version, artifact, version
                     );
 
-                    let full_path = libraries_path.join(&jar_path);
+                    let full_path = resolver_biblioteca(instance_path, &jar_path);
 
                     // Criar uma nova entrada de biblioteca
                     let new_lib = crate::launcher::Library {
@@ -1179,6 +1116,7 @@ pub(super) async fn adjust_fabric_manifest(
                             classifiers: None,
                         }),
                         natives: None,
+                        extract: None,
                     };
 
                     // Adicionar apenas se o arquivo existe
@@ -1190,8 +1128,8 @@ pub(super) async fn adjust_fabric_manifest(
         }
     }
 
-    // Adicionar argumentos do Fabric (se existirem no manifesto)
-    if let Some(args) = fabric_profile["arguments"].as_object() {
+    // Adicionar argumentos do loader (se existirem no manifesto)
+    if let Some(args) = profile["arguments"].as_object() {
         if let Some(jvm_args) = args.get("jvm") {
             if let Some(jvm_arr) = jvm_args.as_array() {
                 if let Some(details_args) = &mut details.arguments {
@@ -1208,7 +1146,8 @@ pub(super) async fn adjust_fabric_manifest(
     }
 
     println!(
-        "[Fabric] Manifesto ajustado - {} bibliotecas adicionadas",
+        "[{}] Manifesto ajustado - {} bibliotecas adicionadas",
+        nome,
         details.libraries.len()
     );
     Ok(())
@@ -1217,123 +1156,25 @@ pub(super) async fn adjust_fabric_manifest(
 pub(super) async fn adjust_neoforge_manifest(
     details: &mut VersionDetail,
     neoforge_version: &str,
+    instance_path: &std::path::Path,
 ) -> Result<(), String> {
-    // Para NeoForge, similar ao Forge
-    let client = reqwest::Client::new();
-    let neoforge_manifest_url = format!(
-        "https://maven.neoforged.net/releases/net/neoforged/neoforge/{}/neoforge-{}.json",
-        neoforge_version, neoforge_version
-    );
+    let manifesto_local = instance_path.join("neoforge_manifest.json");
 
-    let response = client
-        .get(&neoforge_manifest_url)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if response.status().is_success() {
-        let neoforge_details: VersionDetail = response.json().await.map_err(|e| e.to_string())?;
-        *details = neoforge_details;
+    // Instâncias criadas antes do manifesto ser salvo passam pela instalação de novo.
+    if !manifesto_local.exists() {
+        println!("[NeoForge] Manifesto não encontrado. Executando instalação do cliente...");
+        install_neoforge_loader(instance_path, &details.id, neoforge_version, None).await?;
     }
 
+    let conteudo = std::fs::read_to_string(&manifesto_local)
+        .map_err(|e| format!("Erro ao ler manifesto NeoForge: {}", e))?;
+    let manifesto: serde_json::Value = serde_json::from_str(&conteudo)
+        .map_err(|e| format!("Manifesto NeoForge inválido: {}", e))?;
+    mesclar_manifesto_loader(
+        details,
+        &manifesto,
+        "NeoForge",
+        "https://maven.neoforged.net/releases/",
+    );
     Ok(())
 }
-
-fn substituir_placeholders_jvm(
-    arg: &str,
-    natives_path: &std::path::Path,
-    libraries_path: &std::path::Path,
-    classpath: &str,
-    version_id: &str,
-) -> String {
-    let mut res = arg
-        .replace("${natives_directory}", &natives_path.to_string_lossy())
-        .replace("${library_directory}", &libraries_path.to_string_lossy())
-        .replace("${classpath_separator}", ";")
-        .replace("${classpath}", classpath)
-        .replace("${launcher_name}", "DomeLauncher")
-        .replace("${launcher_version}", env!("CARGO_PKG_VERSION"))
-        .replace("${version_name}", version_id);
-
-    // O Forge espera ignorar o jar vanilla do jogo pelo nome. Como usamos client.jar,
-    // precisamos adicioná-lo ao ignoreList para evitar colisão de módulos Java 17.
-    if res.starts_with("-DignoreList=") && !res.contains("client.jar") {
-        res.push_str(",client.jar,client");
-    }
-
-    res
-}
-
-pub(super) fn coletar_argumentos_jvm_manifesto(
-    details: &VersionDetail,
-    natives_path: &std::path::Path,
-    libraries_path: &std::path::Path,
-    classpath: &str,
-) -> Vec<String> {
-    let mut args_jvm = Vec::new();
-    let mut ignorar_proximo_classpath = false;
-
-    let Some(arguments) = &details.arguments else {
-        return args_jvm;
-    };
-    let Some(jvm_args) = arguments.get("jvm").and_then(|v| v.as_array()) else {
-        return args_jvm;
-    };
-
-    for valor in jvm_args {
-        let Some(arg_raw) = valor.as_str() else {
-            continue;
-        };
-
-        let arg_raw_trim = arg_raw.trim();
-        if ignorar_proximo_classpath {
-            ignorar_proximo_classpath = false;
-            continue;
-        }
-
-        // O classpath é montado manualmente pelo launcher.
-        if arg_raw_trim == "-cp" || arg_raw_trim == "-classpath" {
-            ignorar_proximo_classpath = true;
-            continue;
-        }
-        if arg_raw_trim == "${classpath}" {
-            continue;
-        }
-
-        let mut arg = substituir_placeholders_jvm(
-            arg_raw,
-            natives_path,
-            libraries_path,
-            classpath,
-            &details.id,
-        )
-        .trim()
-        .to_string();
-
-        if arg.starts_with("-DFabricMcEmu=") {
-            let valor = arg["-DFabricMcEmu=".len()..].trim();
-            arg = format!("-DFabricMcEmu={}", valor);
-        }
-
-        // Ignorar parâmetros já controlados pelo launcher.
-        if arg.is_empty()
-            || arg == "-cp"
-            || arg == "-classpath"
-            || arg == classpath
-            || arg.starts_with("-Djava.library.path=")
-            || arg.starts_with("-Xmx")
-            || arg.starts_with("-Xms")
-        {
-            continue;
-        }
-
-        // Se ainda restou placeholder não resolvido, evita quebrar o launch.
-        if arg.contains("${") {
-            continue;
-        }
-
-        args_jvm.push(arg);
-    }
-
-    args_jvm
-}
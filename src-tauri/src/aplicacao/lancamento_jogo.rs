@@ -7,43 +7,67 @@ pub(crate) fn timestamp_atual_segundos() -> u64 {
         .unwrap_or(0)
 }
 
-/// Extrai DLLs nativas dos classifiers das bibliotecas para a pasta de natives.
+/// Extrai os natives da plataforma atual (`.dll`, `.so`, `.dylib`) das
+/// bibliotecas para a pasta de natives, respeitando `extract.exclude`.
 fn extrair_natives_de_libs(
     libs: &[crate::launcher::Library],
-    libraries_path: &std::path::Path,
+    instance_path: &std::path::Path,
     natives_path: &std::path::Path,
+    plataforma: &crate::plataforma::Plataforma,
 ) {
     for lib in libs {
-        if let Some(downloads) = &lib.downloads {
-            if let Some(classifiers) = &downloads.classifiers {
-                if let Some(native_obj) = classifiers.get("natives-windows") {
-                    if let Some(path) = native_obj["path"].as_str() {
-                        let native_jar_path = libraries_path.join(path);
-                        if native_jar_path.exists() {
-                            if let Ok(file) = std::fs::File::open(&native_jar_path) {
-                                if let Ok(mut archive) = zip::ZipArchive::new(file) {
-                                    for i in 0..archive.len() {
-                                        if let Ok(mut entry) = archive.by_index(i) {
-                                            let name = entry.name().to_string();
-                                            if name.ends_with(".dll") {
-                                                let out = natives_path.join(
-                                                    std::path::Path::new(&name)
-                                                        .file_name()
-                                                        .unwrap(),
-                                                );
-                                                if let Ok(mut out_file) =
-                                                    std::fs::File::create(&out)
-                                                {
-                                                    std::io::copy(&mut entry, &mut out_file).ok();
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+        if !plataforma.biblioteca_permitida(lib) {
+            continue;
+        }
+
+        // Natives via classifier (LWJGL 2/3 até 1.18) ou no próprio artifact (1.19+).
+        let caminho_jar = plataforma
+            .artefato_natives(lib)
+            .and_then(|native_obj| native_obj["path"].as_str())
+            .or_else(|| {
+                if !crate::plataforma::biblioteca_e_artifact_nativo(lib) {
+                    return None;
                 }
+                lib.downloads
+                    .as_ref()
+                    .and_then(|downloads| downloads.artifact.as_ref())
+                    .and_then(|artifact| artifact.path.as_deref())
+            });
+        let Some(caminho_jar) = caminho_jar else {
+            continue;
+        };
+
+        let exclusoes = lib
+            .extract
+            .as_ref()
+            .map(|extract| extract.exclude.as_slice())
+            .unwrap_or_default();
+
+        let Ok(file) = std::fs::File::open(
+            super::armazenamento_compartilhado::resolver_biblioteca(instance_path, caminho_jar),
+        ) else {
+            continue;
+        };
+        let Ok(mut archive) = zip::ZipArchive::new(file) else {
+            continue;
+        };
+
+        for i in 0..archive.len() {
+            let Ok(mut entry) = archive.by_index(i) else {
+                continue;
+            };
+            if !plataforma.entrada_nativa_extraivel(entry.name(), exclusoes) {
+                continue;
+            }
+            let Some(nome_arquivo) = entry
+                .enclosed_name()
+                .and_then(|caminho| caminho.file_name().map(|nome| nome.to_os_string()))
+            else {
+                continue;
+            };
+
+            if let Ok(mut out_file) = std::fs::File::create(natives_path.join(nome_arquivo)) {
+                std::io::copy(&mut entry, &mut out_file).ok();
             }
         }
     }
@@ -104,31 +128,11 @@ async fn obter_conta_valida_para_launch(
     Ok(conta_atual)
 }
 
-async fn launch_instance_com_opcoes(
-    state: &LauncherState,
-    id: String,
-    quick_play_servidor: Option<String>,
-) -> Result<(), String> {
-    let account = obter_conta_valida_para_launch(state).await?;
-
-    let instance_path = caminho_instancia_por_id(state, &id)?;
-
-    // 0. Carregar informações da instância
-    let instance_config_path = instance_path.join("instance.json");
-    if !instance_config_path.exists() {
-        return Err("Configuração da instância não encontrada.".to_string());
-    }
-    let instance_content =
-        std::fs::read_to_string(&instance_config_path).map_err(|e| e.to_string())?;
-    let mut instance: Instance =
-        serde_json::from_str(&instance_content).map_err(|e| e.to_string())?;
-
-    let bin_path = instance_path.join("bin");
-    let jar_path = bin_path.join("client.jar");
-    let libraries_path = instance_path.join("libraries");
-    let assets_path = instance_path.join("assets");
-    let natives_path = bin_path.join("natives");
-
+/// Lê o `version_manifest.json` da instância e aplica os ajustes do loader.
+pub(super) async fn carregar_manifesto_instancia(
+    instance_path: &std::path::Path,
+    instance: &Instance,
+) -> Result<VersionDetail, String> {
     // 1. Carregar Manifesto
     let version_manifest_path = instance_path.join("version_manifest.json");
     if !version_manifest_path.exists() {
@@ -156,7 +160,7 @@ async fn launch_instance_com_opcoes(
                         .loader_version
                         .as_ref()
                         .unwrap_or(&"latest".to_string()),
-                    &instance_path,
+                    instance_path,
                 )
                 .await?;
             }
@@ -167,7 +171,18 @@ async fn launch_instance_com_opcoes(
                         .loader_version
                         .as_ref()
                         .unwrap_or(&"latest".to_string()),
-                    &instance_path,
+                    instance_path,
+                )
+                .await?;
+            }
+            "quilt" => {
+                super::instancias_criacao::adjust_quilt_manifest(
+                    &mut details,
+                    instance
+                        .loader_version
+                        .as_ref()
+                        .unwrap_or(&"latest".to_string()),
+                    instance_path,
                 )
                 .await?;
             }
@@ -178,6 +193,7 @@ async fn launch_instance_com_opcoes(
                         .loader_version
                         .as_ref()
                         .unwrap_or(&"latest".to_string()),
+                    instance_path,
                 )
                 .await?;
             }
@@ -185,75 +201,241 @@ async fn launch_instance_com_opcoes(
         }
     }
 
-    // 1.2. Garantir que client.jar, bibliotecas e assets estejam presentes
-    super::instancias_criacao::download_instance_files(&instance_path, &details).await?;
+    Ok(details)
+}
 
-    // 2. Extrair Natives e Montar Classpath
-    std::fs::create_dir_all(&natives_path).map_err(|e| e.to_string())?;
+fn erro_inicio_java(java_exe: &str, erro: std::io::Error) -> String {
+    format!(
+        "Falha ao iniciar Java ({}): {}. Verifique suas configurações de Java.",
+        java_exe, erro
+    )
+}
 
-    // Extrair DLLs nativas de todas as libs do manifesto
-    extrair_natives_de_libs(&details.libraries, &libraries_path, &natives_path);
+/// Inicia o jogo desacoplado do launcher, descartando a saída do processo.
+#[cfg(windows)]
+fn iniciar_processo_destacado(
+    mut comando_java: std::process::Command,
+    java_exe: &str,
+    args: &[String],
+    instance_path: &std::path::Path,
+) -> Result<std::process::Child, String> {
+    use std::os::windows::process::CommandExt;
+
+    // Garante que o Minecraft permaneça aberto mesmo após fechar o launcher.
+    const DETACHED_PROCESS: u32 = 0x00000008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+    comando_java.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+
+    match comando_java.spawn() {
+        Ok(child) => Ok(child),
+        Err(erro) if erro.raw_os_error() == Some(5) => {
+            eprintln!(
+                "[Launch] Aviso: criação destacada bloqueada (acesso negado). Tentando fallback padrão."
+            );
 
-    // 3. Detectar Java correto automaticamente
-    let java_major_manifesto = details.java_version.as_ref().map(|java| java.major_version);
-    let java_exe = crate::comandos::configuracoes_java::ensure_java_for_manifest(
-        details.id.clone(),
-        java_major_manifesto,
-    )
-    .await
-    .map_err(|erro| format!("Não foi possível preparar o Java da instância: {}", erro))?;
+            let mut comando_fallback = std::process::Command::new(java_exe);
+            comando_fallback.args(args).current_dir(instance_path);
+            comando_fallback
+                .spawn()
+                .map_err(|e| erro_inicio_java(java_exe, e))
+        }
+        Err(erro) => Err(erro_inicio_java(java_exe, erro)),
+    }
+}
 
-    // 4. Carregar configurações globais
-    let settings = crate::comandos::configuracoes_java::get_settings()
-        .await
-        .unwrap_or_default();
+#[cfg(not(windows))]
+fn iniciar_processo_destacado(
+    mut comando_java: std::process::Command,
+    java_exe: &str,
+    _args: &[String],
+    _instance_path: &std::path::Path,
+) -> Result<std::process::Child, String> {
+    comando_java
+        .spawn()
+        .map_err(|e| erro_inicio_java(java_exe, e))
+}
+
+/// Inicia o jogo com stdout/stderr redirecionados para o launcher, que os
+/// interpreta, transmite à interface e grava em `logs/launcher_output.log`.
+fn iniciar_processo_gerenciado(
+    app: Option<tauri::AppHandle>,
+    state: &LauncherState,
+    instance_id: &str,
+    instance_path: &std::path::Path,
+    mut comando_java: std::process::Command,
+    java_exe: &str,
+) -> Result<(std::process::Child, Vec<std::thread::JoinHandle<()>>), String> {
+    comando_java
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
 
-    // 5. Montar Argumentos (respeitar config da instância ou global)
-    let mut args = Vec::new();
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        comando_java.creation_flags(CREATE_NO_WINDOW);
+    }
 
-    let ram_mb = settings.ram_mb;
-    args.push(format!("-Xmx{}M", ram_mb));
-    args.push(format!("-Xms{}M", (ram_mb / 2).max(512)));
+    let mut processo = comando_java
+        .spawn()
+        .map_err(|e| erro_inicio_java(java_exe, e))?;
+    let leitores = crate::comandos::saida_jogo::capturar_saida_processo(
+        app,
+        &state.saida_jogo,
+        instance_id,
+        &instance_path.join("logs"),
+        &mut processo,
+    );
+    Ok((processo, leitores))
+}
 
-    // JVM args customizados (instância ou global)
-    if let Some(ref custom_args) = instance.java_args {
-        for arg in custom_args.split_whitespace() {
-            args.push(arg.to_string());
-        }
-    } else if !settings.java_args.is_empty() {
-        for arg in settings.java_args.split_whitespace() {
-            args.push(arg.to_string());
+/// Aguarda o fim do jogo em segundo plano para registrar o código de saída e
+/// analisar crash reports / `hs_err_pid*.log` gerados durante a execução.
+/// Processos fechados pelo usuário não passam pela análise de crash.
+fn acompanhar_fim_processo(
+    app: Option<tauri::AppHandle>,
+    state: &LauncherState,
+    instance_id: &str,
+    instance_path: &std::path::Path,
+    mut processo: std::process::Child,
+    leitores: Vec<std::thread::JoinHandle<()>>,
+    instantaneo: crate::comandos::analise_crash::InstantaneoCrash,
+) {
+    let instance_id = instance_id.to_string();
+    let instance_path = instance_path.to_path_buf();
+    let encerramentos_pedidos = state.encerramentos_pedidos.clone();
+    std::thread::spawn(move || {
+        let codigo_saida = match processo.wait() {
+            Ok(status) => status.code(),
+            Err(erro) => {
+                eprintln!(
+                    "[Launch] Aviso: falha ao aguardar o processo de {}: {}",
+                    instance_id, erro
+                );
+                return;
+            }
+        };
+        // A saída capturada precisa estar completa antes da análise.
+        for leitor in leitores {
+            let _ = leitor.join();
         }
+        println!(
+            "[Launch] {} finalizado (código de saída: {}).",
+            instance_id,
+            codigo_saida.map_or_else(|| "desconhecido".to_string(), |codigo| codigo.to_string())
+        );
+        let encerrado_pelo_usuario = encerramentos_pedidos
+            .lock()
+            .map(|mut encerramentos| encerramentos.remove(&instance_id))
+            .unwrap_or(false);
+        crate::comandos::analise_crash::registrar_fim_execucao(
+            app.as_ref(),
+            &instance_id,
+            &instance_path,
+            codigo_saida,
+            encerrado_pelo_usuario,
+            &instantaneo,
+        );
+    });
+}
+
+async fn launch_instance_com_opcoes(
+    app: Option<tauri::AppHandle>,
+    state: &LauncherState,
+    id: String,
+    quick_play_servidor: Option<String>,
+) -> Result<(), String> {
+    let account = obter_conta_valida_para_launch(state).await?;
+
+    let instance_path = caminho_instancia_por_id(state, &id)?;
+
+    // 0. Carregar informações da instância
+    let instance_config_path = instance_path.join("instance.json");
+    if !instance_config_path.exists() {
+        return Err("Configuração da instância não encontrada.".to_string());
     }
+    let instance_content =
+        std::fs::read_to_string(&instance_config_path).map_err(|e| e.to_string())?;
+    let mut instance: Instance =
+        serde_json::from_str(&instance_content).map_err(|e| e.to_string())?;
+
+    let bin_path = instance_path.join("bin");
+    let libraries_path = super::armazenamento_compartilhado::pasta_bibliotecas_store();
+    let assets_path = super::armazenamento_compartilhado::pasta_assets_store();
+    let natives_path = bin_path.join("natives");
+
+    // 1. Carregar manifesto ajustado ao loader
+    let details = carregar_manifesto_instancia(&instance_path, &instance).await?;
+    let jar_path =
+        super::armazenamento_compartilhado::resolver_client_jar(&instance_path, &details);
+
+    // 1.2. Garantir que client.jar, bibliotecas e assets estejam presentes
+    super::instancias_criacao::download_instance_files(&instance_path, &details, None).await?;
+
+    // 2. Extrair Natives e Montar Classpath
+    std::fs::create_dir_all(&natives_path).map_err(|e| e.to_string())?;
+
+    // Extrair natives de todas as libs do manifesto para a plataforma atual
+    let plataforma = crate::plataforma::Plataforma::atual();
+    extrair_natives_de_libs(
+        &details.libraries,
+        &instance_path,
+        &natives_path,
+        &plataforma,
+    );
+
+    // 3. Resolver configurações efetivas (instância > globais > padrões)
+    let configuracao = super::configuracao_lancamento::carregar_configuracao_lancamento(&instance);
+
+    // 4. Detectar Java correto (caminho da instância ou global manual têm prioridade)
+    let java_exe = match (
+        configuracao.java_path.valor.as_deref(),
+        configuracao.java_path.origem,
+    ) {
+        (Some(caminho), origem)
+            if origem != super::configuracao_lancamento::OrigemConfiguracao::Padrao =>
+        {
+            let onde = match origem {
+                super::configuracao_lancamento::OrigemConfiguracao::Instancia => "na instância",
+                _ => "nas configurações globais",
+            };
+            crate::comandos::configuracoes_java::executavel_java_em(std::path::Path::new(caminho))
+                .map(|exe| exe.to_string_lossy().to_string())
+                .ok_or_else(|| {
+                    format!(
+                        "Java configurado {} não foi encontrado em {}.",
+                        onde, caminho
+                    )
+                })?
+        }
+        _ => {
+            let java_major_manifesto = details.java_version.as_ref().map(|java| java.major_version);
+            crate::comandos::configuracoes_java::ensure_java_for_manifest(
+                details.id.clone(),
+                java_major_manifesto,
+            )
+            .await
+            .map_err(|erro| format!("Não foi possível preparar o Java da instância: {}", erro))?
+        }
+    };
+
+    // 5. Montar Argumentos
+    let mut args = configuracao.argumentos_memoria();
+    args.extend(configuracao.java_args.valor.iter().cloned());
 
     // 5.1 Montar Classpath a partir das bibliotecas do manifesto
     let mut cp = Vec::new();
 
     for lib in &details.libraries {
-        let mut allowed = true;
-        if let Some(rules) = &lib.rules {
-            for rule in rules {
-                if rule.action == "allow" {
-                    if let Some(os) = &rule.os {
-                        if os.name != "windows" {
-                            allowed = false;
-                        }
-                    }
-                } else if rule.action == "disallow" {
-                    if let Some(os) = &rule.os {
-                        if os.name == "windows" {
-                            allowed = false;
-                        }
-                    }
-                }
-            }
-        }
-
-        if allowed {
+        if plataforma.biblioteca_permitida(lib) {
             if let Some(downloads) = &lib.downloads {
                 if let Some(artifact) = &downloads.artifact {
                     if let Some(path) = &artifact.path {
-                        let full_path = libraries_path.join(path);
+                        let full_path = super::armazenamento_compartilhado::resolver_biblioteca(
+                            &instance_path,
+                            path,
+                        );
                         if full_path.exists() {
                             let path_str = full_path.to_string_lossy().to_string();
                             if !cp.contains(&path_str) {
@@ -267,81 +449,76 @@ async fn launch_instance_com_opcoes(
     }
 
     cp.push(jar_path.to_string_lossy().to_string());
-    let cp_val = cp.join(";");
-
-    // 5.2 Adicionar JVM args do manifesto (Fabric/Forge/NeoForge), com placeholders resolvidos
-    for arg in super::instancias_criacao::coletar_argumentos_jvm_manifesto(
-        &details,
-        &natives_path,
-        &libraries_path,
-        &cp_val,
-    ) {
-        args.push(arg);
-    }
+    let cp_val = cp.join(crate::plataforma::SEPARADOR_CLASSPATH);
+
+    // 5.2 Resolver argumentos do manifesto (rules com SO e features, placeholders)
+    let servidor_quick_play = quick_play_servidor
+        .as_deref()
+        .map(str::trim)
+        .filter(|servidor| !servidor.is_empty());
+    let recursos = super::argumentos_lancamento::RecursosLancamento {
+        resolucao_personalizada: true,
+        quick_play_multiplayer: servidor_quick_play.is_some(),
+        ..Default::default()
+    };
+    let placeholders = super::argumentos_lancamento::placeholders_lancamento(
+        &super::argumentos_lancamento::DadosLancamento {
+            conta: &account,
+            details: &details,
+            pasta_jogo: &instance_path,
+            pasta_assets: &assets_path,
+            pasta_bibliotecas: &libraries_path,
+            pasta_natives: &natives_path,
+            classpath: &cp_val,
+            largura: configuracao.largura.valor,
+            altura: configuracao.altura.valor,
+            servidor_quick_play,
+        },
+    );
+
+    // JVM args do manifesto (vanilla + Fabric/Forge/NeoForge)
+    let jvm_brutos: Vec<String> =
+        super::argumentos_lancamento::argumentos_jvm_brutos(&details, &plataforma, &recursos)
+            .iter()
+            .map(|arg| {
+                super::argumentos_lancamento::resolver_bibliotecas_no_argumento(arg, &instance_path)
+            })
+            .collect();
+    args.extend(
+        super::argumentos_lancamento::coletar_argumentos_jvm_manifesto(
+            &jvm_brutos,
+            &placeholders,
+            &cp_val,
+        ),
+    );
 
     args.push(format!(
         "-Djava.library.path={}",
         natives_path.to_string_lossy()
     ));
     args.push("-cp".to_string());
-    args.push(cp_val);
+    args.push(cp_val.clone());
     args.push(details.main_class.clone());
 
     // 5.3 Montar Game args (vanilla + loader)
-    let game_args_raw: Vec<String> = if let Some(modern_args) = &details.arguments {
-        if let Some(game) = modern_args.get("game") {
-            game.as_array()
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_default()
-        } else {
-            Vec::new()
-        }
-    } else if let Some(legacy) = &details.minecraft_arguments {
-        legacy.split_whitespace().map(|s| s.to_string()).collect()
-    } else {
-        Vec::new()
-    };
-
-    // Resolução da janela
-    let width = instance.width.unwrap_or(settings.width);
-    let height = instance.height.unwrap_or(settings.height);
-
-    for arg in &game_args_raw {
-        let replaced = arg
-            .replace("${auth_player_name}", &account.name)
-            .replace("${version_name}", &details.id)
-            .replace("${game_directory}", &instance_path.to_string_lossy())
-            .replace("${assets_root}", &assets_path.to_string_lossy())
-            .replace("${assets_index_name}", &details.asset_index.id)
-            .replace("${auth_uuid}", &account.uuid)
-            .replace("${auth_access_token}", &account.access_token)
-            .replace("${clientid}", "")
-            .replace("${client_id}", "")
-            .replace("${auth_xuid}", "")
-            .replace("${user_properties}", "{}")
-            .replace("${user_type}", "msa")
-            .replace("${version_type}", "release")
-            .replace("${resolution_width}", &width.to_string())
-            .replace("${resolution_height}", &height.to_string());
-
-        args.push(replaced);
+    let game_brutos =
+        super::argumentos_lancamento::argumentos_jogo_brutos(&details, &plataforma, &recursos);
+    let suporta_quick_play = game_brutos
+        .iter()
+        .any(|arg| arg.contains("${quickPlayMultiplayer}"));
+    args.extend(game_brutos.iter().map(|arg| placeholders.substituir(arg)));
+
+    // Versões anteriores ao Quick Play (23w14a) entram direto no servidor via --server/--port.
+    if let Some(servidor) = servidor_quick_play.filter(|_| !suporta_quick_play) {
+        let (host, porta) = match servidor.rsplit_once(':') {
+            Some((host, porta)) if porta.parse::<u16>().is_ok() => (host, porta),
+            _ => (servidor, "25565"),
+        };
+        args.extend(["--server", host, "--port", porta].map(String::from));
     }
 
-    if let Some(endereco_servidor) = quick_play_servidor {
-        let endereco_servidor = endereco_servidor.trim();
-        if !endereco_servidor.is_empty()
-            && !args
-                .iter()
-                .any(|a| a.eq_ignore_ascii_case("--quickPlayMultiplayer"))
-        {
-            args.push("--quickPlayMultiplayer".to_string());
-            args.push(endereco_servidor.to_string());
-        }
-    }
+    // Argumentos extras do jogo definidos na instância
+    args.extend(configuracao.game_args.valor.iter().cloned());
 
     println!("[Launch] Executando: {} {:?}", java_exe, args);
 
@@ -360,57 +537,40 @@ async fn launch_instance_com_opcoes(
 
     let mut comando_java = std::process::Command::new(&java_exe);
     comando_java.args(&args).current_dir(&instance_path);
-    let mut pid_iniciado: Option<u32> = None;
-
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-
-        // Garante que o Minecraft permaneça aberto mesmo após fechar o launcher.
-        const DETACHED_PROCESS: u32 = 0x00000008;
-        const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
-        comando_java.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
-
-        let resultado_spawn = match comando_java.spawn() {
-            Ok(child) => {
-                pid_iniciado = Some(child.id());
-                Ok(())
-            }
-            Err(erro) if erro.raw_os_error() == Some(5) => {
-                eprintln!(
-                    "[Launch] Aviso: criação destacada bloqueada (acesso negado). Tentando fallback padrão."
-                );
 
-                let mut comando_fallback = std::process::Command::new(&java_exe);
-                comando_fallback.args(&args).current_dir(&instance_path);
-                let child = comando_fallback.spawn().map_err(|e| {
-                    format!(
-                        "Falha ao iniciar Java ({}): {}. Verifique suas configurações de Java.",
-                        java_exe, e
-                    )
-                })?;
-                pid_iniciado = Some(child.id());
-                Ok(())
-            }
-            Err(erro) => Err(format!(
-                "Falha ao iniciar Java ({}): {}. Verifique suas configurações de Java.",
-                java_exe, erro
-            )),
-        };
-
-        resultado_spawn?;
-    }
-
-    #[cfg(not(windows))]
-    {
-        let child = comando_java.spawn().map_err(|e| {
-            format!(
-                "Falha ao iniciar Java ({}): {}. Verifique suas configurações de Java.",
-                java_exe, e
-            )
-        })?;
-        pid_iniciado = Some(child.id());
-    }
+    // Modo gerenciado: o launcher captura stdout/stderr do jogo.
+    let modo_gerenciado = crate::comandos::configuracoes_java::carregar_settings_salvas()
+        .map(|settings| settings.capturar_saida_jogo)
+        .unwrap_or(false);
+    let instantaneo_crash =
+        crate::comandos::analise_crash::InstantaneoCrash::capturar(&instance_path);
+    // Um kill anterior que não encontrou o processo não vale para esta execução.
+    state.consumir_encerramento_pedido(&id);
+    let (processo, leitores) = if modo_gerenciado {
+        iniciar_processo_gerenciado(
+            app.clone(),
+            state,
+            &id,
+            &instance_path,
+            comando_java,
+            &java_exe,
+        )?
+    } else {
+        (
+            iniciar_processo_destacado(comando_java, &java_exe, &args, &instance_path)?,
+            Vec::new(),
+        )
+    };
+    let pid_iniciado = Some(processo.id());
+    acompanhar_fim_processo(
+        app,
+        state,
+        &id,
+        &instance_path,
+        processo,
+        leitores,
+        instantaneo_crash,
+    );
 
     if let Some(pid) = pid_iniciado {
         state.registrar_processo_instancia(&id, pid);
@@ -422,17 +582,19 @@ async fn launch_instance_com_opcoes(
 
 #[tauri::command]
 pub(crate) async fn launch_instance(
+    app: tauri::AppHandle,
     state: State<'_, LauncherState>,
     id: String,
 ) -> Result<(), String> {
-    launch_instance_com_opcoes(&state, id, None).await
+    launch_instance_com_opcoes(Some(app), &state, id, None).await
 }
 
 #[tauri::command]
 pub(crate) async fn launch_instance_to_server(
+    app: tauri::AppHandle,
     state: State<'_, LauncherState>,
     id: String,
     address: String,
 ) -> Result<(), String> {
-    launch_instance_com_opcoes(&state, id, Some(address)).await
+    launch_instance_com_opcoes(Some(app), &state, id, Some(address)).await
 }
@@ -1,6 +1,11 @@
+use super::registro_conteudo::{
+    atualizar_habilitado, identificacao_modrinth_por_url, registrar_instalacao, remover_conteudo,
+    EntradaConteudo, HashesConteudo, OrigemInstalacao,
+};
+use super::verificacao_arquivos::{escrever_atomico, sha1_bytes};
 use super::*;
 
-fn normalizar_loader_para_mods(loader: Option<&str>) -> Option<String> {
+pub(super) fn normalizar_loader_para_mods(loader: Option<&str>) -> Option<String> {
     let loader = loader?.trim().to_lowercase();
     match loader.as_str() {
         "vanilla" => None,
@@ -9,7 +14,7 @@ fn normalizar_loader_para_mods(loader: Option<&str>) -> Option<String> {
     }
 }
 
-fn lista_str_de_json(valor: &serde_json::Value) -> Vec<String> {
+pub(super) fn lista_str_de_json(valor: &serde_json::Value) -> Vec<String> {
     valor
         .as_array()
         .map(|arr| {
@@ -20,6 +25,15 @@ fn lista_str_de_json(valor: &serde_json::Value) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// Loaders cujos mods rodam na instância, em ordem de preferência. O Quilt
+/// carrega mods do Fabric, então eles servem como alternativa.
+pub(super) fn loaders_compativeis(loader: &str) -> Vec<String> {
+    match loader {
+        "quilt" => vec!["quilt".to_string(), "fabric".to_string()],
+        outro => vec![outro.to_string()],
+    }
+}
+
 fn loader_compativel(tags: &[String], loader_instancia: &Option<String>) -> bool {
     let loader_instancia = match loader_instancia {
         Some(loader) => loader,
@@ -30,8 +44,9 @@ fn loader_compativel(tags: &[String], loader_instancia: &Option<String>) -> bool
         return true;
     }
 
+    let aceitos = loaders_compativeis(loader_instancia);
     tags.iter()
-        .any(|t| t.eq_ignore_ascii_case(loader_instancia))
+        .any(|t| aceitos.iter().any(|aceito| t.eq_ignore_ascii_case(aceito)))
 }
 
 fn pontuar_tag_minecraft_curseforge(tag: &str, versao_instancia: &str) -> Option<i32> {
@@ -119,6 +134,8 @@ fn pontuar_arquivo_curseforge(
                 .any(|tag| tag.eq_ignore_ascii_case(loader))
             {
                 score += 200;
+            } else if loader_compativel(&tags_loader, loader_instancia) {
+                score += 150;
             } else {
                 return None;
             }
@@ -129,7 +146,7 @@ fn pontuar_arquivo_curseforge(
     Some(score.saturating_mul(100_000) + file_id.max(0))
 }
 
-fn selecionar_arquivo_curseforge_compativel<'a>(
+pub(super) fn selecionar_arquivo_curseforge_compativel<'a>(
     arquivos: &'a [serde_json::Value],
     tipo_conteudo: &str,
     versao_instancia: &str,
@@ -145,7 +162,7 @@ fn selecionar_arquivo_curseforge_compativel<'a>(
         .map(|(_, arquivo)| arquivo)
 }
 
-fn versao_modrinth_compativel(
+pub(super) fn versao_modrinth_compativel(
     versao: &serde_json::Value,
     versao_instancia: &str,
     loader_instancia: &Option<String>,
@@ -161,32 +178,26 @@ fn versao_modrinth_compativel(
     versao_exata && loader_compativel(&loaders, loader_instancia)
 }
 
-fn url_arquivo_modrinth(versao: &serde_json::Value) -> Option<String> {
+/// Escolhe o arquivo da versão: o jar primário, depois qualquer jar e, por
+/// fim, o primeiro arquivo listado.
+pub(super) fn arquivo_principal_modrinth(versao: &serde_json::Value) -> Option<&serde_json::Value> {
     let arquivos = versao["files"].as_array()?;
-    if arquivos.is_empty() {
-        return None;
-    }
-
-    if let Some(arquivo_primario_jar) = arquivos.iter().find(|f| {
-        f["primary"].as_bool().unwrap_or(false)
-            && f["filename"]
-                .as_str()
-                .is_some_and(|nome| nome.to_lowercase().ends_with(".jar"))
-    }) {
-        return arquivo_primario_jar["url"].as_str().map(|s| s.to_string());
-    }
-
-    if let Some(primeiro_jar) = arquivos.iter().find(|f| {
+    let eh_jar = |f: &&serde_json::Value| {
         f["filename"]
             .as_str()
             .is_some_and(|nome| nome.to_lowercase().ends_with(".jar"))
-    }) {
-        return primeiro_jar["url"].as_str().map(|s| s.to_string());
-    }
+    };
 
     arquivos
-        .first()
-        .and_then(|f| f["url"].as_str().map(|s| s.to_string()))
+        .iter()
+        .filter(eh_jar)
+        .find(|f| f["primary"].as_bool().unwrap_or(false))
+        .or_else(|| arquivos.iter().find(eh_jar))
+        .or_else(|| arquivos.first())
+}
+
+fn url_arquivo_modrinth(versao: &serde_json::Value) -> Option<String> {
+    arquivo_principal_modrinth(versao).and_then(|f| f["url"].as_str().map(|s| s.to_string()))
 }
 
 #[tauri::command]
@@ -209,8 +220,8 @@ pub(crate) async fn install_mod(
     let versao_instancia = instance.version.clone();
 
     // Para CurseForge, precisamos buscar a URL de download primeiro
-    let download_url = if !mod_info.download_url.trim().is_empty() {
-        mod_info.download_url.clone()
+    let (download_url, versao_id) = if !mod_info.download_url.trim().is_empty() {
+        (mod_info.download_url.clone(), None)
     } else if mod_info.platform == ModPlatform::CurseForge {
         // Buscar informações detalhadas do mod
         let mod_details_url = format!("{}/mods/{}", CURSEFORGE_API_BASE, mod_info.id);
@@ -253,10 +264,14 @@ pub(crate) async fn install_mod(
                 )
             })?;
 
-            arquivo_escolhido["downloadUrl"]
+            let url = arquivo_escolhido["downloadUrl"]
                 .as_str()
                 .ok_or("Arquivo CurseForge compatível sem downloadUrl".to_string())?
-                .to_string()
+                .to_string();
+            (
+                url,
+                arquivo_escolhido["id"].as_u64().map(|id| id.to_string()),
+            )
         } else {
             return Err("Campo 'latestFiles' não encontrado na resposta do CurseForge".to_string());
         }
@@ -270,7 +285,8 @@ pub(crate) async fn install_mod(
         );
         if let Some(loader) = &loader_instancia {
             let loaders_param =
-                urlencoding::encode(&serde_json::json!([loader]).to_string()).to_string();
+                urlencoding::encode(&serde_json::json!(loaders_compativeis(loader)).to_string())
+                    .to_string();
             version_url.push_str(&format!("&loaders={}", loaders_param));
         }
 
@@ -299,8 +315,12 @@ pub(crate) async fn install_mod(
                     )
                 })?;
 
-            url_arquivo_modrinth(versao_compativel)
-                .ok_or("Nenhum arquivo válido encontrado na versão compatível".to_string())?
+            let url = url_arquivo_modrinth(versao_compativel)
+                .ok_or("Nenhum arquivo válido encontrado na versão compatível".to_string())?;
+            (
+                url,
+                versao_compativel["id"].as_str().map(|id| id.to_string()),
+            )
         } else {
             return Err("Resposta da API Modrinth não é um array de versões".to_string());
         }
@@ -324,34 +344,74 @@ pub(crate) async fn install_mod(
     };
 
     let file_path = mods_dir.join(file_name);
-    std::fs::write(&file_path, bytes).map_err(|e| e.to_string())?;
+    std::fs::write(&file_path, &bytes).map_err(|e| e.to_string())?;
+
+    let entrada = EntradaConteudo::nova(
+        &file_path.file_name().unwrap_or_default().to_string_lossy(),
+        "mod",
+        HashesConteudo::de_bytes(&bytes),
+        OrigemInstalacao::Usuario,
+    )
+    .com_projeto(
+        mod_info.platform.clone(),
+        Some(mod_info.id.clone()),
+        versao_id,
+    )
+    .com_url_download(&download_url);
+    registrar_instalacao(&instance.path, Ok(entrada));
 
     Ok(())
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn install_project_file(
     instance_id: String,
     project_type: String,
     download_url: String,
     file_name: String,
+    platform: Option<ModPlatform>,
+    project_id: Option<String>,
+    version_id: Option<String>,
     state: State<'_, LauncherState>,
 ) -> Result<(), String> {
     let instance = obter_instancia_por_id(&state, &instance_id)?;
     let tipo_normalizado = project_type.trim().to_lowercase();
     let pasta_destino = pasta_destino_conteudo(&instance, &tipo_normalizado)?;
 
-    baixar_arquivo_para_pasta(
+    // Sem identificação explícita, o CDN do Modrinth ainda revela projeto e versão.
+    let (platform, project_id, version_id) = match (platform, project_id) {
+        (Some(platform), project_id) => (Some(platform), project_id, version_id),
+        (None, _) => match identificacao_modrinth_por_url(&download_url) {
+            Some((projeto, versao)) => (Some(ModPlatform::Modrinth), Some(projeto), Some(versao)),
+            None => (None, None, None),
+        },
+    };
+
+    let caminho = baixar_arquivo_para_pasta(
         &pasta_destino,
         &tipo_normalizado,
-        download_url,
+        download_url.clone(),
         file_name,
         "projeto",
+        None,
     )
-    .await
+    .await?;
+
+    let entrada =
+        EntradaConteudo::de_arquivo(&caminho, &tipo_normalizado, OrigemInstalacao::Usuario).map(
+            |entrada| match platform {
+                Some(platform) => entrada
+                    .com_projeto(platform, project_id, version_id)
+                    .com_url_download(&download_url),
+                None => entrada,
+            },
+        );
+    registrar_instalacao(&instance.path, entrada);
+    Ok(())
 }
 
-fn pasta_destino_conteudo(
+pub(super) fn pasta_destino_conteudo(
     instance: &Instance,
     tipo_normalizado: &str,
 ) -> Result<std::path::PathBuf, String> {
@@ -366,13 +426,14 @@ fn pasta_destino_conteudo(
     }
 }
 
-async fn baixar_arquivo_para_pasta(
+pub(super) async fn baixar_arquivo_para_pasta(
     pasta_destino: &std::path::Path,
     tipo_normalizado: &str,
     download_url: String,
     file_name: String,
     prefixo_fallback: &str,
-) -> Result<(), String> {
+    sha1_esperado: Option<&str>,
+) -> Result<std::path::PathBuf, String> {
     std::fs::create_dir_all(pasta_destino).map_err(|e| e.to_string())?;
 
     let client = reqwest::Client::builder()
@@ -398,6 +459,16 @@ async fn baixar_arquivo_para_pasta(
         .await
         .map_err(|e| format!("Erro ao ler bytes do projeto: {}", e))?;
 
+    if let Some(sha1) = sha1_esperado {
+        let obtido = sha1_bytes(&bytes);
+        if !obtido.eq_ignore_ascii_case(sha1) {
+            return Err(format!(
+                "SHA-1 divergente no download do projeto ({}, esperado {})",
+                obtido, sha1
+            ));
+        }
+    }
+
     let extensao_padrao = if tipo_normalizado == "mod" {
         ".jar"
     } else {
@@ -437,9 +508,9 @@ async fn baixar_arquivo_para_pasta(
         });
 
     let caminho_arquivo = pasta_destino.join(nome_arquivo_final);
-    std::fs::write(caminho_arquivo, bytes).map_err(|e| e.to_string())?;
+    escrever_atomico(&caminho_arquivo, &bytes)?;
 
-    Ok(())
+    Ok(caminho_arquivo)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -660,15 +731,28 @@ pub(crate) async fn install_curseforge_project_file(
         .as_str()
         .unwrap_or("")
         .to_string();
+    let file_id = arquivo_escolhido["id"].as_u64().map(|id| id.to_string());
 
-    baixar_arquivo_para_pasta(
+    let caminho = baixar_arquivo_para_pasta(
         &pasta_destino,
         &tipo_normalizado,
-        download_url,
+        download_url.clone(),
         nome_arquivo,
         "curseforge",
+        None,
     )
-    .await
+    .await?;
+
+    let entrada =
+        EntradaConteudo::de_arquivo(&caminho, &tipo_normalizado, OrigemInstalacao::Usuario).map(
+            |entrada| {
+                entrada
+                    .com_projeto(ModPlatform::CurseForge, Some(project_id), file_id)
+                    .com_url_download(&download_url)
+            },
+        );
+    registrar_instalacao(&instance.path, entrada);
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -819,7 +903,10 @@ pub(crate) async fn resolver_modpack_curseforge(
         .filter(|versao| !versao.trim().is_empty())
         .ok_or("Não foi possível detectar a versão do Minecraft do modpack CurseForge.")?;
 
-    if !matches!(loader_type.as_str(), "forge" | "fabric" | "neoforge") {
+    if !matches!(
+        loader_type.as_str(),
+        "forge" | "fabric" | "neoforge" | "quilt"
+    ) {
         loader_type = "vanilla".to_string();
     }
 
@@ -944,8 +1031,9 @@ pub(crate) fn remove_mod(
         .find(|i: &&Instance| i.id == instance_id)
         .ok_or("Instância não encontrada")?;
 
-    let mod_path = instance.path.join("mods").join(mod_file);
+    let mod_path = instance.path.join("mods").join(&mod_file);
     std::fs::remove_file(mod_path).map_err(|e| e.to_string())?;
+    remover_conteudo(&instance.path, "mod", &mod_file)?;
 
     Ok(())
 }
@@ -972,18 +1060,16 @@ pub(crate) fn remove_project_file(
     }
 
     let caminho_alvo = pasta_destino.join(&nome_seguro);
-    if !caminho_alvo.exists() {
-        return Ok(());
-    }
-
-    let caminho_validado = validar_caminho_dentro_raiz(&pasta_destino, &caminho_alvo)?;
-    if caminho_validado.is_dir() {
-        std::fs::remove_dir_all(caminho_validado).map_err(|e| e.to_string())?;
-    } else {
-        std::fs::remove_file(caminho_validado).map_err(|e| e.to_string())?;
+    if caminho_alvo.exists() {
+        let caminho_validado = validar_caminho_dentro_raiz(&pasta_destino, &caminho_alvo)?;
+        if caminho_validado.is_dir() {
+            std::fs::remove_dir_all(caminho_validado).map_err(|e| e.to_string())?;
+        } else {
+            std::fs::remove_file(caminho_validado).map_err(|e| e.to_string())?;
+        }
     }
 
-    Ok(())
+    remover_conteudo(&instance.path, &tipo_normalizado, &nome_seguro)
 }
 
 #[tauri::command]
@@ -1034,6 +1120,7 @@ pub(crate) fn toggle_project_file_enabled(
     let caminho_destino = pasta_destino.join(&destino_nome);
 
     if caminho_destino.exists() && !caminho_origem.exists() {
+        atualizar_habilitado(&instance.path, &tipo_normalizado, &destino_nome, enabled)?;
         return Ok(destino_nome);
     }
     if !caminho_origem.exists() {
@@ -1051,6 +1138,7 @@ pub(crate) fn toggle_project_file_enabled(
 
     std::fs::rename(origem_validada, &caminho_destino)
         .map_err(|e| format!("Falha ao alternar estado do arquivo: {}", e))?;
+    atualizar_habilitado(&instance.path, &tipo_normalizado, &destino_nome, enabled)?;
 
     Ok(destino_nome)
 }
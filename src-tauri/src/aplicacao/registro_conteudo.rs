@@ -0,0 +1,502 @@
+use super::verificacao_arquivos::escrever_atomico;
+use super::*;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use std::path::Path;
+
+/// Lockfile por instância com a origem de cada arquivo de conteúdo instalado.
+pub(crate) const ARQUIVO_LOCKFILE_CONTEUDO: &str = "conteudo.lock.json";
+const VERSAO_LOCKFILE_CONTEUDO: u32 = 1;
+pub(crate) const SUFIXO_LOCKFILE_CORROMPIDO: &str = ".corrompido";
+
+/// Serializa leitura-modificação-escrita do lockfile entre comandos concorrentes.
+static TRAVA_LOCKFILE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OrigemInstalacao {
+    #[default]
+    Usuario,
+    Modpack,
+    Dependencia,
+    /// Arquivo que já estava na pasta e foi só identificado por hash; pode ter
+    /// vindo de um modpack ou ter sido copiado à mão.
+    Desconhecida,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct HashesConteudo {
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+}
+
+impl HashesConteudo {
+    pub(crate) fn de_bytes(bytes: &[u8]) -> Self {
+        Self {
+            sha1: Some(format!("{:x}", Sha1::digest(bytes))),
+            sha512: Some(format!("{:x}", Sha512::digest(bytes))),
+        }
+    }
+
+    pub(crate) fn de_arquivo(caminho: &Path) -> std::io::Result<Self> {
+        let mut arquivo = std::fs::File::open(caminho)?;
+        let mut sha1 = Sha1::new();
+        let mut sha512 = Sha512::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let lidos = std::io::Read::read(&mut arquivo, &mut buffer)?;
+            if lidos == 0 {
+                break;
+            }
+            sha1.update(&buffer[..lidos]);
+            sha512.update(&buffer[..lidos]);
+        }
+        Ok(Self {
+            sha1: Some(format!("{:x}", sha1.finalize())),
+            sha512: Some(format!("{:x}", sha512.finalize())),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EntradaConteudo {
+    /// Nome do arquivo sem o sufixo `.disabled`.
+    pub arquivo: String,
+    pub tipo_conteudo: String,
+    #[serde(default)]
+    pub plataforma: Option<ModPlatform>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Id da versão no Modrinth ou do arquivo no CurseForge.
+    #[serde(default)]
+    pub versao_id: Option<String>,
+    #[serde(default)]
+    pub hashes: HashesConteudo,
+    #[serde(default)]
+    pub origem: OrigemInstalacao,
+    #[serde(default)]
+    pub fixado: bool,
+    #[serde(default = "habilitado_padrao")]
+    pub habilitado: bool,
+    #[serde(default)]
+    pub instalado_em: String,
+    /// Nome e ícone do projeto, quando conhecidos pela plataforma.
+    #[serde(default)]
+    pub nome: Option<String>,
+    #[serde(default)]
+    pub icone_url: Option<String>,
+    /// URL de onde o arquivo foi baixado, usada ao exportar modpacks.
+    #[serde(default)]
+    pub url_download: Option<String>,
+}
+
+fn habilitado_padrao() -> bool {
+    true
+}
+
+impl EntradaConteudo {
+    pub(crate) fn nova(
+        nome_arquivo: &str,
+        tipo_conteudo: &str,
+        hashes: HashesConteudo,
+        origem: OrigemInstalacao,
+    ) -> Self {
+        Self {
+            arquivo: nome_habilitado(nome_arquivo).to_string(),
+            tipo_conteudo: tipo_conteudo.to_string(),
+            plataforma: None,
+            project_id: None,
+            versao_id: None,
+            hashes,
+            origem,
+            fixado: false,
+            habilitado: !nome_arquivo.ends_with(".disabled"),
+            instalado_em: chrono::Utc::now().to_rfc3339(),
+            nome: None,
+            icone_url: None,
+            url_download: None,
+        }
+    }
+
+    /// Monta a entrada a partir de um arquivo já gravado, calculando os hashes.
+    pub(crate) fn de_arquivo(
+        caminho: &Path,
+        tipo_conteudo: &str,
+        origem: OrigemInstalacao,
+    ) -> Result<Self, String> {
+        let nome = caminho
+            .file_name()
+            .map(|nome| nome.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Caminho inválido: {}", caminho.display()))?;
+        let hashes = HashesConteudo::de_arquivo(caminho)
+            .map_err(|e| format!("Erro ao calcular hash de {}: {}", nome, e))?;
+        Ok(Self::nova(&nome, tipo_conteudo, hashes, origem))
+    }
+
+    pub(crate) fn com_projeto(
+        mut self,
+        plataforma: ModPlatform,
+        project_id: Option<String>,
+        versao_id: Option<String>,
+    ) -> Self {
+        self.plataforma = Some(plataforma);
+        self.project_id = project_id.filter(|id| !id.trim().is_empty());
+        self.versao_id = versao_id.filter(|id| !id.trim().is_empty());
+        self
+    }
+
+    pub(crate) fn com_url_download(mut self, url: &str) -> Self {
+        self.url_download = Some(url.trim().to_string()).filter(|url| !url.is_empty());
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct LockfileConteudo {
+    pub versao: u32,
+    pub entradas: Vec<EntradaConteudo>,
+}
+
+impl Default for LockfileConteudo {
+    fn default() -> Self {
+        Self {
+            versao: VERSAO_LOCKFILE_CONTEUDO,
+            entradas: Vec::new(),
+        }
+    }
+}
+
+impl LockfileConteudo {
+    fn posicao(&self, tipo_conteudo: &str, arquivo: &str) -> Option<usize> {
+        let arquivo = nome_habilitado(arquivo);
+        self.entradas
+            .iter()
+            .position(|e| e.tipo_conteudo == tipo_conteudo && e.arquivo == arquivo)
+    }
+
+    /// Insere ou substitui a entrada do mesmo arquivo. A fixação é mantida e
+    /// uma instalação explícita não é rebaixada para dependência.
+    pub(crate) fn registrar(&mut self, mut entrada: EntradaConteudo) {
+        match self.posicao(&entrada.tipo_conteudo, &entrada.arquivo) {
+            Some(indice) => {
+                let anterior = &self.entradas[indice];
+                entrada.fixado |= anterior.fixado;
+                if entrada.nome.is_none() && entrada.project_id == anterior.project_id {
+                    entrada.nome = anterior.nome.clone();
+                    entrada.icone_url = anterior.icone_url.clone();
+                }
+                if anterior.origem == OrigemInstalacao::Usuario
+                    && entrada.origem == OrigemInstalacao::Dependencia
+                {
+                    entrada.origem = OrigemInstalacao::Usuario;
+                }
+                self.entradas[indice] = entrada;
+            }
+            None => self.entradas.push(entrada),
+        }
+    }
+
+    pub(crate) fn remover(&mut self, tipo_conteudo: &str, arquivo: &str) -> bool {
+        match self.posicao(tipo_conteudo, arquivo) {
+            Some(indice) => {
+                self.entradas.remove(indice);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn entrada_mut(
+        &mut self,
+        tipo_conteudo: &str,
+        arquivo: &str,
+    ) -> Option<&mut EntradaConteudo> {
+        self.posicao(tipo_conteudo, arquivo)
+            .map(|indice| &mut self.entradas[indice])
+    }
+}
+
+pub(crate) fn nome_habilitado(arquivo: &str) -> &str {
+    arquivo.strip_suffix(".disabled").unwrap_or(arquivo)
+}
+
+/// Tipo de conteúdo rastreado a partir do caminho relativo à instância.
+pub(crate) fn tipo_conteudo_por_caminho(caminho_relativo: &str) -> Option<&'static str> {
+    let caminho = caminho_relativo.replace('\\', "/");
+    if caminho.starts_with("mods/") {
+        Some("mod")
+    } else if caminho.starts_with("resourcepacks/") {
+        Some("resourcepack")
+    } else if caminho.starts_with("shaderpacks/") {
+        Some("shader")
+    } else {
+        None
+    }
+}
+
+/// SHA-1 declarado por um arquivo da API do CurseForge (`algo` 1).
+pub(crate) fn sha1_arquivo_curseforge(arquivo: &serde_json::Value) -> Option<String> {
+    arquivo["hashes"].as_array()?.iter().find_map(|hash| {
+        (hash["algo"].as_u64() == Some(1))
+            .then(|| hash["value"].as_str().map(|s| s.to_lowercase()))
+            .flatten()
+    })
+}
+
+/// Extrai projeto e versão de uma URL do CDN do Modrinth
+/// (`cdn.modrinth.com/data/<projeto>/versions/<versão>/<arquivo>`).
+pub(crate) fn identificacao_modrinth_por_url(url: &str) -> Option<(String, String)> {
+    let caminho = url.split_once("cdn.modrinth.com/data/")?.1;
+    let mut partes = caminho.split('/');
+    let project_id = partes.next().filter(|p| !p.is_empty())?;
+    if partes.next()? != "versions" {
+        return None;
+    }
+    let versao_id = partes.next().filter(|p| !p.is_empty())?;
+    Some((project_id.to_string(), versao_id.to_string()))
+}
+
+pub(crate) fn carregar_lockfile(instance_path: &Path) -> LockfileConteudo {
+    let caminho = instance_path.join(ARQUIVO_LOCKFILE_CONTEUDO);
+    let Ok(conteudo) = std::fs::read_to_string(&caminho) else {
+        return LockfileConteudo::default();
+    };
+    serde_json::from_str(&conteudo).unwrap_or_else(|e| {
+        eprintln!(
+            "[Lockfile] {} inválido, ignorando: {}",
+            caminho.display(),
+            e
+        );
+        LockfileConteudo::default()
+    })
+}
+
+/// Aplica uma alteração ao lockfile da instância e o grava de forma atômica.
+pub(crate) fn alterar_lockfile<T>(
+    instance_path: &Path,
+    alterar: impl FnOnce(&mut LockfileConteudo) -> T,
+) -> Result<T, String> {
+    let _trava = TRAVA_LOCKFILE
+        .lock()
+        .map_err(|_| "Lockfile de conteúdo ocupado.".to_string())?;
+    let caminho = instance_path.join(ARQUIVO_LOCKFILE_CONTEUDO);
+    let mut lockfile = match std::fs::read_to_string(&caminho) {
+        Ok(conteudo) => match serde_json::from_str(&conteudo) {
+            Ok(lockfile) => lockfile,
+            Err(e) => {
+                guardar_lockfile_corrompido(&caminho, &e)?;
+                LockfileConteudo::default()
+            }
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => LockfileConteudo::default(),
+        Err(e) => return Err(format!("Erro ao ler lockfile de conteúdo: {}", e)),
+    };
+    let resultado = alterar(&mut lockfile);
+    lockfile.versao = VERSAO_LOCKFILE_CONTEUDO;
+    let json = serde_json::to_string_pretty(&lockfile)
+        .map_err(|e| format!("Erro ao serializar lockfile de conteúdo: {}", e))?;
+    escrever_atomico(&caminho, json.as_bytes())?;
+    Ok(resultado)
+}
+
+/// Tira do caminho um lockfile que não pôde ser lido antes de gravar um novo,
+/// para que fixações, origens e URLs possam ser recuperadas à mão.
+fn guardar_lockfile_corrompido(caminho: &Path, erro: &serde_json::Error) -> Result<(), String> {
+    let destino = caminho.with_file_name(format!(
+        "{}{}",
+        ARQUIVO_LOCKFILE_CONTEUDO, SUFIXO_LOCKFILE_CORROMPIDO
+    ));
+    std::fs::rename(caminho, &destino).map_err(|e| {
+        format!(
+            "Lockfile de conteúdo inválido ({}) e não foi possível guardá-lo: {}",
+            erro, e
+        )
+    })?;
+    eprintln!(
+        "[Lockfile] {} inválido ({}); guardado em {} e recriado",
+        caminho.display(),
+        erro,
+        destino.display()
+    );
+    Ok(())
+}
+
+pub(crate) fn registrar_conteudo(
+    instance_path: &Path,
+    entradas: Vec<EntradaConteudo>,
+) -> Result<(), String> {
+    if entradas.is_empty() {
+        return Ok(());
+    }
+    alterar_lockfile(instance_path, |lockfile| {
+        for entrada in entradas {
+            lockfile.registrar(entrada);
+        }
+    })
+}
+
+/// Registra um arquivo recém-instalado; falhas só são logadas, porque o
+/// arquivo já está no lugar e o lockfile é reconstruível.
+pub(crate) fn registrar_instalacao(instance_path: &Path, entrada: Result<EntradaConteudo, String>) {
+    let resultado = entrada.and_then(|entrada| registrar_conteudo(instance_path, vec![entrada]));
+    if let Err(e) = resultado {
+        eprintln!("[Lockfile] Falha ao registrar conteúdo: {}", e);
+    }
+}
+
+pub(crate) fn remover_conteudo(
+    instance_path: &Path,
+    tipo_conteudo: &str,
+    arquivo: &str,
+) -> Result<(), String> {
+    alterar_lockfile(instance_path, |lockfile| {
+        lockfile.remover(tipo_conteudo, arquivo);
+    })
+}
+
+pub(crate) fn atualizar_habilitado(
+    instance_path: &Path,
+    tipo_conteudo: &str,
+    arquivo: &str,
+    habilitado: bool,
+) -> Result<(), String> {
+    alterar_lockfile(instance_path, |lockfile| {
+        if let Some(entrada) = lockfile.entrada_mut(tipo_conteudo, arquivo) {
+            entrada.habilitado = habilitado;
+        }
+    })
+}
+
+#[tauri::command]
+pub(crate) fn obter_lockfile_conteudo(
+    instance_id: String,
+    state: State<LauncherState>,
+) -> Result<LockfileConteudo, String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    Ok(carregar_lockfile(&instance_path))
+}
+
+/// Fixa ou libera um arquivo; itens fixados ficam fora das atualizações automáticas.
+#[tauri::command]
+pub(crate) fn definir_fixacao_conteudo(
+    instance_id: String,
+    project_type: String,
+    file_name: String,
+    fixado: bool,
+    state: State<LauncherState>,
+) -> Result<(), String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    let tipo_normalizado = project_type.trim().to_lowercase();
+    alterar_lockfile(&instance_path, |lockfile| {
+        match lockfile.entrada_mut(&tipo_normalizado, &file_name) {
+            Some(entrada) => {
+                entrada.fixado = fixado;
+                Ok(())
+            }
+            None => Err(format!(
+                "'{}' não está registrado no lockfile da instância.",
+                file_name
+            )),
+        }
+    })?
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn entrada(arquivo: &str, origem: OrigemInstalacao) -> EntradaConteudo {
+        EntradaConteudo::nova(arquivo, "mod", HashesConteudo::de_bytes(b"jar"), origem).com_projeto(
+            ModPlatform::Modrinth,
+            Some("AANobbMI".to_string()),
+            Some("v1".to_string()),
+        )
+    }
+
+    #[test]
+    fn registrar_mantem_fixacao_e_origem_explicita() {
+        let mut lockfile = LockfileConteudo::default();
+        let mut primeira = entrada("sodium.jar", OrigemInstalacao::Usuario);
+        primeira.fixado = true;
+        lockfile.registrar(primeira);
+
+        lockfile.registrar(entrada("sodium.jar", OrigemInstalacao::Dependencia));
+
+        assert_eq!(lockfile.entradas.len(), 1);
+        assert!(lockfile.entradas[0].fixado);
+        assert_eq!(lockfile.entradas[0].origem, OrigemInstalacao::Usuario);
+        let hashes = &lockfile.entradas[0].hashes;
+        assert_eq!(hashes.sha1.as_ref().map(String::len), Some(40));
+        assert_eq!(hashes.sha512.as_ref().map(String::len), Some(128));
+    }
+
+    #[test]
+    fn desativar_e_remover_usam_nome_sem_sufixo() {
+        let pasta = std::env::temp_dir().join(format!("dome_lockfile_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+
+        registrar_conteudo(
+            &pasta,
+            vec![entrada("create.jar.disabled", OrigemInstalacao::Modpack)],
+        )
+        .unwrap();
+        let lockfile = carregar_lockfile(&pasta);
+        assert_eq!(lockfile.entradas[0].arquivo, "create.jar");
+        assert!(!lockfile.entradas[0].habilitado);
+
+        atualizar_habilitado(&pasta, "mod", "create.jar.disabled", true).unwrap();
+        assert!(carregar_lockfile(&pasta).entradas[0].habilitado);
+
+        remover_conteudo(&pasta, "mod", "create.jar").unwrap();
+        assert!(carregar_lockfile(&pasta).entradas.is_empty());
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn lockfile_corrompido_e_guardado_antes_de_ser_recriado() {
+        let pasta = std::env::temp_dir().join(format!("dome_lockfile_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        std::fs::write(pasta.join(ARQUIVO_LOCKFILE_CONTEUDO), "{\"entradas\": [").unwrap();
+
+        registrar_conteudo(
+            &pasta,
+            vec![entrada("sodium.jar", OrigemInstalacao::Usuario)],
+        )
+        .unwrap();
+
+        let guardado = pasta.join(format!(
+            "{}{}",
+            ARQUIVO_LOCKFILE_CONTEUDO, SUFIXO_LOCKFILE_CORROMPIDO
+        ));
+        assert_eq!(
+            std::fs::read_to_string(guardado).unwrap(),
+            "{\"entradas\": ["
+        );
+        assert_eq!(carregar_lockfile(&pasta).entradas.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&pasta);
+    }
+
+    #[test]
+    fn identifica_projeto_pela_url_do_cdn_modrinth() {
+        assert_eq!(
+            identificacao_modrinth_por_url(
+                "https://cdn.modrinth.com/data/AANobbMI/versions/OihdIimA/sodium-fabric.jar"
+            ),
+            Some(("AANobbMI".to_string(), "OihdIimA".to_string()))
+        );
+        assert_eq!(
+            identificacao_modrinth_por_url("https://edge.forgecdn.net/files/1/2/mod.jar"),
+            None
+        );
+        assert_eq!(
+            tipo_conteudo_por_caminho("shaderpacks/bsl.zip"),
+            Some("shader")
+        );
+        assert_eq!(tipo_conteudo_por_caminho("config/mod.toml"), None);
+    }
+}
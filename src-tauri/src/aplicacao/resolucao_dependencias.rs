@@ -0,0 +1,1233 @@
+use super::mods_conteudo::{
+    arquivo_principal_modrinth, baixar_arquivo_para_pasta, loaders_compativeis,
+    normalizar_loader_para_mods, pasta_destino_conteudo, selecionar_arquivo_curseforge_compativel,
+    versao_modrinth_compativel,
+};
+use super::registro_conteudo::{
+    carregar_lockfile, nome_habilitado, registrar_conteudo, remover_conteudo,
+    sha1_arquivo_curseforge, EntradaConteudo, LockfileConteudo, OrigemInstalacao,
+};
+use super::verificacao_arquivos::sha1_arquivo;
+use super::*;
+use crate::comandos::tarefas::{Tarefa, MENSAGEM_TAREFA_CANCELADA};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Teto de projetos visitados, para que um grafo de dependências malformado
+/// não prenda a resolução.
+const LIMITE_PROJETOS_PLANO: usize = 200;
+
+/// Pastas cujo conteúdo conta como "já instalado" ao montar o plano.
+const PASTAS_CONTEUDO: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TipoDependencia {
+    Obrigatoria,
+    Incompativel,
+}
+
+/// Dependência declarada por uma versão (Modrinth) ou arquivo (CurseForge).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DependenciaDeclarada {
+    project_id: Option<String>,
+    versao_id: Option<String>,
+    tipo: TipoDependencia,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MotivoItemPlano {
+    Solicitado,
+    Dependencia,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemPlanoInstalacao {
+    pub plataforma: ModPlatform,
+    pub project_id: String,
+    pub nome: String,
+    pub versao_id: String,
+    pub versao_nome: String,
+    pub file_name: String,
+    pub download_url: String,
+    pub sha1: Option<String>,
+    pub tamanho: Option<u64>,
+    pub tipo_conteudo: String,
+    pub motivo: MotivoItemPlano,
+    pub requerido_por: Option<String>,
+    /// Arquivo de outra versão do mesmo projeto que este item substitui. Só para
+    /// exibição: ao executar o plano, o arquivo é relido do lockfile.
+    #[serde(default)]
+    pub substitui: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConteudoJaInstalado {
+    pub plataforma: ModPlatform,
+    pub project_id: String,
+    pub nome: String,
+    pub arquivo: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IncompatibilidadePlano {
+    pub plataforma: ModPlatform,
+    pub project_id: String,
+    pub nome: String,
+    pub declarado_por: String,
+    /// Arquivo instalado que conflita; `None` quando o conflito é com outro item do plano.
+    pub arquivo_instalado: Option<String>,
+}
+
+/// Tudo o que será baixado para instalar um projeto, para confirmação na interface.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlanoInstalacao {
+    pub instance_id: String,
+    pub versao_minecraft: String,
+    pub loader: Option<String>,
+    pub itens: Vec<ItemPlanoInstalacao>,
+    pub ja_instalados: Vec<ConteudoJaInstalado>,
+    pub incompatibilidades: Vec<IncompatibilidadePlano>,
+    pub nao_resolvidas: Vec<String>,
+    pub bytes_totais: u64,
+}
+
+/// Conteúdo presente na instância, indexado por hash e por projeto.
+#[derive(Debug, Default)]
+pub(crate) struct ConteudoExistente {
+    hashes: HashMap<String, String>,
+    projetos: HashMap<(&'static str, String), String>,
+}
+
+impl ConteudoExistente {
+    pub(crate) fn registrar_hash(&mut self, sha1: &str, arquivo: &str) {
+        self.hashes.insert(sha1.to_lowercase(), arquivo.to_string());
+    }
+
+    pub(crate) fn registrar_projeto(
+        &mut self,
+        plataforma: &ModPlatform,
+        project_id: &str,
+        arquivo: &str,
+    ) {
+        self.projetos.insert(
+            (chave_plataforma(plataforma), project_id.to_string()),
+            arquivo.to_string(),
+        );
+    }
+
+    fn arquivo_do_projeto(&self, plataforma: &ModPlatform, project_id: &str) -> Option<&String> {
+        self.projetos
+            .get(&(chave_plataforma(plataforma), project_id.to_string()))
+    }
+
+    fn arquivo_do_hash(&self, item: &ItemPlanoInstalacao) -> Option<&String> {
+        item.sha1
+            .as_deref()
+            .and_then(|sha1| self.hashes.get(&sha1.to_lowercase()))
+    }
+
+    fn arquivo_do_item(&self, item: &ItemPlanoInstalacao) -> Option<&String> {
+        self.arquivo_do_hash(item)
+            .or_else(|| self.arquivo_do_projeto(&item.plataforma, &item.project_id))
+    }
+}
+
+fn chave_plataforma(plataforma: &ModPlatform) -> &'static str {
+    match plataforma {
+        ModPlatform::CurseForge => "curseforge",
+        ModPlatform::Modrinth => "modrinth",
+        ModPlatform::Ftb => "ftb",
+    }
+}
+
+fn texto_id_json(valor: &serde_json::Value) -> Option<String> {
+    valor
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .or_else(|| valor.as_u64().map(|n| n.to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+/// Lê `dependencies` de uma versão Modrinth; opcionais e embutidas são ignoradas.
+fn dependencias_modrinth(versao: &serde_json::Value) -> Vec<DependenciaDeclarada> {
+    versao["dependencies"]
+        .as_array()
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| {
+                    let tipo = match dep["dependency_type"].as_str()? {
+                        "required" => TipoDependencia::Obrigatoria,
+                        "incompatible" => TipoDependencia::Incompativel,
+                        _ => return None,
+                    };
+                    let project_id = texto_id_json(&dep["project_id"]);
+                    let versao_id = texto_id_json(&dep["version_id"]);
+                    if project_id.is_none() && versao_id.is_none() {
+                        return None;
+                    }
+                    Some(DependenciaDeclarada {
+                        project_id,
+                        versao_id,
+                        tipo,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Lê `dependencies` de um arquivo CurseForge (`relationType` 3 = obrigatória,
+/// 5 = incompatível).
+fn dependencias_curseforge(arquivo: &serde_json::Value) -> Vec<DependenciaDeclarada> {
+    arquivo["dependencies"]
+        .as_array()
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| {
+                    let tipo = match dep["relationType"].as_u64()? {
+                        3 => TipoDependencia::Obrigatoria,
+                        5 => TipoDependencia::Incompativel,
+                        _ => return None,
+                    };
+                    Some(DependenciaDeclarada {
+                        project_id: Some(texto_id_json(&dep["modId"])?),
+                        versao_id: None,
+                        tipo,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn item_de_versao_modrinth(
+    versao: &serde_json::Value,
+    tipo_conteudo: &str,
+) -> Result<ItemPlanoInstalacao, String> {
+    let arquivo =
+        arquivo_principal_modrinth(versao).ok_or("Versão Modrinth sem arquivos para download.")?;
+    let project_id =
+        texto_id_json(&versao["project_id"]).ok_or("Versão Modrinth sem project_id.")?;
+    let file_name = arquivo["filename"].as_str().unwrap_or("").to_string();
+
+    Ok(ItemPlanoInstalacao {
+        plataforma: ModPlatform::Modrinth,
+        nome: file_name.clone(),
+        project_id,
+        versao_id: texto_id_json(&versao["id"]).unwrap_or_default(),
+        versao_nome: versao["version_number"]
+            .as_str()
+            .or_else(|| versao["name"].as_str())
+            .unwrap_or("")
+            .to_string(),
+        download_url: arquivo["url"]
+            .as_str()
+            .ok_or("Arquivo Modrinth sem URL de download.")?
+            .to_string(),
+        file_name,
+        sha1: arquivo["hashes"]["sha1"].as_str().map(|s| s.to_lowercase()),
+        tamanho: arquivo["size"].as_u64(),
+        tipo_conteudo: tipo_conteudo.to_string(),
+        motivo: MotivoItemPlano::Solicitado,
+        requerido_por: None,
+        substitui: None,
+    })
+}
+
+fn item_de_arquivo_curseforge(
+    project_id: &str,
+    arquivo: &serde_json::Value,
+    tipo_conteudo: &str,
+) -> Result<ItemPlanoInstalacao, String> {
+    let file_name = arquivo["fileName"].as_str().unwrap_or("").to_string();
+    Ok(ItemPlanoInstalacao {
+        plataforma: ModPlatform::CurseForge,
+        project_id: project_id.to_string(),
+        nome: file_name.clone(),
+        versao_id: texto_id_json(&arquivo["id"]).unwrap_or_default(),
+        versao_nome: arquivo["displayName"].as_str().unwrap_or("").to_string(),
+        download_url: arquivo["downloadUrl"]
+            .as_str()
+            .ok_or("Arquivo CurseForge sem URL de download (distribuição desativada pelo autor).")?
+            .to_string(),
+        file_name,
+        sha1: sha1_arquivo_curseforge(arquivo),
+        tamanho: arquivo["fileLength"].as_u64(),
+        tipo_conteudo: tipo_conteudo.to_string(),
+        motivo: MotivoItemPlano::Solicitado,
+        requerido_por: None,
+        substitui: None,
+    })
+}
+
+/// Cruza as incompatibilidades declaradas com o plano e com o que já está instalado.
+fn detectar_incompatibilidades(
+    itens: &[ItemPlanoInstalacao],
+    existente: &ConteudoExistente,
+    declaracoes: &[(ModPlatform, DependenciaDeclarada, String)],
+) -> Vec<IncompatibilidadePlano> {
+    let mut conflitos: Vec<IncompatibilidadePlano> = Vec::new();
+
+    for (plataforma, declaracao, declarado_por) in declaracoes {
+        let Some(project_id) = declaracao.project_id.as_deref() else {
+            continue;
+        };
+
+        let arquivo_instalado =
+            if let Some(arquivo) = existente.arquivo_do_projeto(plataforma, project_id) {
+                Some(arquivo.clone())
+            } else if itens
+                .iter()
+                .any(|item| &item.plataforma == plataforma && item.project_id == project_id)
+            {
+                None
+            } else {
+                continue;
+            };
+
+        let conflito = IncompatibilidadePlano {
+            plataforma: plataforma.clone(),
+            project_id: project_id.to_string(),
+            nome: project_id.to_string(),
+            declarado_por: declarado_por.clone(),
+            arquivo_instalado,
+        };
+        if !conflitos.contains(&conflito) {
+            conflitos.push(conflito);
+        }
+    }
+
+    conflitos
+}
+
+/// Indexa por SHA-1 os arquivos das pastas de conteúdo, inclusive os desativados,
+/// e por projeto o que o lockfile da instância já identifica.
+pub(crate) fn coletar_conteudo_existente(instance_path: &std::path::Path) -> ConteudoExistente {
+    let mut existente = ConteudoExistente::default();
+
+    for entrada in carregar_lockfile(instance_path).entradas {
+        if let (Some(plataforma), Some(project_id)) = (&entrada.plataforma, &entrada.project_id) {
+            existente.registrar_projeto(plataforma, project_id, &entrada.arquivo);
+        }
+    }
+
+    for pasta in PASTAS_CONTEUDO {
+        let Ok(entradas) = std::fs::read_dir(instance_path.join(pasta)) else {
+            continue;
+        };
+        for entrada in entradas.flatten() {
+            let caminho = entrada.path();
+            if !caminho.is_file() {
+                continue;
+            }
+            let nome = entrada.file_name().to_string_lossy().to_string();
+            if let Ok(sha1) = sha1_arquivo(&caminho) {
+                existente.registrar_hash(&sha1, &nome);
+            }
+        }
+    }
+
+    existente
+}
+
+/// Cliente e filtros (versão do Minecraft e loader) de uma instância para
+/// consultas às plataformas.
+pub(super) struct ContextoResolucao {
+    pub(super) client: reqwest::Client,
+    pub(super) versao_minecraft: String,
+    pub(super) loader: Option<String>,
+}
+
+struct Pendente {
+    plataforma: ModPlatform,
+    project_id: Option<String>,
+    versao_id: Option<String>,
+    tipo_conteudo: String,
+    requerido_por: Option<String>,
+}
+
+impl ContextoResolucao {
+    pub(super) fn para_instancia(instance: &Instance) -> Result<Self, String> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .user_agent("DomeLauncher/1.0 (+https://domestudios.com.br)")
+                .build()
+                .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?,
+            versao_minecraft: instance.version.clone(),
+            loader: normalizar_loader_para_mods(instance.loader_type.as_deref()),
+        })
+    }
+
+    pub(super) fn loader_para(&self, tipo_conteudo: &str) -> Option<String> {
+        if tipo_conteudo == "mod" {
+            self.loader.clone()
+        } else {
+            None
+        }
+    }
+
+    pub(super) async fn get_json(&self, url: &str) -> Result<serde_json::Value, String> {
+        let resposta = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Erro na requisição Modrinth: {}", e))?;
+        if !resposta.status().is_success() {
+            return Err(format!(
+                "Modrinth retornou HTTP {} para {}",
+                resposta.status().as_u16(),
+                url
+            ));
+        }
+        resposta
+            .json()
+            .await
+            .map_err(|e| format!("Erro ao parsear resposta Modrinth: {}", e))
+    }
+
+    pub(super) async fn get_json_curseforge(&self, url: &str) -> Result<serde_json::Value, String> {
+        let resposta = anexar_headers_curseforge(self.client.get(url))?
+            .send()
+            .await
+            .map_err(|e| format!("Erro na requisição CurseForge: {}", e))?;
+        if !resposta.status().is_success() {
+            return Err(format!(
+                "CurseForge retornou HTTP {} para {}",
+                resposta.status().as_u16(),
+                url
+            ));
+        }
+        resposta
+            .json()
+            .await
+            .map_err(|e| format!("Erro ao parsear JSON CurseForge: {}", e))
+    }
+
+    /// Usa a versão fixada pela dependência quando ela serve para a instância;
+    /// caso contrário, a versão compatível mais recente do projeto.
+    async fn versao_modrinth(
+        &self,
+        project_id: Option<&str>,
+        versao_id: Option<&str>,
+        tipo_conteudo: &str,
+    ) -> Result<serde_json::Value, String> {
+        let loader = self.loader_para(tipo_conteudo);
+        let mut project_id = project_id.map(|id| id.to_string());
+
+        if let Some(versao_id) = versao_id {
+            let versao = self
+                .get_json(&format!("{}/version/{}", MODRINTH_API_BASE, versao_id))
+                .await?;
+            if versao_modrinth_compativel(&versao, &self.versao_minecraft, &loader) {
+                return Ok(versao);
+            }
+            if project_id.is_none() {
+                project_id = texto_id_json(&versao["project_id"]);
+            }
+        }
+
+        let project_id = project_id.ok_or("Dependência Modrinth sem projeto identificável.")?;
+        let game_versions =
+            urlencoding::encode(&serde_json::json!([&self.versao_minecraft]).to_string())
+                .to_string();
+        let mut url = format!(
+            "{}/project/{}/version?game_versions={}",
+            MODRINTH_API_BASE, project_id, game_versions
+        );
+        if let Some(loader) = &loader {
+            let loaders =
+                urlencoding::encode(&serde_json::json!(loaders_compativeis(loader)).to_string())
+                    .to_string();
+            url.push_str(&format!("&loaders={}", loaders));
+        }
+
+        let versoes = self.get_json(&url).await?;
+        versoes
+            .as_array()
+            .and_then(|versoes| {
+                versoes
+                    .iter()
+                    .find(|v| versao_modrinth_compativel(v, &self.versao_minecraft, &loader))
+            })
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Nenhuma versão Modrinth de {} compatível com MC {} e loader {:?}",
+                    project_id, self.versao_minecraft, loader
+                )
+            })
+    }
+
+    /// Procura primeiro entre os arquivos da versão do Minecraft e, sem
+    /// resultado, na lista geral (que cobre tags só com a versão maior).
+    pub(super) async fn arquivo_curseforge(
+        &self,
+        project_id: &str,
+        tipo_conteudo: &str,
+    ) -> Result<serde_json::Value, String> {
+        let loader = self.loader_para(tipo_conteudo);
+        let base = format!(
+            "{}/mods/{}/files?pageSize=50&sortField=1&sortOrder=desc",
+            CURSEFORGE_API_BASE, project_id
+        );
+        let urls = [
+            format!(
+                "{}&gameVersion={}",
+                base,
+                urlencoding::encode(&self.versao_minecraft)
+            ),
+            base,
+        ];
+
+        for url in urls {
+            let payload = self.get_json_curseforge(&url).await?;
+            let arquivos = payload["data"]
+                .as_array()
+                .ok_or("Resposta inválida do CurseForge (data ausente)")?;
+            if let Some(arquivo) = selecionar_arquivo_curseforge_compativel(
+                arquivos,
+                tipo_conteudo,
+                &self.versao_minecraft,
+                &loader,
+            ) {
+                return Ok(arquivo.clone());
+            }
+        }
+
+        Err(format!(
+            "Nenhum arquivo CurseForge de {} compatível com MC {} e loader {:?}",
+            project_id, self.versao_minecraft, loader
+        ))
+    }
+
+    async fn resolver(
+        &self,
+        pendente: &Pendente,
+    ) -> Result<(ItemPlanoInstalacao, Vec<DependenciaDeclarada>), String> {
+        match pendente.plataforma {
+            ModPlatform::Modrinth => {
+                let versao = self
+                    .versao_modrinth(
+                        pendente.project_id.as_deref(),
+                        pendente.versao_id.as_deref(),
+                        &pendente.tipo_conteudo,
+                    )
+                    .await?;
+                let item = item_de_versao_modrinth(&versao, &pendente.tipo_conteudo)?;
+                Ok((item, dependencias_modrinth(&versao)))
+            }
+            ModPlatform::CurseForge => {
+                let project_id = pendente
+                    .project_id
+                    .as_deref()
+                    .ok_or("Dependência CurseForge sem projeto.")?;
+                let arquivo = self
+                    .arquivo_curseforge(project_id, &pendente.tipo_conteudo)
+                    .await?;
+                let item =
+                    item_de_arquivo_curseforge(project_id, &arquivo, &pendente.tipo_conteudo)?;
+                Ok((item, dependencias_curseforge(&arquivo)))
+            }
+            ModPlatform::Ftb => Err("Plataforma FTB não suportada para instalação.".to_string()),
+        }
+    }
+
+    /// Descobre o projeto Modrinth de cada arquivo instalado pelo hash (`version_files`).
+    async fn identificar_projetos_modrinth(&self, existente: &mut ConteudoExistente) {
+        if existente.hashes.is_empty() {
+            return;
+        }
+        let hashes: Vec<&String> = existente.hashes.keys().collect();
+        let corpo = serde_json::json!({ "hashes": hashes, "algorithm": "sha1" });
+        let resposta = self
+            .client
+            .post(format!("{}/version_files", MODRINTH_API_BASE))
+            .json(&corpo)
+            .send()
+            .await;
+
+        let versoes: serde_json::Value = match resposta {
+            Ok(resposta) if resposta.status().is_success() => {
+                resposta.json().await.unwrap_or_default()
+            }
+            Ok(resposta) => {
+                eprintln!(
+                    "[Dependencias] Modrinth retornou HTTP {} ao identificar arquivos",
+                    resposta.status().as_u16()
+                );
+                return;
+            }
+            Err(e) => {
+                eprintln!(
+                    "[Dependencias] Erro ao identificar arquivos no Modrinth: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        let Some(mapa) = versoes.as_object() else {
+            return;
+        };
+        for (hash, versao) in mapa {
+            let Some(project_id) = texto_id_json(&versao["project_id"]) else {
+                continue;
+            };
+            if let Some(arquivo) = existente.hashes.get(hash).cloned() {
+                existente.registrar_projeto(&ModPlatform::Modrinth, &project_id, &arquivo);
+            }
+        }
+    }
+
+    /// Busca os nomes legíveis dos projetos do plano; falhas mantêm o nome do arquivo.
+    async fn preencher_nomes(&self, plano: &mut PlanoInstalacao) {
+        let mut ids_modrinth = HashSet::new();
+        let mut ids_curseforge = HashSet::new();
+        let referencias = plano
+            .itens
+            .iter()
+            .map(|item| (&item.plataforma, &item.project_id))
+            .chain(
+                plano
+                    .ja_instalados
+                    .iter()
+                    .map(|item| (&item.plataforma, &item.project_id)),
+            )
+            .chain(
+                plano
+                    .incompatibilidades
+                    .iter()
+                    .map(|item| (&item.plataforma, &item.project_id)),
+            );
+        for (plataforma, id) in referencias {
+            match plataforma {
+                ModPlatform::Modrinth => ids_modrinth.insert(id.clone()),
+                ModPlatform::CurseForge => ids_curseforge.insert(id.clone()),
+                ModPlatform::Ftb => false,
+            };
+        }
+
+        let mut nomes: HashMap<(&'static str, String), String> = HashMap::new();
+
+        if !ids_modrinth.is_empty() {
+            let ids: Vec<&String> = ids_modrinth.iter().collect();
+            let url = format!(
+                "{}/projects?ids={}",
+                MODRINTH_API_BASE,
+                urlencoding::encode(&serde_json::json!(ids).to_string())
+            );
+            if let Ok(projetos) = self.get_json(&url).await {
+                for projeto in projetos.as_array().into_iter().flatten() {
+                    if let (Some(id), Some(titulo)) =
+                        (texto_id_json(&projeto["id"]), projeto["title"].as_str())
+                    {
+                        nomes.insert(("modrinth", id), titulo.to_string());
+                    }
+                }
+            }
+        }
+
+        if !ids_curseforge.is_empty() {
+            let ids: Vec<u64> = ids_curseforge
+                .iter()
+                .filter_map(|id| id.parse().ok())
+                .collect();
+            let requisicao = anexar_headers_curseforge(
+                self.client
+                    .post(format!("{}/mods", CURSEFORGE_API_BASE))
+                    .json(&serde_json::json!({ "modIds": ids })),
+            );
+            if let Ok(requisicao) = requisicao {
+                if let Ok(resposta) = requisicao.send().await {
+                    let payload: serde_json::Value = resposta.json().await.unwrap_or_default();
+                    for projeto in payload["data"].as_array().into_iter().flatten() {
+                        if let (Some(id), Some(nome)) =
+                            (texto_id_json(&projeto["id"]), projeto["name"].as_str())
+                        {
+                            nomes.insert(("curseforge", id), nome.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        let nome_de = |plataforma: &ModPlatform, id: &str| {
+            nomes
+                .get(&(chave_plataforma(plataforma), id.to_string()))
+                .cloned()
+        };
+        for item in &mut plano.itens {
+            if let Some(nome) = nome_de(&item.plataforma, &item.project_id) {
+                item.nome = nome;
+            }
+        }
+        for item in &mut plano.ja_instalados {
+            if let Some(nome) = nome_de(&item.plataforma, &item.project_id) {
+                item.nome = nome;
+            }
+        }
+        for item in &mut plano.incompatibilidades {
+            if let Some(nome) = nome_de(&item.plataforma, &item.project_id) {
+                item.nome = nome;
+            }
+        }
+    }
+}
+
+/// Monta o plano de instalação de um projeto percorrendo suas dependências
+/// obrigatórias, filtradas pela versão do Minecraft e pelo loader da instância.
+#[tauri::command]
+pub(crate) async fn resolver_plano_instalacao(
+    instance_id: String,
+    platform: ModPlatform,
+    project_id: String,
+    project_type: Option<String>,
+    version_id: Option<String>,
+    state: State<'_, LauncherState>,
+) -> Result<PlanoInstalacao, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let tipo_conteudo = project_type
+        .map(|tipo| tipo.trim().to_lowercase())
+        .filter(|tipo| !tipo.is_empty())
+        .unwrap_or_else(|| "mod".to_string());
+    pasta_destino_conteudo(&instance, &tipo_conteudo)?;
+
+    let contexto = ContextoResolucao::para_instancia(&instance)?;
+
+    let instance_path = instance.path.clone();
+    let mut existente =
+        tokio::task::spawn_blocking(move || coletar_conteudo_existente(&instance_path))
+            .await
+            .map_err(|e| format!("Erro ao inspecionar conteúdo instalado: {}", e))?;
+    contexto.identificar_projetos_modrinth(&mut existente).await;
+
+    let mut plano = PlanoInstalacao {
+        instance_id: instance_id.clone(),
+        versao_minecraft: contexto.versao_minecraft.clone(),
+        loader: contexto.loader.clone(),
+        itens: Vec::new(),
+        ja_instalados: Vec::new(),
+        incompatibilidades: Vec::new(),
+        nao_resolvidas: Vec::new(),
+        bytes_totais: 0,
+    };
+
+    let mut fila = VecDeque::from([Pendente {
+        plataforma: platform,
+        project_id: Some(project_id),
+        versao_id: version_id,
+        tipo_conteudo,
+        requerido_por: None,
+    }]);
+    let mut visitados: HashSet<(&'static str, String)> = HashSet::new();
+    let mut declaracoes_incompativeis = Vec::new();
+
+    while let Some(pendente) = fila.pop_front() {
+        if let Some(id) = &pendente.project_id {
+            if visitados.contains(&(chave_plataforma(&pendente.plataforma), id.clone())) {
+                continue;
+            }
+        }
+        if visitados.len() >= LIMITE_PROJETOS_PLANO {
+            plano.nao_resolvidas.push(format!(
+                "Limite de {} projetos atingido; dependências restantes ignoradas.",
+                LIMITE_PROJETOS_PLANO
+            ));
+            break;
+        }
+
+        let (mut item, dependencias) = match contexto.resolver(&pendente).await {
+            Ok(resolvido) => resolvido,
+            Err(e) if pendente.requerido_por.is_none() => return Err(e),
+            Err(e) => {
+                eprintln!("[Dependencias] {}", e);
+                plano.nao_resolvidas.push(e);
+                continue;
+            }
+        };
+        if !visitados.insert((chave_plataforma(&item.plataforma), item.project_id.clone())) {
+            continue;
+        }
+
+        // O item pedido só é pulado se o mesmo arquivo já estiver instalado; outra
+        // versão do projeto é substituída (atualização ou troca de versão).
+        let instalado = if pendente.requerido_por.is_none() {
+            let mesmo_arquivo = existente.arquivo_do_hash(&item);
+            if mesmo_arquivo.is_none() {
+                item.substitui = existente
+                    .arquivo_do_projeto(&item.plataforma, &item.project_id)
+                    .cloned();
+            }
+            mesmo_arquivo
+        } else {
+            existente.arquivo_do_item(&item)
+        };
+        if let Some(arquivo) = instalado {
+            plano.ja_instalados.push(ConteudoJaInstalado {
+                plataforma: item.plataforma.clone(),
+                project_id: item.project_id.clone(),
+                nome: item.nome.clone(),
+                arquivo: arquivo.clone(),
+            });
+            continue;
+        }
+
+        if let Some(requerido_por) = pendente.requerido_por {
+            item.motivo = MotivoItemPlano::Dependencia;
+            item.requerido_por = Some(requerido_por);
+        }
+
+        for dependencia in dependencias {
+            match dependencia.tipo {
+                // Dependências de resourcepacks e shaders (ex.: Iris) também são mods.
+                TipoDependencia::Obrigatoria => fila.push_back(Pendente {
+                    plataforma: item.plataforma.clone(),
+                    project_id: dependencia.project_id,
+                    versao_id: dependencia.versao_id,
+                    tipo_conteudo: "mod".to_string(),
+                    requerido_por: Some(item.project_id.clone()),
+                }),
+                TipoDependencia::Incompativel => declaracoes_incompativeis.push((
+                    item.plataforma.clone(),
+                    dependencia,
+                    item.project_id.clone(),
+                )),
+            }
+        }
+        plano.itens.push(item);
+    }
+
+    plano.incompatibilidades =
+        detectar_incompatibilidades(&plano.itens, &existente, &declaracoes_incompativeis);
+    plano.bytes_totais = plano.itens.iter().filter_map(|item| item.tamanho).sum();
+    contexto.preencher_nomes(&mut plano).await;
+
+    // `declaradoPor` e `requeridoPor` passam a exibir o nome do projeto.
+    let nomes_itens: HashMap<String, String> = plano
+        .itens
+        .iter()
+        .map(|item| (item.project_id.clone(), item.nome.clone()))
+        .collect();
+    for conflito in &mut plano.incompatibilidades {
+        if let Some(nome) = nomes_itens.get(&conflito.declarado_por) {
+            conflito.declarado_por = nome.clone();
+        }
+    }
+    for item in &mut plano.itens {
+        if let Some(nome) = item
+            .requerido_por
+            .as_ref()
+            .and_then(|id| nomes_itens.get(id))
+        {
+            item.requerido_por = Some(nome.clone());
+        }
+    }
+
+    println!(
+        "[Dependencias] Plano para {}: {} item(ns), {} já instalado(s), {} conflito(s)",
+        instance_id,
+        plano.itens.len(),
+        plano.ja_instalados.len(),
+        plano.incompatibilidades.len()
+    );
+    Ok(plano)
+}
+
+/// Baixa os itens de um plano confirmado, conferindo o SHA-1 de cada arquivo.
+/// Se algum download falhar, os arquivos já gravados nesta execução são removidos.
+#[tauri::command]
+pub(crate) async fn executar_plano_instalacao(
+    app: tauri::AppHandle,
+    instance_id: String,
+    plano: PlanoInstalacao,
+    ignorar_incompatibilidades: Option<bool>,
+    task_id: Option<String>,
+    state: State<'_, LauncherState>,
+) -> Result<Vec<String>, String> {
+    if plano.instance_id != instance_id {
+        return Err("O plano de instalação pertence a outra instância.".to_string());
+    }
+    if !plano.incompatibilidades.is_empty() && !ignorar_incompatibilidades.unwrap_or(false) {
+        let nomes: Vec<&str> = plano
+            .incompatibilidades
+            .iter()
+            .map(|conflito| conflito.nome.as_str())
+            .collect();
+        return Err(format!(
+            "O plano possui incompatibilidades não confirmadas: {}",
+            nomes.join(", ")
+        ));
+    }
+
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "instalar_conteudo",
+        &format!(
+            "Instalando {} arquivo(s) em {}",
+            plano.itens.len(),
+            instance.name
+        ),
+        Some(&instance_id),
+    )?;
+    let resultado = instalar_itens_plano(&instance, &plano, &tarefa).await;
+    tarefa.finalizar(resultado)
+}
+
+async fn instalar_itens_plano(
+    instance: &Instance,
+    plano: &PlanoInstalacao,
+    tarefa: &Tarefa,
+) -> Result<Vec<String>, String> {
+    tarefa.fase("arquivos", plano.itens.len(), plano.bytes_totais);
+    let mut gravados: Vec<std::path::PathBuf> = Vec::new();
+    // Só o que esta instalação criou é apagado se outro item falhar; um arquivo
+    // sobrescrito com o mesmo nome continua na pasta.
+    let mut criados: Vec<std::path::PathBuf> = Vec::new();
+    let mut entradas = Vec::new();
+
+    for item in &plano.itens {
+        let resultado = async {
+            tarefa.verificar_cancelamento()?;
+            let pasta = pasta_destino_conteudo(instance, &item.tipo_conteudo)?;
+            let ja_existia = std::path::Path::new(item.file_name.trim())
+                .file_name()
+                .is_some_and(|nome| pasta.join(nome).exists());
+            tokio::select! {
+                resultado = baixar_arquivo_para_pasta(
+                    &pasta,
+                    &item.tipo_conteudo,
+                    item.download_url.clone(),
+                    item.file_name.clone(),
+                    "dependencia",
+                    item.sha1.as_deref(),
+                ) => resultado.map(|caminho| (caminho, ja_existia)),
+                _ = tarefa.cancelamento().cancelled() => Err(MENSAGEM_TAREFA_CANCELADA.to_string()),
+            }
+        }
+        .await;
+
+        match resultado {
+            Ok((caminho, ja_existia)) => {
+                if !ja_existia {
+                    tarefa.limpar_ao_cancelar(caminho.clone());
+                    criados.push(caminho.clone());
+                }
+                tarefa.avancar(1, item.tamanho.unwrap_or(0));
+                let origem = match item.motivo {
+                    MotivoItemPlano::Solicitado => OrigemInstalacao::Usuario,
+                    MotivoItemPlano::Dependencia => OrigemInstalacao::Dependencia,
+                };
+                match EntradaConteudo::de_arquivo(&caminho, &item.tipo_conteudo, origem) {
+                    Ok(entrada) => entradas.push(
+                        entrada
+                            .com_projeto(
+                                item.plataforma.clone(),
+                                Some(item.project_id.clone()),
+                                Some(item.versao_id.clone()),
+                            )
+                            .com_url_download(&item.download_url),
+                    ),
+                    Err(e) => eprintln!("[Lockfile] {}", e),
+                }
+                gravados.push(caminho);
+            }
+            Err(e) => {
+                for caminho in &criados {
+                    let _ = std::fs::remove_file(caminho);
+                }
+                return Err(format!("Erro ao instalar {}: {}", item.nome, e));
+            }
+        }
+    }
+
+    // A versão anterior vem do lockfile da instância; o `substitui` recebido no
+    // plano serve só para exibição.
+    let lockfile = carregar_lockfile(&instance.path);
+    for (item, caminho) in plano.itens.iter().zip(&gravados) {
+        if item.motivo != MotivoItemPlano::Solicitado {
+            continue;
+        }
+        let nome_gravado = caminho
+            .file_name()
+            .map(|nome| nome.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for anterior in arquivos_substituidos(&lockfile, item, &nome_gravado) {
+            remover_arquivo_substituido(instance, &anterior.tipo_conteudo, &anterior.arquivo);
+        }
+    }
+
+    if let Err(e) = registrar_conteudo(&instance.path, entradas) {
+        eprintln!("[Lockfile] Falha ao registrar conteúdo: {}", e);
+    }
+
+    Ok(gravados
+        .iter()
+        .filter_map(|caminho| caminho.file_name())
+        .map(|nome| nome.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Entradas do lockfile de outras versões do mesmo projeto do item.
+fn arquivos_substituidos<'a>(
+    lockfile: &'a LockfileConteudo,
+    item: &ItemPlanoInstalacao,
+    nome_gravado: &str,
+) -> Vec<&'a EntradaConteudo> {
+    lockfile
+        .entradas
+        .iter()
+        .filter(|entrada| {
+            entrada.plataforma.as_ref() == Some(&item.plataforma)
+                && entrada.project_id.as_deref() == Some(item.project_id.as_str())
+                && entrada.arquivo != nome_habilitado(nome_gravado)
+        })
+        .collect()
+}
+
+fn nome_arquivo_simples(arquivo: &str) -> bool {
+    std::path::Path::new(arquivo)
+        .file_name()
+        .is_some_and(|nome| nome == arquivo)
+}
+
+/// Apaga a versão anterior de um projeto atualizado, ativa ou desativada. Nomes
+/// com diretórios ou que resolvem fora da pasta de conteúdo são ignorados.
+fn remover_arquivo_substituido(instance: &Instance, tipo_conteudo: &str, arquivo: &str) {
+    let Ok(pasta) = pasta_destino_conteudo(instance, tipo_conteudo) else {
+        return;
+    };
+    if !nome_arquivo_simples(arquivo) {
+        eprintln!(
+            "[Dependencias] Nome de arquivo inválido ignorado: {}",
+            arquivo
+        );
+        return;
+    }
+    for nome in [arquivo.to_string(), format!("{}.disabled", arquivo)] {
+        let caminho = pasta.join(&nome);
+        if !caminho.is_file() {
+            continue;
+        }
+        let caminho = match validar_caminho_dentro_raiz(&pasta, &caminho) {
+            Ok(caminho) => caminho,
+            Err(e) => {
+                eprintln!("[Dependencias] {} ignorado: {}", nome, e);
+                continue;
+            }
+        };
+        if let Err(e) = std::fs::remove_file(&caminho) {
+            eprintln!("[Dependencias] Falha ao remover {}: {}", nome, e);
+        }
+    }
+    if let Err(e) = remover_conteudo(&instance.path, tipo_conteudo, arquivo) {
+        eprintln!("[Lockfile] Falha ao remover {}: {}", arquivo, e);
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn quilt_aceita_arquivos_do_fabric_como_alternativa() {
+        let quilt = Some("quilt".to_string());
+        let fabric = Some("fabric".to_string());
+        let so_fabric = serde_json::json!({ "game_versions": ["1.20.1"], "loaders": ["fabric"] });
+        let so_quilt = serde_json::json!({ "game_versions": ["1.20.1"], "loaders": ["quilt"] });
+        assert!(versao_modrinth_compativel(&so_fabric, "1.20.1", &quilt));
+        assert!(versao_modrinth_compativel(&so_quilt, "1.20.1", &quilt));
+        assert!(!versao_modrinth_compativel(&so_quilt, "1.20.1", &fabric));
+
+        let arquivo = |id: i64, loader: &str| {
+            serde_json::json!({
+                "id": id,
+                "fileName": format!("mod-{}.jar", id),
+                "downloadUrl": "https://edge.forgecdn.net/x.jar",
+                "gameVersions": ["1.20.1", loader],
+            })
+        };
+        let arquivos = vec![arquivo(20, "Fabric"), arquivo(10, "Quilt")];
+        let escolhido =
+            selecionar_arquivo_curseforge_compativel(&arquivos, "mod", "1.20.1", &quilt).unwrap();
+        assert_eq!(escolhido["id"], 10);
+    }
+
+    #[test]
+    fn le_dependencias_obrigatorias_e_incompativeis_do_modrinth() {
+        let versao = serde_json::json!({
+            "dependencies": [
+                { "project_id": "P7dR8mSH", "version_id": null, "dependency_type": "required" },
+                { "project_id": null, "version_id": "abc123", "dependency_type": "required" },
+                { "project_id": "AANobbMI", "dependency_type": "incompatible" },
+                { "project_id": "mOgUt4GM", "dependency_type": "optional" },
+                { "project_id": "emb", "dependency_type": "embedded" }
+            ]
+        });
+
+        let deps = dependencias_modrinth(&versao);
+        assert_eq!(deps.len(), 3);
+        assert_eq!(deps[0].project_id.as_deref(), Some("P7dR8mSH"));
+        assert_eq!(deps[0].tipo, TipoDependencia::Obrigatoria);
+        assert_eq!(deps[1].project_id, None);
+        assert_eq!(deps[1].versao_id.as_deref(), Some("abc123"));
+        assert_eq!(deps[2].tipo, TipoDependencia::Incompativel);
+    }
+
+    #[test]
+    fn le_relation_type_e_sha1_do_curseforge() {
+        let arquivo = serde_json::json!({
+            "dependencies": [
+                { "modId": 306612, "relationType": 3 },
+                { "modId": 238222, "relationType": 2 },
+                { "modId": 32274, "relationType": 5 }
+            ],
+            "hashes": [
+                { "value": "d41d8cd98f00b204e9800998ecf8427e", "algo": 2 },
+                { "value": "ABCDEF0123", "algo": 1 }
+            ]
+        });
+
+        let deps = dependencias_curseforge(&arquivo);
+        assert_eq!(
+            deps,
+            vec![
+                DependenciaDeclarada {
+                    project_id: Some("306612".to_string()),
+                    versao_id: None,
+                    tipo: TipoDependencia::Obrigatoria,
+                },
+                DependenciaDeclarada {
+                    project_id: Some("32274".to_string()),
+                    versao_id: None,
+                    tipo: TipoDependencia::Incompativel,
+                },
+            ]
+        );
+        assert_eq!(
+            sha1_arquivo_curseforge(&arquivo).as_deref(),
+            Some("abcdef0123")
+        );
+    }
+
+    #[test]
+    fn detecta_conflitos_com_plano_e_com_instalados() {
+        let item = |project_id: &str| ItemPlanoInstalacao {
+            plataforma: ModPlatform::Modrinth,
+            project_id: project_id.to_string(),
+            nome: project_id.to_string(),
+            versao_id: "v1".to_string(),
+            versao_nome: "1.0".to_string(),
+            file_name: format!("{}.jar", project_id),
+            download_url: String::new(),
+            sha1: Some("aa".to_string()),
+            tamanho: None,
+            tipo_conteudo: "mod".to_string(),
+            motivo: MotivoItemPlano::Solicitado,
+            requerido_por: None,
+            substitui: None,
+        };
+        let incompativel = |project_id: &str| {
+            (
+                ModPlatform::Modrinth,
+                DependenciaDeclarada {
+                    project_id: Some(project_id.to_string()),
+                    versao_id: None,
+                    tipo: TipoDependencia::Incompativel,
+                },
+                "create".to_string(),
+            )
+        };
+
+        let mut existente = ConteudoExistente::default();
+        existente.registrar_hash("AA", "sodium.jar");
+        existente.registrar_projeto(&ModPlatform::Modrinth, "optifine", "optifine.jar");
+        assert_eq!(
+            existente
+                .arquivo_do_item(&item("sodium"))
+                .map(String::as_str),
+            Some("sodium.jar")
+        );
+        // Sem o mesmo hash, o projeto instalado é só substituído.
+        let outra_versao = ItemPlanoInstalacao {
+            sha1: Some("bb".to_string()),
+            ..item("optifine")
+        };
+        assert!(existente.arquivo_do_hash(&outra_versao).is_none());
+        assert_eq!(
+            existente.arquivo_do_item(&outra_versao).map(String::as_str),
+            Some("optifine.jar")
+        );
+
+        let itens = vec![item("create"), item("rubidium")];
+        let conflitos = detectar_incompatibilidades(
+            &itens,
+            &existente,
+            &[
+                incompativel("optifine"),
+                incompativel("rubidium"),
+                incompativel("ausente"),
+            ],
+        );
+
+        assert_eq!(conflitos.len(), 2);
+        assert_eq!(
+            conflitos[0].arquivo_instalado.as_deref(),
+            Some("optifine.jar")
+        );
+        assert_eq!(conflitos[1].project_id, "rubidium");
+        assert_eq!(conflitos[1].arquivo_instalado, None);
+    }
+
+    #[test]
+    fn substituicao_vem_do_lockfile_e_rejeita_caminhos() {
+        let entrada = |arquivo: &str, project_id: &str| {
+            EntradaConteudo::nova(
+                arquivo,
+                "mod",
+                Default::default(),
+                OrigemInstalacao::Usuario,
+            )
+            .com_projeto(ModPlatform::Modrinth, Some(project_id.to_string()), None)
+        };
+        let lockfile = LockfileConteudo {
+            entradas: vec![
+                entrada("sodium-0.5.jar", "sodium"),
+                entrada("sodium-0.6.jar", "sodium"),
+                entrada("iris.jar", "iris"),
+            ],
+            ..Default::default()
+        };
+        let item = ItemPlanoInstalacao {
+            plataforma: ModPlatform::Modrinth,
+            project_id: "sodium".to_string(),
+            nome: "Sodium".to_string(),
+            versao_id: "v2".to_string(),
+            versao_nome: "0.6".to_string(),
+            file_name: "sodium-0.6.jar".to_string(),
+            download_url: String::new(),
+            sha1: None,
+            tamanho: None,
+            tipo_conteudo: "mod".to_string(),
+            motivo: MotivoItemPlano::Solicitado,
+            requerido_por: None,
+            substitui: Some("../../instance.json".to_string()),
+        };
+
+        let substituidos = arquivos_substituidos(&lockfile, &item, "sodium-0.6.jar");
+        assert_eq!(substituidos.len(), 1);
+        assert_eq!(substituidos[0].arquivo, "sodium-0.5.jar");
+
+        assert!(nome_arquivo_simples("sodium-0.5.jar"));
+        assert!(!nome_arquivo_simples("../../instance.json"));
+        assert!(!nome_arquivo_simples("mods/sodium.jar"));
+        assert!(!nome_arquivo_simples(".."));
+    }
+}
@@ -0,0 +1,655 @@
+use super::armazenamento_compartilhado::ARQUIVO_REFERENCIAS;
+use super::atualizacao_modpack::gravar_instancia;
+use super::instancias_criacao::{
+    buscar_detalhes_versao, download_instance_files, instalar_loader, nome_loader_instancia,
+};
+use super::mods_conteudo::{lista_str_de_json, loaders_compativeis};
+use super::registro_conteudo::{carregar_lockfile, EntradaConteudo};
+use super::resolucao_dependencias::ContextoResolucao;
+use super::*;
+use crate::comandos::instancia_sistema::obter_mapa_instancias_em_execucao;
+use crate::comandos::tarefas::Tarefa;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub(crate) const PASTA_BACKUP_VERSAO: &str = ".backup_versao";
+const ARQUIVO_BACKUP_VERSAO: &str = "backup.json";
+/// Itens da raiz da instância que pertencem à versão e ao loader instalados.
+const ITENS_VERSAO: [&str; 7] = [
+    "version_manifest.json",
+    "forge_manifest.json",
+    "neoforge_manifest.json",
+    "fabric_manifest.json",
+    "quilt_manifest.json",
+    ARQUIVO_REFERENCIAS,
+    "libraries",
+];
+const LOADERS_CURSEFORGE: [&str; 4] = ["forge", "fabric", "neoforge", "quilt"];
+
+/// Versão e loader da instância antes da troca, guardados junto do backup.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupVersao {
+    pub criado_em: String,
+    pub version: String,
+    pub mc_type: String,
+    pub loader_type: Option<String>,
+    pub loader_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConteudoIncompativel {
+    pub arquivo: String,
+    pub nome: String,
+    pub motivo: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioTrocaVersao {
+    pub versao_anterior: String,
+    pub loader_anterior: Option<String>,
+    pub versao_nova: String,
+    pub loader_novo: Option<String>,
+    pub incompativeis: Vec<ConteudoIncompativel>,
+    /// Mods sem origem conhecida, que não puderam ser conferidos.
+    pub nao_verificados: Vec<String>,
+    pub erros: Vec<String>,
+}
+
+fn descricao_loader(loader_type: Option<&str>, loader_version: Option<&str>) -> Option<String> {
+    let loader = loader_type.filter(|loader| !loader.eq_ignore_ascii_case("vanilla"))?;
+    Some(
+        format!("{} {}", loader, loader_version.unwrap_or(""))
+            .trim()
+            .to_string(),
+    )
+}
+
+fn remover_item(caminho: &Path) -> std::io::Result<()> {
+    if caminho.is_dir() {
+        std::fs::remove_dir_all(caminho)
+    } else if caminho.exists() {
+        std::fs::remove_file(caminho)
+    } else {
+        Ok(())
+    }
+}
+
+/// Move a versão atual (manifestos, referências e bibliotecas locais) para o
+/// backup, substituindo um backup anterior.
+fn criar_backup_versao(instance: &Instance) -> Result<(), String> {
+    let pasta_backup = instance.path.join(PASTA_BACKUP_VERSAO);
+    remover_item(&pasta_backup)
+        .map_err(|e| format!("Erro ao descartar backup de versão anterior: {}", e))?;
+    std::fs::create_dir_all(&pasta_backup)
+        .map_err(|e| format!("Erro ao criar backup de versão: {}", e))?;
+
+    let backup = BackupVersao {
+        criado_em: chrono::Utc::now().to_rfc3339(),
+        version: instance.version.clone(),
+        mc_type: instance.mc_type.clone(),
+        loader_type: instance.loader_type.clone(),
+        loader_version: instance.loader_version.clone(),
+    };
+    let json = serde_json::to_string_pretty(&backup)
+        .map_err(|e| format!("Erro ao serializar backup de versão: {}", e))?;
+    std::fs::write(pasta_backup.join(ARQUIVO_BACKUP_VERSAO), json)
+        .map_err(|e| format!("Erro ao salvar backup de versão: {}", e))?;
+
+    for (posicao, item) in ITENS_VERSAO.iter().enumerate() {
+        let origem = instance.path.join(item);
+        if !origem.exists() {
+            continue;
+        }
+        if let Err(e) = std::fs::rename(&origem, pasta_backup.join(item)) {
+            for movido in &ITENS_VERSAO[..posicao] {
+                let _ = std::fs::rename(pasta_backup.join(movido), instance.path.join(movido));
+            }
+            let _ = std::fs::remove_dir_all(&pasta_backup);
+            return Err(format!("Erro ao mover {} para o backup: {}", item, e));
+        }
+    }
+    Ok(())
+}
+
+fn ler_backup_versao(instance_path: &Path) -> Option<BackupVersao> {
+    let conteudo = std::fs::read_to_string(
+        instance_path
+            .join(PASTA_BACKUP_VERSAO)
+            .join(ARQUIVO_BACKUP_VERSAO),
+    )
+    .ok()?;
+    serde_json::from_str(&conteudo).ok()
+}
+
+/// Devolve à instância a versão guardada no backup e descarta o backup.
+fn restaurar_backup_versao(instance_path: &Path) -> Result<BackupVersao, String> {
+    let pasta_backup = instance_path.join(PASTA_BACKUP_VERSAO);
+    let backup = ler_backup_versao(instance_path)
+        .ok_or("Não há backup de versão para restaurar nesta instância.")?;
+
+    for item in ITENS_VERSAO {
+        let atual = instance_path.join(item);
+        remover_item(&atual).map_err(|e| format!("Erro ao remover {}: {}", item, e))?;
+        let guardado = pasta_backup.join(item);
+        if guardado.exists() {
+            std::fs::rename(&guardado, &atual)
+                .map_err(|e| format!("Erro ao restaurar {}: {}", item, e))?;
+        }
+    }
+    let _ = std::fs::remove_dir_all(&pasta_backup);
+    Ok(backup)
+}
+
+/// Explica por que um arquivo não serve para a versão e o loader informados.
+/// Listas vazias valem como "qualquer uma", como na busca de conteúdo.
+fn motivo_incompatibilidade(
+    versoes_mc: &[String],
+    loaders: &[String],
+    versao_minecraft: &str,
+    loader: &str,
+) -> Option<String> {
+    if !versoes_mc.is_empty() && !versoes_mc.iter().any(|v| v == versao_minecraft) {
+        return Some(format!(
+            "Não há indicação de suporte ao Minecraft {}.",
+            versao_minecraft
+        ));
+    }
+    let aceitos = loaders_compativeis(loader);
+    if !loaders.is_empty()
+        && !loaders.iter().any(|tag| {
+            aceitos
+                .iter()
+                .any(|aceito| tag.eq_ignore_ascii_case(aceito))
+        })
+    {
+        return Some(format!(
+            "Feito para {}, não para {}.",
+            loaders.join(", "),
+            loader
+        ));
+    }
+    None
+}
+
+/// Separa as `gameVersions` de um arquivo do CurseForge em versões do
+/// Minecraft e loaders, ignorando as demais tags (`Client`, `Java 17`...).
+fn separar_tags_curseforge(tags: &[String]) -> (Vec<String>, Vec<String>) {
+    let versoes_mc = tags
+        .iter()
+        .filter(|tag| tag.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .cloned()
+        .collect();
+    let loaders = tags
+        .iter()
+        .filter(|tag| {
+            LOADERS_CURSEFORGE
+                .iter()
+                .any(|loader| tag.eq_ignore_ascii_case(loader))
+        })
+        .cloned()
+        .collect();
+    (versoes_mc, loaders)
+}
+
+fn incompativel(entrada: &EntradaConteudo, motivo: String) -> ConteudoIncompativel {
+    ConteudoIncompativel {
+        arquivo: entrada.arquivo.clone(),
+        nome: entrada
+            .nome
+            .clone()
+            .unwrap_or_else(|| entrada.arquivo.clone()),
+        motivo,
+    }
+}
+
+async fn conferir_modrinth(
+    contexto: &ContextoResolucao,
+    loader: &str,
+    entradas: &[&EntradaConteudo],
+    relatorio: &mut RelatorioTrocaVersao,
+) -> Result<(), String> {
+    let hashes: Vec<&str> = entradas
+        .iter()
+        .filter_map(|e| e.hashes.sha1.as_deref())
+        .collect();
+    let resposta = contexto
+        .client
+        .post(format!("{}/version_files", MODRINTH_API_BASE))
+        .json(&serde_json::json!({ "hashes": hashes, "algorithm": "sha1" }))
+        .send()
+        .await
+        .map_err(|e| format!("Erro ao consultar mods no Modrinth: {}", e))?;
+    if !resposta.status().is_success() {
+        return Err(format!(
+            "Modrinth retornou HTTP {} ao conferir mods",
+            resposta.status().as_u16()
+        ));
+    }
+    let versoes: serde_json::Value = resposta
+        .json()
+        .await
+        .map_err(|e| format!("Erro ao parsear JSON Modrinth: {}", e))?;
+
+    for entrada in entradas {
+        let Some(versao) = entrada.hashes.sha1.as_deref().and_then(|s| versoes.get(s)) else {
+            relatorio.nao_verificados.push(entrada.arquivo.clone());
+            continue;
+        };
+        if let Some(motivo) = motivo_incompatibilidade(
+            &lista_str_de_json(&versao["game_versions"]),
+            &lista_str_de_json(&versao["loaders"]),
+            &contexto.versao_minecraft,
+            loader,
+        ) {
+            relatorio.incompativeis.push(incompativel(entrada, motivo));
+        }
+    }
+    Ok(())
+}
+
+async fn conferir_curseforge(
+    contexto: &ContextoResolucao,
+    loader: &str,
+    entradas: &[&EntradaConteudo],
+    relatorio: &mut RelatorioTrocaVersao,
+) -> Result<(), String> {
+    let ids: Vec<u64> = entradas
+        .iter()
+        .filter_map(|e| e.versao_id.as_deref()?.parse().ok())
+        .collect();
+    let resposta = anexar_headers_curseforge(
+        contexto
+            .client
+            .post(format!("{}/mods/files", CURSEFORGE_API_BASE))
+            .json(&serde_json::json!({ "fileIds": ids })),
+    )?
+    .send()
+    .await
+    .map_err(|e| format!("Erro ao consultar mods no CurseForge: {}", e))?;
+    if !resposta.status().is_success() {
+        return Err(format!(
+            "CurseForge retornou HTTP {} ao conferir mods",
+            resposta.status().as_u16()
+        ));
+    }
+    let payload: serde_json::Value = resposta
+        .json()
+        .await
+        .map_err(|e| format!("Erro ao parsear JSON CurseForge: {}", e))?;
+    let arquivos: HashMap<String, &serde_json::Value> = payload["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|arquivo| Some((arquivo["id"].as_u64()?.to_string(), arquivo)))
+        .collect();
+
+    for entrada in entradas {
+        let Some(arquivo) = entrada.versao_id.as_ref().and_then(|id| arquivos.get(id)) else {
+            relatorio.nao_verificados.push(entrada.arquivo.clone());
+            continue;
+        };
+        let (versoes_mc, loaders) =
+            separar_tags_curseforge(&lista_str_de_json(&arquivo["gameVersions"]));
+        if let Some(motivo) =
+            motivo_incompatibilidade(&versoes_mc, &loaders, &contexto.versao_minecraft, loader)
+        {
+            relatorio.incompativeis.push(incompativel(entrada, motivo));
+        }
+    }
+    Ok(())
+}
+
+/// Confere os mods ativos da instância contra a versão e o loader novos.
+/// Falhas de rede entram como erros do relatório, sem desfazer a troca.
+async fn conferir_mods_instalados(instance: &Instance, relatorio: &mut RelatorioTrocaVersao) {
+    let lockfile = carregar_lockfile(&instance.path);
+    let mods: Vec<&EntradaConteudo> = lockfile
+        .entradas
+        .iter()
+        .filter(|entrada| entrada.tipo_conteudo == "mod" && entrada.habilitado)
+        .collect();
+    if mods.is_empty() {
+        return;
+    }
+
+    let contexto = match ContextoResolucao::para_instancia(instance) {
+        Ok(contexto) => contexto,
+        Err(e) => {
+            relatorio.erros.push(e);
+            return;
+        }
+    };
+    let Some(loader) = contexto.loader.clone() else {
+        for entrada in mods {
+            relatorio.incompativeis.push(incompativel(
+                entrada,
+                "A instância não tem mais um loader de mods.".to_string(),
+            ));
+        }
+        return;
+    };
+
+    let mut modrinth = Vec::new();
+    let mut curseforge = Vec::new();
+    for entrada in mods {
+        match entrada.plataforma {
+            Some(ModPlatform::Modrinth) if entrada.hashes.sha1.is_some() => modrinth.push(entrada),
+            Some(ModPlatform::CurseForge) if entrada.versao_id.is_some() => {
+                curseforge.push(entrada)
+            }
+            _ => relatorio.nao_verificados.push(entrada.arquivo.clone()),
+        }
+    }
+
+    if !modrinth.is_empty() {
+        if let Err(e) = conferir_modrinth(&contexto, &loader, &modrinth, relatorio).await {
+            relatorio.erros.push(e);
+        }
+    }
+    if !curseforge.is_empty() {
+        if let Err(e) = conferir_curseforge(&contexto, &loader, &curseforge, relatorio).await {
+            relatorio.erros.push(e);
+        }
+    }
+    relatorio
+        .incompativeis
+        .sort_by_key(|conteudo| conteudo.nome.to_lowercase());
+}
+
+fn conferir_instancia_parada(state: &LauncherState, instance_id: &str) -> Result<(), String> {
+    let em_execucao = obter_mapa_instancias_em_execucao(state, &[instance_id.to_string()])?;
+    if em_execucao.get(instance_id).copied().unwrap_or(false) {
+        return Err("Feche o jogo antes de trocar a versão da instância.".to_string());
+    }
+    Ok(())
+}
+
+/// Baixa a versão nova e instala o loader por cima do backup já criado.
+async fn instalar_versao(
+    instance: &Instance,
+    details: &VersionDetail,
+    loader: &str,
+    loader_version: Option<&str>,
+    tarefa: &Tarefa,
+) -> Result<Option<LoaderType>, String> {
+    download_instance_files(&instance.path, details, Some(tarefa)).await?;
+    tarefa.fase("loader", 0, 0);
+    let tipo = instalar_loader(
+        &instance.path,
+        &details.id,
+        loader,
+        loader_version,
+        Some(tarefa),
+    )
+    .await?;
+    tarefa.verificar_cancelamento()?;
+
+    let conteudo = serde_json::to_string_pretty(details)
+        .map_err(|e| format!("Erro ao serializar version_manifest.json: {}", e))?;
+    std::fs::write(instance.path.join("version_manifest.json"), conteudo)
+        .map_err(|e| format!("Erro ao salvar version_manifest.json: {}", e))?;
+    Ok(tipo)
+}
+
+async fn trocar_versao(
+    instance: &Instance,
+    versao_minecraft: &str,
+    loader: &str,
+    loader_version: Option<String>,
+    tarefa: &Tarefa,
+) -> Result<RelatorioTrocaVersao, String> {
+    tarefa.fase("manifesto", 2, 0);
+    let details = buscar_detalhes_versao(versao_minecraft, Some(tarefa)).await?;
+    tarefa.verificar_cancelamento()?;
+
+    criar_backup_versao(instance)?;
+    let tipo = match instalar_versao(
+        instance,
+        &details,
+        loader,
+        loader_version.as_deref(),
+        tarefa,
+    )
+    .await
+    {
+        Ok(tipo) => tipo,
+        Err(e) => {
+            if let Err(erro_restauracao) = restaurar_backup_versao(&instance.path) {
+                eprintln!("[Versao] {}", erro_restauracao);
+            }
+            return Err(e);
+        }
+    };
+
+    tarefa.fase("finalizando", 1, 0);
+    let mut nova = instance.clone();
+    nova.version = versao_minecraft.to_string();
+    nova.mc_type = loader.to_string();
+    nova.loader_type = Some(nome_loader_instancia(tipo.as_ref()));
+    nova.loader_version = tipo.as_ref().and(loader_version);
+    // Sem o instance.json novo, os arquivos instalados não batem com a instância.
+    if let Err(e) = gravar_instancia(&nova) {
+        if let Err(erro_restauracao) = restaurar_backup_versao(&instance.path) {
+            eprintln!("[Versao] {}", erro_restauracao);
+        }
+        return Err(e);
+    }
+
+    let mut relatorio = RelatorioTrocaVersao {
+        versao_anterior: instance.version.clone(),
+        loader_anterior: descricao_loader(
+            instance.loader_type.as_deref(),
+            instance.loader_version.as_deref(),
+        ),
+        versao_nova: nova.version.clone(),
+        loader_novo: descricao_loader(nova.loader_type.as_deref(), nova.loader_version.as_deref()),
+        ..Default::default()
+    };
+    conferir_mods_instalados(&nova, &mut relatorio).await;
+    Ok(relatorio)
+}
+
+/// Troca a versão do Minecraft e/ou o loader de uma instância existente.
+/// A versão anterior fica em um backup até a próxima troca e pode ser
+/// restaurada com `reverter_versao_instancia`. Sem `loader_type`, mantém o
+/// loader atual; sem `loader_version`, usa a versão recomendada.
+#[tauri::command]
+pub(crate) async fn update_instance_version(
+    app: tauri::AppHandle,
+    state: State<'_, LauncherState>,
+    instance_id: String,
+    minecraft_version: String,
+    loader_type: Option<String>,
+    loader_version: Option<String>,
+    task_id: Option<String>,
+) -> Result<RelatorioTrocaVersao, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    conferir_instancia_parada(&state, &instance_id)?;
+
+    let versao_minecraft = minecraft_version.trim().to_string();
+    if versao_minecraft.is_empty() {
+        return Err("Informe a versão do Minecraft.".to_string());
+    }
+    let loader_atual = detectar_loader_normalizado(instance.loader_type.as_deref())
+        .unwrap_or_else(|| "vanilla".to_string());
+    let loader = loader_type
+        .as_deref()
+        .and_then(|loader| detectar_loader_normalizado(Some(loader)))
+        .unwrap_or_else(|| loader_atual.clone());
+    let loader_version = loader_version
+        .map(|versao| versao.trim().to_string())
+        .filter(|versao| !versao.is_empty());
+
+    let mesma_base = loader == loader_atual && versao_minecraft == instance.version;
+    let loader_version = match loader_version {
+        _ if loader == "vanilla" => None,
+        Some(versao) => Some(versao),
+        None if mesma_base => instance.loader_version.clone(),
+        None => Some(
+            super::versoes_loader::versao_loader_recomendada(&loader, &versao_minecraft).await?,
+        ),
+    };
+    if mesma_base && loader_version == instance.loader_version {
+        return Err("A instância já está nessa versão.".to_string());
+    }
+
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "trocar_versao",
+        &format!("Trocando versão de {}", instance.name),
+        Some(&instance_id),
+    )?;
+    println!(
+        "[Versao] {}: {} {} -> {} {} {}",
+        instance_id,
+        instance.version,
+        instance.loader_type.as_deref().unwrap_or("Vanilla"),
+        versao_minecraft,
+        loader,
+        loader_version.as_deref().unwrap_or("")
+    );
+    let resultado = trocar_versao(
+        &instance,
+        &versao_minecraft,
+        &loader,
+        loader_version,
+        &tarefa,
+    )
+    .await;
+    if let Ok(relatorio) = &resultado {
+        println!(
+            "[Versao] {}: {} mod(s) incompatível(is), {} sem verificação",
+            instance_id,
+            relatorio.incompativeis.len(),
+            relatorio.nao_verificados.len()
+        );
+    }
+    tarefa.finalizar(resultado)
+}
+
+/// Versão guardada pela última troca, se ainda houver backup.
+#[tauri::command]
+pub(crate) fn obter_backup_versao_instancia(
+    state: State<LauncherState>,
+    instance_id: String,
+) -> Result<Option<BackupVersao>, String> {
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    Ok(ler_backup_versao(&instance_path))
+}
+
+/// Desfaz a última troca de versão, mantendo as demais configurações atuais.
+#[tauri::command]
+pub(crate) fn reverter_versao_instancia(
+    state: State<LauncherState>,
+    instance_id: String,
+) -> Result<BackupVersao, String> {
+    let mut instance = obter_instancia_por_id(&state, &instance_id)?;
+    conferir_instancia_parada(&state, &instance_id)?;
+
+    let backup = restaurar_backup_versao(&instance.path)?;
+    instance.version = backup.version.clone();
+    instance.mc_type = backup.mc_type.clone();
+    instance.loader_type = backup.loader_type.clone();
+    instance.loader_version = backup.loader_version.clone();
+    gravar_instancia(&instance)?;
+    println!(
+        "[Versao] {}: restaurada a versão {} ({})",
+        instance_id,
+        backup.version,
+        backup.loader_type.as_deref().unwrap_or("Vanilla")
+    );
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn tags(valores: &[&str]) -> Vec<String> {
+        valores.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn instancia_de_teste() -> Instance {
+        let pasta =
+            std::env::temp_dir().join(format!("dome-troca-versao-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(pasta.join("libraries/net/minecraftforge")).unwrap();
+        let json = serde_json::json!({
+            "id": "teste",
+            "name": "Teste",
+            "version": "1.20.1",
+            "mc_type": "forge",
+            "loader_type": "Forge",
+            "loader_version": "47.2.0",
+            "created": "2024-01-01T00:00:00Z",
+            "path": pasta,
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn backup_guarda_e_restaura_a_versao_anterior() {
+        let instance = instancia_de_teste();
+        let pasta = &instance.path;
+        std::fs::write(pasta.join("version_manifest.json"), "1.20.1").unwrap();
+        std::fs::write(pasta.join("forge_manifest.json"), "forge").unwrap();
+        std::fs::write(pasta.join("libraries/net/minecraftforge/forge.jar"), "jar").unwrap();
+
+        criar_backup_versao(&instance).unwrap();
+        assert!(!pasta.join("forge_manifest.json").exists());
+        assert!(!pasta.join("libraries").exists());
+        std::fs::write(pasta.join("version_manifest.json"), "1.20.4").unwrap();
+        std::fs::write(pasta.join("fabric_manifest.json"), "fabric").unwrap();
+
+        let backup = restaurar_backup_versao(pasta).unwrap();
+        assert_eq!(backup.version, "1.20.1");
+        assert_eq!(backup.loader_version.as_deref(), Some("47.2.0"));
+        assert_eq!(
+            std::fs::read_to_string(pasta.join("version_manifest.json")).unwrap(),
+            "1.20.1"
+        );
+        assert!(pasta.join("forge_manifest.json").exists());
+        assert!(!pasta.join("fabric_manifest.json").exists());
+        assert!(pasta
+            .join("libraries/net/minecraftforge/forge.jar")
+            .exists());
+        assert!(!pasta.join(PASTA_BACKUP_VERSAO).exists());
+        let _ = std::fs::remove_dir_all(pasta);
+    }
+
+    #[test]
+    fn aponta_versao_e_loader_incompativeis() {
+        assert!(motivo_incompatibilidade(
+            &tags(&["1.20.1", "1.20.4"]),
+            &tags(&["fabric"]),
+            "1.20.4",
+            "quilt"
+        )
+        .is_none());
+        assert!(motivo_incompatibilidade(&[], &[], "1.21", "forge").is_none());
+        assert!(
+            motivo_incompatibilidade(&tags(&["1.20.1"]), &tags(&["forge"]), "1.20.4", "forge")
+                .unwrap()
+                .contains("1.20.4")
+        );
+        assert!(motivo_incompatibilidade(
+            &tags(&["1.20.4"]),
+            &tags(&["fabric"]),
+            "1.20.4",
+            "forge"
+        )
+        .unwrap()
+        .contains("fabric"));
+    }
+
+    #[test]
+    fn separa_tags_do_curseforge() {
+        let (versoes, loaders) =
+            separar_tags_curseforge(&tags(&["1.20.1", "Forge", "Client", "Java 17", "NeoForge"]));
+        assert_eq!(versoes, tags(&["1.20.1"]));
+        assert_eq!(loaders, tags(&["Forge", "NeoForge"]));
+    }
+}
@@ -0,0 +1,536 @@
+use super::armazenamento_compartilhado::{
+    pasta_assets_store, pasta_store, resolver_biblioteca, resolver_client_jar,
+};
+use super::*;
+use crate::comandos::tarefas::{Tarefa, MENSAGEM_TAREFA_CANCELADA};
+use sha1::{Digest, Sha1};
+
+const TENTATIVAS_DOWNLOAD: u32 = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CategoriaArquivo {
+    ClientJar,
+    Biblioteca,
+    Native,
+    AssetIndex,
+    Asset,
+}
+
+/// Arquivo que a instância precisa ter, com os metadados declarados no manifesto.
+#[derive(Debug, Clone)]
+pub(crate) struct ArquivoEsperado {
+    pub url: String,
+    pub caminho: std::path::PathBuf,
+    pub sha1: Option<String>,
+    pub tamanho: Option<u64>,
+    pub categoria: CategoriaArquivo,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum EstadoArquivo {
+    Ok,
+    Ausente,
+    TamanhoDivergente,
+    HashDivergente,
+}
+
+/// Sem hash completo, a verificação rápida confere apenas existência e tamanho.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModoVerificacao {
+    Rapida,
+    Completa,
+}
+
+pub(crate) fn sha1_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(bytes))
+}
+
+pub(crate) fn sha1_arquivo(caminho: &std::path::Path) -> std::io::Result<String> {
+    let mut arquivo = std::fs::File::open(caminho)?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut arquivo, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Grava o conteúdo em um arquivo temporário ao lado do destino e o renomeia,
+/// para que um download interrompido nunca deixe um arquivo truncado no lugar.
+pub(crate) fn escrever_atomico(caminho: &std::path::Path, bytes: &[u8]) -> Result<(), String> {
+    let pasta = caminho
+        .parent()
+        .ok_or_else(|| format!("Caminho inválido: {}", caminho.display()))?;
+    std::fs::create_dir_all(pasta)
+        .map_err(|e| format!("Erro ao criar pasta {}: {}", pasta.display(), e))?;
+
+    let nome = caminho
+        .file_name()
+        .map(|nome| nome.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temporario = pasta.join(format!(".{}.{}.part", nome, uuid::Uuid::new_v4().simple()));
+
+    let resultado =
+        std::fs::write(&temporario, bytes).and_then(|_| std::fs::rename(&temporario, caminho));
+    if let Err(e) = resultado {
+        let _ = std::fs::remove_file(&temporario);
+        return Err(format!("Erro ao salvar {}: {}", caminho.display(), e));
+    }
+    Ok(())
+}
+
+fn conferir_conteudo(esperado: &ArquivoEsperado, bytes: &[u8]) -> Result<(), String> {
+    if let Some(tamanho) = esperado.tamanho {
+        if bytes.len() as u64 != tamanho {
+            return Err(format!(
+                "tamanho divergente ({} bytes, esperado {})",
+                bytes.len(),
+                tamanho
+            ));
+        }
+    }
+    if let Some(sha1) = esperado.sha1.as_deref() {
+        let obtido = sha1_bytes(bytes);
+        if !obtido.eq_ignore_ascii_case(sha1) {
+            return Err(format!("SHA-1 divergente ({}, esperado {})", obtido, sha1));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn estado_arquivo(esperado: &ArquivoEsperado, modo: ModoVerificacao) -> EstadoArquivo {
+    let Ok(metadados) = std::fs::metadata(&esperado.caminho) else {
+        return EstadoArquivo::Ausente;
+    };
+    if !metadados.is_file() {
+        return EstadoArquivo::Ausente;
+    }
+    if esperado
+        .tamanho
+        .is_some_and(|tamanho| tamanho != metadados.len())
+    {
+        return EstadoArquivo::TamanhoDivergente;
+    }
+    if modo == ModoVerificacao::Completa {
+        if let Some(sha1) = esperado.sha1.as_deref() {
+            match sha1_arquivo(&esperado.caminho) {
+                Ok(obtido) if obtido.eq_ignore_ascii_case(sha1) => {}
+                _ => return EstadoArquivo::HashDivergente,
+            }
+        }
+    }
+    EstadoArquivo::Ok
+}
+
+/// Baixa o arquivo conferindo tamanho e SHA-1 antes de gravá-lo, com novas
+/// tentativas em falhas de rede ou conteúdo divergente.
+pub(crate) async fn baixar_verificado(
+    client: &reqwest::Client,
+    esperado: &ArquivoEsperado,
+) -> Result<(), String> {
+    if esperado.url.trim().is_empty() {
+        return Err(format!(
+            "{} não tem URL de download",
+            esperado.caminho.display()
+        ));
+    }
+
+    let mut ultimo_erro = String::new();
+    for tentativa in 1..=TENTATIVAS_DOWNLOAD {
+        let resultado = async {
+            let resposta = client
+                .get(&esperado.url)
+                .send()
+                .await
+                .and_then(|res| res.error_for_status())
+                .map_err(|e| format!("Erro ao baixar: {}", e))?;
+            let bytes = resposta
+                .bytes()
+                .await
+                .map_err(|e| format!("Erro ao ler bytes: {}", e))?;
+            conferir_conteudo(esperado, &bytes)?;
+            escrever_atomico(&esperado.caminho, &bytes)
+        }
+        .await;
+
+        match resultado {
+            Ok(()) => return Ok(()),
+            Err(erro) => {
+                ultimo_erro = erro;
+                if tentativa < TENTATIVAS_DOWNLOAD {
+                    tokio::time::sleep(std::time::Duration::from_millis(500 * tentativa as u64))
+                        .await;
+                }
+            }
+        }
+    }
+
+    Err(format!(
+        "{} ({} tentativas): {}",
+        esperado.url, TENTATIVAS_DOWNLOAD, ultimo_erro
+    ))
+}
+
+/// Baixa em paralelo e devolve o erro de cada arquivo que falhou. Com uma
+/// tarefa, relata o progresso e interrompe os downloads pendentes ao cancelar.
+pub(crate) async fn baixar_lote_verificado(
+    client: &reqwest::Client,
+    arquivos: Vec<ArquivoEsperado>,
+    concorrencia: usize,
+    tarefa: Option<&Tarefa>,
+) -> Vec<(ArquivoEsperado, String)> {
+    stream::iter(arquivos)
+        .map(|arquivo| async move {
+            let resultado = match tarefa {
+                Some(tarefa) => tokio::select! {
+                    _ = tarefa.cancelamento().cancelled() => Err(MENSAGEM_TAREFA_CANCELADA.to_string()),
+                    resultado = baixar_verificado(client, &arquivo) => resultado,
+                },
+                None => baixar_verificado(client, &arquivo).await,
+            };
+            // Falhas também contam, para o progresso chegar ao fim do lote.
+            if let Some(tarefa) = tarefa {
+                tarefa.avancar(1, arquivo.tamanho.unwrap_or(0));
+            }
+            resultado.err().map(|erro| (arquivo, erro))
+        })
+        .buffer_unordered(concorrencia)
+        .filter_map(|falha| async move { falha })
+        .collect()
+        .await
+}
+
+/// Soma dos tamanhos declarados, usada como total de bytes no progresso.
+pub(crate) fn tamanho_total(arquivos: &[ArquivoEsperado]) -> u64 {
+    arquivos.iter().filter_map(|arquivo| arquivo.tamanho).sum()
+}
+
+fn arquivo_de_classifier(
+    valor: &serde_json::Value,
+    instance_path: &std::path::Path,
+) -> Option<ArquivoEsperado> {
+    Some(ArquivoEsperado {
+        url: valor["url"].as_str()?.to_string(),
+        caminho: resolver_biblioteca(instance_path, valor["path"].as_str()?),
+        sha1: valor["sha1"].as_str().map(str::to_string),
+        tamanho: valor["size"].as_u64(),
+        categoria: CategoriaArquivo::Native,
+    })
+}
+
+/// Client jar e bibliotecas (artifacts e natives da plataforma atual), já
+/// resolvidos para o armazenamento compartilhado ou para a cópia local.
+pub(crate) fn arquivos_client_e_bibliotecas(
+    instance_path: &std::path::Path,
+    details: &VersionDetail,
+) -> Vec<ArquivoEsperado> {
+    let plataforma = crate::plataforma::Plataforma::atual();
+    let mut arquivos = vec![ArquivoEsperado {
+        url: details.downloads.client.url.clone(),
+        caminho: resolver_client_jar(instance_path, details),
+        sha1: Some(details.downloads.client.sha1.clone()).filter(|sha1| !sha1.is_empty()),
+        tamanho: Some(details.downloads.client.size).filter(|tamanho| *tamanho > 0),
+        categoria: CategoriaArquivo::ClientJar,
+    }];
+    let mut caminhos_vistos = std::collections::HashSet::new();
+
+    for lib in &details.libraries {
+        if !plataforma.biblioteca_permitida(lib) {
+            continue;
+        }
+        let Some(downloads) = &lib.downloads else {
+            continue;
+        };
+
+        if let Some(artifact) = &downloads.artifact {
+            if let Some(path) = &artifact.path {
+                arquivos.push(ArquivoEsperado {
+                    url: artifact.url.clone(),
+                    caminho: resolver_biblioteca(instance_path, path),
+                    sha1: artifact.sha1.clone().filter(|sha1| !sha1.is_empty()),
+                    tamanho: artifact.size.filter(|tamanho| *tamanho > 0),
+                    categoria: CategoriaArquivo::Biblioteca,
+                });
+            }
+        }
+
+        if let Some(native) = plataforma
+            .artefato_natives(lib)
+            .and_then(|valor| arquivo_de_classifier(valor, instance_path))
+        {
+            arquivos.push(native);
+        }
+    }
+
+    // Loaders às vezes repetem bibliotecas do vanilla.
+    arquivos.retain(|arquivo| caminhos_vistos.insert(arquivo.caminho.clone()));
+    arquivos
+}
+
+fn arquivo_asset_index(details: &VersionDetail) -> ArquivoEsperado {
+    ArquivoEsperado {
+        url: details.asset_index.url.clone(),
+        caminho: pasta_assets_store()
+            .join("indexes")
+            .join(format!("{}.json", details.asset_index.id)),
+        sha1: Some(details.asset_index.sha1.clone()).filter(|sha1| !sha1.is_empty()),
+        tamanho: Some(details.asset_index.size).filter(|tamanho| *tamanho > 0),
+        categoria: CategoriaArquivo::AssetIndex,
+    }
+}
+
+/// Objetos listados no asset index já presente em disco.
+pub(crate) fn arquivos_assets(index_content: &str) -> Result<Vec<ArquivoEsperado>, String> {
+    arquivos_assets_em(&pasta_assets_store().join("objects"), index_content)
+}
+
+pub(crate) fn arquivos_assets_em(
+    objects_dir: &std::path::Path,
+    index_content: &str,
+) -> Result<Vec<ArquivoEsperado>, String> {
+    let index_json: serde_json::Value = serde_json::from_str(index_content)
+        .map_err(|e| format!("Erro ao parsear asset index: {}", e))?;
+
+    let mut arquivos = Vec::new();
+    let mut hashes_vistos = std::collections::HashSet::new();
+    if let Some(objects) = index_json["objects"].as_object() {
+        for obj in objects.values() {
+            let Some(hash) = obj["hash"].as_str().filter(|hash| hash.len() > 2) else {
+                continue;
+            };
+            if !hashes_vistos.insert(hash.to_string()) {
+                continue;
+            }
+            let prefix = &hash[0..2];
+            arquivos.push(ArquivoEsperado {
+                url: format!(
+                    "https://resources.download.minecraft.net/{}/{}",
+                    prefix, hash
+                ),
+                caminho: objects_dir.join(prefix).join(hash),
+                sha1: Some(hash.to_string()),
+                tamanho: obj["size"].as_u64(),
+                categoria: CategoriaArquivo::Asset,
+            });
+        }
+    }
+    Ok(arquivos)
+}
+
+/// Garante o asset index íntegro em disco e devolve seu conteúdo.
+pub(crate) async fn garantir_asset_index(
+    client: &reqwest::Client,
+    details: &VersionDetail,
+) -> Result<String, String> {
+    let index = arquivo_asset_index(details);
+    if estado_arquivo(&index, ModoVerificacao::Completa) != EstadoArquivo::Ok {
+        println!("Baixando asset index...");
+        baixar_verificado(client, &index)
+            .await
+            .map_err(|e| format!("Erro ao baixar asset index: {}", e))?;
+    }
+    std::fs::read_to_string(&index.caminho).map_err(|e| format!("Erro ao ler asset index: {}", e))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProblemaArquivo {
+    pub caminho: String,
+    pub categoria: CategoriaArquivo,
+    pub estado: EstadoArquivo,
+    pub reparado: bool,
+    pub erro: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelatorioVerificacao {
+    pub total_verificados: usize,
+    pub arquivos_ok: usize,
+    pub problemas: Vec<ProblemaArquivo>,
+}
+
+/// Confere client jar, bibliotecas e assets pelo SHA-1 do manifesto e, se
+/// `reparar` for verdadeiro, baixa novamente os arquivos ausentes ou corrompidos.
+#[tauri::command]
+pub(crate) async fn verify_instance_files(
+    state: State<'_, LauncherState>,
+    instance_id: String,
+    reparar: Option<bool>,
+) -> Result<RelatorioVerificacao, String> {
+    let instancia = obter_instancia_por_id(&state, &instance_id)?;
+    let instance_path = caminho_instancia_por_id(&state, &instance_id)?;
+    let reparar = reparar.unwrap_or(false);
+
+    let details =
+        super::lancamento_jogo::carregar_manifesto_instancia(&instance_path, &instancia).await?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut arquivos = arquivos_client_e_bibliotecas(&instance_path, &details);
+    let index = arquivo_asset_index(&details);
+    let estado_index = estado_arquivo(&index, ModoVerificacao::Completa);
+    let index_content = if estado_index == EstadoArquivo::Ok || reparar {
+        Some(garantir_asset_index(&client, &details).await?)
+    } else {
+        None
+    };
+    if let Some(conteudo) = index_content.as_deref() {
+        arquivos.extend(arquivos_assets(conteudo)?);
+    }
+
+    let mut problemas: Vec<ProblemaArquivo> = Vec::new();
+    if estado_index != EstadoArquivo::Ok {
+        problemas.push(ProblemaArquivo {
+            caminho: caminho_relativo(&instance_path, &index.caminho),
+            categoria: index.categoria,
+            estado: estado_index,
+            reparado: reparar,
+            erro: None,
+        });
+    }
+
+    let verificacoes = tokio::task::spawn_blocking(move || {
+        arquivos
+            .into_iter()
+            .map(|arquivo| {
+                let estado = estado_arquivo(&arquivo, ModoVerificacao::Completa);
+                (arquivo, estado)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Erro ao verificar arquivos: {}", e))?;
+
+    let total_verificados = verificacoes.len() + 1;
+    let mut para_reparar = Vec::new();
+    for (arquivo, estado) in verificacoes {
+        if estado == EstadoArquivo::Ok {
+            continue;
+        }
+        problemas.push(ProblemaArquivo {
+            caminho: caminho_relativo(&instance_path, &arquivo.caminho),
+            categoria: arquivo.categoria,
+            estado,
+            reparado: false,
+            erro: None,
+        });
+        para_reparar.push(arquivo);
+    }
+
+    if reparar && !para_reparar.is_empty() {
+        println!(
+            "[Verificação] Reparando {} arquivo(s) da instância {}...",
+            para_reparar.len(),
+            instance_id
+        );
+        let falhas = baixar_lote_verificado(&client, para_reparar, 20, None).await;
+        for problema in problemas
+            .iter_mut()
+            .filter(|problema| problema.categoria != CategoriaArquivo::AssetIndex)
+        {
+            let falha = falhas.iter().find(|(arquivo, _)| {
+                caminho_relativo(&instance_path, &arquivo.caminho) == problema.caminho
+            });
+            match falha {
+                Some((_, erro)) => problema.erro = Some(erro.clone()),
+                None => problema.reparado = true,
+            }
+        }
+    }
+
+    Ok(RelatorioVerificacao {
+        total_verificados,
+        arquivos_ok: total_verificados - problemas.len(),
+        problemas,
+    })
+}
+
+/// Caminho exibido no relatório: relativo à instância ou ao armazenamento compartilhado.
+fn caminho_relativo(base: &std::path::Path, caminho: &std::path::Path) -> String {
+    let store = pasta_store();
+    caminho
+        .strip_prefix(base)
+        .or_else(|_| caminho.strip_prefix(&store))
+        .unwrap_or(caminho)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    fn arquivo_em(caminho: std::path::PathBuf, conteudo: &[u8]) -> ArquivoEsperado {
+        ArquivoEsperado {
+            url: String::new(),
+            caminho,
+            sha1: Some(sha1_bytes(conteudo)),
+            tamanho: Some(conteudo.len() as u64),
+            categoria: CategoriaArquivo::Biblioteca,
+        }
+    }
+
+    #[test]
+    fn calcula_sha1_conhecido() {
+        assert_eq!(
+            sha1_bytes(b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn detecta_arquivo_ausente_truncado_e_corrompido() {
+        let pasta = std::env::temp_dir().join(format!("dome-verificacao-{}", uuid::Uuid::new_v4()));
+        let caminho = pasta.join("libraries").join("lib.jar");
+        let esperado = arquivo_em(caminho.clone(), b"conteudo original");
+
+        assert_eq!(
+            estado_arquivo(&esperado, ModoVerificacao::Completa),
+            EstadoArquivo::Ausente
+        );
+
+        escrever_atomico(&caminho, b"conteudo").unwrap();
+        assert_eq!(
+            estado_arquivo(&esperado, ModoVerificacao::Rapida),
+            EstadoArquivo::TamanhoDivergente
+        );
+
+        escrever_atomico(&caminho, b"conteudo alterado").unwrap();
+        assert_eq!(
+            estado_arquivo(&esperado, ModoVerificacao::Rapida),
+            EstadoArquivo::Ok
+        );
+        assert_eq!(
+            estado_arquivo(&esperado, ModoVerificacao::Completa),
+            EstadoArquivo::HashDivergente
+        );
+
+        escrever_atomico(&caminho, b"conteudo original").unwrap();
+        assert_eq!(
+            estado_arquivo(&esperado, ModoVerificacao::Completa),
+            EstadoArquivo::Ok
+        );
+
+        // Nenhum temporário deve sobrar após as gravações.
+        let sobras = std::fs::read_dir(caminho.parent().unwrap())
+            .unwrap()
+            .count();
+        assert_eq!(sobras, 1);
+
+        let _ = std::fs::remove_dir_all(pasta);
+    }
+
+    #[test]
+    fn rejeita_conteudo_baixado_divergente() {
+        let esperado = arquivo_em(std::path::PathBuf::from("x"), b"abc");
+        assert!(conferir_conteudo(&esperado, b"abc").is_ok());
+        assert!(conferir_conteudo(&esperado, b"abd")
+            .unwrap_err()
+            .contains("SHA-1"));
+        assert!(conferir_conteudo(&esperado, b"ab")
+            .unwrap_err()
+            .contains("tamanho"));
+    }
+}
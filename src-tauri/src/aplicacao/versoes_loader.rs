@@ -0,0 +1,415 @@
+use super::instancias_criacao::LoaderVersionInfo;
+use super::*;
+use std::cmp::Ordering;
+
+const FABRIC_VERSOES: &str = "https://meta.fabricmc.net/v2/versions/loader";
+const QUILT_VERSOES: &str = "https://meta.quiltmc.org/v3/versions/loader";
+const FORGE_METADATA: &str =
+    "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
+const FORGE_PROMOCOES: &str =
+    "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+const NEOFORGE_VERSOES: &str =
+    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MarcadorVersaoLoader {
+    Recomendada,
+    MaisRecente,
+    Beta,
+}
+
+/// Versões promovidas pelo próprio loader (hoje só o Forge publica).
+#[derive(Debug, Default)]
+struct Promocoes {
+    recomendadas: Vec<String>,
+    mais_recentes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ParteVersao {
+    Numero(u64),
+    Texto(String),
+}
+
+fn partes_versao(versao: &str) -> Vec<ParteVersao> {
+    // Metadados de build (`+build.1`) não entram na ordem, como no semver.
+    let versao = versao.split('+').next().unwrap_or(versao);
+    versao
+        .split(['.', '-', '_'])
+        .filter(|parte| !parte.is_empty())
+        .map(|parte| match parte.parse::<u64>() {
+            Ok(numero) => ParteVersao::Numero(numero),
+            Err(_) => ParteVersao::Texto(parte.to_lowercase()),
+        })
+        .collect()
+}
+
+/// Compara versões de loader componente a componente (`1.9` < `1.20`).
+/// Sufixos como `-beta` ou `-rc1` ficam abaixo da mesma versão sem sufixo.
+pub(super) fn comparar_versoes_loader(a: &str, b: &str) -> Ordering {
+    let (partes_a, partes_b) = (partes_versao(a), partes_versao(b));
+    for indice in 0..partes_a.len().max(partes_b.len()) {
+        let ordem = match (partes_a.get(indice), partes_b.get(indice)) {
+            (Some(ParteVersao::Numero(x)), Some(ParteVersao::Numero(y))) => x.cmp(y),
+            (Some(ParteVersao::Numero(_)), Some(ParteVersao::Texto(_))) => Ordering::Greater,
+            (Some(ParteVersao::Texto(_)), Some(ParteVersao::Numero(_))) => Ordering::Less,
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(ParteVersao::Texto(_)), None) => Ordering::Less,
+            (Some(ParteVersao::Numero(_)), None) => Ordering::Greater,
+            (None, Some(ParteVersao::Texto(_))) => Ordering::Greater,
+            (None, Some(ParteVersao::Numero(_))) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        };
+        if ordem != Ordering::Equal {
+            return ordem;
+        }
+    }
+    Ordering::Equal
+}
+
+fn eh_beta(versao: &str) -> bool {
+    partes_versao(versao).iter().any(|parte| {
+        matches!(parte, ParteVersao::Texto(texto)
+            if ["alpha", "beta", "pre", "rc"].iter().any(|tag| texto.starts_with(tag)))
+    })
+}
+
+/// Prefixo das versões do NeoForge para uma versão do Minecraft: `1.20.4`
+/// vira `20.4.` e `1.21` vira `21.0.`; no versionamento por ano (`26.1`) a
+/// versão do NeoForge repete a do jogo (`26.1.0.`).
+fn prefixo_neoforge(versao_minecraft: &str) -> Option<String> {
+    let partes: Vec<&str> = versao_minecraft.split('.').collect();
+    if partes.is_empty()
+        || partes.len() > 3
+        || !partes
+            .iter()
+            .all(|parte| !parte.is_empty() && parte.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    if partes[0] == "1" {
+        let major = partes.get(1)?;
+        return Some(format!("{}.{}.", major, partes.get(2).unwrap_or(&"0")));
+    }
+    Some(format!(
+        "{}.{}.{}.",
+        partes[0],
+        partes.get(1).unwrap_or(&"0"),
+        partes.get(2).unwrap_or(&"0")
+    ))
+}
+
+fn versoes_maven_metadata(xml: &str) -> Vec<String> {
+    xml.split("<version>")
+        .skip(1)
+        .filter_map(|trecho| trecho.split_once("</version>"))
+        .map(|(versao, _)| versao.trim().to_string())
+        .filter(|versao| !versao.is_empty())
+        .collect()
+}
+
+fn promocoes_forge(json: &serde_json::Value) -> Promocoes {
+    let mut promocoes = Promocoes::default();
+    if let Some(promos) = json["promos"].as_object() {
+        for (chave, valor) in promos {
+            let Some(build) = valor.as_str() else {
+                continue;
+            };
+            if let Some(mc) = chave.strip_suffix("-recommended") {
+                promocoes.recomendadas.push(format!("{}-{}", mc, build));
+            } else if let Some(mc) = chave.strip_suffix("-latest") {
+                promocoes.mais_recentes.push(format!("{}-{}", mc, build));
+            }
+        }
+    }
+    promocoes
+}
+
+/// Ordena da mais nova para a mais antiga e marca recomendada, mais recente e
+/// beta. Sem promoções do loader, a recomendada é a estável mais nova.
+fn ordenar_e_marcar(versoes: &mut Vec<LoaderVersionInfo>, promocoes: &Promocoes) {
+    versoes.sort_by(|a, b| comparar_versoes_loader(&b.version, &a.version));
+    versoes.dedup_by(|a, b| a.version == b.version);
+
+    // Builds antigos do Forge repetem o Minecraft no fim (`1.7.10-10.13.4.1614-1.7.10`).
+    let promovida = |versao: &str, lista: &[String]| {
+        lista
+            .iter()
+            .any(|p| versao == p || versao.starts_with(&format!("{}-", p)))
+    };
+    let usa_promocoes = versoes.iter().any(|v| {
+        promovida(&v.version, &promocoes.recomendadas)
+            || promovida(&v.version, &promocoes.mais_recentes)
+    });
+    let estavel_mais_nova = versoes.iter().position(|v| v.stable != Some(false));
+
+    for (indice, versao) in versoes.iter_mut().enumerate() {
+        let (recomendada, mais_recente) = if usa_promocoes {
+            (
+                promovida(&versao.version, &promocoes.recomendadas),
+                promovida(&versao.version, &promocoes.mais_recentes),
+            )
+        } else {
+            (Some(indice) == estavel_mais_nova, indice == 0)
+        };
+        if recomendada {
+            versao.marcadores.push(MarcadorVersaoLoader::Recomendada);
+        }
+        if mais_recente {
+            versao.marcadores.push(MarcadorVersaoLoader::MaisRecente);
+        }
+        if versao.stable == Some(false) {
+            versao.marcadores.push(MarcadorVersaoLoader::Beta);
+        }
+    }
+}
+
+fn info(version: String, stable: bool) -> LoaderVersionInfo {
+    LoaderVersionInfo {
+        version,
+        stable: Some(stable),
+        marcadores: Vec::new(),
+    }
+}
+
+async fn buscar(client: &reqwest::Client, url: &str, nome: &str) -> Result<String, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Erro ao buscar versões do {}: {}", nome, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "API do {} retornou erro: {}",
+            nome,
+            response.status()
+        ));
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| format!("Erro ao ler resposta do {}: {}", nome, e))
+}
+
+/// Fabric e Quilt: com a versão do Minecraft, o endpoint do jogo só traz
+/// loaders compatíveis (`[{ "loader": { ... } }]`).
+async fn buscar_versoes_meta(
+    client: &reqwest::Client,
+    base: &str,
+    nome: &str,
+    versao_minecraft: Option<&str>,
+) -> Result<Vec<LoaderVersionInfo>, String> {
+    let url = match versao_minecraft {
+        Some(mc) => format!("{}/{}", base, urlencoding::encode(mc)),
+        None => base.to_string(),
+    };
+    let texto = buscar(client, &url, nome).await?;
+    let itens: Vec<serde_json::Value> = serde_json::from_str(&texto)
+        .map_err(|e| format!("Erro ao parsear resposta do {}: {}", nome, e))?;
+    Ok(itens
+        .iter()
+        .filter_map(|item| {
+            let loader = item.get("loader").unwrap_or(item);
+            let versao = loader["version"].as_str()?.to_string();
+            let estavel = loader["stable"]
+                .as_bool()
+                .unwrap_or_else(|| !eh_beta(&versao));
+            Some(info(versao, estavel))
+        })
+        .collect())
+}
+
+async fn buscar_versoes_forge(
+    client: &reqwest::Client,
+    versao_minecraft: Option<&str>,
+) -> Result<(Vec<LoaderVersionInfo>, Promocoes), String> {
+    let xml = buscar(client, FORGE_METADATA, "Forge").await?;
+    let versoes = versoes_maven_metadata(&xml)
+        .into_iter()
+        .filter(|versao| versao_minecraft.is_none_or(|mc| versao.starts_with(&format!("{}-", mc))))
+        .map(|versao| {
+            let estavel = !eh_beta(&versao);
+            info(versao, estavel)
+        })
+        .collect();
+
+    // As promoções só marcam versões; sem elas a lista continua válida.
+    let promocoes = match buscar(client, FORGE_PROMOCOES, "Forge").await {
+        Ok(texto) => serde_json::from_str(&texto)
+            .map(|json| promocoes_forge(&json))
+            .unwrap_or_default(),
+        Err(erro) => {
+            eprintln!("[Forge] Promoções indisponíveis: {}", erro);
+            Promocoes::default()
+        }
+    };
+    Ok((versoes, promocoes))
+}
+
+async fn buscar_versoes_neoforge(
+    client: &reqwest::Client,
+    versao_minecraft: Option<&str>,
+) -> Result<Vec<LoaderVersionInfo>, String> {
+    let prefixo = match versao_minecraft {
+        Some(mc) => match prefixo_neoforge(mc) {
+            Some(prefixo) => Some(prefixo),
+            None => return Ok(Vec::new()),
+        },
+        None => None,
+    };
+    let texto = buscar(client, NEOFORGE_VERSOES, "NeoForge").await?;
+    let json: serde_json::Value = serde_json::from_str(&texto)
+        .map_err(|e| format!("Erro ao parsear resposta do NeoForge: {}", e))?;
+    Ok(json["versions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|versao| versao.as_str())
+        .filter(|versao| {
+            prefixo
+                .as_ref()
+                .is_none_or(|p| versao.starts_with(p.as_str()))
+        })
+        .map(|versao| info(versao.to_string(), !eh_beta(versao)))
+        .collect())
+}
+
+/// Versões do loader, filtradas pela versão do Minecraft quando informada,
+/// da mais nova para a mais antiga e com os marcadores preenchidos.
+pub(super) async fn buscar_versoes_loader(
+    loader: &str,
+    versao_minecraft: Option<&str>,
+) -> Result<Vec<LoaderVersionInfo>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+
+    let (mut versoes, promocoes) = match loader {
+        "fabric" => (
+            buscar_versoes_meta(&client, FABRIC_VERSOES, "Fabric", versao_minecraft).await?,
+            Promocoes::default(),
+        ),
+        "quilt" => (
+            buscar_versoes_meta(&client, QUILT_VERSOES, "Quilt", versao_minecraft).await?,
+            Promocoes::default(),
+        ),
+        "forge" => buscar_versoes_forge(&client, versao_minecraft).await?,
+        "neoforge" => (
+            buscar_versoes_neoforge(&client, versao_minecraft).await?,
+            Promocoes::default(),
+        ),
+        _ => return Err(format!("Tipo de loader desconhecido: {}", loader)),
+    };
+    ordenar_e_marcar(&mut versoes, &promocoes);
+    Ok(versoes)
+}
+
+/// Versão recomendada do loader para a versão do Minecraft, ou a mais nova
+/// quando nenhuma estiver marcada como recomendada.
+pub(super) async fn versao_loader_recomendada(
+    loader: &str,
+    versao_minecraft: &str,
+) -> Result<String, String> {
+    let mut versoes = buscar_versoes_loader(loader, Some(versao_minecraft)).await?;
+    if let Some(indice) = versoes
+        .iter()
+        .position(|v| v.marcadores.contains(&MarcadorVersaoLoader::Recomendada))
+    {
+        return Ok(versoes.swap_remove(indice).version);
+    }
+    versoes
+        .into_iter()
+        .next()
+        .map(|v| v.version)
+        .ok_or_else(|| {
+            format!(
+                "Nenhuma versão do loader {} disponível para Minecraft {}.",
+                loader, versao_minecraft
+            )
+        })
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn ordena_por_versao_semantica() {
+        assert_eq!(comparar_versoes_loader("1.9", "1.20"), Ordering::Less);
+        assert_eq!(
+            comparar_versoes_loader("1.20.1-47.2.0", "1.20.1-47.10.0"),
+            Ordering::Less
+        );
+        assert_eq!(
+            comparar_versoes_loader("21.0.0-beta", "21.0.0"),
+            Ordering::Less
+        );
+        assert_eq!(
+            comparar_versoes_loader("21.0.10-beta", "21.0.9"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            comparar_versoes_loader("0.16.14+build.1", "0.16.14"),
+            Ordering::Equal
+        );
+        assert!(eh_beta("21.4.0-beta") && eh_beta("0.27.0-rc.1") && !eh_beta("47.2.0"));
+    }
+
+    #[test]
+    fn mapeia_versoes_do_neoforge_pelo_minecraft() {
+        assert_eq!(prefixo_neoforge("1.20.4").as_deref(), Some("20.4."));
+        assert_eq!(prefixo_neoforge("1.21").as_deref(), Some("21.0."));
+        assert_eq!(prefixo_neoforge("1.21.1").as_deref(), Some("21.1."));
+        assert_eq!(prefixo_neoforge("26.1").as_deref(), Some("26.1.0."));
+        assert_eq!(prefixo_neoforge("26.1.2").as_deref(), Some("26.1.2."));
+        assert_eq!(prefixo_neoforge("24w14a"), None);
+    }
+
+    #[test]
+    fn filtra_forge_e_marca_promocoes() {
+        let xml = "<metadata><versioning><versions>\
+            <version>1.20.1-47.2.0</version><version>1.20.1-47.10.1</version>\
+            <version>1.20.1-47.1.3</version><version>1.9-12.16.0.1865</version>\
+            <version>1.7.10-10.13.4.1614-1.7.10</version>\
+            </versions></versioning></metadata>";
+        let mut versoes: Vec<LoaderVersionInfo> = versoes_maven_metadata(xml)
+            .into_iter()
+            .filter(|v| v.starts_with("1.20.1-"))
+            .map(|v| info(v, true))
+            .collect();
+        let promocoes = promocoes_forge(&serde_json::json!({
+            "promos": {
+                "1.20.1-recommended": "47.2.0",
+                "1.20.1-latest": "47.10.1",
+                "1.7.10-recommended": "10.13.4.1614"
+            }
+        }));
+        ordenar_e_marcar(&mut versoes, &promocoes);
+        let nomes: Vec<&str> = versoes.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(nomes, ["1.20.1-47.10.1", "1.20.1-47.2.0", "1.20.1-47.1.3"]);
+        assert_eq!(versoes[0].marcadores, [MarcadorVersaoLoader::MaisRecente]);
+        assert_eq!(versoes[1].marcadores, [MarcadorVersaoLoader::Recomendada]);
+        assert!(versoes[2].marcadores.is_empty());
+
+        let mut antigas = vec![info("1.7.10-10.13.4.1614-1.7.10".to_string(), true)];
+        ordenar_e_marcar(&mut antigas, &promocoes);
+        assert_eq!(antigas[0].marcadores, [MarcadorVersaoLoader::Recomendada]);
+
+        // Sem promoções: a estável mais nova é a recomendada.
+        let mut neoforge = vec![
+            info("21.1.5".to_string(), true),
+            info("21.1.10-beta".to_string(), false),
+        ];
+        ordenar_e_marcar(&mut neoforge, &Promocoes::default());
+        assert_eq!(
+            neoforge[0].marcadores,
+            [
+                MarcadorVersaoLoader::MaisRecente,
+                MarcadorVersaoLoader::Beta
+            ]
+        );
+        assert_eq!(neoforge[1].marcadores, [MarcadorVersaoLoader::Recomendada]);
+    }
+}
@@ -13,7 +13,7 @@ use tauri::State;
 use uuid::Uuid;
 
 // Mojang/Microsoft Constants
-const MICROSOFT_CLIENT_ID: &str = "00000000402b5328";
+pub(crate) const MICROSOFT_CLIENT_ID: &str = "00000000402b5328";
 const REQUESTED_SCOPE: &str = "service::user.auth.xboxlive.com::MBI_SSL";
 const AUTH_REPLY_URL: &str = "https://login.live.com/oauth20_desktop.srf";
 const TITLE_ID: &str = "1794566092"; // Launcher Title ID
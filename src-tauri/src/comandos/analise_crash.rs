@@ -0,0 +1,436 @@
+use crate::comandos::instancia_sistema::caminho_instancia_por_id;
+use crate::launcher::LauncherState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{Emitter, State};
+
+/// Evento emitido quando o jogo termina com falha (payload: `CrashReport`).
+pub const EVENTO_CRASH_REPORT: &str = "crash-report";
+/// Resultado da última execução de cada instância.
+pub(crate) const ARQUIVO_ULTIMA_EXECUCAO: &str = ".ultima_execucao.json";
+/// Limite lido de cada arquivo analisado (crash reports grandes repetem stack traces).
+const LIMITE_LEITURA_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CausaCrash {
+    DependenciaAusente,
+    ConflitoMixin,
+    VersaoJavaIncorreta,
+    FaltaMemoria,
+    ModDuplicado,
+    Desconhecida,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub instance_id: String,
+    pub codigo_saida: Option<i32>,
+    pub causa: CausaCrash,
+    pub titulo: String,
+    /// Trecho do arquivo que disparou a regra.
+    pub detalhe: Option<String>,
+    pub sugestao: String,
+    /// Arquivo analisado, relativo à instância.
+    pub arquivo: Option<String>,
+    pub horario: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistroExecucao {
+    pub codigo_saida: Option<i32>,
+    pub finalizada_em: String,
+    pub crash: Option<CrashReport>,
+}
+
+struct RegraCrash {
+    causa: CausaCrash,
+    titulo: &'static str,
+    sugestao: &'static str,
+    padroes: &'static [&'static str],
+}
+
+/// Regras na ordem de prioridade: causas específicas antes das genéricas
+/// (um mod ausente costuma vir acompanhado de erros de mixin, por exemplo).
+/// Erros genéricos da JVM só contam no início da linha de exceção ou em
+/// linhas de log ERROR/FATAL, e não quando um mod apenas os menciona.
+const REGRAS_CRASH: &[RegraCrash] = &[
+    RegraCrash {
+        causa: CausaCrash::ModDuplicado,
+        titulo: "Mod duplicado",
+        sugestao: "Remova uma das cópias do mod indicado na pasta mods.",
+        padroes: &[
+            r"(?i)duplicate mods? (?:found|detected)[^\n]*",
+            r"DuplicateModsFoundException[^\n]*",
+            r"Mod ID '?[\w\-]+'? is provided by multiple[^\n]*",
+            r"(?i)found duplicate mods?[^\n]*",
+        ],
+    },
+    RegraCrash {
+        causa: CausaCrash::DependenciaAusente,
+        titulo: "Dependência de mod ausente",
+        sugestao: "Instale a dependência indicada (na versão pedida) ou remova o mod que a exige.",
+        padroes: &[
+            r"Mod '[^']+' \([^)]+\) [^\n]* requires [^\n]*",
+            r"(?i)requires (?:any version|version [^\n]*) of '?[\w\- ]+'?[^\n]*, which is missing[^\n]*",
+            r"Missing or unsupported mandatory dependencies:[^\n]*(?:\n\s+Mod ID[^\n]*)*",
+            r"(?i)missing mods?:[^\n]*",
+            r#"(?m)^(?:Caused by: |Exception in thread "[^"]*" |\[[^\]]*\] \[[^\]]*/(?:ERROR|FATAL)\][^\n]*?)?java\.lang\.NoClassDefFoundError: [^\n]*"#,
+        ],
+    },
+    RegraCrash {
+        causa: CausaCrash::VersaoJavaIncorreta,
+        titulo: "Versão do Java incompatível",
+        sugestao: "Use a versão de Java exigida por esta versão do Minecraft/loader (ative o Java automático nas configurações).",
+        padroes: &[
+            r"UnsupportedClassVersionError[^\n]*",
+            r"has been compiled by a more recent version of the Java Runtime[^\n]*",
+            r"(?i)requires java \d+[^\n]*",
+            r"Unrecognized VM option[^\n]*",
+        ],
+    },
+    RegraCrash {
+        causa: CausaCrash::FaltaMemoria,
+        titulo: "Memória insuficiente",
+        sugestao: "Aumente a memória alocada para a instância ou feche outros programas. Se o erro for do sistema, reduza a memória para caber na RAM livre.",
+        padroes: &[
+            r"There is insufficient memory for the Java Runtime Environment[^\n]*",
+            r#"(?m)^(?:Caused by: |Exception in thread "[^"]*" |\[[^\]]*\] \[[^\]]*/(?:ERROR|FATAL)\][^\n]*?)?java\.lang\.OutOfMemoryError[^\n]*"#,
+            r"(?m)^# (?:Native memory allocation \(\w+\) failed|Out of Memory Error)[^\n]*",
+            r"Could not reserve enough space for[^\n]*",
+        ],
+    },
+    RegraCrash {
+        causa: CausaCrash::ConflitoMixin,
+        titulo: "Conflito de mixin entre mods",
+        sugestao: "Atualize ou remova o mod citado no erro de mixin; conflitos costumam acontecer entre mods que alteram a mesma parte do jogo.",
+        padroes: &[
+            r"Mixin apply(?: for mod [\w\-]+)? failed[^\n]*",
+            r"MixinApplyError[^\n]*",
+            r"InvalidInjectionException[^\n]*",
+            r"MixinTransformerError[^\n]*",
+            r"InjectionError[^\n]*",
+        ],
+    },
+];
+
+fn regras_compiladas() -> &'static Vec<(&'static RegraCrash, Vec<regex::Regex>)> {
+    static REGRAS: OnceLock<Vec<(&'static RegraCrash, Vec<regex::Regex>)>> = OnceLock::new();
+    REGRAS.get_or_init(|| {
+        REGRAS_CRASH
+            .iter()
+            .map(|regra| {
+                let padroes = regra
+                    .padroes
+                    .iter()
+                    .map(|padrao| regex::Regex::new(padrao).expect("regra de crash válida"))
+                    .collect();
+                (regra, padroes)
+            })
+            .collect()
+    })
+}
+
+/// Aplica as regras ao texto e devolve a causa provável com o trecho encontrado.
+pub fn analisar_texto(texto: &str) -> Option<(CausaCrash, &'static str, &'static str, String)> {
+    regras_compiladas().iter().find_map(|(regra, padroes)| {
+        padroes.iter().find_map(|padrao| {
+            padrao.find(texto).map(|encontrado| {
+                (
+                    regra.causa,
+                    regra.titulo,
+                    regra.sugestao,
+                    encontrado.as_str().trim().to_string(),
+                )
+            })
+        })
+    })
+}
+
+fn arquivos_crash(instance_path: &Path) -> Vec<PathBuf> {
+    let mut arquivos: Vec<PathBuf> = std::fs::read_dir(instance_path.join("crash-reports"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entrada| entrada.path())
+        .filter(|caminho| caminho.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    arquivos.extend(
+        std::fs::read_dir(instance_path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entrada| entrada.path())
+            .filter(|caminho| {
+                caminho
+                    .file_name()
+                    .and_then(|nome| nome.to_str())
+                    .is_some_and(|nome| nome.starts_with("hs_err_pid") && nome.ends_with(".log"))
+            }),
+    );
+    arquivos
+}
+
+/// Arquivos de crash existentes antes do lançamento, para detectar os novos.
+#[derive(Debug, Clone)]
+pub struct InstantaneoCrash {
+    existentes: HashSet<PathBuf>,
+}
+
+impl InstantaneoCrash {
+    pub fn capturar(instance_path: &Path) -> Self {
+        Self {
+            existentes: arquivos_crash(instance_path).into_iter().collect(),
+        }
+    }
+
+    /// Arquivos criados depois do instantâneo, do mais recente ao mais antigo.
+    fn novos(&self, instance_path: &Path) -> Vec<PathBuf> {
+        let mut novos: Vec<(std::time::SystemTime, PathBuf)> = arquivos_crash(instance_path)
+            .into_iter()
+            .filter(|caminho| !self.existentes.contains(caminho))
+            .map(|caminho| {
+                let modificado = std::fs::metadata(&caminho)
+                    .and_then(|metadata| metadata.modified())
+                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+                (modificado, caminho)
+            })
+            .collect();
+        novos.sort_by_key(|(modificado, _)| std::cmp::Reverse(*modificado));
+        novos.into_iter().map(|(_, caminho)| caminho).collect()
+    }
+}
+
+fn ler_limitado(caminho: &Path) -> Option<String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut arquivo = std::fs::File::open(caminho).ok()?;
+    let tamanho = arquivo.metadata().ok()?.len();
+    // Logs crescem no fim: lê o final se o arquivo for grande demais.
+    if tamanho > LIMITE_LEITURA_BYTES {
+        arquivo
+            .seek(SeekFrom::Start(tamanho - LIMITE_LEITURA_BYTES))
+            .ok()?;
+    }
+    let mut bytes = Vec::new();
+    arquivo
+        .take(LIMITE_LEITURA_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    Some(String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Decide se a execução terminou em crash e monta o relatório. Uma saída com
+/// código 0 e sem novos arquivos de crash é considerada normal.
+pub fn analisar_fim_execucao(
+    instance_id: &str,
+    instance_path: &Path,
+    codigo_saida: Option<i32>,
+    instantaneo: &InstantaneoCrash,
+) -> Option<CrashReport> {
+    let novos = instantaneo.novos(instance_path);
+    if codigo_saida == Some(0) && novos.is_empty() {
+        return None;
+    }
+
+    // Crash reports e hs_err primeiro; depois a saída capturada e o latest.log.
+    let mut candidatos = novos;
+    candidatos.push(
+        instance_path
+            .join("logs")
+            .join(crate::comandos::saida_jogo::ARQUIVO_SAIDA_LAUNCHER),
+    );
+    candidatos.push(instance_path.join("logs").join("latest.log"));
+
+    let relativo = |caminho: &Path| {
+        caminho
+            .strip_prefix(instance_path)
+            .unwrap_or(caminho)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let arquivo_principal = candidatos
+        .first()
+        .filter(|caminho| caminho.is_file())
+        .cloned();
+
+    let analise = candidatos.iter().find_map(|caminho| {
+        let texto = ler_limitado(caminho)?;
+        analisar_texto(&texto).map(|resultado| (caminho.clone(), resultado))
+    });
+
+    let horario = chrono::Utc::now().to_rfc3339();
+    let relatorio = match analise {
+        Some((caminho, (causa, titulo, sugestao, detalhe))) => CrashReport {
+            instance_id: instance_id.to_string(),
+            codigo_saida,
+            causa,
+            titulo: titulo.to_string(),
+            detalhe: Some(detalhe),
+            sugestao: sugestao.to_string(),
+            arquivo: Some(relativo(&caminho)),
+            horario,
+        },
+        None => CrashReport {
+            instance_id: instance_id.to_string(),
+            codigo_saida,
+            causa: CausaCrash::Desconhecida,
+            titulo: "O jogo fechou inesperadamente".to_string(),
+            detalhe: None,
+            sugestao: "Abra o crash report ou o log para ver o erro. Remover mods adicionados recentemente costuma ajudar a isolar a causa.".to_string(),
+            arquivo: arquivo_principal.as_deref().map(relativo),
+            horario,
+        },
+    };
+    Some(relatorio)
+}
+
+/// Registra o código de saída, analisa um possível crash e avisa a interface.
+/// Execuções encerradas pelo usuário são registradas sem análise.
+pub fn registrar_fim_execucao(
+    app: Option<&tauri::AppHandle>,
+    instance_id: &str,
+    instance_path: &Path,
+    codigo_saida: Option<i32>,
+    encerrado_pelo_usuario: bool,
+    instantaneo: &InstantaneoCrash,
+) {
+    let crash = if encerrado_pelo_usuario {
+        None
+    } else {
+        analisar_fim_execucao(instance_id, instance_path, codigo_saida, instantaneo)
+    };
+    let registro = RegistroExecucao {
+        codigo_saida,
+        finalizada_em: chrono::Utc::now().to_rfc3339(),
+        crash: crash.clone(),
+    };
+    if let Ok(conteudo) = serde_json::to_string_pretty(&registro) {
+        if let Err(erro) = std::fs::write(instance_path.join(ARQUIVO_ULTIMA_EXECUCAO), conteudo) {
+            eprintln!(
+                "[Crash] Aviso: falha ao salvar resultado da execução de {}: {}",
+                instance_id, erro
+            );
+        }
+    }
+
+    if let Some(crash) = crash {
+        println!(
+            "[Crash] {} terminou com falha ({:?}): {}",
+            instance_id, crash.causa, crash.titulo
+        );
+        if let Some(app) = app {
+            let _ = app.emit(EVENTO_CRASH_REPORT, crash);
+        }
+    }
+}
+
+/// Código de saída e crash (se houve) da última execução da instância.
+#[tauri::command]
+pub fn get_last_run_result(
+    state: State<'_, LauncherState>,
+    instance_id: String,
+) -> Result<Option<RegistroExecucao>, String> {
+    let caminho = caminho_instancia_por_id(&state, &instance_id)?.join(ARQUIVO_ULTIMA_EXECUCAO);
+    if !caminho.exists() {
+        return Ok(None);
+    }
+    let conteudo = std::fs::read_to_string(&caminho)
+        .map_err(|e| format!("Erro ao ler resultado da execução: {}", e))?;
+    serde_json::from_str(&conteudo)
+        .map(Some)
+        .map_err(|e| format!("Erro ao parsear resultado da execução: {}", e))
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn identifica_causas_conhecidas() {
+        let casos = [
+            (
+                "net.fabricmc.loader.impl.FormattedException: Mod resolution encountered an incompatible mod set!\n - Mod 'Sodium Extra' (sodium-extra) 0.5.1 requires any version of 'sodium', which is missing!",
+                CausaCrash::DependenciaAusente,
+            ),
+            (
+                "Caused by: org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: An unexpected critical error was encountered",
+                CausaCrash::ConflitoMixin,
+            ),
+            (
+                "java.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been compiled by a more recent version of the Java Runtime (class file version 65.0)",
+                CausaCrash::VersaoJavaIncorreta,
+            ),
+            (
+                "java.lang.OutOfMemoryError: Java heap space",
+                CausaCrash::FaltaMemoria,
+            ),
+            (
+                "net.minecraftforge.fml.ModLoadingException: Found duplicate mods: jei",
+                CausaCrash::ModDuplicado,
+            ),
+        ];
+        for (texto, esperado) in casos {
+            assert_eq!(
+                analisar_texto(texto).map(|r| r.0),
+                Some(esperado),
+                "{}",
+                texto
+            );
+        }
+        assert!(analisar_texto("[12:00:00] [main/INFO]: Stopping!").is_none());
+        // Menções soltas em logs de mods não são o erro que fechou o jogo.
+        assert!(analisar_texto(
+            "[12:00:00] [Render thread/WARN]: Texture atlas is running out of memory, shrinking"
+        )
+        .is_none());
+        assert!(analisar_texto(
+            "[12:00:00] [main/DEBUG]: Optional class skipped (NoClassDefFoundError: optifine/Config)"
+        )
+        .is_none());
+        assert_eq!(
+            analisar_texto(
+                "Caused by: java.lang.NoClassDefFoundError: net/fabricmc/api/ModInitializer"
+            )
+            .map(|r| r.0),
+            Some(CausaCrash::DependenciaAusente)
+        );
+        assert_eq!(
+            analisar_texto(
+                "Exception in thread \"Render thread\" java.lang.OutOfMemoryError: Java heap space"
+            )
+            .map(|r| r.0),
+            Some(CausaCrash::FaltaMemoria)
+        );
+    }
+
+    #[test]
+    fn detecta_crash_report_novo_apos_execucao() {
+        let pasta = std::env::temp_dir().join(format!("dome-crash-{}", uuid::Uuid::new_v4()));
+        let crash_reports = pasta.join("crash-reports");
+        std::fs::create_dir_all(&crash_reports).unwrap();
+        std::fs::write(
+            crash_reports.join("crash-antigo.txt"),
+            "java.lang.OutOfMemoryError",
+        )
+        .unwrap();
+
+        let instantaneo = InstantaneoCrash::capturar(&pasta);
+        assert!(analisar_fim_execucao("a", &pasta, Some(0), &instantaneo).is_none());
+
+        std::fs::write(
+            pasta.join("hs_err_pid123.log"),
+            "# There is insufficient memory for the Java Runtime Environment to continue.",
+        )
+        .unwrap();
+        let relatorio = analisar_fim_execucao("a", &pasta, Some(1), &instantaneo).unwrap();
+        assert_eq!(relatorio.causa, CausaCrash::FaltaMemoria);
+        assert_eq!(relatorio.arquivo.as_deref(), Some("hs_err_pid123.log"));
+        assert_eq!(relatorio.codigo_saida, Some(1));
+
+        let _ = std::fs::remove_dir_all(pasta);
+    }
+}
@@ -30,6 +30,7 @@ pub struct GlobalSettings {
     pub show_snapshots: bool,  // mostrar snapshots na lista de versões
     pub discord_rpc_ativo: bool,
     pub cor_destaque: String,
+    pub capturar_saida_jogo: bool, // modo gerenciado: captura stdout/stderr do jogo
 }
 
 impl Default for GlobalSettings {
@@ -56,28 +57,34 @@ impl Default for GlobalSettings {
             show_snapshots: false,
             discord_rpc_ativo: true,
             cor_destaque: "verde".to_string(),
+            capturar_saida_jogo: false,
         }
     }
 }
 
 fn get_settings_path() -> std::path::PathBuf {
-    std::env::var("APPDATA")
-        .map(|app_data| {
-            std::path::PathBuf::from(app_data)
-                .join("dome")
-                .join("settings.json")
-        })
-        .unwrap_or_else(|_| std::path::PathBuf::from("settings.json"))
+    crate::plataforma::pasta_dados_launcher()
+        .map(|pasta| pasta.join("settings.json"))
+        .unwrap_or_else(|| std::path::PathBuf::from("settings.json"))
 }
 
 fn get_runtime_dir() -> std::path::PathBuf {
-    std::env::var("APPDATA")
-        .map(|app_data| {
-            std::path::PathBuf::from(app_data)
-                .join("dome")
-                .join("runtime")
-        })
-        .unwrap_or_else(|_| std::path::PathBuf::from("runtime"))
+    crate::plataforma::pasta_dados_launcher()
+        .map(|pasta| pasta.join("runtime"))
+        .unwrap_or_else(|| std::path::PathBuf::from("runtime"))
+}
+
+/// Configurações globais salvas pelo usuário, sem cair nos padrões.
+pub(crate) fn carregar_settings_salvas() -> Option<GlobalSettings> {
+    let path = get_settings_path();
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(settings) => Some(settings),
+        Err(e) => {
+            eprintln!("[Configurações] Aviso: settings.json inválido: {}", e);
+            None
+        }
+    }
 }
 
 #[tauri::command]
@@ -124,14 +131,7 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInfo>, String> {
         if let Ok(entries) = std::fs::read_dir(&runtime_dir) {
             for entry in entries.flatten() {
                 if entry.path().is_dir() {
-                    let java_exe = entry.path().join("bin").join("javaw.exe");
-                    let java_exe_alt = entry.path().join("bin").join("java.exe");
-                    let exe = if java_exe.exists() {
-                        java_exe
-                    } else {
-                        java_exe_alt
-                    };
-                    if exe.exists() {
+                    if let Some(exe) = executavel_java_em(&entry.path()) {
                         let path_str = entry.path().to_string_lossy().to_string();
                         if checked_paths.insert(path_str.clone()) {
                             if let Some(info) = probe_java(&exe, true).await {
@@ -147,40 +147,26 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInfo>, String> {
     // 2. Verificar JAVA_HOME e outras variáveis de ambiente
     for env_key in &["JAVA_HOME", "JRE_HOME", "JDK_HOME"] {
         if let Ok(val) = std::env::var(env_key) {
-            let java_exe = std::path::PathBuf::from(&val).join("bin").join("java.exe");
-            if java_exe.exists() && checked_paths.insert(val.clone()) {
-                if let Some(info) = probe_java(&java_exe, false).await {
-                    javas.push(info);
+            if let Some(java_exe) = executavel_java_em(std::path::Path::new(&val)) {
+                if checked_paths.insert(val.clone()) {
+                    if let Some(info) = probe_java(&java_exe, false).await {
+                        javas.push(info);
+                    }
                 }
             }
         }
     }
 
-    // 3. Verificar pastas padrão do Windows
-    let drive_letters = vec!["C", "D", "E"];
-    let search_dirs = vec![
-        "Program Files\\Java",
-        "Program Files\\Eclipse Adoptium",
-        "Program Files\\Eclipse Foundation",
-        "Program Files\\AdoptOpenJDK",
-        "Program Files\\Amazon Corretto",
-        "Program Files\\Microsoft",
-        "Program Files\\Zulu",
-    ];
-
-    for drive in &drive_letters {
-        for dir in &search_dirs {
-            let base = std::path::PathBuf::from(format!("{}:\\{}", drive, dir));
-            if base.exists() {
-                if let Ok(entries) = std::fs::read_dir(&base) {
-                    for entry in entries.flatten() {
-                        if entry.path().is_dir() {
-                            let java_exe = entry.path().join("bin").join("java.exe");
-                            let path_str = entry.path().to_string_lossy().to_string();
-                            if java_exe.exists() && checked_paths.insert(path_str.clone()) {
-                                if let Some(info) = probe_java(&java_exe, false).await {
-                                    javas.push(info);
-                                }
+    // 3. Verificar pastas padrão do sistema operacional
+    for base in crate::plataforma::pastas_padrao_java() {
+        if let Ok(entries) = std::fs::read_dir(&base) {
+            for entry in entries.flatten() {
+                if entry.path().is_dir() {
+                    let path_str = entry.path().to_string_lossy().to_string();
+                    if let Some(java_exe) = executavel_java_em(&entry.path()) {
+                        if checked_paths.insert(path_str) {
+                            if let Some(info) = probe_java(&java_exe, false).await {
+                                javas.push(info);
                             }
                         }
                     }
@@ -246,6 +232,25 @@ fn get_required_java_major(mc_version: &str) -> u32 {
     8
 }
 
+/// Localiza o executável do Java a partir de um caminho configurado, que pode
+/// apontar direto para o binário ou para a pasta raiz (JAVA_HOME) do runtime.
+/// No macOS também aceita o bundle do JDK (`Contents/Home`).
+pub(crate) fn executavel_java_em(caminho: &std::path::Path) -> Option<std::path::PathBuf> {
+    if caminho.is_file() {
+        return Some(caminho.to_path_buf());
+    }
+
+    let executaveis = crate::plataforma::SistemaOperacional::atual().executaveis_java();
+    [caminho.to_path_buf(), caminho.join("Contents").join("Home")]
+        .iter()
+        .flat_map(|home| {
+            executaveis
+                .iter()
+                .map(move |nome| home.join("bin").join(nome))
+        })
+        .find(|exe| exe.is_file())
+}
+
 /// Probar un ejecutable de Java para obtener info de versión
 pub async fn probe_java(exe_path: &std::path::Path, is_managed: bool) -> Option<JavaInfo> {
     let mut comando_probe = tokio::process::Command::new(exe_path);
@@ -322,10 +327,12 @@ pub async fn install_java(major: u32) -> Result<JavaInfo, String> {
     let client = reqwest::Client::new();
     let arch = if cfg!(target_arch = "aarch64") {
         "aarch64"
+    } else if cfg!(target_arch = "x86") {
+        "x32"
     } else {
         "x64"
     };
-    let os = "windows";
+    let os = crate::plataforma::SistemaOperacional::atual().nome_adoptium();
 
     println!(
         "[Java] Buscando JDK {} do Adoptium ({} {})...",
@@ -347,7 +354,7 @@ pub async fn install_java(major: u32) -> Result<JavaInfo, String> {
         .await
         .map_err(|e| format!("Erro ao parsear resposta: {}", e))?;
 
-    // Encontrar o binário correto (JDK, Windows, x64)
+    // Encontrar o binário correto (JDK do sistema e arquitetura atuais)
     let target = res
         .iter()
         .find(|entry| {
@@ -394,10 +401,43 @@ pub async fn install_java(major: u32) -> Result<JavaInfo, String> {
 
     println!("[Java] Extraindo {}...", file_name);
 
-    // Extrair o ZIP
+    // Windows usa ZIP; Linux e macOS recebem tar.gz.
     let extract_dir = runtime_dir.clone();
+    let resultado_extracao = if file_name.ends_with(".tar.gz") {
+        extrair_jdk_tar_gz(&archive_path, &extract_dir)
+    } else {
+        extrair_jdk_zip(&archive_path, &extract_dir)
+    };
+
+    // Limpar arquivo baixado
+    std::fs::remove_file(&archive_path).ok();
+    let root_folder = resultado_extracao?;
+
+    // Verificar a instalação
+    let java_home = extract_dir.join(&root_folder);
+    let java_exe = executavel_java_em(&java_home)
+        .ok_or_else(|| format!("Java não encontrado após extração em {:?}", java_home))?;
+
+    println!(
+        "[Java] JDK {} instalado com sucesso em {:?}",
+        major, java_home
+    );
+
+    // Probar a instalação
+    let info = probe_java(&java_exe, true)
+        .await
+        .ok_or("Falha ao verificar Java instalado")?;
+
+    Ok(info)
+}
+
+/// Extrai o ZIP do JDK e devolve o nome da pasta raiz.
+fn extrair_jdk_zip(
+    archive_path: &std::path::Path,
+    extract_dir: &std::path::Path,
+) -> Result<String, String> {
     let file =
-        std::fs::File::open(&archive_path).map_err(|e| format!("Erro ao abrir arquivo: {}", e))?;
+        std::fs::File::open(archive_path).map_err(|e| format!("Erro ao abrir arquivo: {}", e))?;
 
     let mut zip_archive =
         zip::ZipArchive::new(file).map_err(|e| format!("Erro ao abrir ZIP: {}", e))?;
@@ -411,12 +451,14 @@ pub async fn install_java(major: u32) -> Result<JavaInfo, String> {
         name.split('/').next().unwrap_or("").to_string()
     };
 
-    // Extrair
     for i in 0..zip_archive.len() {
         let mut entry = zip_archive
             .by_index(i)
             .map_err(|e| format!("Erro no ZIP: {}", e))?;
-        let outpath = extract_dir.join(entry.name());
+        let Some(relativo) = entry.enclosed_name() else {
+            continue;
+        };
+        let outpath = extract_dir.join(relativo);
 
         if entry.is_dir() {
             std::fs::create_dir_all(&outpath).ok();
@@ -431,31 +473,48 @@ pub async fn install_java(major: u32) -> Result<JavaInfo, String> {
         }
     }
 
-    // Limpar arquivo ZIP
-    std::fs::remove_file(&archive_path).ok();
-
-    // Verificar a instalação
-    let java_home = extract_dir.join(&root_folder);
-    let java_exe = java_home.join("bin").join("java.exe");
+    Ok(root_folder)
+}
 
-    if !java_exe.exists() {
-        return Err(format!(
-            "Java não encontrado após extração em {:?}",
-            java_exe
-        ));
-    }
+/// Extrai o tar.gz do JDK (preservando permissões de execução) e devolve o
+/// nome da pasta raiz.
+fn extrair_jdk_tar_gz(
+    archive_path: &std::path::Path,
+    extract_dir: &std::path::Path,
+) -> Result<String, String> {
+    let abrir = || -> Result<tar::Archive<flate2::read::GzDecoder<std::fs::File>>, String> {
+        let file = std::fs::File::open(archive_path)
+            .map_err(|e| format!("Erro ao abrir arquivo: {}", e))?;
+        Ok(tar::Archive::new(flate2::read::GzDecoder::new(file)))
+    };
 
-    println!(
-        "[Java] JDK {} instalado com sucesso em {:?}",
-        major, java_home
-    );
+    // Encontrar o nome da pasta raiz dentro do tar.gz
+    let root_folder = {
+        let mut archive = abrir()?;
+        let mut entries = archive
+            .entries()
+            .map_err(|e| format!("Erro ao ler tar.gz: {}", e))?;
+        let first = entries
+            .next()
+            .ok_or("tar.gz vazio")?
+            .map_err(|e| format!("Erro no tar.gz: {}", e))?;
+        let path = first
+            .path()
+            .map_err(|e| format!("Erro no tar.gz: {}", e))?
+            .into_owned();
+        path.components()
+            .find_map(|componente| match componente {
+                std::path::Component::Normal(nome) => Some(nome.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    };
 
-    // Probar a instalação
-    let info = probe_java(&java_exe, true)
-        .await
-        .ok_or("Falha ao verificar Java instalado")?;
+    abrir()?
+        .unpack(extract_dir)
+        .map_err(|e| format!("Erro ao extrair: {}", e))?;
 
-    Ok(info)
+    Ok(root_folder)
 }
 
 async fn garantir_java_compativel(mc_version: &str, required_major: u32) -> Result<String, String> {
@@ -466,8 +525,7 @@ async fn garantir_java_compativel(mc_version: &str, required_major: u32) -> Resu
     if !settings.auto_java {
         if let Some(ref path) = settings.java_path {
             if !path.is_empty() {
-                let java_exe = std::path::PathBuf::from(path).join("bin").join("java.exe");
-                if java_exe.exists() {
+                if let Some(java_exe) = executavel_java_em(std::path::Path::new(path)) {
                     return Ok(java_exe.to_string_lossy().to_string());
                 }
             }
@@ -496,16 +554,11 @@ async fn garantir_java_compativel(mc_version: &str, required_major: u32) -> Resu
             "[Java] Encontrado Java {} ({}) em {}",
             java.version, java.vendor, java.path
         );
-        let exe_path = std::path::PathBuf::from(&java.path)
-            .join("bin")
-            .join("javaw.exe");
-        if exe_path.exists() {
+        if let Some(exe_path) = executavel_java_em(std::path::Path::new(&java.path)) {
             return Ok(exe_path.to_string_lossy().to_string());
         }
-        let exe_path = std::path::PathBuf::from(&java.path)
-            .join("bin")
-            .join("java.exe");
-        return Ok(exe_path.to_string_lossy().to_string());
+        // Java encontrado apenas pelo PATH
+        return Ok(java.path.clone());
     }
 
     // 3. Se auto_java, baixar automaticamente
@@ -514,9 +567,8 @@ async fn garantir_java_compativel(mc_version: &str, required_major: u32) -> Resu
         required_major
     );
     let installed = install_java(required_major).await?;
-    let exe_path = std::path::PathBuf::from(&installed.path)
-        .join("bin")
-        .join("javaw.exe");
+    let exe_path = executavel_java_em(std::path::Path::new(&installed.path))
+        .ok_or_else(|| format!("Java instalado não encontrado em {}", installed.path))?;
     Ok(exe_path.to_string_lossy().to_string())
 }
 
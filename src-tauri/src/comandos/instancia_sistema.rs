@@ -18,7 +18,10 @@ fn identificador_instancia_valido(id: &str) -> bool {
     let mut componentes = std::path::Path::new(id).components();
     let componente_valido = matches!(componentes.next(), Some(std::path::Component::Normal(_)))
         && componentes.next().is_none();
-    !id.is_empty() && componente_valido && id != "." && id != ".."
+    // Separadores e drives do Windows não são componentes no Linux/macOS, então
+    // precisam ser rejeitados explicitamente para o id valer em qualquer sistema.
+    let contem_separador_windows = id.contains(['\\', ':']);
+    !id.is_empty() && componente_valido && !contem_separador_windows && id != "." && id != ".."
 }
 
 fn normalizar_caminho_processo(caminho: &std::path::Path) -> String {
@@ -62,12 +65,12 @@ pub(crate) fn validar_caminho_dentro_raiz(
 }
 
 pub(crate) fn nome_arquivo_valido_log(nome: &str) -> bool {
-    nome.ends_with(".log") || nome.ends_with(".txt")
+    nome.ends_with(".log") || nome.ends_with(".log.gz") || nome.ends_with(".txt")
 }
 
 // ===== FUNÇÕES DE MONITORAMENTO DO MINECRAFT =====
 
-fn obter_mapa_instancias_em_execucao(
+pub(crate) fn obter_mapa_instancias_em_execucao(
     state: &LauncherState,
     ids_instancia: &[String],
 ) -> Result<std::collections::HashMap<String, bool>, String> {
@@ -215,6 +218,8 @@ pub fn kill_instance(instance_id: String, state: State<LauncherState>) -> Result
     system.refresh_processes();
 
     let mut finalizados = 0usize;
+    // Marcado antes do kill: o processo pode terminar antes desta função voltar.
+    state.marcar_encerramento_pedido(&instance_id);
 
     if let Some(pid) = state.obter_pid_instancia(&instance_id) {
         let pid_sistema = sysinfo::Pid::from_u32(pid);
@@ -248,6 +253,7 @@ pub fn kill_instance(instance_id: String, state: State<LauncherState>) -> Result
     }
 
     if finalizados == 0 {
+        state.consumir_encerramento_pedido(&instance_id);
         return Err("Nenhum processo do Minecraft foi encontrado para essa instância.".to_string());
     }
 
@@ -267,13 +273,38 @@ pub fn kill_instance(instance_id: String, state: State<LauncherState>) -> Result
 #[serde(rename_all = "camelCase")]
 pub struct WorldInfo {
     pub name: String,
+    pub folder_name: String,
     pub path: String,
     pub game_mode: String,
+    pub hardcore: bool,
     pub difficulty: String,
+    /// Seed como texto: valores `i64` perdem precisão no JavaScript.
+    pub seed: Option<String>,
     pub last_played: String,
+    pub last_played_timestamp: Option<i64>,
+    pub data_version: Option<i32>,
+    pub game_version: Option<String>,
     pub size_on_disk: String,
+    pub size_bytes: u64,
+    pub icon: Option<String>,
+    /// Preenchido quando o `level.dat` não pôde ser lido; os demais metadados ficam vazios.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct MetadadosLevelDat {
+    nome: Option<String>,
+    modo_jogo: Option<i32>,
+    hardcore: bool,
+    dificuldade: Option<i8>,
+    seed: Option<i64>,
+    ultimo_acesso_ms: Option<i64>,
+    versao_dados: Option<i32>,
+    versao_jogo: Option<String>,
 }
 
+const LIMITE_ICONE_MUNDO_BYTES: u64 = 4 * 1024 * 1024;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerInfo {
@@ -504,6 +535,161 @@ fn salvar_servidores_instancia(
     Ok(())
 }
 
+fn ler_level_dat(caminho: &std::path::Path) -> Result<MetadadosLevelDat, String> {
+    let dados = std::fs::read(caminho).map_err(|e| format!("Erro ao ler level.dat: {}", e))?;
+    let (raiz, _) = quartz_nbt::io::read_nbt(
+        &mut std::io::Cursor::new(dados),
+        quartz_nbt::io::Flavor::GzCompressed,
+    )
+    .map_err(|e| format!("level.dat corrompido: {}", e))?;
+
+    let dados = raiz
+        .get::<_, &quartz_nbt::NbtCompound>("Data")
+        .map_err(|e| format!("level.dat sem o bloco Data: {}", e))?;
+
+    // Até a 1.15 a seed ficava em `RandomSeed`; depois passou para `WorldGenSettings`.
+    let seed = dados.get::<_, i64>("RandomSeed").ok().or_else(|| {
+        dados
+            .get::<_, &quartz_nbt::NbtCompound>("WorldGenSettings")
+            .and_then(|configuracoes| configuracoes.get::<_, i64>("seed"))
+            .ok()
+    });
+
+    Ok(MetadadosLevelDat {
+        nome: dados
+            .get::<_, &str>("LevelName")
+            .ok()
+            .map(|nome| nome.trim().to_string())
+            .filter(|nome| !nome.is_empty()),
+        modo_jogo: dados.get::<_, i32>("GameType").ok(),
+        hardcore: dados
+            .get::<_, i8>("hardcore")
+            .map(|v| v != 0)
+            .unwrap_or(false),
+        dificuldade: dados.get::<_, i8>("Difficulty").ok(),
+        seed,
+        ultimo_acesso_ms: dados.get::<_, i64>("LastPlayed").ok(),
+        versao_dados: dados.get::<_, i32>("DataVersion").ok(),
+        versao_jogo: dados
+            .get::<_, &quartz_nbt::NbtCompound>("Version")
+            .and_then(|versao| versao.get::<_, &str>("Name"))
+            .ok()
+            .map(|nome| nome.to_string()),
+    })
+}
+
+fn rotulo_modo_jogo(modo: Option<i32>) -> String {
+    match modo {
+        Some(0) => "Survival",
+        Some(1) => "Creative",
+        Some(2) => "Adventure",
+        Some(3) => "Spectator",
+        _ => "Desconhecido",
+    }
+    .to_string()
+}
+
+fn rotulo_dificuldade(dificuldade: Option<i8>) -> String {
+    match dificuldade {
+        Some(0) => "Peaceful",
+        Some(1) => "Easy",
+        Some(2) => "Normal",
+        Some(3) => "Hard",
+        _ => "Desconhecida",
+    }
+    .to_string()
+}
+
+fn formatar_ultimo_acesso(timestamp_ms: Option<i64>) -> String {
+    timestamp_ms
+        .filter(|valor| *valor > 0)
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|data| {
+            data.with_timezone(&chrono::Local)
+                .format("%d/%m/%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "Nunca".to_string())
+}
+
+fn formatar_tamanho_bytes(bytes: u64) -> String {
+    const UNIDADES: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut valor = bytes as f64;
+    let mut unidade = 0;
+    while valor >= 1024.0 && unidade < UNIDADES.len() - 1 {
+        valor /= 1024.0;
+        unidade += 1;
+    }
+
+    if unidade == 0 {
+        format!("{} {}", bytes, UNIDADES[0])
+    } else {
+        format!("{:.1} {}", valor, UNIDADES[unidade])
+    }
+}
+
+fn tamanho_diretorio_recursivo(caminho: &std::path::Path) -> u64 {
+    let Ok(entradas) = std::fs::read_dir(caminho) else {
+        return 0;
+    };
+
+    entradas
+        .flatten()
+        .map(|entrada| match entrada.path().symlink_metadata() {
+            Ok(metadados) if metadados.is_dir() => tamanho_diretorio_recursivo(&entrada.path()),
+            Ok(metadados) if metadados.is_file() => metadados.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn ler_icone_mundo(pasta_mundo: &std::path::Path) -> Option<String> {
+    use base64::Engine as _;
+
+    let caminho = pasta_mundo.join("icon.png");
+    let tamanho = caminho.metadata().ok()?.len();
+    if tamanho == 0 || tamanho > LIMITE_ICONE_MUNDO_BYTES {
+        return None;
+    }
+
+    let bytes = std::fs::read(caminho).ok()?;
+    Some(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+fn montar_info_mundo(pasta_mundo: &std::path::Path) -> WorldInfo {
+    let nome_pasta = pasta_mundo
+        .file_name()
+        .and_then(|nome| nome.to_str())
+        .unwrap_or("Mundo Desconhecido")
+        .to_string();
+    let tamanho = tamanho_diretorio_recursivo(pasta_mundo);
+    let (metadados, erro) = match ler_level_dat(&pasta_mundo.join("level.dat")) {
+        Ok(metadados) => (metadados, None),
+        Err(erro) => (MetadadosLevelDat::default(), Some(erro)),
+    };
+
+    WorldInfo {
+        name: metadados.nome.unwrap_or_else(|| nome_pasta.clone()),
+        folder_name: nome_pasta,
+        path: pasta_mundo.to_str().unwrap_or("").to_string(),
+        game_mode: rotulo_modo_jogo(metadados.modo_jogo),
+        hardcore: metadados.hardcore,
+        difficulty: rotulo_dificuldade(metadados.dificuldade),
+        seed: metadados.seed.map(|seed| seed.to_string()),
+        last_played: formatar_ultimo_acesso(metadados.ultimo_acesso_ms),
+        last_played_timestamp: metadados.ultimo_acesso_ms,
+        data_version: metadados.versao_dados,
+        game_version: metadados.versao_jogo,
+        size_on_disk: formatar_tamanho_bytes(tamanho),
+        size_bytes: tamanho,
+        icon: ler_icone_mundo(pasta_mundo),
+        error: erro,
+    }
+}
+
 #[tauri::command]
 pub fn get_worlds(
     instance_id: String,
@@ -521,29 +707,16 @@ pub fn get_worlds(
     if saves_dir.exists() {
         if let Ok(entries) = std::fs::read_dir(saves_dir) {
             for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    let level_dat = entry.path().join("level.dat");
-                    if level_dat.exists() {
-                        let world_name = entry
-                            .file_name()
-                            .to_str()
-                            .unwrap_or("Mundo Desconhecido")
-                            .to_string();
-
-                        worlds.push(WorldInfo {
-                            name: world_name,
-                            path: entry.path().to_str().unwrap_or("").to_string(),
-                            game_mode: "Survival".to_string(), // Simulado
-                            difficulty: "Normal".to_string(),  // Simulado
-                            last_played: "Recentemente".to_string(), // Simulado
-                            size_on_disk: "100 MB".to_string(), // Simulado
-                        });
-                    }
+                if entry.path().is_dir() && entry.path().join("level.dat").exists() {
+                    worlds.push(montar_info_mundo(&entry.path()));
                 }
             }
         }
     }
 
+    // Mundos jogados mais recentemente primeiro; corrompidos vão para o fim.
+    worlds.sort_by_key(|mundo| std::cmp::Reverse(mundo.last_played_timestamp));
+
     Ok(worlds)
 }
 
@@ -1005,7 +1178,7 @@ pub fn abrir_pasta_mundo(
 
 #[cfg(test)]
 mod testes {
-    use super::identificador_instancia_valido;
+    use super::{formatar_tamanho_bytes, identificador_instancia_valido, montar_info_mundo};
 
     #[test]
     fn aceita_identificadores_simples() {
@@ -1021,4 +1194,81 @@ mod testes {
         assert!(!identificador_instancia_valido(".."));
         assert!(!identificador_instancia_valido(""));
     }
+
+    fn criar_pasta_mundo(level_dat: Option<&quartz_nbt::NbtCompound>) -> std::path::PathBuf {
+        let pasta = std::env::temp_dir().join(format!("dome-mundo-teste-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).expect("deve criar pasta do mundo");
+
+        let mut bytes = Vec::new();
+        match level_dat {
+            Some(raiz) => quartz_nbt::io::write_nbt(
+                &mut bytes,
+                Some(""),
+                raiz,
+                quartz_nbt::io::Flavor::GzCompressed,
+            )
+            .expect("deve serializar level.dat"),
+            None => bytes.extend_from_slice(b"isto nao e nbt"),
+        }
+        std::fs::write(pasta.join("level.dat"), bytes).expect("deve gravar level.dat");
+        pasta
+    }
+
+    #[test]
+    fn le_metadados_reais_do_level_dat() {
+        let mut versao = quartz_nbt::NbtCompound::new();
+        versao.insert("Name", "1.20.4");
+        let mut geracao = quartz_nbt::NbtCompound::new();
+        geracao.insert("seed", -4_172_144_997_902_289_642i64);
+        let mut dados = quartz_nbt::NbtCompound::new();
+        dados.insert("LevelName", "Mundo do Time");
+        dados.insert("GameType", 1i32);
+        dados.insert("hardcore", 1i8);
+        dados.insert("Difficulty", 3i8);
+        dados.insert("LastPlayed", 1_700_000_000_000i64);
+        dados.insert("DataVersion", 3700i32);
+        dados.insert("Version", versao);
+        dados.insert("WorldGenSettings", geracao);
+        let mut raiz = quartz_nbt::NbtCompound::new();
+        raiz.insert("Data", dados);
+
+        let pasta = criar_pasta_mundo(Some(&raiz));
+        std::fs::write(pasta.join("icon.png"), b"\x89PNG\r\n\x1a\n").expect("deve gravar ícone");
+        let info = montar_info_mundo(&pasta);
+        let _ = std::fs::remove_dir_all(&pasta);
+
+        assert_eq!(info.name, "Mundo do Time");
+        assert_eq!(info.game_mode, "Creative");
+        assert!(info.hardcore);
+        assert_eq!(info.difficulty, "Hard");
+        assert_eq!(info.seed.as_deref(), Some("-4172144997902289642"));
+        assert_eq!(info.last_played_timestamp, Some(1_700_000_000_000));
+        assert_eq!(info.data_version, Some(3700));
+        assert_eq!(info.game_version.as_deref(), Some("1.20.4"));
+        assert!(info.size_bytes > 0);
+        assert!(info
+            .icon
+            .is_some_and(|icone| icone.starts_with("data:image/png;base64,")));
+        assert!(info.error.is_none());
+    }
+
+    #[test]
+    fn sinaliza_level_dat_corrompido_sem_dados_simulados() {
+        let pasta = criar_pasta_mundo(None);
+        let info = montar_info_mundo(&pasta);
+        let _ = std::fs::remove_dir_all(&pasta);
+
+        assert!(info.error.is_some());
+        assert_eq!(info.game_mode, "Desconhecido");
+        assert_eq!(info.difficulty, "Desconhecida");
+        assert_eq!(info.last_played, "Nunca");
+        assert!(info.seed.is_none());
+    }
+
+    #[test]
+    fn formata_tamanho_em_unidades_legiveis() {
+        assert_eq!(formatar_tamanho_bytes(512), "512 B");
+        assert_eq!(formatar_tamanho_bytes(1536), "1.5 KB");
+        assert_eq!(formatar_tamanho_bytes(100 * 1024 * 1024), "100.0 MB");
+    }
 }
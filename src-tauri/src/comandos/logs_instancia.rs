@@ -3,8 +3,21 @@ use crate::comandos::instancia_sistema::{
 };
 use crate::launcher::{Instance, LauncherState};
 use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Read, Seek, SeekFrom};
 use tauri::State;
 
+/// Tamanho padrão de cada página lida de um log.
+const BYTES_PAGINA_PADRAO: u64 = 256 * 1024;
+const BYTES_PAGINA_MAXIMO: u64 = 4 * 1024 * 1024;
+const PASTA_CRASH_REPORTS: &str = "crash-reports";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CategoriaLog {
+    Log,
+    CrashReport,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LogFile {
@@ -12,79 +25,89 @@ pub struct LogFile {
     pub path: String,
     pub size: u64,
     pub modified: String,
+    pub categoria: CategoriaLog,
+    /// Logs rotacionados pelo Minecraft (`*.log.gz`), descompactados na leitura.
+    pub compactado: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LinhaLog {
+    /// Posição da linha no arquivo (descompactado, no caso de `.log.gz`).
+    pub offset: u64,
+    pub nivel: Option<String>,
+    pub texto: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginaLog {
+    pub offset_inicial: u64,
+    /// Offset para pedir a próxima página; `None` quando o arquivo terminou.
+    pub proximo_offset: Option<u64>,
+    /// Tamanho do arquivo em disco (para `.log.gz`, o tamanho compactado).
+    pub tamanho_arquivo: u64,
+    pub linhas_lidas: usize,
+    pub linhas: Vec<LinhaLog>,
+}
+
+fn arquivo_compactado(nome: &str) -> bool {
+    nome.ends_with(".log.gz")
 }
 
+/// Resolve o arquivo pedido pela interface dentro de `logs/` ou `crash-reports/`.
 fn validar_caminho_log_instancia(
     instance: &Instance,
     file_path: &str,
 ) -> Result<std::path::PathBuf, String> {
-    let logs_dir = instance.path.join("logs");
-    if !logs_dir.exists() {
-        return Err("Diretório de logs da instância não encontrado.".to_string());
-    }
-
-    let nome_arquivo = std::path::Path::new(file_path)
+    let caminho = std::path::Path::new(file_path);
+    let nome_arquivo = caminho
         .file_name()
         .and_then(|f| f.to_str())
         .ok_or("Nome de arquivo de log inválido.")?;
 
     if !nome_arquivo_valido_log(nome_arquivo) {
-        return Err("Apenas arquivos .log ou .txt são permitidos.".to_string());
+        return Err("Apenas arquivos .log, .log.gz ou .txt são permitidos.".to_string());
     }
 
-    let caminho_candidato = logs_dir.join(nome_arquivo);
+    let em_crash_reports = caminho
+        .parent()
+        .and_then(|pasta| pasta.file_name())
+        .is_some_and(|pasta| pasta == PASTA_CRASH_REPORTS);
+    let pasta_base = if em_crash_reports {
+        instance.path.join(PASTA_CRASH_REPORTS)
+    } else {
+        instance.path.join("logs")
+    };
+    if !pasta_base.exists() {
+        return Err("Diretório de logs da instância não encontrado.".to_string());
+    }
+
+    let caminho_candidato = pasta_base.join(nome_arquivo);
     if !caminho_candidato.exists() {
         return Err("Arquivo de log não encontrado.".to_string());
     }
 
-    validar_caminho_dentro_raiz(&logs_dir, &caminho_candidato)
+    validar_caminho_dentro_raiz(&pasta_base, &caminho_candidato)
 }
 
-#[tauri::command]
-pub fn get_log_files(
-    instance_id: String,
-    state: State<LauncherState>,
-) -> Result<Vec<LogFile>, String> {
-    let instances = state.get_instances().map_err(|e| e.to_string())?;
-    let instance = instances
-        .iter()
-        .find(|i: &&Instance| i.id == instance_id)
-        .ok_or("Instância não encontrada")?;
-
-    let logs_dir = instance.path.join("logs");
-    let mut log_files = vec![];
-
-    if logs_dir.exists() {
-        if let Ok(entries) = std::fs::read_dir(logs_dir) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_file() {
-                        let filename = entry.file_name().to_str().unwrap_or("").to_string();
-                        if filename.ends_with(".log") || filename.ends_with(".txt") {
-                            let modified = metadata
-                                .modified()
-                                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
-                                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs();
-
-                            log_files.push(LogFile {
-                                filename: filename.clone(),
-                                path: entry.path().to_str().unwrap_or("").to_string(),
-                                size: metadata.len(),
-                                modified: format!("{}", modified),
-                            });
-                        }
-                    }
-                }
-            }
-        }
-    }
+fn listar_arquivos_log(
+    pasta: &std::path::Path,
+    categoria: CategoriaLog,
+    aceitar: impl Fn(&str) -> bool,
+) -> Vec<LogFile> {
+    let Ok(entries) = std::fs::read_dir(pasta) else {
+        return Vec::new();
+    };
 
-    // Adicionar latest.log se existir
-    let latest_log = instance.path.join("logs").join("latest.log");
-    if latest_log.exists() {
-        if let Ok(metadata) = latest_log.metadata() {
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let filename = entry.file_name().to_str()?.to_string();
+            if !aceitar(&filename) {
+                return None;
+            }
             let modified = metadata
                 .modified()
                 .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
@@ -92,18 +115,58 @@ pub fn get_log_files(
                 .unwrap_or_default()
                 .as_secs();
 
-            log_files.push(LogFile {
-                filename: "latest.log".to_string(),
-                path: latest_log.to_str().unwrap_or("").to_string(),
+            Some(LogFile {
+                compactado: arquivo_compactado(&filename),
+                filename,
+                path: entry.path().to_str().unwrap_or("").to_string(),
                 size: metadata.len(),
                 modified: format!("{}", modified),
-            });
-        }
-    }
+                categoria,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_log_files(
+    instance_id: String,
+    state: State<LauncherState>,
+) -> Result<Vec<LogFile>, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
 
+    // logs/ inclui latest.log, debug.log, launcher_output.log e os rotacionados (.log.gz)
+    let mut log_files = listar_arquivos_log(
+        &instance.path.join("logs"),
+        CategoriaLog::Log,
+        nome_arquivo_valido_log,
+    );
+    log_files.extend(listar_arquivos_log(
+        &instance.path.join(PASTA_CRASH_REPORTS),
+        CategoriaLog::CrashReport,
+        |nome| nome.ends_with(".txt"),
+    ));
+
+    // Mais recentes primeiro
+    log_files.sort_by(|a, b| {
+        let modificado = |log: &LogFile| log.modified.parse::<u64>().unwrap_or(0);
+        modificado(b).cmp(&modificado(a))
+    });
     Ok(log_files)
 }
 
+fn abrir_leitor_log(caminho: &std::path::Path) -> Result<Box<dyn Read>, String> {
+    let arquivo = std::fs::File::open(caminho).map_err(|e| format!("Erro ao abrir log: {}", e))?;
+    let nome = caminho
+        .file_name()
+        .and_then(|nome| nome.to_str())
+        .unwrap_or("");
+    if arquivo_compactado(nome) {
+        Ok(Box::new(flate2::read::GzDecoder::new(arquivo)))
+    } else {
+        Ok(Box::new(arquivo))
+    }
+}
+
 #[tauri::command]
 pub fn get_log_content(
     instance_id: String,
@@ -112,7 +175,169 @@ pub fn get_log_content(
 ) -> Result<String, String> {
     let instance = obter_instancia_por_id(&state, &instance_id)?;
     let caminho_log = validar_caminho_log_instancia(&instance, &file_path)?;
-    std::fs::read_to_string(caminho_log).map_err(|e| e.to_string())
+    let mut bytes = Vec::new();
+    abrir_leitor_log(&caminho_log)?
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Erro ao ler log: {}", e))?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn regex_nivel_log() -> &'static regex::Regex {
+    static REGEX: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    // [12:34:56] [main/INFO] ... ou [main/WARN] [logger]: ...
+    REGEX.get_or_init(|| {
+        regex::Regex::new(r"^\[[^\]]*\] \[[^\]]*/(TRACE|DEBUG|INFO|WARN|ERROR|FATAL)\]")
+            .expect("regex de nível válida")
+    })
+}
+
+/// Filtros aplicados no backend antes de devolver as linhas.
+struct FiltroLog {
+    niveis: Vec<String>,
+    regex: Option<regex::Regex>,
+}
+
+impl FiltroLog {
+    fn novo(niveis: Option<Vec<String>>, filtro_regex: Option<String>) -> Result<Self, String> {
+        let regex = filtro_regex
+            .map(|padrao| padrao.trim().to_string())
+            .filter(|padrao| !padrao.is_empty())
+            .map(|padrao| {
+                regex::RegexBuilder::new(&padrao)
+                    .case_insensitive(true)
+                    .size_limit(1 << 20)
+                    .build()
+                    .map_err(|e| format!("Expressão de filtro inválida: {}", e))
+            })
+            .transpose()?;
+        Ok(Self {
+            niveis: niveis
+                .unwrap_or_default()
+                .into_iter()
+                .map(|nivel| nivel.trim().to_uppercase())
+                .filter(|nivel| !nivel.is_empty())
+                .collect(),
+            regex,
+        })
+    }
+
+    fn aceita(&self, linha: &LinhaLog) -> bool {
+        let nivel_ok = self.niveis.is_empty()
+            || linha
+                .nivel
+                .as_ref()
+                .is_some_and(|nivel| self.niveis.contains(nivel));
+        nivel_ok
+            && self
+                .regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&linha.texto))
+    }
+}
+
+/// Lê até `limite` bytes a partir de `offset`, sempre terminando em fim de
+/// linha (exceto quando uma única linha é maior que a página).
+fn ler_pagina(
+    leitor: &mut dyn BufRead,
+    offset: u64,
+    limite: u64,
+    filtro: &FiltroLog,
+) -> Result<(Vec<LinhaLog>, usize, Option<u64>), String> {
+    let mut linhas = Vec::new();
+    let mut linhas_lidas = 0;
+    let mut posicao = offset;
+    let mut nivel_atual: Option<String> = None;
+    let mut bytes = Vec::new();
+
+    loop {
+        if posicao - offset >= limite {
+            return Ok((linhas, linhas_lidas, Some(posicao)));
+        }
+        bytes.clear();
+        let lidos = leitor
+            .read_until(b'\n', &mut bytes)
+            .map_err(|e| format!("Erro ao ler log: {}", e))?;
+        if lidos == 0 {
+            return Ok((linhas, linhas_lidas, None));
+        }
+
+        let texto = String::from_utf8_lossy(&bytes)
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+        // Linhas sem cabeçalho (stack traces) herdam o nível da anterior.
+        if let Some(captura) = regex_nivel_log().captures(&texto) {
+            nivel_atual = Some(captura[1].to_string());
+        }
+        let linha = LinhaLog {
+            offset: posicao,
+            nivel: nivel_atual.clone(),
+            texto,
+        };
+        posicao += lidos as u64;
+        linhas_lidas += 1;
+        if filtro.aceita(&linha) {
+            linhas.push(linha);
+        }
+    }
+}
+
+/// Leitura paginada por faixa de bytes, com filtro por nível e regex no backend,
+/// para que logs grandes (ou `.log.gz`) não travem a interface.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn read_log_page(
+    instance_id: String,
+    file_path: String,
+    offset: Option<u64>,
+    limite_bytes: Option<u64>,
+    niveis: Option<Vec<String>>,
+    filtro_regex: Option<String>,
+    state: State<'_, LauncherState>,
+) -> Result<PaginaLog, String> {
+    let instance = obter_instancia_por_id(&state, &instance_id)?;
+    let caminho_log = validar_caminho_log_instancia(&instance, &file_path)?;
+    let offset = offset.unwrap_or(0);
+    let limite = limite_bytes
+        .unwrap_or(BYTES_PAGINA_PADRAO)
+        .clamp(1024, BYTES_PAGINA_MAXIMO);
+    let filtro = FiltroLog::novo(niveis, filtro_regex)?;
+
+    tokio::task::spawn_blocking(move || {
+        let tamanho_arquivo = std::fs::metadata(&caminho_log)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        let nome = caminho_log
+            .file_name()
+            .and_then(|nome| nome.to_str())
+            .unwrap_or("");
+
+        let mut leitor: Box<dyn BufRead> = if arquivo_compactado(nome) {
+            // gzip não permite seek: descarta o trecho já lido.
+            let mut leitor = std::io::BufReader::new(abrir_leitor_log(&caminho_log)?);
+            std::io::copy(&mut (&mut leitor).take(offset), &mut std::io::sink())
+                .map_err(|e| format!("Erro ao descompactar log: {}", e))?;
+            Box::new(leitor)
+        } else {
+            let mut arquivo = std::fs::File::open(&caminho_log)
+                .map_err(|e| format!("Erro ao abrir log: {}", e))?;
+            arquivo
+                .seek(SeekFrom::Start(offset.min(tamanho_arquivo)))
+                .map_err(|e| format!("Erro ao posicionar leitura do log: {}", e))?;
+            Box::new(std::io::BufReader::new(arquivo))
+        };
+
+        let (linhas, linhas_lidas, proximo_offset) =
+            ler_pagina(leitor.as_mut(), offset, limite, &filtro)?;
+        Ok(PaginaLog {
+            offset_inicial: offset,
+            proximo_offset,
+            tamanho_arquivo,
+            linhas_lidas,
+            linhas,
+        })
+    })
+    .await
+    .map_err(|e| format!("Erro ao ler log: {}", e))?
 }
 
 #[tauri::command]
@@ -125,3 +350,68 @@ pub fn delete_log_file(
     let caminho_log = validar_caminho_log_instancia(&instance, &file_path)?;
     std::fs::remove_file(caminho_log).map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    const LOG: &str = "[10:00:00] [main/INFO]: Iniciando\n\
+[10:00:01] [Render thread/ERROR]: Falhou\n\
+\tat a.b.C.d(C.java:1)\n\
+[10:00:02] [main/WARN] [mod/]: Aviso\n";
+
+    #[test]
+    fn pagina_termina_em_fim_de_linha_e_continua_do_offset() {
+        let filtro = FiltroLog::novo(None, None).unwrap();
+        let mut leitor = std::io::Cursor::new(LOG.as_bytes());
+        let (linhas, lidas, proximo) = ler_pagina(&mut leitor, 0, 40, &filtro).unwrap();
+        assert_eq!(lidas, 2);
+        assert_eq!(linhas[1].texto, "[10:00:01] [Render thread/ERROR]: Falhou");
+        let proximo = proximo.unwrap();
+
+        let mut leitor = std::io::Cursor::new(&LOG.as_bytes()[proximo as usize..]);
+        let (linhas, _, fim) = ler_pagina(&mut leitor, proximo, 1024, &filtro).unwrap();
+        assert_eq!(linhas[0].offset, proximo);
+        assert_eq!(linhas[0].texto, "\tat a.b.C.d(C.java:1)");
+        assert_eq!(linhas.len(), 2);
+        assert!(fim.is_none());
+    }
+
+    #[test]
+    fn filtra_por_nivel_e_regex_com_stack_trace_herdando_nivel() {
+        let filtro = FiltroLog::novo(Some(vec!["error".to_string()]), None).unwrap();
+        let mut leitor = std::io::Cursor::new(LOG.as_bytes());
+        let (linhas, lidas, _) = ler_pagina(&mut leitor, 0, 1024, &filtro).unwrap();
+        assert_eq!(lidas, 4);
+        assert_eq!(linhas.len(), 2);
+        assert!(linhas[1].texto.starts_with("\tat"));
+
+        let filtro = FiltroLog::novo(None, Some("aviso|iniciando".to_string())).unwrap();
+        let mut leitor = std::io::Cursor::new(LOG.as_bytes());
+        let (linhas, _, _) = ler_pagina(&mut leitor, 0, 1024, &filtro).unwrap();
+        assert_eq!(linhas.len(), 2);
+
+        assert!(FiltroLog::novo(None, Some("(".to_string())).is_err());
+    }
+
+    #[test]
+    fn le_logs_rotacionados_compactados() {
+        use std::io::Write;
+        let pasta = std::env::temp_dir().join(format!("dome-logs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&pasta).unwrap();
+        let caminho = pasta.join("2024-01-01-1.log.gz");
+        let mut codificador =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        codificador.write_all(LOG.as_bytes()).unwrap();
+        std::fs::write(&caminho, codificador.finish().unwrap()).unwrap();
+
+        let mut conteudo = String::new();
+        abrir_leitor_log(&caminho)
+            .unwrap()
+            .read_to_string(&mut conteudo)
+            .unwrap();
+        assert_eq!(conteudo, LOG);
+
+        let _ = std::fs::remove_dir_all(pasta);
+    }
+}
@@ -1,7 +1,10 @@
+pub mod analise_crash;
 pub mod configuracoes_java;
 pub mod instancia_sistema;
 pub mod logs_instancia;
 pub mod modpacks;
 pub mod noticias_minecraft;
 pub mod presenca_discord;
+pub mod saida_jogo;
 pub mod social_launcher;
+pub mod tarefas;
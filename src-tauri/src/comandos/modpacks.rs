@@ -1,19 +1,31 @@
-use crate::launcher::LauncherState;
+use crate::aplicacao::extracao_segura::{
+    caminho_relativo_seguro, extrair_pasta_zip, LimitesExtracao,
+};
+use crate::aplicacao::registro_conteudo::{
+    identificacao_modrinth_por_url, registrar_conteudo, sha1_arquivo_curseforge,
+    tipo_conteudo_por_caminho, EntradaConteudo, HashesConteudo, OrigemInstalacao,
+};
+use crate::aplicacao::verificacao_arquivos::escrever_atomico;
+use crate::comandos::tarefas::{Tarefa, MENSAGEM_TAREFA_CANCELADA};
+use crate::launcher::{Instance, LauncherState, ModPlatform};
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
 use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModpackInfo {
-    project_id: String,
-    version_id: String,
-    file_id: Option<String>,
-    name: String,
-    author: String,
-    icon: Option<String>,
-    slug: String,
-    source: String,
-    installed_version: String,
+    pub(crate) project_id: String,
+    pub(crate) version_id: String,
+    pub(crate) file_id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) author: String,
+    pub(crate) icon: Option<String>,
+    pub(crate) slug: String,
+    pub(crate) source: String,
+    pub(crate) installed_version: String,
 }
 
 #[tauri::command]
@@ -67,43 +79,281 @@ pub async fn save_modpack_info(
     Ok(())
 }
 
-#[tauri::command]
-pub async fn install_modpack_files(
-    state: State<'_, LauncherState>,
-    instance_id: String,
-    download_url: String,
-    file_name: String,
+/// Registro dos arquivos entregues pelo modpack instalado. A atualização usa
+/// esse registro para separar o que pertence ao pacote do que o usuário alterou.
+pub(crate) const ARQUIVO_REGISTRO_MODPACK: &str = ".modpack_arquivos.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct ArquivosModpackInstalados {
+    pub versao_id: Option<String>,
+    /// Caminho relativo à instância -> SHA-1 do arquivo como o pacote o entregou.
+    pub arquivos: BTreeMap<String, String>,
+}
+
+pub(crate) fn carregar_arquivos_modpack(instance_path: &Path) -> Option<ArquivosModpackInstalados> {
+    let conteudo = std::fs::read_to_string(instance_path.join(ARQUIVO_REGISTRO_MODPACK)).ok()?;
+    serde_json::from_str(&conteudo)
+        .map_err(|e| eprintln!("[Modpack] {} inválido: {}", ARQUIVO_REGISTRO_MODPACK, e))
+        .ok()
+}
+
+pub(crate) fn salvar_arquivos_modpack(
+    instance_path: &Path,
+    registro: &ArquivosModpackInstalados,
 ) -> Result<(), String> {
-    let instance_path =
-        crate::comandos::instancia_sistema::caminho_instancia_por_id(&state, &instance_id)?;
-    let mods_path = instance_path.join("mods");
-    let temp_path = instance_path.join("temp");
+    let json = serde_json::to_string_pretty(registro)
+        .map_err(|e| format!("Erro ao serializar arquivos do modpack: {}", e))?;
+    std::fs::write(instance_path.join(ARQUIVO_REGISTRO_MODPACK), json)
+        .map_err(|e| format!("Erro ao salvar arquivos do modpack: {}", e))
+}
 
-    // Criar diretórios
-    std::fs::create_dir_all(&mods_path).map_err(|e| format!("Erro ao criar pasta mods: {}", e))?;
-    std::fs::create_dir_all(&temp_path).map_err(|e| format!("Erro ao criar pasta temp: {}", e))?;
+pub(crate) fn ler_modpack_info(instance_path: &Path) -> Result<Option<ModpackInfo>, String> {
+    let modpack_path = instance_path.join("modpack.json");
+    if !modpack_path.exists() {
+        return Ok(None);
+    }
 
-    let nome_arquivo_seguro = std::path::Path::new(&file_name)
-        .file_name()
-        .and_then(|nome| nome.to_str())
-        .filter(|nome| !nome.trim().is_empty())
-        .unwrap_or("modpack.zip")
-        .to_string();
-    let mrpack_path = temp_path.join(&nome_arquivo_seguro);
+    let content = std::fs::read_to_string(&modpack_path)
+        .map_err(|e| format!("Erro ao ler modpack.json: {}", e))?;
+    let info: ModpackInfo = serde_json::from_str(&content)
+        .map_err(|e| format!("Erro ao parsear modpack.json: {}", e))?;
+    Ok(Some(info))
+}
 
-    println!("[install_modpack_files] Baixando: {}", download_url);
+pub(crate) fn gravar_modpack_info(instance_path: &Path, info: &ModpackInfo) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Erro ao serializar modpack.json: {}", e))?;
+    std::fs::write(instance_path.join("modpack.json"), content)
+        .map_err(|e| format!("Erro ao salvar modpack.json: {}", e))
+}
 
-    // Baixar o arquivo .mrpack
-    let client = reqwest::Client::builder()
-        .connect_timeout(std::time::Duration::from_secs(30))
-        .timeout(std::time::Duration::from_secs(180))
-        .user_agent("DomeLauncher/1.0 (+https://domestudios.com.br)")
-        .build()
-        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))?;
+/// Arquivo listado no índice do modpack, com a identificação usada no lockfile.
+#[derive(Debug, Clone)]
+pub(crate) struct ArquivoModpack {
+    /// URLs de download em ordem de preferência; as seguintes são alternativas.
+    pub urls: Vec<String>,
+    pub nome: String,
+    /// Caminho relativo à instância onde o arquivo é gravado.
+    pub caminho: String,
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+    pub tamanho: Option<u64>,
+    /// Marcado como `optional` para o cliente em `env`.
+    pub opcional: bool,
+    pub plataforma: ModPlatform,
+    pub project_id: Option<String>,
+    pub versao_id: Option<String>,
+}
+
+/// Conteúdo do `modrinth.index.json` ou `manifest.json` de um modpack.
+#[derive(Debug, Clone)]
+pub(crate) struct IndiceModpack {
+    pub nome: Option<String>,
+    pub versao: Option<String>,
+    /// Arquivos com URL conhecida (índice Modrinth).
+    pub arquivos: Vec<ArquivoModpack>,
+    /// Pares (projectID, fileID) do manifesto CurseForge, resolvidos depois.
+    pub arquivos_curseforge: Vec<(u64, u64)>,
+    /// Pastas de overrides na ordem de aplicação; as últimas prevalecem.
+    pub pastas_overrides: Vec<String>,
+    pub versao_minecraft: Option<String>,
+    /// Loader normalizado (`fabric`, `forge`, `neoforge`, `quilt`).
+    pub loader: Option<String>,
+    pub versao_loader: Option<String>,
+}
+
+/// Loader declarado em `dependencies` do índice Modrinth.
+pub(crate) fn loader_de_dependencias_modrinth(
+    dependencias: &serde_json::Value,
+) -> Option<(String, String)> {
+    [
+        ("neoforge", "neoforge"),
+        ("forge", "forge"),
+        ("quilt-loader", "quilt"),
+        ("fabric-loader", "fabric"),
+    ]
+    .iter()
+    .find_map(|(chave, loader)| {
+        dependencias[*chave]
+            .as_str()
+            .filter(|versao| !versao.trim().is_empty())
+            .map(|versao| (loader.to_string(), versao.trim().to_string()))
+    })
+}
+
+/// Loader de `minecraft.modLoaders` do manifesto CurseForge (ids como `forge-47.2.0`),
+/// priorizando o marcado como primário.
+pub(crate) fn loader_de_manifesto_curseforge(
+    manifesto: &serde_json::Value,
+) -> Option<(String, String)> {
+    let loaders = manifesto["minecraft"]["modLoaders"].as_array()?;
+    let mut candidatos: Vec<&serde_json::Value> = loaders.iter().collect();
+    candidatos.sort_by_key(|loader| !loader["primary"].as_bool().unwrap_or(false));
+    candidatos.into_iter().find_map(|loader| {
+        let (nome, versao) = loader["id"].as_str()?.trim().split_once('-')?;
+        let nome = match nome.to_lowercase().as_str() {
+            "neoforge" => "neoforge",
+            "forge" => "forge",
+            "fabric" => "fabric",
+            "quilt" => "quilt",
+            _ => return None,
+        };
+        (!versao.is_empty()).then(|| (nome.to_string(), versao.to_string()))
+    })
+}
+
+/// Lê uma entrada de `files` do índice Modrinth. Arquivos sem suporte no
+/// cliente (`env.client = unsupported`) são ignorados.
+fn arquivo_do_indice_modrinth(
+    entrada: &serde_json::Value,
+) -> Result<Option<ArquivoModpack>, String> {
+    let ambiente_cliente = entrada["env"]["client"].as_str().unwrap_or("required");
+    if ambiente_cliente == "unsupported" {
+        return Ok(None);
+    }
+
+    let path = entrada["path"].as_str().unwrap_or("");
+    let caminho = caminho_relativo_seguro(path)
+        .map_err(|_| format!("Caminho inválido no índice do modpack: {}", path))?;
+    let urls: Vec<String> = entrada["downloads"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|url| url.as_str())
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+        .map(|url| url.to_string())
+        .collect();
+    if urls.is_empty() {
+        return Err(format!(
+            "Arquivo {} do modpack sem URL de download.",
+            caminho
+        ));
+    }
+
+    let (project_id, versao_id) = urls
+        .iter()
+        .find_map(|url| identificacao_modrinth_por_url(url))
+        .map(|(projeto, versao)| (Some(projeto), Some(versao)))
+        .unwrap_or_default();
+    Ok(Some(ArquivoModpack {
+        urls,
+        nome: caminho.rsplit('/').next().unwrap_or(&caminho).to_string(),
+        sha1: entrada["hashes"]["sha1"].as_str().map(|s| s.to_lowercase()),
+        sha512: entrada["hashes"]["sha512"]
+            .as_str()
+            .map(|s| s.to_lowercase()),
+        tamanho: entrada["fileSize"].as_u64(),
+        opcional: ambiente_cliente == "optional",
+        caminho,
+        plataforma: ModPlatform::Modrinth,
+        project_id,
+        versao_id,
+    }))
+}
+
+fn texto_json(valor: &serde_json::Value) -> Option<String> {
+    match valor {
+        serde_json::Value::String(texto) if !texto.trim().is_empty() => {
+            Some(texto.trim().to_string())
+        }
+        serde_json::Value::Number(numero) => Some(numero.to_string()),
+        _ => None,
+    }
+}
+
+fn ler_json_do_zip<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    nome: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let mut arquivo = match archive.by_name(nome) {
+        Ok(arquivo) => arquivo,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Erro ao ler {} do ZIP: {}", nome, e)),
+    };
+    let mut contents = String::new();
+    std::io::Read::read_to_string(&mut arquivo, &mut contents)
+        .map_err(|e| format!("Erro ao ler {}: {}", nome, e))?;
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| format!("Erro ao parsear {}: {}", nome, e))
+}
+
+/// Lê o índice do modpack, seja Modrinth (`modrinth.index.json`) ou
+/// CurseForge (`manifest.json`).
+pub(crate) fn ler_indice_modpack<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<IndiceModpack, String> {
+    if let Some(index) = ler_json_do_zip(archive, "modrinth.index.json")? {
+        if index["game"]
+            .as_str()
+            .is_some_and(|jogo| jogo != "minecraft")
+        {
+            return Err("O modpack não é para Minecraft.".to_string());
+        }
+        let mut arquivos = Vec::new();
+        for entrada in index["files"].as_array().into_iter().flatten() {
+            arquivos.extend(arquivo_do_indice_modrinth(entrada)?);
+        }
+
+        let loader = loader_de_dependencias_modrinth(&index["dependencies"]);
+        return Ok(IndiceModpack {
+            nome: texto_json(&index["name"]),
+            versao: texto_json(&index["versionId"]),
+            arquivos,
+            arquivos_curseforge: Vec::new(),
+            pastas_overrides: vec!["overrides".to_string(), "client-overrides".to_string()],
+            versao_minecraft: texto_json(&index["dependencies"]["minecraft"]),
+            loader: loader.as_ref().map(|(nome, _)| nome.clone()),
+            versao_loader: loader.map(|(_, versao)| versao),
+        });
+    }
+
+    if let Some(manifesto) = ler_json_do_zip(archive, "manifest.json")? {
+        let pasta_overrides = manifesto["overrides"]
+            .as_str()
+            .map(|overrides| overrides.trim_matches('/').trim())
+            .filter(|overrides| !overrides.is_empty())
+            .unwrap_or("overrides")
+            .to_string();
+        let arquivos_curseforge = manifesto["files"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|entrada| entrada["required"].as_bool().unwrap_or(true))
+            .filter_map(|entrada| {
+                Some((entrada["projectID"].as_u64()?, entrada["fileID"].as_u64()?))
+            })
+            .collect();
+
+        let loader = loader_de_manifesto_curseforge(&manifesto);
+        return Ok(IndiceModpack {
+            nome: texto_json(&manifesto["name"]),
+            versao: texto_json(&manifesto["version"]),
+            arquivos: Vec::new(),
+            arquivos_curseforge,
+            pastas_overrides: vec![pasta_overrides],
+            versao_minecraft: texto_json(&manifesto["minecraft"]["version"]),
+            loader: loader.as_ref().map(|(nome, _)| nome.clone()),
+            versao_loader: loader.map(|(_, versao)| versao),
+        });
+    }
+
+    Err("O arquivo não contém modrinth.index.json nem manifest.json.".to_string())
+}
+
+/// Baixa o arquivo do modpack com até 3 tentativas.
+pub(crate) async fn baixar_pacote_modpack(
+    client: &reqwest::Client,
+    download_url: &str,
+    destino: &Path,
+    tarefa: &Tarefa,
+) -> Result<(), String> {
     let mut bytes_modpack = None;
     let mut ultima_falha_modpack = String::new();
     for tentativa in 1..=3 {
-        match client.get(&download_url).send().await {
+        tarefa.verificar_cancelamento()?;
+        match client.get(download_url).send().await {
             Ok(resposta) => {
                 if !resposta.status().is_success() {
                     ultima_falha_modpack = format!("HTTP {}", resposta.status());
@@ -136,317 +386,310 @@ pub async fn install_modpack_files(
         )
     })?;
 
-    std::fs::write(&mrpack_path, &bytes).map_err(|e| format!("Erro ao salvar arquivo: {}", e))?;
-
-    println!("[install_modpack_files] Arquivo salvo: {:?}", mrpack_path);
+    escrever_atomico(destino, &bytes)?;
+    tarefa.avancar(1, bytes.len() as u64);
+    Ok(())
+}
 
-    // Extrair .mrpack (é um ZIP)
-    let file =
-        std::fs::File::open(&mrpack_path).map_err(|e| format!("Erro ao abrir mrpack: {}", e))?;
+/// Confere se um pacote já baixado abre e traz um índice legível, antes de
+/// reaproveitá-lo.
+fn pacote_modpack_legivel(caminho: &Path) -> bool {
+    std::fs::File::open(caminho)
+        .ok()
+        .and_then(|arquivo| zip::ZipArchive::new(arquivo).ok())
+        .is_some_and(|mut archive| ler_indice_modpack(&mut archive).is_ok())
+}
 
-    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Erro ao ler ZIP: {}", e))?;
+/// URL de download de um arquivo CurseForge pela rota `download-url`, usada
+/// quando os detalhes do arquivo não trazem `downloadUrl`.
+pub(crate) async fn url_download_curseforge(
+    client: &reqwest::Client,
+    mod_id: u64,
+    file_id: u64,
+) -> Result<String, String> {
+    let rota_download_url = format!(
+        "{}/mods/{}/files/{}/download-url",
+        crate::CURSEFORGE_API_BASE,
+        mod_id,
+        file_id
+    );
+    let request_download_url = crate::anexar_headers_curseforge(client.get(&rota_download_url))?;
+    let resposta_download_url = request_download_url.send().await.map_err(|e| {
+        format!(
+            "Falha ao consultar download-url de {}:{} ({})",
+            mod_id, file_id, e
+        )
+    })?;
+    if !resposta_download_url.status().is_success() {
+        return Err(format!(
+            "download-url de {}:{} retornou HTTP {}",
+            mod_id,
+            file_id,
+            resposta_download_url.status()
+        ));
+    }
+    let payload_download_url = resposta_download_url
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            format!(
+                "Falha ao parsear download-url de {}:{} ({})",
+                mod_id, file_id, e
+            )
+        })?;
+    Ok(payload_download_url["data"]
+        .as_str()
+        .unwrap_or("")
+        .trim()
+        .to_string())
+}
 
-    // Pode ser Modrinth (modrinth.index.json) ou CurseForge (manifest.json)
-    let mut arquivos_para_baixar: Vec<(String, String, String)> = Vec::new();
-    let mut arquivos_curseforge: Vec<(u64, u64)> = Vec::new();
-    let mut total_arquivos_manifesto_curseforge = 0usize;
-    let mut pasta_overrides = "overrides".to_string();
-    let mut detectou_modrinth = false;
-    let mut detectou_curseforge = false;
+/// Resolve os pares (projectID, fileID) do manifesto CurseForge em arquivos
+/// baixáveis. Falhas individuais são devolvidas como mensagens (até 8).
+pub(crate) async fn resolver_arquivos_curseforge(
+    client: &reqwest::Client,
+    arquivos_curseforge: &[(u64, u64)],
+    tarefa: &Tarefa,
+) -> Result<(Vec<ArquivoModpack>, Vec<String>), String> {
+    let mut arquivos = Vec::new();
     let mut erros_curseforge: Vec<String> = Vec::new();
-
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Erro ao ler arquivo do ZIP: {}", e))?;
-
-        if file.name() == "modrinth.index.json" {
-            detectou_modrinth = true;
-            let mut contents = String::new();
-            std::io::Read::read_to_string(&mut file, &mut contents)
-                .map_err(|e| format!("Erro ao ler index Modrinth: {}", e))?;
-
-            if let Ok(index) = serde_json::from_str::<serde_json::Value>(&contents) {
-                if let Some(files) = index["files"].as_array() {
-                    for f in files {
-                        if let (Some(path), Some(downloads)) =
-                            (f["path"].as_str(), f["downloads"].as_array())
-                        {
-                            if let Some(url) = downloads.first().and_then(|d| d.as_str()) {
-                                let filename = path.split('/').next_back().unwrap_or("mod.jar");
-                                arquivos_para_baixar.push((
-                                    url.to_string(),
-                                    filename.to_string(),
-                                    path.to_string(),
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-        } else if file.name() == "manifest.json" {
-            detectou_curseforge = true;
-            let mut contents = String::new();
-            std::io::Read::read_to_string(&mut file, &mut contents)
-                .map_err(|e| format!("Erro ao ler manifest CurseForge: {}", e))?;
-
-            if let Ok(manifesto) = serde_json::from_str::<serde_json::Value>(&contents) {
-                if let Some(overrides) = manifesto["overrides"].as_str() {
-                    let normalizado = overrides.trim_matches('/').trim();
-                    if !normalizado.is_empty() {
-                        pasta_overrides = normalizado.to_string();
-                    }
-                }
-
-                if let Some(files) = manifesto["files"].as_array() {
-                    for entrada in files {
-                        let obrigatorio = entrada["required"].as_bool().unwrap_or(true);
-                        if !obrigatorio {
-                            continue;
-                        }
-                        let mod_id = entrada["projectID"].as_u64();
-                        let file_id = entrada["fileID"].as_u64();
-                        if let (Some(mid), Some(fid)) = (mod_id, file_id) {
-                            total_arquivos_manifesto_curseforge += 1;
-                            arquivos_curseforge.push((mid, fid));
-                        }
-                    }
-                }
-            }
-        }
+    if arquivos_curseforge.is_empty() {
+        return Ok((arquivos, erros_curseforge));
     }
 
-    if !arquivos_curseforge.is_empty() {
-        for (mod_id, file_id) in arquivos_curseforge {
-            let detalhes_url = format!(
-                "{}/mods/{}/files/{}",
-                crate::CURSEFORGE_API_BASE,
-                mod_id,
-                file_id
-            );
-            let request = crate::anexar_headers_curseforge(client.get(&detalhes_url))?;
-            let resposta = request.send().await.map_err(|e| {
-                format!(
-                    "Erro ao buscar arquivo CurseForge {}:{}: {}",
-                    mod_id, file_id, e
-                )
-            })?;
-
-            if !resposta.status().is_success() {
-                if erros_curseforge.len() < 8 {
-                    erros_curseforge.push(format!(
-                        "CurseForge {}:{} retornou HTTP {}",
-                        mod_id,
-                        file_id,
-                        resposta.status()
-                    ));
-                }
-                println!(
-                    "[install_modpack_files] CurseForge {}:{} retornou HTTP {}",
+    tarefa.fase("resolvendo_arquivos", arquivos_curseforge.len(), 0);
+    for &(mod_id, file_id) in arquivos_curseforge {
+        tarefa.verificar_cancelamento()?;
+        tarefa.avancar(1, 0);
+        let detalhes_url = format!(
+            "{}/mods/{}/files/{}",
+            crate::CURSEFORGE_API_BASE,
+            mod_id,
+            file_id
+        );
+        let request = crate::anexar_headers_curseforge(client.get(&detalhes_url))?;
+        let resposta = request.send().await.map_err(|e| {
+            format!(
+                "Erro ao buscar arquivo CurseForge {}:{}: {}",
+                mod_id, file_id, e
+            )
+        })?;
+
+        if !resposta.status().is_success() {
+            if erros_curseforge.len() < 8 {
+                erros_curseforge.push(format!(
+                    "CurseForge {}:{} retornou HTTP {}",
                     mod_id,
                     file_id,
                     resposta.status()
-                );
-                continue;
+                ));
             }
+            println!(
+                "[install_modpack_files] CurseForge {}:{} retornou HTTP {}",
+                mod_id,
+                file_id,
+                resposta.status()
+            );
+            continue;
+        }
 
-            let payload: serde_json::Value = resposta
-                .json()
-                .await
-                .map_err(|e| format!("Erro ao parsear detalhe de arquivo CurseForge: {}", e))?;
-            let data = &payload["data"];
-            let mut url_download = data["downloadUrl"]
-                .as_str()
-                .unwrap_or("")
-                .trim()
-                .to_string();
-            if url_download.is_empty() {
-                let rota_download_url = format!(
-                    "{}/mods/{}/files/{}/download-url",
-                    crate::CURSEFORGE_API_BASE,
-                    mod_id,
-                    file_id
-                );
-                let request_download_url =
-                    crate::anexar_headers_curseforge(client.get(&rota_download_url))?;
-                match request_download_url.send().await {
-                    Ok(resposta_download_url) => {
-                        if resposta_download_url.status().is_success() {
-                            match resposta_download_url.json::<serde_json::Value>().await {
-                                Ok(payload_download_url) => {
-                                    url_download = payload_download_url["data"]
-                                        .as_str()
-                                        .unwrap_or("")
-                                        .trim()
-                                        .to_string();
-                                }
-                                Err(e) => {
-                                    if erros_curseforge.len() < 8 {
-                                        erros_curseforge.push(format!(
-                                            "Falha ao parsear download-url de {}:{} ({})",
-                                            mod_id, file_id, e
-                                        ));
-                                    }
-                                }
-                            }
-                        } else if erros_curseforge.len() < 8 {
-                            erros_curseforge.push(format!(
-                                "download-url de {}:{} retornou HTTP {}",
-                                mod_id,
-                                file_id,
-                                resposta_download_url.status()
-                            ));
-                        }
-                    }
-                    Err(e) => {
-                        if erros_curseforge.len() < 8 {
-                            erros_curseforge.push(format!(
-                                "Falha ao consultar download-url de {}:{} ({})",
-                                mod_id, file_id, e
-                            ));
-                        }
+        let payload: serde_json::Value = resposta
+            .json()
+            .await
+            .map_err(|e| format!("Erro ao parsear detalhe de arquivo CurseForge: {}", e))?;
+        let data = &payload["data"];
+        let mut url_download = data["downloadUrl"]
+            .as_str()
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        if url_download.is_empty() {
+            match url_download_curseforge(client, mod_id, file_id).await {
+                Ok(url) => url_download = url,
+                Err(e) => {
+                    if erros_curseforge.len() < 8 {
+                        erros_curseforge.push(e);
                     }
                 }
             }
+        }
 
-            if url_download.is_empty() {
-                if erros_curseforge.len() < 8 {
-                    erros_curseforge.push(format!(
-                        "Arquivo CurseForge {}:{} sem URL de download",
-                        mod_id, file_id
-                    ));
-                }
-                continue;
+        if url_download.is_empty() {
+            if erros_curseforge.len() < 8 {
+                erros_curseforge.push(format!(
+                    "Arquivo CurseForge {}:{} sem URL de download",
+                    mod_id, file_id
+                ));
             }
-
-            let file_name = data["fileName"]
-                .as_str()
-                .filter(|nome| !nome.trim().is_empty())
-                .unwrap_or("mod.jar")
-                .to_string();
-
-            arquivos_para_baixar.push((
-                url_download,
-                file_name.clone(),
-                format!("mods/{}", file_name),
-            ));
+            continue;
         }
-    }
 
-    if detectou_curseforge
-        && total_arquivos_manifesto_curseforge > 0
-        && arquivos_para_baixar.is_empty()
-    {
-        let detalhe = if erros_curseforge.is_empty() {
-            "Nenhuma URL válida foi retornada pelo CurseForge.".to_string()
-        } else {
-            format!("Detalhes: {}", erros_curseforge.join(" | "))
-        };
-        return Err(format!(
-            "Não foi possível obter arquivos do modpack no CurseForge. {}",
-            detalhe
-        ));
-    }
+        let file_name = data["fileName"]
+            .as_str()
+            .filter(|nome| !nome.trim().is_empty())
+            .unwrap_or("mod.jar")
+            .to_string();
 
-    if !detectou_modrinth && !detectou_curseforge {
-        return Err("O arquivo não contém modrinth.index.json nem manifest.json.".to_string());
+        arquivos.push(ArquivoModpack {
+            urls: vec![url_download],
+            nome: file_name.clone(),
+            caminho: format!("mods/{}", file_name),
+            sha1: sha1_arquivo_curseforge(data),
+            sha512: None,
+            tamanho: data["fileLength"].as_u64(),
+            opcional: false,
+            plataforma: ModPlatform::CurseForge,
+            project_id: Some(mod_id.to_string()),
+            versao_id: Some(file_id.to_string()),
+        });
     }
 
-    let prefixo_overrides = format!("{}/", pasta_overrides.trim_matches('/'));
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Erro ao ler arquivo do ZIP: {}", e))?;
-        let nome = file.name().replace('\\', "/");
-        if !nome.starts_with(&prefixo_overrides) {
-            continue;
-        }
+    Ok((arquivos, erros_curseforge))
+}
 
-        let relative = nome.trim_start_matches(&prefixo_overrides);
-        if relative.is_empty() {
-            continue;
-        }
-        let dest = instance_path.join(relative);
+/// Extrai as pastas de overrides do modpack para `destino`, na ordem dada
+/// (`client-overrides` depois de `overrides`), devolvendo o caminho relativo
+/// e o SHA-1 de cada arquivo extraído.
+pub(crate) fn extrair_overrides<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    pastas_overrides: &[String],
+    destino: &Path,
+    tarefa: &Tarefa,
+) -> Result<Vec<(String, String)>, String> {
+    let mut extraidos: BTreeMap<String, String> = BTreeMap::new();
+    tarefa.verificar_cancelamento()?;
+    tarefa.fase("overrides", pastas_overrides.len(), 0);
+    let limites = LimitesExtracao::default();
+    for pasta_overrides in pastas_overrides {
+        let arquivos = extrair_pasta_zip(archive, pasta_overrides, destino, &limites)
+            .map_err(|e| format!("Overrides do modpack recusados: {}", e))?;
+        extraidos.extend(
+            arquivos
+                .into_iter()
+                .map(|arquivo| (arquivo.caminho, arquivo.sha1)),
+        );
+        tarefa.avancar(1, 0);
+    }
+    Ok(extraidos.into_iter().collect())
+}
 
-        if file.is_dir() {
-            std::fs::create_dir_all(&dest).ok();
-        } else {
-            if let Some(parent) = dest.parent() {
-                std::fs::create_dir_all(parent).ok();
-            }
-            let mut outfile = std::fs::File::create(&dest)
-                .map_err(|e| format!("Erro ao criar arquivo: {}", e))?;
-            std::io::copy(&mut file, &mut outfile)
-                .map_err(|e| format!("Erro ao copiar arquivo: {}", e))?;
+/// Confere tamanho e hashes declarados no índice contra os bytes baixados.
+fn conferir_arquivo_baixado(
+    arquivo: &ArquivoModpack,
+    bytes: &[u8],
+) -> Result<HashesConteudo, String> {
+    if let Some(tamanho) = arquivo.tamanho {
+        if bytes.len() as u64 != tamanho {
+            return Err(format!(
+                "tamanho {} diferente do esperado ({})",
+                bytes.len(),
+                tamanho
+            ));
         }
     }
+    let hashes = HashesConteudo::de_bytes(bytes);
+    if arquivo.sha512.is_some() && arquivo.sha512 != hashes.sha512 {
+        return Err("SHA-512 não confere".to_string());
+    }
+    if arquivo.sha1.is_some() && arquivo.sha1 != hashes.sha1 {
+        return Err("SHA-1 não confere".to_string());
+    }
+    Ok(hashes)
+}
 
-    println!(
-        "[install_modpack_files] {} mods para baixar",
-        arquivos_para_baixar.len()
-    );
+/// Arquivo do índice baixado para o disco.
+#[derive(Debug, Clone)]
+pub(crate) struct ArquivoBaixado {
+    /// Caminho relativo à pasta de destino.
+    pub caminho: String,
+    pub sha1: String,
+    pub entrada: Option<EntradaConteudo>,
+}
 
-    // Baixar mods em paralelo
+/// Baixa os arquivos do índice em paralelo (10 simultâneos) para dentro de
+/// `destino`, mantendo a estrutura de pastas da instância. Cada URL
+/// alternativa é tentada quando a anterior falha ou entrega um arquivo cujo
+/// hash não confere.
+pub(crate) async fn baixar_arquivos_modpack(
+    client: &reqwest::Client,
+    destino: &Path,
+    arquivos: &[ArquivoModpack],
+    tarefa: &Tarefa,
+) -> Vec<Result<ArquivoBaixado, String>> {
     use futures::stream::{self, StreamExt};
+    tarefa.fase("arquivos", arquivos.len(), 0);
 
-    let download_tasks: Vec<_> = arquivos_para_baixar
+    let download_tasks: Vec<_> = arquivos
         .iter()
-        .map(|(url, filename, path)| {
+        .map(|arquivo| {
             let client = client.clone();
-            let url = url.clone();
-            let filename = filename.clone();
-            let path = path.clone();
-            let instance_path = instance_path.clone();
+            let filename = arquivo.nome.clone();
+            let path = arquivo.caminho.clone();
+            let dest_path = destino.join(&path);
 
-            async move {
-                // Determinar pasta de destino (mods, resourcepacks, etc)
-                let dest_folder = if path.starts_with("mods/") {
-                    instance_path.join("mods")
-                } else if path.starts_with("resourcepacks/") {
-                    instance_path.join("resourcepacks")
-                } else if path.starts_with("shaderpacks/") {
-                    instance_path.join("shaderpacks")
-                } else if path.starts_with("config/") {
-                    instance_path.join("config")
-                } else {
-                    instance_path.join("mods")
-                };
-
-                std::fs::create_dir_all(&dest_folder).ok();
-                let dest_path = dest_folder.join(&filename);
+            let download = async move {
+                if let Some(dest_folder) = dest_path.parent() {
+                    std::fs::create_dir_all(dest_folder).ok();
+                }
 
                 let mut ultima_falha = String::new();
-                for tentativa in 1..=2 {
-                    match client.get(&url).send().await {
-                        Ok(response) => {
-                            if !response.status().is_success() {
-                                ultima_falha = format!("HTTP {}", response.status());
-                            } else {
+                for url in &arquivo.urls {
+                    for tentativa in 1..=2 {
+                        let bytes = match client.get(url).send().await {
+                            Ok(response) if response.status().is_success() => {
                                 match response.bytes().await {
-                                    Ok(bytes) => {
-                                        if let Err(e) = std::fs::write(&dest_path, &bytes) {
-                                            ultima_falha = format!("erro ao salvar arquivo: {}", e);
-                                        } else {
-                                            println!(
-                                                "[install_modpack_files] Baixado: {}",
-                                                filename
-                                            );
-                                            return Ok::<(), String>(());
-                                        }
-                                    }
+                                    Ok(bytes) => bytes,
                                     Err(e) => {
                                         ultima_falha = format!("erro ao ler bytes: {}", e);
+                                        continue;
                                     }
                                 }
                             }
-                        }
-                        Err(e) => {
-                            ultima_falha = e.to_string();
-                        }
-                    }
+                            Ok(response) => {
+                                ultima_falha = format!("HTTP {} em {}", response.status(), url);
+                                break;
+                            }
+                            Err(e) => {
+                                ultima_falha = e.to_string();
+                                if tentativa < 2 {
+                                    tokio::time::sleep(std::time::Duration::from_millis(800))
+                                        .await;
+                                }
+                                continue;
+                            }
+                        };
+
+                        // Arquivo corrompido ou adulterado: passa para a próxima URL.
+                        let hashes = match conferir_arquivo_baixado(arquivo, &bytes) {
+                            Ok(hashes) => hashes,
+                            Err(e) => {
+                                ultima_falha = format!("{} em {}", e, url);
+                                break;
+                            }
+                        };
+                        escrever_atomico(&dest_path, &bytes)?;
 
-                    if tentativa < 2 {
-                        tokio::time::sleep(std::time::Duration::from_millis(800)).await;
+                        println!("[install_modpack_files] Baixado: {}", filename);
+                        let sha1 = hashes.sha1.clone().unwrap_or_default();
+                        let entrada = tipo_conteudo_por_caminho(&path).map(|tipo| {
+                            EntradaConteudo::nova(
+                                &filename,
+                                tipo,
+                                hashes,
+                                OrigemInstalacao::Modpack,
+                            )
+                            .com_projeto(
+                                arquivo.plataforma.clone(),
+                                arquivo.project_id.clone(),
+                                arquivo.versao_id.clone(),
+                            )
+                            .com_url_download(url)
+                        });
+                        return Ok::<_, String>(ArquivoBaixado {
+                            caminho: path,
+                            sha1,
+                            entrada,
+                        });
                     }
                 }
 
@@ -454,99 +697,480 @@ pub async fn install_modpack_files(
                     "[install_modpack_files] Falha ao baixar {}: {}",
                     filename, ultima_falha
                 );
-                Err(format!(
-                    "Falha ao baixar {} ({}): {}",
-                    filename, url, ultima_falha
-                ))
+                Err(format!("Falha ao baixar {}: {}", filename, ultima_falha))
+            };
+
+            async move {
+                let resultado = tokio::select! {
+                    _ = tarefa.cancelamento().cancelled() => Err(MENSAGEM_TAREFA_CANCELADA.to_string()),
+                    resultado = download => resultado,
+                };
+                tarefa.avancar(1, 0);
+                resultado
             }
         })
         .collect();
 
-    // Executar downloads em paralelo (10 simultâneos)
-    let resultados_download = stream::iter(download_tasks)
+    stream::iter(download_tasks)
         .buffer_unordered(10)
         .collect::<Vec<_>>()
-        .await;
-    let total_sucesso = resultados_download.iter().filter(|r| r.is_ok()).count();
-    let erros_download: Vec<String> = resultados_download
-        .into_iter()
-        .filter_map(Result::err)
-        .take(8)
+        .await
+}
+
+/// Pacote baixado por `ler_modpack`, reaproveitado pela instalação logo em
+/// seguida para não baixar o mesmo arquivo duas vezes.
+fn caminho_cache_modpack(download_url: &str) -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join("dome_launcher_modpacks")
+        .join(format!("{:x}.zip", Sha1::digest(download_url.as_bytes())))
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ArquivoOpcionalModpack {
+    caminho: String,
+    nome: String,
+    tamanho: Option<u64>,
+}
+
+/// Dados do índice do modpack para criar a instância e perguntar ao usuário
+/// sobre os arquivos opcionais antes de instalar.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumoModpack {
+    nome: Option<String>,
+    versao: Option<String>,
+    versao_minecraft: Option<String>,
+    loader: Option<String>,
+    versao_loader: Option<String>,
+    total_arquivos: usize,
+    arquivos_opcionais: Vec<ArquivoOpcionalModpack>,
+}
+
+/// Baixa o modpack e lê seu índice sem instalar nada.
+#[tauri::command]
+pub async fn ler_modpack(
+    app: tauri::AppHandle,
+    state: State<'_, LauncherState>,
+    download_url: String,
+    task_id: Option<String>,
+) -> Result<ResumoModpack, String> {
+    let tarefa = state
+        .tarefas
+        .iniciar(Some(app), task_id, "ler_modpack", "Lendo modpack", None)?;
+    let resultado = async {
+        let caminho = caminho_cache_modpack(&download_url);
+        if caminho.exists() && !pacote_modpack_legivel(&caminho) {
+            let _ = std::fs::remove_file(&caminho);
+        }
+        if !caminho.exists() {
+            if let Some(pasta) = caminho.parent() {
+                std::fs::create_dir_all(pasta)
+                    .map_err(|e| format!("Erro ao criar pasta temporária: {}", e))?;
+            }
+            tarefa.fase("baixando_modpack", 1, 0);
+            let client = cliente_http_modpack()?;
+            baixar_pacote_modpack(&client, &download_url, &caminho, &tarefa).await?;
+        }
+
+        let file =
+            std::fs::File::open(&caminho).map_err(|e| format!("Erro ao abrir modpack: {}", e))?;
+        let mut archive =
+            zip::ZipArchive::new(file).map_err(|e| format!("Erro ao ler ZIP: {}", e))?;
+        let indice = ler_indice_modpack(&mut archive)?;
+        Ok(ResumoModpack {
+            nome: indice.nome,
+            versao: indice.versao,
+            versao_minecraft: indice.versao_minecraft,
+            loader: indice.loader,
+            versao_loader: indice.versao_loader,
+            total_arquivos: indice.arquivos.len() + indice.arquivos_curseforge.len(),
+            arquivos_opcionais: indice
+                .arquivos
+                .iter()
+                .filter(|arquivo| arquivo.opcional)
+                .map(|arquivo| ArquivoOpcionalModpack {
+                    caminho: arquivo.caminho.clone(),
+                    nome: arquivo.nome.clone(),
+                    tamanho: arquivo.tamanho,
+                })
+                .collect(),
+        })
+    }
+    .await;
+    tarefa.finalizar(resultado)
+}
+
+/// Instala os arquivos do modpack como uma tarefa cancelável, com progresso
+/// emitido no evento `tarefa-progresso`. `arquivos_opcionais` lista os
+/// caminhos opcionais escolhidos pelo usuário; sem a lista, todos são instalados.
+/// Com `nova_instancia`, a pasta da instância é apagada se a tarefa for cancelada.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn install_modpack_files(
+    app: tauri::AppHandle,
+    state: State<'_, LauncherState>,
+    instance_id: String,
+    download_url: String,
+    file_name: String,
+    arquivos_opcionais: Option<Vec<String>>,
+    nova_instancia: Option<bool>,
+    task_id: Option<String>,
+) -> Result<(), String> {
+    let instance_path =
+        crate::comandos::instancia_sistema::caminho_instancia_por_id(&state, &instance_id)?;
+    let instancia =
+        crate::comandos::instancia_sistema::obter_instancia_por_id(&state, &instance_id).ok();
+    let tarefa = state.tarefas.iniciar(
+        Some(app),
+        task_id,
+        "instalar_modpack",
+        &format!("Instalando modpack em {}", instance_id),
+        Some(&instance_id),
+    )?;
+    // Instância criada só para este modpack: cancelar não deixa uma cópia pela metade.
+    if nova_instancia.unwrap_or(false) {
+        tarefa.limpar_ao_cancelar(instance_path.clone());
+    }
+    let resultado = instalar_arquivos_modpack(
+        instance_path,
+        instancia,
+        download_url,
+        file_name,
+        arquivos_opcionais,
+        &tarefa,
+    )
+    .await;
+    tarefa.finalizar(resultado)
+}
+
+async fn instalar_arquivos_modpack(
+    instance_path: std::path::PathBuf,
+    instancia: Option<Instance>,
+    download_url: String,
+    file_name: String,
+    arquivos_opcionais: Option<Vec<String>>,
+    tarefa: &Tarefa,
+) -> Result<(), String> {
+    let temp_path = instance_path.join("temp");
+    std::fs::create_dir_all(&temp_path).map_err(|e| format!("Erro ao criar pasta temp: {}", e))?;
+    tarefa.limpar_ao_cancelar(temp_path.clone());
+
+    let resultado = preparar_e_instalar_modpack(
+        &instance_path,
+        instancia.as_ref(),
+        &download_url,
+        &file_name,
+        arquivos_opcionais,
+        &temp_path,
+        tarefa,
+    )
+    .await;
+
+    // Limpar arquivos temporários, inclusive os preparados de uma instalação que falhou
+    std::fs::remove_dir_all(&temp_path).ok();
+    if resultado.is_ok() {
+        println!("[install_modpack_files] Instalação concluída!");
+    }
+    resultado
+}
+
+/// Baixa o pacote, prepara overrides e arquivos do índice em `temp/preparacao`
+/// e só os move para a instância quando tudo o que é obrigatório chegou.
+async fn preparar_e_instalar_modpack(
+    instance_path: &Path,
+    instancia: Option<&Instance>,
+    download_url: &str,
+    file_name: &str,
+    arquivos_opcionais: Option<Vec<String>>,
+    temp_path: &Path,
+    tarefa: &Tarefa,
+) -> Result<(), String> {
+    let nome_arquivo_seguro = std::path::Path::new(file_name)
+        .file_name()
+        .and_then(|nome| nome.to_str())
+        .filter(|nome| !nome.trim().is_empty())
+        .unwrap_or("modpack.zip")
+        .to_string();
+    let mrpack_path = temp_path.join(&nome_arquivo_seguro);
+    let preparacao = temp_path.join("preparacao");
+
+    tarefa.fase("baixando_modpack", 1, 0);
+    let client = cliente_http_modpack()?;
+    let cache = caminho_cache_modpack(download_url);
+    let reaproveitado = cache.exists()
+        && (std::fs::rename(&cache, &mrpack_path).is_ok()
+            || std::fs::copy(&cache, &mrpack_path).is_ok());
+    let _ = std::fs::remove_file(&cache);
+    if reaproveitado && pacote_modpack_legivel(&mrpack_path) {
+        tarefa.avancar(1, 0);
+    } else {
+        println!("[install_modpack_files] Baixando: {}", download_url);
+        baixar_pacote_modpack(&client, download_url, &mrpack_path, tarefa).await?;
+    }
+
+    println!("[install_modpack_files] Arquivo salvo: {:?}", mrpack_path);
+
+    // Extrair .mrpack (é um ZIP)
+    let file =
+        std::fs::File::open(&mrpack_path).map_err(|e| format!("Erro ao abrir mrpack: {}", e))?;
+
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Erro ao ler ZIP: {}", e))?;
+
+    // Pode ser Modrinth (modrinth.index.json) ou CurseForge (manifest.json)
+    let indice = ler_indice_modpack(&mut archive)?;
+    if let Some(instancia) = instancia {
+        crate::aplicacao::conferir_versao_minecraft(instancia, &indice)?;
+    }
+    let (arquivos_curseforge, erros_curseforge) =
+        resolver_arquivos_curseforge(&client, &indice.arquivos_curseforge, tarefa).await?;
+    // Arquivos do manifesto CurseForge não têm opcionais: todos são obrigatórios.
+    if indice.arquivos_curseforge.len() > arquivos_curseforge.len() {
+        let detalhe = if erros_curseforge.is_empty() {
+            "Nenhuma URL válida foi retornada pelo CurseForge.".to_string()
+        } else {
+            format!("Detalhes: {}", erros_curseforge.join(" | "))
+        };
+        return Err(format!(
+            "Não foi possível obter todos os arquivos do modpack no CurseForge. {}",
+            detalhe
+        ));
+    }
+    let mut arquivos_para_baixar: Vec<ArquivoModpack> = indice
+        .arquivos
+        .iter()
+        .filter(|arquivo| {
+            !arquivo.opcional
+                || arquivos_opcionais
+                    .as_ref()
+                    .is_none_or(|escolhidos| escolhidos.contains(&arquivo.caminho))
+        })
+        .cloned()
         .collect();
+    arquivos_para_baixar.extend(arquivos_curseforge);
 
-    if !arquivos_para_baixar.is_empty() && total_sucesso == 0 {
+    let mut registro = ArquivosModpackInstalados {
+        versao_id: ler_modpack_info(instance_path)
+            .ok()
+            .flatten()
+            .map(|info| info.version_id),
+        arquivos: BTreeMap::new(),
+    };
+    let mut entradas_lockfile: Vec<EntradaConteudo> = Vec::new();
+    let overrides = extrair_overrides(&mut archive, &indice.pastas_overrides, &preparacao, tarefa)?;
+    for (relative, sha1) in overrides {
+        if let Some(tipo) = tipo_conteudo_por_caminho(&relative) {
+            match EntradaConteudo::de_arquivo(
+                &preparacao.join(&relative),
+                tipo,
+                OrigemInstalacao::Modpack,
+            ) {
+                Ok(entrada) => entradas_lockfile.push(entrada),
+                Err(e) => eprintln!("[Lockfile] {}", e),
+            }
+        }
+        registro.arquivos.insert(relative, sha1);
+    }
+
+    println!(
+        "[install_modpack_files] {} mods para baixar",
+        arquivos_para_baixar.len()
+    );
+
+    // Baixar mods em paralelo
+    tarefa.verificar_cancelamento()?;
+    let resultados_download =
+        baixar_arquivos_modpack(&client, &preparacao, &arquivos_para_baixar, tarefa).await;
+    tarefa.verificar_cancelamento()?;
+    let mut baixados: HashSet<String> = HashSet::new();
+    let mut erros_download: Vec<String> = Vec::new();
+    for resultado in resultados_download {
+        match resultado {
+            Ok(baixado) => {
+                entradas_lockfile.extend(baixado.entrada);
+                baixados.insert(baixado.caminho.clone());
+                registro.arquivos.insert(baixado.caminho, baixado.sha1);
+            }
+            Err(e) if erros_download.len() < 8 => erros_download.push(e),
+            Err(_) => {}
+        }
+    }
+
+    // Só opcionais podem faltar; sem um obrigatório o pack não abre ou abre quebrado.
+    let obrigatorios_faltando: Vec<&str> = arquivos_para_baixar
+        .iter()
+        .filter(|arquivo| !arquivo.opcional && !baixados.contains(&arquivo.caminho))
+        .map(|arquivo| arquivo.nome.as_str())
+        .collect();
+    if !obrigatorios_faltando.is_empty() {
         let detalhe_download = if erros_download.is_empty() {
             "sem detalhes do download".to_string()
         } else {
             erros_download.join(" | ")
         };
-        let detalhe_curseforge = if erros_curseforge.is_empty() {
-            String::new()
-        } else {
-            format!(" | CurseForge: {}", erros_curseforge.join(" | "))
-        };
         return Err(format!(
-            "Nenhum arquivo do modpack pôde ser baixado. {}{}",
-            detalhe_download, detalhe_curseforge
+            "{} arquivo(s) obrigatório(s) do modpack não puderam ser baixados ou verificados: {}. {}",
+            obrigatorios_faltando.len(),
+            obrigatorios_faltando.join(", "),
+            detalhe_download
         ));
     }
+    if !erros_download.is_empty() {
+        eprintln!(
+            "[install_modpack_files] Opcionais ignorados: {}",
+            erros_download.join(" | ")
+        );
+    }
 
-    // Limpar arquivos temporários
-    std::fs::remove_dir_all(&temp_path).ok();
+    // O loader segue as dependências declaradas pelo pacote.
+    let instancia_atualizada = match instancia {
+        Some(instancia) => {
+            crate::aplicacao::alinhar_loader_com_modpack(instancia, &indice, tarefa).await?
+        }
+        None => None,
+    };
+
+    // Com tudo preparado, o conteúdo vai para a instância
+    tarefa.verificar_cancelamento()?;
+    std::fs::create_dir_all(instance_path.join("mods"))
+        .map_err(|e| format!("Erro ao criar pasta mods: {}", e))?;
+    mover_arquivos_preparados(&preparacao, instance_path, registro.arquivos.keys())?;
+    if let Some(atualizada) = &instancia_atualizada {
+        crate::aplicacao::gravar_instancia(atualizada)?;
+    }
+
+    if let Err(e) = registrar_conteudo(instance_path, entradas_lockfile) {
+        eprintln!("[Lockfile] Falha ao registrar conteúdo do modpack: {}", e);
+    }
+    if let Err(e) = salvar_arquivos_modpack(instance_path, &registro) {
+        eprintln!("[Modpack] {}", e);
+    }
+    Ok(())
+}
 
-    println!("[install_modpack_files] Instalação concluída!");
+/// Move os arquivos preparados (caminhos relativos) para dentro da instância.
+fn mover_arquivos_preparados<'a>(
+    preparacao: &Path,
+    instance_path: &Path,
+    relativos: impl Iterator<Item = &'a String>,
+) -> Result<(), String> {
+    for relativo in relativos {
+        let destino = instance_path.join(relativo);
+        if let Some(pasta) = destino.parent() {
+            std::fs::create_dir_all(pasta)
+                .map_err(|e| format!("Erro ao criar pasta {}: {}", pasta.display(), e))?;
+        }
+        std::fs::rename(preparacao.join(relativo), &destino)
+            .map_err(|e| format!("Erro ao instalar {}: {}", relativo, e))?;
+    }
     Ok(())
 }
 
+pub(crate) fn cliente_http_modpack() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(30))
+        .timeout(std::time::Duration::from_secs(180))
+        .user_agent("DomeLauncher/1.0 (+https://domestudios.com.br)")
+        .build()
+        .map_err(|e| format!("Erro ao criar cliente HTTP: {}", e))
+}
+
 #[tauri::command]
 pub async fn get_modpack_info(
     state: State<'_, LauncherState>,
     instance_id: String,
 ) -> Result<Option<ModpackInfo>, String> {
-    let modpack_path =
-        crate::comandos::instancia_sistema::caminho_instancia_por_id(&state, &instance_id)?
-            .join("modpack.json");
+    let instance_path =
+        crate::comandos::instancia_sistema::caminho_instancia_por_id(&state, &instance_id)?;
+    ler_modpack_info(&instance_path)
+}
 
-    if !modpack_path.exists() {
-        return Ok(None);
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn le_loader_do_indice_modrinth_e_do_manifesto_curseforge() {
+        let dependencias = serde_json::json!({
+            "minecraft": "1.20.1",
+            "fabric-loader": "0.15.11"
+        });
+        assert_eq!(
+            loader_de_dependencias_modrinth(&dependencias),
+            Some(("fabric".to_string(), "0.15.11".to_string()))
+        );
+
+        let manifesto = serde_json::json!({
+            "minecraft": {
+                "version": "1.20.1",
+                "modLoaders": [
+                    { "id": "fabric-0.14.0", "primary": false },
+                    { "id": "forge-47.2.0", "primary": true }
+                ]
+            }
+        });
+        assert_eq!(
+            loader_de_manifesto_curseforge(&manifesto),
+            Some(("forge".to_string(), "47.2.0".to_string()))
+        );
     }
 
-    let content = std::fs::read_to_string(&modpack_path)
-        .map_err(|e| format!("Erro ao ler modpack.json: {}", e))?;
+    #[test]
+    fn indice_modrinth_respeita_env_path_e_hashes() {
+        let instalar = serde_json::json!({
+            "path": "config/pasta/opcoes.jar",
+            "hashes": { "sha1": "AB", "sha512": "cd" },
+            "env": { "client": "optional", "server": "required" },
+            "downloads": [
+                "https://cdn.modrinth.com/data/AABBCCDD/versions/EEFFGGHH/opcoes.jar",
+                "https://espelho.exemplo/opcoes.jar"
+            ],
+            "fileSize": 2
+        });
+        let arquivo = arquivo_do_indice_modrinth(&instalar).unwrap().unwrap();
+        assert_eq!(arquivo.caminho, "config/pasta/opcoes.jar");
+        assert_eq!(arquivo.urls.len(), 2);
+        assert_eq!(arquivo.sha1.as_deref(), Some("ab"));
+        assert!(arquivo.opcional);
+        assert_eq!(arquivo.project_id.as_deref(), Some("AABBCCDD"));
 
-    let info: ModpackInfo = serde_json::from_str(&content)
-        .map_err(|e| format!("Erro ao parsear modpack.json: {}", e))?;
+        let servidor = serde_json::json!({
+            "path": "mods/servidor.jar",
+            "env": { "client": "unsupported", "server": "required" },
+            "downloads": ["https://cdn.modrinth.com/data/a/versions/b/servidor.jar"]
+        });
+        assert!(arquivo_do_indice_modrinth(&servidor).unwrap().is_none());
 
-    Ok(Some(info))
-}
+        let fuga = serde_json::json!({
+            "path": "../../fora.jar",
+            "downloads": ["https://cdn.modrinth.com/data/a/versions/b/fora.jar"]
+        });
+        assert!(arquivo_do_indice_modrinth(&fuga).is_err());
+    }
 
-#[tauri::command]
-pub async fn check_modpack_updates(
-    _instance_id: String,
-    project_id: String,
-    installed_version: String,
-) -> Result<Option<String>, String> {
-    let url = format!("https://api.modrinth.com/v2/project/{}/version", project_id);
-
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Erro ao verificar atualizações: {}", e))?;
+    #[test]
+    fn confere_tamanho_e_hashes_do_download() {
+        let bytes = b"conteudo";
+        let hashes = HashesConteudo::de_bytes(bytes);
+        let mut arquivo = ArquivoModpack {
+            urls: Vec::new(),
+            nome: "x.jar".to_string(),
+            caminho: "mods/x.jar".to_string(),
+            sha1: hashes.sha1.clone(),
+            sha512: hashes.sha512.clone(),
+            tamanho: Some(bytes.len() as u64),
+            opcional: false,
+            plataforma: ModPlatform::Modrinth,
+            project_id: None,
+            versao_id: None,
+        };
+        assert!(conferir_arquivo_baixado(&arquivo, bytes).is_ok());
 
-    let versions: Vec<serde_json::Value> = response
-        .json()
-        .await
-        .map_err(|e| format!("Erro ao parsear resposta: {}", e))?;
+        arquivo.sha512 = Some("0".repeat(128));
+        assert!(conferir_arquivo_baixado(&arquivo, bytes).is_err());
 
-    if let Some(latest) = versions.first() {
-        if let Some(version_number) = latest["version_number"].as_str() {
-            if version_number != installed_version {
-                return Ok(Some(version_number.to_string()));
-            }
-        }
+        arquivo.sha512 = None;
+        arquivo.tamanho = Some(1);
+        assert!(conferir_arquivo_baixado(&arquivo, bytes).is_err());
     }
-
-    Ok(None)
 }
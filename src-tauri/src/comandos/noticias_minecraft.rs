@@ -28,13 +28,9 @@ fn agora_em_ms() -> u64 {
 }
 
 fn get_cache_dir() -> std::path::PathBuf {
-    std::env::var("APPDATA")
-        .map(|app_data| {
-            std::path::PathBuf::from(app_data)
-                .join("dome")
-                .join("cache")
-        })
-        .unwrap_or_else(|_| std::path::PathBuf::from("cache"))
+    crate::plataforma::pasta_dados_launcher()
+        .map(|pasta| pasta.join("cache"))
+        .unwrap_or_else(|| std::path::PathBuf::from("cache"))
 }
 
 fn get_minecraft_news_cache_path() -> std::path::PathBuf {
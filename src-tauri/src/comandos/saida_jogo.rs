@@ -0,0 +1,459 @@
+use crate::launcher::LauncherState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{Emitter, State};
+
+/// Evento emitido para cada linha capturada (payload: `EventoSaidaJogo`).
+pub const EVENTO_SAIDA_JOGO: &str = "saida-jogo";
+/// Arquivo com a saída bruta do processo, ao lado dos logs do jogo.
+pub const ARQUIVO_SAIDA_LAUNCHER: &str = "launcher_output.log";
+/// Linhas mantidas em memória por instância em execução.
+const CAPACIDADE_BUFFER: usize = 5000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FluxoSaida {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistroLog {
+    /// Posição crescente dentro da sessão, para a interface descartar repetidos.
+    pub sequencia: u64,
+    pub horario: Option<String>,
+    pub nivel: String,
+    pub thread: Option<String>,
+    pub logger: Option<String>,
+    pub mensagem: String,
+    pub fluxo: FluxoSaida,
+}
+
+impl RegistroLog {
+    /// Linha no formato do `latest.log`, usada no `launcher_output.log`.
+    pub fn formatar(&self) -> String {
+        let mut linha = String::new();
+        if let Some(horario) = &self.horario {
+            linha.push_str(&format!("[{}] ", horario));
+        }
+        if self.thread.is_some() || self.horario.is_some() {
+            linha.push_str(&format!(
+                "[{}/{}] ",
+                self.thread.as_deref().unwrap_or("?"),
+                self.nivel
+            ));
+        }
+        if let Some(logger) = &self.logger {
+            linha.push_str(&format!("[{}] ", logger));
+        }
+        if !linha.is_empty() {
+            linha.pop();
+            linha.push_str(": ");
+        }
+        linha.push_str(&self.mensagem);
+        linha
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventoSaidaJogo {
+    pub instance_id: String,
+    pub registro: RegistroLog,
+}
+
+fn normalizar_nivel(nivel: &str) -> String {
+    match nivel.trim().to_uppercase().as_str() {
+        "WARNING" => "WARN".to_string(),
+        "SEVERE" => "ERROR".to_string(),
+        "FINE" | "FINER" | "FINEST" => "DEBUG".to_string(),
+        outro => outro.to_string(),
+    }
+}
+
+fn desescapar_xml(texto: &str) -> String {
+    texto
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn regex_linha_simples() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    // [12:34:56] [Render thread/INFO] [net.minecraft.Logger/]: mensagem
+    REGEX.get_or_init(|| {
+        regex::Regex::new(r"^\[([^\]]+)\] \[(.+?)/([A-Za-z]+)\](?: \[([^\]]*)\])?:? ?(.*)$")
+            .expect("regex de log válida")
+    })
+}
+
+fn regex_atributos_xml() -> &'static regex::Regex {
+    static REGEX: OnceLock<regex::Regex> = OnceLock::new();
+    REGEX.get_or_init(|| regex::Regex::new(r#"(\w+)="([^"]*)""#).expect("regex de XML válida"))
+}
+
+fn conteudo_tag_xml(evento: &str, tag: &str) -> Option<String> {
+    let inicio = evento.find(&format!("<log4j:{}>", tag))? + tag.len() + 8;
+    let fim = evento[inicio..].find(&format!("</log4j:{}>", tag))? + inicio;
+    let conteudo = evento[inicio..fim].trim();
+    Some(
+        conteudo
+            .strip_prefix("<![CDATA[")
+            .and_then(|resto| resto.strip_suffix("]]>"))
+            .map(str::to_string)
+            .unwrap_or_else(|| desescapar_xml(conteudo)),
+    )
+}
+
+/// Interpreta a saída do jogo: eventos XML do log4j (`<log4j:Event>`, que podem
+/// ocupar várias linhas) ou linhas no padrão `[hora] [thread/NÍVEL]: mensagem`.
+#[derive(Debug, Default)]
+pub struct ParserSaidaJogo {
+    evento_xml: Option<String>,
+    ultimo_nivel: Option<String>,
+    sequencia: u64,
+}
+
+impl ParserSaidaJogo {
+    pub fn processar_linha(&mut self, linha: &str, fluxo: FluxoSaida) -> Option<RegistroLog> {
+        let linha = linha.trim_end_matches(['\r', '\n']);
+
+        if let Some(evento) = self.evento_xml.as_mut() {
+            evento.push('\n');
+            evento.push_str(linha);
+            if linha.contains("</log4j:Event>") {
+                let evento = self.evento_xml.take().unwrap_or_default();
+                return Some(self.registro_xml(&evento, fluxo));
+            }
+            return None;
+        }
+
+        if linha.trim_start().starts_with("<log4j:Event") {
+            if linha.contains("</log4j:Event>") {
+                return Some(self.registro_xml(linha, fluxo));
+            }
+            self.evento_xml = Some(linha.to_string());
+            return None;
+        }
+
+        if linha.trim().is_empty() {
+            return None;
+        }
+        Some(self.registro_simples(linha, fluxo))
+    }
+
+    fn proxima_sequencia(&mut self) -> u64 {
+        self.sequencia += 1;
+        self.sequencia
+    }
+
+    fn registro_xml(&mut self, evento: &str, fluxo: FluxoSaida) -> RegistroLog {
+        let abertura = evento.split('>').next().unwrap_or_default();
+        let atributos: HashMap<&str, String> = regex_atributos_xml()
+            .captures_iter(abertura)
+            .filter_map(|captura| {
+                Some((
+                    captura.get(1)?.as_str(),
+                    desescapar_xml(captura.get(2)?.as_str()),
+                ))
+            })
+            .collect();
+
+        let horario = atributos
+            .get("timestamp")
+            .and_then(|valor| valor.parse::<i64>().ok())
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|data| {
+                data.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            });
+        let nivel = normalizar_nivel(atributos.get("level").map_or("INFO", String::as_str));
+
+        let mut mensagem = conteudo_tag_xml(evento, "Message").unwrap_or_default();
+        if let Some(throwable) = conteudo_tag_xml(evento, "Throwable") {
+            mensagem.push('\n');
+            mensagem.push_str(throwable.trim_end());
+        }
+
+        self.ultimo_nivel = Some(nivel.clone());
+        RegistroLog {
+            sequencia: self.proxima_sequencia(),
+            horario,
+            nivel,
+            thread: atributos.get("thread").cloned(),
+            logger: atributos
+                .get("logger")
+                .cloned()
+                .filter(|logger| !logger.is_empty()),
+            mensagem,
+            fluxo,
+        }
+    }
+
+    fn registro_simples(&mut self, linha: &str, fluxo: FluxoSaida) -> RegistroLog {
+        if let Some(captura) = regex_linha_simples().captures(linha) {
+            let nivel = normalizar_nivel(&captura[3]);
+            self.ultimo_nivel = Some(nivel.clone());
+            return RegistroLog {
+                sequencia: self.proxima_sequencia(),
+                horario: Some(captura[1].to_string()),
+                nivel,
+                thread: Some(captura[2].to_string()),
+                logger: captura
+                    .get(4)
+                    .map(|logger| logger.as_str().trim_end_matches('/').to_string())
+                    .filter(|logger| !logger.is_empty()),
+                mensagem: captura[5].to_string(),
+                fluxo,
+            };
+        }
+
+        // Stack traces continuam o nível da linha anterior.
+        let continuacao = linha.starts_with('\t')
+            || linha.starts_with("Caused by:")
+            || linha.trim_start().starts_with("at ")
+            || linha.trim_start().starts_with("...");
+        let nivel = match (&self.ultimo_nivel, continuacao, fluxo) {
+            (Some(nivel), true, _) => nivel.clone(),
+            (_, _, FluxoSaida::Stderr) => "ERROR".to_string(),
+            _ => "INFO".to_string(),
+        };
+        RegistroLog {
+            sequencia: self.proxima_sequencia(),
+            horario: None,
+            nivel,
+            thread: None,
+            logger: None,
+            mensagem: linha.to_string(),
+            fluxo,
+        }
+    }
+}
+
+/// Últimas linhas de cada instância iniciada em modo gerenciado.
+#[derive(Debug, Clone, Default)]
+pub struct GerenciadorSaidaJogo {
+    buffers: Arc<Mutex<HashMap<String, VecDeque<RegistroLog>>>>,
+}
+
+impl GerenciadorSaidaJogo {
+    fn reiniciar(&self, instance_id: &str) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            buffers.insert(instance_id.to_string(), VecDeque::new());
+        }
+    }
+
+    fn adicionar(&self, instance_id: &str, registro: RegistroLog) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            let buffer = buffers.entry(instance_id.to_string()).or_default();
+            if buffer.len() >= CAPACIDADE_BUFFER {
+                buffer.pop_front();
+            }
+            buffer.push_back(registro);
+        }
+    }
+
+    pub fn obter(&self, instance_id: &str) -> Vec<RegistroLog> {
+        self.buffers
+            .lock()
+            .ok()
+            .and_then(|buffers| {
+                buffers
+                    .get(instance_id)
+                    .map(|buffer| buffer.iter().cloned().collect())
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Lê stdout e stderr do jogo em threads próprias: cada linha vira um
+/// `RegistroLog`, vai para o buffer, para o evento `saida-jogo` e para o
+/// `launcher_output.log`. Retorna as threads de leitura.
+pub fn capturar_saida_processo(
+    app: Option<tauri::AppHandle>,
+    gerenciador: &GerenciadorSaidaJogo,
+    instance_id: &str,
+    pasta_logs: &std::path::Path,
+    processo: &mut std::process::Child,
+) -> Vec<std::thread::JoinHandle<()>> {
+    gerenciador.reiniciar(instance_id);
+
+    let arquivo = std::fs::create_dir_all(pasta_logs)
+        .and_then(|_| std::fs::File::create(pasta_logs.join(ARQUIVO_SAIDA_LAUNCHER)))
+        .map_err(|e| {
+            eprintln!(
+                "[Saída] Aviso: não foi possível criar o log de saída: {}",
+                e
+            )
+        })
+        .ok();
+    let arquivo = Arc::new(Mutex::new(arquivo.map(std::io::BufWriter::new)));
+    // Cada fluxo tem seu parser (eventos XML não se misturam), mas a sequência é única.
+    let sequencia = Arc::new(std::sync::atomic::AtomicU64::new(0));
+
+    let leitores: Vec<(Box<dyn std::io::Read + Send>, FluxoSaida)> = [
+        processo.stdout.take().map(|saida| {
+            (
+                Box::new(saida) as Box<dyn std::io::Read + Send>,
+                FluxoSaida::Stdout,
+            )
+        }),
+        processo.stderr.take().map(|saida| {
+            (
+                Box::new(saida) as Box<dyn std::io::Read + Send>,
+                FluxoSaida::Stderr,
+            )
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    leitores
+        .into_iter()
+        .map(|(leitor, fluxo)| {
+            let app = app.clone();
+            let gerenciador = gerenciador.clone();
+            let instance_id = instance_id.to_string();
+            let arquivo = Arc::clone(&arquivo);
+            let sequencia = Arc::clone(&sequencia);
+
+            std::thread::spawn(move || {
+                let mut parser = ParserSaidaJogo::default();
+                let mut leitor = std::io::BufReader::new(leitor);
+                let mut bytes = Vec::new();
+                loop {
+                    bytes.clear();
+                    match leitor.read_until(b'\n', &mut bytes) {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => {}
+                    }
+                    let linha = String::from_utf8_lossy(&bytes);
+                    let Some(mut registro) = parser.processar_linha(&linha, fluxo) else {
+                        continue;
+                    };
+                    registro.sequencia =
+                        sequencia.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+
+                    if let Ok(mut arquivo) = arquivo.lock() {
+                        if let Some(arquivo) = arquivo.as_mut() {
+                            let _ = writeln!(arquivo, "{}", registro.formatar());
+                            let _ = arquivo.flush();
+                        }
+                    }
+                    gerenciador.adicionar(&instance_id, registro.clone());
+                    if let Some(app) = &app {
+                        let _ = app.emit(
+                            EVENTO_SAIDA_JOGO,
+                            EventoSaidaJogo {
+                                instance_id: instance_id.clone(),
+                                registro,
+                            },
+                        );
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Linhas capturadas da última execução em modo gerenciado, para a interface
+/// preencher o console ao reabrir.
+#[tauri::command]
+pub fn get_game_output(state: State<'_, LauncherState>, instance_id: String) -> Vec<RegistroLog> {
+    state.saida_jogo.obter(&instance_id)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn interpreta_linhas_simples_e_continuacoes() {
+        let mut parser = ParserSaidaJogo::default();
+        let registro = parser
+            .processar_linha(
+                "[12:34:56] [Render thread/WARN] [net.minecraft.client.Minecraft/]: Aviso\n",
+                FluxoSaida::Stdout,
+            )
+            .unwrap();
+        assert_eq!(registro.horario.as_deref(), Some("12:34:56"));
+        assert_eq!(registro.thread.as_deref(), Some("Render thread"));
+        assert_eq!(registro.nivel, "WARN");
+        assert_eq!(
+            registro.logger.as_deref(),
+            Some("net.minecraft.client.Minecraft")
+        );
+        assert_eq!(registro.mensagem, "Aviso");
+
+        let continuacao = parser
+            .processar_linha(
+                "\tat net.minecraft.Main.main(Main.java:1)",
+                FluxoSaida::Stdout,
+            )
+            .unwrap();
+        assert_eq!(continuacao.nivel, "WARN");
+        assert_eq!(continuacao.sequencia, 2);
+
+        let erro = parser
+            .processar_linha(
+                "Error: Could not create the Java Virtual Machine.",
+                FluxoSaida::Stderr,
+            )
+            .unwrap();
+        assert_eq!(erro.nivel, "ERROR");
+        assert!(parser.processar_linha("   ", FluxoSaida::Stdout).is_none());
+    }
+
+    #[test]
+    fn interpreta_eventos_xml_do_log4j() {
+        let mut parser = ParserSaidaJogo::default();
+        let linhas = [
+            r#"<log4j:Event logger="net.minecraft.server.Main" timestamp="1700000000000" level="ERROR" thread="Server &amp; Main">"#,
+            "  <log4j:Message><![CDATA[Falha ao carregar <mundo>]]></log4j:Message>",
+            "  <log4j:Throwable><![CDATA[java.lang.RuntimeException: x",
+            "\tat a.b(C.java:1)",
+            "]]></log4j:Throwable>",
+            "</log4j:Event>",
+        ];
+        let mut registros: Vec<RegistroLog> = linhas
+            .iter()
+            .filter_map(|linha| parser.processar_linha(linha, FluxoSaida::Stdout))
+            .collect();
+        assert_eq!(registros.len(), 1);
+        let registro = registros.remove(0);
+        assert_eq!(registro.nivel, "ERROR");
+        assert_eq!(registro.thread.as_deref(), Some("Server & Main"));
+        assert_eq!(
+            registro.logger.as_deref(),
+            Some("net.minecraft.server.Main")
+        );
+        assert!(registro.horario.is_some());
+        assert_eq!(
+            registro.mensagem,
+            "Falha ao carregar <mundo>\njava.lang.RuntimeException: x\n\tat a.b(C.java:1)"
+        );
+    }
+
+    #[test]
+    fn buffer_descarta_linhas_mais_antigas() {
+        let gerenciador = GerenciadorSaidaJogo::default();
+        let mut parser = ParserSaidaJogo::default();
+        for indice in 0..CAPACIDADE_BUFFER + 10 {
+            let registro = parser
+                .processar_linha(&format!("linha {}", indice), FluxoSaida::Stdout)
+                .unwrap();
+            gerenciador.adicionar("a", registro);
+        }
+        let linhas = gerenciador.obter("a");
+        assert_eq!(linhas.len(), CAPACIDADE_BUFFER);
+        assert_eq!(linhas[0].mensagem, "linha 10");
+        assert!(gerenciador.obter("b").is_empty());
+    }
+}
@@ -857,6 +857,9 @@ pub async fn export_launcher_social_sync_package(
     let account = state.account.clone();
     let accounts = state.accounts.clone();
     let processos = state.processos_instancias.clone();
+    let encerramentos_pedidos = state.encerramentos_pedidos.clone();
+    let tarefas = state.tarefas.clone();
+    let saida_jogo = state.saida_jogo.clone();
 
     // A exportação zip é I/O síncrono pesado — mover para thread blocking
     let resultado = tauri::async_runtime::spawn_blocking(move || {
@@ -865,6 +868,9 @@ pub async fn export_launcher_social_sync_package(
             accounts,
             instances_path,
             processos_instancias: processos,
+            encerramentos_pedidos,
+            tarefas,
+            saida_jogo,
         };
         crate::aplicacao::importacao_exportacao::exportar_instancia_social_sem_saves(
             &state_local,
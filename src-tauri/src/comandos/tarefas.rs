@@ -0,0 +1,395 @@
+use crate::launcher::LauncherState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, State};
+use tokio_util::sync::CancellationToken;
+
+/// Evento emitido a cada mudança de progresso (payload: `InfoTarefa`).
+pub const EVENTO_PROGRESSO_TAREFA: &str = "tarefa-progresso";
+pub const MENSAGEM_TAREFA_CANCELADA: &str = "Operação cancelada pelo usuário.";
+
+/// Tarefas finalizadas mantidas para a interface consultar depois de recarregar.
+const LIMITE_TAREFAS_FINALIZADAS: usize = 20;
+const INTERVALO_MINIMO_EVENTOS: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EstadoTarefa {
+    EmAndamento,
+    Concluida,
+    Falhou,
+    Cancelada,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InfoTarefa {
+    pub id: String,
+    pub tipo: String,
+    pub titulo: String,
+    pub instance_id: Option<String>,
+    pub estado: EstadoTarefa,
+    pub fase: String,
+    pub arquivos_concluidos: usize,
+    pub arquivos_totais: usize,
+    pub bytes_concluidos: u64,
+    pub bytes_totais: u64,
+    pub eta_segundos: Option<u64>,
+    pub erro: Option<String>,
+    pub iniciada_em: String,
+    pub finalizada_em: Option<String>,
+}
+
+#[derive(Debug)]
+struct EntradaTarefa {
+    info: InfoTarefa,
+    cancelamento: CancellationToken,
+    /// Caminhos parciais removidos se a tarefa for cancelada.
+    limpeza: Vec<PathBuf>,
+    inicio_fase: Instant,
+    ultimo_evento: Option<Instant>,
+}
+
+impl EntradaTarefa {
+    fn atualizar_eta(&mut self) {
+        let info = &mut self.info;
+        let (feito, total) = if info.bytes_totais > 0 && info.bytes_concluidos > 0 {
+            (info.bytes_concluidos as f64, info.bytes_totais as f64)
+        } else {
+            (info.arquivos_concluidos as f64, info.arquivos_totais as f64)
+        };
+        info.eta_segundos = if feito > 0.0 && total > feito {
+            let decorrido = self.inicio_fase.elapsed().as_secs_f64();
+            Some((decorrido * (total - feito) / feito).ceil() as u64)
+        } else {
+            None
+        };
+    }
+}
+
+/// Registro das operações longas (criação de instância, downloads, modpacks).
+#[derive(Debug, Clone, Default)]
+pub struct GerenciadorTarefas {
+    tarefas: Arc<Mutex<HashMap<String, EntradaTarefa>>>,
+}
+
+impl GerenciadorTarefas {
+    /// Registra uma nova tarefa. O id pode vir da interface, para que ela
+    /// consiga cancelar antes mesmo do comando retornar.
+    pub fn iniciar(
+        &self,
+        app: Option<tauri::AppHandle>,
+        id: Option<String>,
+        tipo: &str,
+        titulo: &str,
+        instance_id: Option<&str>,
+    ) -> Result<Tarefa, String> {
+        let id = id
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let cancelamento = CancellationToken::new();
+
+        {
+            let mut tarefas = self
+                .tarefas
+                .lock()
+                .map_err(|_| "Erro ao acessar tarefas.".to_string())?;
+            if tarefas
+                .get(&id)
+                .is_some_and(|tarefa| tarefa.info.estado == EstadoTarefa::EmAndamento)
+            {
+                return Err(format!("A tarefa {} já está em andamento.", id));
+            }
+            tarefas.insert(
+                id.clone(),
+                EntradaTarefa {
+                    info: InfoTarefa {
+                        id: id.clone(),
+                        tipo: tipo.to_string(),
+                        titulo: titulo.to_string(),
+                        instance_id: instance_id.map(str::to_string),
+                        estado: EstadoTarefa::EmAndamento,
+                        fase: "iniciando".to_string(),
+                        arquivos_concluidos: 0,
+                        arquivos_totais: 0,
+                        bytes_concluidos: 0,
+                        bytes_totais: 0,
+                        eta_segundos: None,
+                        erro: None,
+                        iniciada_em: chrono::Utc::now().to_rfc3339(),
+                        finalizada_em: None,
+                    },
+                    cancelamento: cancelamento.clone(),
+                    limpeza: Vec::new(),
+                    inicio_fase: Instant::now(),
+                    ultimo_evento: None,
+                },
+            );
+        }
+
+        let tarefa = Tarefa {
+            id,
+            gerenciador: self.clone(),
+            app,
+            cancelamento,
+        };
+        tarefa.emitir(true);
+        Ok(tarefa)
+    }
+
+    pub fn listar(&self) -> Vec<InfoTarefa> {
+        let mut lista: Vec<InfoTarefa> = self
+            .tarefas
+            .lock()
+            .map(|tarefas| tarefas.values().map(|tarefa| tarefa.info.clone()).collect())
+            .unwrap_or_default();
+        lista.sort_by(|a, b| a.iniciada_em.cmp(&b.iniciada_em));
+        lista
+    }
+
+    pub fn cancelar(&self, id: &str) -> Result<(), String> {
+        let tarefas = self
+            .tarefas
+            .lock()
+            .map_err(|_| "Erro ao acessar tarefas.".to_string())?;
+        let tarefa = tarefas
+            .get(id)
+            .ok_or_else(|| format!("Tarefa {} não encontrada.", id))?;
+        if tarefa.info.estado != EstadoTarefa::EmAndamento {
+            return Err("A tarefa já foi finalizada.".to_string());
+        }
+        tarefa.cancelamento.cancel();
+        println!("[Tarefas] Cancelamento solicitado: {}", id);
+        Ok(())
+    }
+
+    fn remover_finalizadas_antigas(tarefas: &mut HashMap<String, EntradaTarefa>) {
+        let mut finalizadas: Vec<(String, String)> = tarefas
+            .values()
+            .filter(|tarefa| tarefa.info.estado != EstadoTarefa::EmAndamento)
+            .map(|tarefa| {
+                (
+                    tarefa.info.finalizada_em.clone().unwrap_or_default(),
+                    tarefa.info.id.clone(),
+                )
+            })
+            .collect();
+        if finalizadas.len() <= LIMITE_TAREFAS_FINALIZADAS {
+            return;
+        }
+        finalizadas.sort();
+        let excedentes = finalizadas.len() - LIMITE_TAREFAS_FINALIZADAS;
+        for (_, id) in finalizadas.into_iter().take(excedentes) {
+            tarefas.remove(&id);
+        }
+    }
+}
+
+/// Alça passada às operações longas para relatar progresso e observar cancelamento.
+#[derive(Debug, Clone)]
+pub struct Tarefa {
+    id: String,
+    gerenciador: GerenciadorTarefas,
+    app: Option<tauri::AppHandle>,
+    cancelamento: CancellationToken,
+}
+
+impl Tarefa {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn cancelamento(&self) -> &CancellationToken {
+        &self.cancelamento
+    }
+
+    pub fn cancelada(&self) -> bool {
+        self.cancelamento.is_cancelled()
+    }
+
+    pub fn verificar_cancelamento(&self) -> Result<(), String> {
+        if self.cancelada() {
+            return Err(MENSAGEM_TAREFA_CANCELADA.to_string());
+        }
+        Ok(())
+    }
+
+    fn atualizar(&self, alterar: impl FnOnce(&mut EntradaTarefa)) {
+        if let Ok(mut tarefas) = self.gerenciador.tarefas.lock() {
+            if let Some(entrada) = tarefas.get_mut(&self.id) {
+                alterar(entrada);
+            }
+        }
+    }
+
+    /// Emite o estado atual; sem `forcar`, respeita um intervalo mínimo entre eventos.
+    fn emitir(&self, forcar: bool) {
+        let mut payload = None;
+        self.atualizar(|entrada| {
+            let agora = Instant::now();
+            let recente = entrada
+                .ultimo_evento
+                .is_some_and(|ultimo| agora.duration_since(ultimo) < INTERVALO_MINIMO_EVENTOS);
+            if forcar || !recente {
+                entrada.ultimo_evento = Some(agora);
+                payload = Some(entrada.info.clone());
+            }
+        });
+        if let (Some(app), Some(info)) = (&self.app, payload) {
+            let _ = app.emit(EVENTO_PROGRESSO_TAREFA, info);
+        }
+    }
+
+    /// Inicia uma nova fase, zerando os contadores de arquivos e bytes.
+    pub fn fase(&self, fase: &str, arquivos_totais: usize, bytes_totais: u64) {
+        self.atualizar(|entrada| {
+            entrada.info.fase = fase.to_string();
+            entrada.info.arquivos_concluidos = 0;
+            entrada.info.arquivos_totais = arquivos_totais;
+            entrada.info.bytes_concluidos = 0;
+            entrada.info.bytes_totais = bytes_totais;
+            entrada.info.eta_segundos = None;
+            entrada.inicio_fase = Instant::now();
+        });
+        self.emitir(true);
+    }
+
+    pub fn avancar(&self, arquivos: usize, bytes: u64) {
+        let mut concluiu_fase = false;
+        self.atualizar(|entrada| {
+            entrada.info.arquivos_concluidos += arquivos;
+            entrada.info.bytes_concluidos += bytes;
+            entrada.atualizar_eta();
+            concluiu_fase = entrada.info.arquivos_concluidos >= entrada.info.arquivos_totais;
+        });
+        self.emitir(concluiu_fase);
+    }
+
+    /// Registra um arquivo ou pasta parcial a ser apagado em caso de cancelamento.
+    pub fn limpar_ao_cancelar(&self, caminho: PathBuf) {
+        self.atualizar(|entrada| entrada.limpeza.push(caminho));
+    }
+
+    /// Marca a tarefa como concluída, com falha ou cancelada e repassa o resultado.
+    pub fn finalizar<T>(&self, resultado: Result<T, String>) -> Result<T, String> {
+        let cancelada = self.cancelada();
+        let mut limpeza = Vec::new();
+        self.atualizar(|entrada| {
+            entrada.info.estado = match (&resultado, cancelada) {
+                (_, true) => EstadoTarefa::Cancelada,
+                (Ok(_), false) => EstadoTarefa::Concluida,
+                (Err(_), false) => EstadoTarefa::Falhou,
+            };
+            entrada.info.erro = resultado.as_ref().err().cloned();
+            entrada.info.eta_segundos = None;
+            entrada.info.finalizada_em = Some(chrono::Utc::now().to_rfc3339());
+            if cancelada {
+                limpeza = std::mem::take(&mut entrada.limpeza);
+            }
+        });
+
+        for caminho in limpeza {
+            let removido = if caminho.is_dir() {
+                std::fs::remove_dir_all(&caminho)
+            } else {
+                std::fs::remove_file(&caminho)
+            };
+            if removido.is_ok() {
+                println!(
+                    "[Tarefas] Removido após cancelamento: {}",
+                    caminho.display()
+                );
+            }
+        }
+
+        self.emitir(true);
+        if let Ok(mut tarefas) = self.gerenciador.tarefas.lock() {
+            GerenciadorTarefas::remover_finalizadas_antigas(&mut tarefas);
+        }
+
+        if cancelada {
+            return Err(MENSAGEM_TAREFA_CANCELADA.to_string());
+        }
+        resultado
+    }
+}
+
+/// Executa um processo externo (instaladores de loader) encerrando-o se a tarefa for cancelada.
+pub async fn executar_processo(
+    comando: std::process::Command,
+    tarefa: Option<&Tarefa>,
+) -> Result<std::process::Output, String> {
+    let mut comando = tokio::process::Command::from(comando);
+    comando.kill_on_drop(true);
+    let execucao = comando.output();
+    match tarefa {
+        Some(tarefa) => tokio::select! {
+            _ = tarefa.cancelamento().cancelled() => Err(MENSAGEM_TAREFA_CANCELADA.to_string()),
+            saida = execucao => saida.map_err(|e| format!("Erro ao executar processo: {}", e)),
+        },
+        None => execucao
+            .await
+            .map_err(|e| format!("Erro ao executar processo: {}", e)),
+    }
+}
+
+/// Lista tarefas em andamento e finalizadas recentemente, para a interface se
+/// reconectar ao progresso depois de recarregar.
+#[tauri::command]
+pub fn list_tasks(state: State<'_, LauncherState>) -> Vec<InfoTarefa> {
+    state.tarefas.listar()
+}
+
+#[tauri::command]
+pub fn cancel_task(state: State<'_, LauncherState>, task_id: String) -> Result<(), String> {
+    state.tarefas.cancelar(&task_id)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn acompanha_progresso_e_finaliza_tarefa() {
+        let gerenciador = GerenciadorTarefas::default();
+        let tarefa = gerenciador
+            .iniciar(None, Some("t1".to_string()), "teste", "Teste", None)
+            .unwrap();
+        assert!(gerenciador
+            .iniciar(None, Some("t1".to_string()), "teste", "Teste", None)
+            .is_err());
+
+        tarefa.fase("bibliotecas", 4, 400);
+        tarefa.avancar(1, 100);
+        let info = &gerenciador.listar()[0];
+        assert_eq!(info.fase, "bibliotecas");
+        assert_eq!(info.arquivos_concluidos, 1);
+        assert_eq!(info.bytes_concluidos, 100);
+
+        assert_eq!(tarefa.finalizar(Ok(5)), Ok(5));
+        assert_eq!(gerenciador.listar()[0].estado, EstadoTarefa::Concluida);
+        assert!(gerenciador.cancelar("t1").is_err());
+    }
+
+    #[test]
+    fn cancelamento_remove_arquivos_parciais() {
+        let pasta = std::env::temp_dir().join(format!("dome-tarefa-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(pasta.join("mods")).unwrap();
+
+        let gerenciador = GerenciadorTarefas::default();
+        let tarefa = gerenciador
+            .iniciar(None, None, "teste", "Teste", Some("instancia"))
+            .unwrap();
+        tarefa.limpar_ao_cancelar(pasta.clone());
+
+        gerenciador.cancelar(tarefa.id()).unwrap();
+        assert!(tarefa.verificar_cancelamento().is_err());
+        assert!(tarefa.finalizar(Ok(())).is_err());
+        assert!(!pasta.exists());
+        assert_eq!(gerenciador.listar()[0].estado, EstadoTarefa::Cancelada);
+    }
+}
@@ -50,6 +50,15 @@ pub struct Library {
     pub downloads: Option<LibraryDownloads>,
     pub rules: Option<Vec<Rule>>,
     pub natives: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract: Option<LibraryExtract>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LibraryExtract {
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 #[allow(dead_code)]
@@ -57,12 +66,15 @@ pub struct Library {
 pub struct Rule {
     pub action: String,
     pub os: Option<OsRule>,
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OsRule {
-    pub name: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub arch: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -153,6 +165,8 @@ pub struct Instance {
     pub java_args: Option<String>,
     #[serde(alias = "mc_args")]
     pub mc_args: Option<String>,
+    #[serde(default, alias = "java_path", skip_serializing_if = "Option::is_none")]
+    pub java_path: Option<String>,
     pub memory: Option<u32>,
     pub width: Option<u32>,
     pub height: Option<u32>,
@@ -388,13 +402,9 @@ fn escrever_json_seguro<T: Serialize>(caminho: &std::path::Path, valor: &T) -> R
 }
 
 fn caminho_sessao_social() -> PathBuf {
-    std::env::var("APPDATA")
-        .map(|app_data| {
-            PathBuf::from(app_data)
-                .join("dome")
-                .join("social-session.dat")
-        })
-        .unwrap_or_else(|_| PathBuf::from("social-session.dat"))
+    crate::plataforma::pasta_dados_launcher()
+        .map(|pasta| pasta.join("social-session.dat"))
+        .unwrap_or_else(|| PathBuf::from("social-session.dat"))
 }
 
 #[tauri::command]
@@ -427,6 +437,11 @@ pub struct LauncherState {
     pub accounts: Arc<Mutex<Vec<MinecraftAccount>>>,
     pub instances_path: PathBuf,
     pub processos_instancias: Arc<Mutex<HashMap<String, u32>>>,
+    /// Instâncias fechadas pelo usuário (`kill_instance`); o fim delas não é
+    /// analisado como crash.
+    pub encerramentos_pedidos: Arc<Mutex<HashSet<String>>>,
+    pub tarefas: crate::comandos::tarefas::GerenciadorTarefas,
+    pub saida_jogo: crate::comandos::saida_jogo::GerenciadorSaidaJogo,
 }
 
 impl LauncherState {
@@ -611,9 +626,8 @@ impl LauncherState {
 
     pub fn new() -> Self {
         // Determinar o caminho correto para dados do launcher
-        let data_path = std::env::var("APPDATA")
-            .map(|app_data| PathBuf::from(app_data).join("dome"))
-            .unwrap_or_else(|_| PathBuf::from("."));
+        let data_path =
+            crate::plataforma::pasta_dados_launcher().unwrap_or_else(|| PathBuf::from("."));
 
         let instances_path = data_path.join("instances");
 
@@ -631,6 +645,9 @@ impl LauncherState {
             accounts: Arc::new(Mutex::new(accounts)),
             instances_path,
             processos_instancias: Arc::new(Mutex::new(HashMap::new())),
+            encerramentos_pedidos: Arc::new(Mutex::new(HashSet::new())),
+            tarefas: Default::default(),
+            saida_jogo: Default::default(),
         };
 
         match crate::aplicacao::importacao_exportacao::atualizar_icones_instancias_modrinth_existentes(
@@ -660,6 +677,20 @@ impl LauncherState {
         }
     }
 
+    pub fn marcar_encerramento_pedido(&self, instance_id: &str) {
+        if let Ok(mut encerramentos) = self.encerramentos_pedidos.lock() {
+            encerramentos.insert(instance_id.to_string());
+        }
+    }
+
+    /// Remove a marca de encerramento pedido, informando se ela existia.
+    pub fn consumir_encerramento_pedido(&self, instance_id: &str) -> bool {
+        self.encerramentos_pedidos
+            .lock()
+            .map(|mut encerramentos| encerramentos.remove(instance_id))
+            .unwrap_or(false)
+    }
+
     pub fn obter_pid_instancia(&self, instance_id: &str) -> Option<u32> {
         self.processos_instancias
             .lock()
@@ -675,16 +706,16 @@ impl LauncherState {
 
     /// Caminho para o arquivo de conta
     fn get_account_path() -> PathBuf {
-        std::env::var("APPDATA")
-            .map(|app_data| PathBuf::from(app_data).join("dome").join("account.json"))
-            .unwrap_or_else(|_| PathBuf::from("account.json"))
+        crate::plataforma::pasta_dados_launcher()
+            .map(|pasta| pasta.join("account.json"))
+            .unwrap_or_else(|| PathBuf::from("account.json"))
     }
 
     /// Caminho para o arquivo de contas salvas (multi-conta)
     fn get_accounts_path() -> PathBuf {
-        std::env::var("APPDATA")
-            .map(|app_data| PathBuf::from(app_data).join("dome").join("accounts.json"))
-            .unwrap_or_else(|_| PathBuf::from("accounts.json"))
+        crate::plataforma::pasta_dados_launcher()
+            .map(|pasta| pasta.join("accounts.json"))
+            .unwrap_or_else(|| PathBuf::from("accounts.json"))
     }
 
     /// Carrega a conta salva do arquivo
@@ -4,6 +4,7 @@ mod auth_sisu;
 mod comandos;
 mod discord_social;
 mod launcher;
+mod plataforma;
 mod skin;
 
 pub(crate) use aplicacao::anexar_headers_curseforge;
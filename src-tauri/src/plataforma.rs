@@ -0,0 +1,414 @@
+use crate::launcher::{Library, Rule};
+use std::path::{Path, PathBuf};
+
+/// Separador usado pela JVM entre entradas do classpath.
+pub(crate) const SEPARADOR_CLASSPATH: &str = if cfg!(windows) { ";" } else { ":" };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SistemaOperacional {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+impl SistemaOperacional {
+    pub(crate) fn atual() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Linux
+        }
+    }
+
+    /// Nome usado pela Mojang em `rules[].os.name` e no mapa `natives`.
+    pub(crate) fn nome_mojang(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::MacOs => "osx",
+            Self::Linux => "linux",
+        }
+    }
+
+    fn corresponde_nome_mojang(self, nome: &str) -> bool {
+        match self {
+            Self::MacOs => nome == "osx" || nome == "macos",
+            _ => nome == self.nome_mojang(),
+        }
+    }
+
+    /// Nome do sistema na API do Adoptium.
+    pub(crate) fn nome_adoptium(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::MacOs => "mac",
+            Self::Linux => "linux",
+        }
+    }
+
+    /// Executáveis do Java em ordem de preferência (sem console no Windows).
+    pub(crate) fn executaveis_java(self) -> &'static [&'static str] {
+        match self {
+            Self::Windows => &["javaw.exe", "java.exe"],
+            _ => &["java"],
+        }
+    }
+
+    fn extensoes_natives(self) -> &'static [&'static str] {
+        match self {
+            Self::Windows => &["dll"],
+            Self::MacOs => &["dylib", "jnilib"],
+            Self::Linux => &["so"],
+        }
+    }
+}
+
+/// Sistema, arquitetura e versão do SO usados para avaliar manifestos da Mojang.
+#[derive(Debug, Clone)]
+pub(crate) struct Plataforma {
+    pub os: SistemaOperacional,
+    /// Arquitetura no formato da Mojang: `x86`, `x86_64` ou `arm64`.
+    pub arch: &'static str,
+    pub versao_os: String,
+}
+
+impl Plataforma {
+    pub(crate) fn atual() -> Self {
+        let arch = if cfg!(target_arch = "aarch64") {
+            "arm64"
+        } else if cfg!(target_arch = "x86") {
+            "x86"
+        } else {
+            "x86_64"
+        };
+
+        Self {
+            os: SistemaOperacional::atual(),
+            arch,
+            versao_os: sysinfo::System::os_version().unwrap_or_default(),
+        }
+    }
+
+    /// Bits da arquitetura, usados no placeholder `${arch}` dos natives.
+    fn bits_arch(&self) -> &'static str {
+        if self.arch == "x86" {
+            "32"
+        } else {
+            "64"
+        }
+    }
+
+    fn regra_corresponde(&self, regra: &Rule, recurso_ativo: &dyn Fn(&str) -> bool) -> bool {
+        if let Some(features) = &regra.features {
+            if features
+                .iter()
+                .any(|(nome, esperado)| recurso_ativo(nome) != *esperado)
+            {
+                return false;
+            }
+        }
+
+        let Some(os) = &regra.os else {
+            return true;
+        };
+
+        if let Some(nome) = os.name.as_deref() {
+            if !self.os.corresponde_nome_mojang(nome) {
+                return false;
+            }
+        }
+
+        if let Some(arch) = os.arch.as_deref() {
+            if arch != self.arch {
+                return false;
+            }
+        }
+
+        if let Some(padrao_versao) = os.version.as_deref() {
+            let corresponde = regex::Regex::new(padrao_versao)
+                .map(|regex| regex.is_match(&self.versao_os))
+                .unwrap_or(false);
+            if !corresponde {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Avalia `rules` como o launcher oficial: sem regras tudo é permitido;
+    /// com regras, a última que corresponder à plataforma decide.
+    pub(crate) fn regras_permitem(&self, regras: Option<&[Rule]>) -> bool {
+        self.regras_permitem_com_recursos(regras, &|_| false)
+    }
+
+    /// Igual a [`Self::regras_permitem`], considerando também `features`
+    /// (`is_demo_user`, `has_custom_resolution`, quick play...).
+    pub(crate) fn regras_permitem_com_recursos(
+        &self,
+        regras: Option<&[Rule]>,
+        recurso_ativo: &dyn Fn(&str) -> bool,
+    ) -> bool {
+        let Some(regras) = regras.filter(|regras| !regras.is_empty()) else {
+            return true;
+        };
+
+        let mut permitido = false;
+        for regra in regras {
+            if self.regra_corresponde(regra, recurso_ativo) {
+                permitido = regra.action == "allow";
+            }
+        }
+        permitido
+    }
+
+    pub(crate) fn biblioteca_permitida(&self, lib: &Library) -> bool {
+        self.regras_permitem(lib.rules.as_deref())
+    }
+
+    /// Classifier de natives da biblioteca para esta plataforma.
+    ///
+    /// Usa o mapa `natives` do manifesto quando existir; manifestos sem ele
+    /// caem nos nomes conhecidos presentes em `downloads.classifiers`.
+    pub(crate) fn classificador_natives(&self, lib: &Library) -> Option<String> {
+        if let Some(mapa) = lib.natives.as_ref().and_then(|natives| natives.as_object()) {
+            return mapa
+                .get(self.os.nome_mojang())
+                .and_then(|valor| valor.as_str())
+                .map(|classificador| classificador.replace("${arch}", self.bits_arch()));
+        }
+
+        let classifiers = lib.downloads.as_ref()?.classifiers.as_ref()?.as_object()?;
+
+        let candidatos: Vec<String> = match self.os {
+            SistemaOperacional::Windows => vec![
+                format!("natives-windows-{}", self.bits_arch()),
+                "natives-windows".to_string(),
+            ],
+            SistemaOperacional::MacOs if self.arch == "arm64" => vec![
+                "natives-macos-arm64".to_string(),
+                "natives-macos".to_string(),
+                "natives-osx".to_string(),
+            ],
+            SistemaOperacional::MacOs => {
+                vec!["natives-macos".to_string(), "natives-osx".to_string()]
+            }
+            SistemaOperacional::Linux => vec!["natives-linux".to_string()],
+        };
+
+        candidatos
+            .into_iter()
+            .find(|candidato| classifiers.contains_key(candidato))
+    }
+
+    /// Entrada `downloads.classifiers[...]` com os natives desta plataforma.
+    pub(crate) fn artefato_natives<'a>(&self, lib: &'a Library) -> Option<&'a serde_json::Value> {
+        let classificador = self.classificador_natives(lib)?;
+        lib.downloads
+            .as_ref()?
+            .classifiers
+            .as_ref()?
+            .get(classificador.as_str())
+    }
+
+    /// Indica se uma entrada de um jar de natives deve ser extraída,
+    /// respeitando `extract.exclude` da biblioteca.
+    pub(crate) fn entrada_nativa_extraivel(
+        &self,
+        nome_entrada: &str,
+        exclusoes: &[String],
+    ) -> bool {
+        if nome_entrada.ends_with('/')
+            || exclusoes
+                .iter()
+                .any(|prefixo| nome_entrada.starts_with(prefixo.as_str()))
+        {
+            return false;
+        }
+
+        let extensao = Path::new(nome_entrada)
+            .extension()
+            .and_then(|extensao| extensao.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        self.os.extensoes_natives().contains(&extensao.as_str())
+    }
+}
+
+/// Bibliotecas como `org.lwjgl:lwjgl:3.3.1:natives-linux` trazem os natives
+/// no próprio artifact, filtrados por `rules`.
+pub(crate) fn biblioteca_e_artifact_nativo(lib: &Library) -> bool {
+    lib.name
+        .split(':')
+        .nth(3)
+        .map(|classificador| classificador.starts_with("natives-"))
+        .unwrap_or(false)
+}
+
+/// Pasta de dados do launcher: `%APPDATA%\dome` no Windows e a pasta de dados
+/// do usuário nos demais sistemas (`~/.local/share/dome`, `~/Library/Application Support/dome`).
+pub(crate) fn pasta_dados_launcher() -> Option<PathBuf> {
+    if let Ok(app_data) = std::env::var("APPDATA") {
+        return Some(PathBuf::from(app_data).join("dome"));
+    }
+
+    directories::BaseDirs::new().map(|base| base.data_dir().join("dome"))
+}
+
+/// Pastas onde JDKs costumam ser instalados nesta plataforma.
+pub(crate) fn pastas_padrao_java() -> Vec<PathBuf> {
+    let mut pastas = Vec::new();
+    let home = directories::BaseDirs::new().map(|base| base.home_dir().to_path_buf());
+
+    match SistemaOperacional::atual() {
+        SistemaOperacional::Windows => {
+            for drive in ["C", "D", "E"] {
+                for dir in [
+                    "Program Files\\Java",
+                    "Program Files\\Eclipse Adoptium",
+                    "Program Files\\Eclipse Foundation",
+                    "Program Files\\AdoptOpenJDK",
+                    "Program Files\\Amazon Corretto",
+                    "Program Files\\Microsoft",
+                    "Program Files\\Zulu",
+                ] {
+                    pastas.push(PathBuf::from(format!("{}:\\{}", drive, dir)));
+                }
+            }
+        }
+        SistemaOperacional::MacOs => {
+            pastas.push(PathBuf::from("/Library/Java/JavaVirtualMachines"));
+            if let Some(home) = &home {
+                pastas.push(home.join("Library/Java/JavaVirtualMachines"));
+            }
+        }
+        SistemaOperacional::Linux => {
+            pastas.push(PathBuf::from("/usr/lib/jvm"));
+            pastas.push(PathBuf::from("/usr/lib64/jvm"));
+            pastas.push(PathBuf::from("/usr/java"));
+            pastas.push(PathBuf::from("/opt/java"));
+            pastas.push(PathBuf::from("/opt/jdk"));
+        }
+    }
+
+    if let Some(home) = &home {
+        pastas.push(home.join(".jdks"));
+        pastas.push(home.join(".sdkman").join("candidates").join("java"));
+    }
+
+    pastas
+}
+
+#[cfg(test)]
+mod testes {
+    use super::{Plataforma, SistemaOperacional};
+    use crate::launcher::Library;
+
+    fn plataforma(os: SistemaOperacional, arch: &'static str, versao: &str) -> Plataforma {
+        Plataforma {
+            os,
+            arch,
+            versao_os: versao.to_string(),
+        }
+    }
+
+    fn biblioteca(json: serde_json::Value) -> Library {
+        serde_json::from_value(json).expect("biblioteca de teste deve ser válida")
+    }
+
+    #[test]
+    fn avalia_regras_por_sistema_arquitetura_e_versao() {
+        let lwjgl_macos = biblioteca(serde_json::json!({
+            "name": "org.lwjgl.lwjgl:lwjgl:2.9.4",
+            "rules": [
+                { "action": "allow" },
+                { "action": "disallow", "os": { "name": "osx" } }
+            ]
+        }));
+        let linux = plataforma(SistemaOperacional::Linux, "x86_64", "6.1");
+        let mac = plataforma(SistemaOperacional::MacOs, "arm64", "14.2");
+        assert!(linux.biblioteca_permitida(&lwjgl_macos));
+        assert!(!mac.biblioteca_permitida(&lwjgl_macos));
+
+        let so_x86 = biblioteca(serde_json::json!({
+            "name": "exemplo:x86:1.0",
+            "rules": [{ "action": "allow", "os": { "arch": "x86" } }]
+        }));
+        assert!(!linux.biblioteca_permitida(&so_x86));
+
+        let windows_10 = biblioteca(serde_json::json!({
+            "name": "exemplo:win10:1.0",
+            "rules": [{ "action": "allow", "os": { "name": "windows", "version": "^10\\." } }]
+        }));
+        assert!(
+            plataforma(SistemaOperacional::Windows, "x86_64", "10.0.19045")
+                .biblioteca_permitida(&windows_10)
+        );
+        assert!(
+            !plataforma(SistemaOperacional::Windows, "x86_64", "6.1.7601")
+                .biblioteca_permitida(&windows_10)
+        );
+    }
+
+    #[test]
+    fn escolhe_classifier_de_natives_da_plataforma() {
+        let lwjgl_legado = biblioteca(serde_json::json!({
+            "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+            "natives": {
+                "linux": "natives-linux",
+                "osx": "natives-osx",
+                "windows": "natives-windows-${arch}"
+            },
+            "downloads": { "classifiers": {
+                "natives-linux": { "path": "linux.jar", "url": "https://exemplo/linux.jar" },
+                "natives-osx": { "path": "osx.jar", "url": "https://exemplo/osx.jar" },
+                "natives-windows-64": { "path": "win.jar", "url": "https://exemplo/win.jar" }
+            }}
+        }));
+
+        let linux = plataforma(SistemaOperacional::Linux, "x86_64", "");
+        let mac = plataforma(SistemaOperacional::MacOs, "x86_64", "");
+        let windows = plataforma(SistemaOperacional::Windows, "x86_64", "");
+        assert_eq!(
+            linux.classificador_natives(&lwjgl_legado).as_deref(),
+            Some("natives-linux")
+        );
+        assert_eq!(
+            mac.classificador_natives(&lwjgl_legado).as_deref(),
+            Some("natives-osx")
+        );
+        assert_eq!(
+            windows
+                .artefato_natives(&lwjgl_legado)
+                .and_then(|v| v["path"].as_str()),
+            Some("win.jar")
+        );
+
+        let sem_mapa = biblioteca(serde_json::json!({
+            "name": "org.lwjgl:lwjgl-glfw:3.2.2",
+            "downloads": { "classifiers": {
+                "natives-macos": { "path": "macos.jar", "url": "https://exemplo/macos.jar" }
+            }}
+        }));
+        assert_eq!(
+            mac.classificador_natives(&sem_mapa).as_deref(),
+            Some("natives-macos")
+        );
+        assert_eq!(linux.classificador_natives(&sem_mapa), None);
+    }
+
+    #[test]
+    fn extrai_apenas_bibliotecas_nativas_fora_das_exclusoes() {
+        let linux = plataforma(SistemaOperacional::Linux, "x86_64", "");
+        let mac = plataforma(SistemaOperacional::MacOs, "arm64", "");
+        let exclusoes = vec!["META-INF/".to_string()];
+
+        assert!(linux.entrada_nativa_extraivel("liblwjgl64.so", &exclusoes));
+        assert!(linux.entrada_nativa_extraivel("linux/x64/org/lwjgl/liblwjgl.so", &exclusoes));
+        assert!(!linux.entrada_nativa_extraivel("META-INF/libfalso.so", &exclusoes));
+        assert!(!linux.entrada_nativa_extraivel("lwjgl64.dll", &exclusoes));
+        assert!(mac.entrada_nativa_extraivel("liblwjgl.dylib", &exclusoes));
+        assert!(mac.entrada_nativa_extraivel("libjinput-osx.jnilib", &exclusoes));
+    }
+}
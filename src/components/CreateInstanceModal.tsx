@@ -11,6 +11,18 @@ import {
   type CreatingInstance,
 } from "../stores/creatingInstances";
 
+interface VersaoLoader {
+  version: string;
+  stable?: boolean;
+  marcadores?: Array<"recomendada" | "maisRecente" | "beta">;
+}
+
+const ROTULOS_MARCADOR: Record<string, string> = {
+  recomendada: "Recomendada",
+  maisRecente: "Mais recente",
+  beta: "Beta",
+};
+
 interface CreateInstanceModalProps {
   isOpen: boolean;
   onClose: () => void;
@@ -20,6 +32,7 @@ const LOADERS = [
   { id: "forge", name: "Forge" },
   { id: "fabric", name: "Fabric" },
   { id: "neoforge", name: "NeoForge" },
+  { id: "quilt", name: "Quilt" },
   { id: "vanilla", name: "Vanilla" },
 ];
 
@@ -28,7 +41,7 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
   const [version, setVersion] = useState("");
   const [loader, setLoader] = useState("forge");
   const [loaderVersion, setLoaderVersion] = useState("");
-  const [loaderVersions, setLoaderVersions] = useState<string[]>([]);
+  const [loaderVersions, setLoaderVersions] = useState<VersaoLoader[]>([]);
   const [versions, setVersions] = useState<{ id: string; type: string }[]>([]);
   const [mostrarSnapshots, setMostrarSnapshots] = useState(false);
   const [loadingVersions, setLoadingVersions] = useState(false);
@@ -70,14 +83,20 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
     if (loader !== "vanilla" && version) {
       const fetchLoaderVersions = async () => {
         setLoadingLoaderVersions(true);
+        setLoaderVersion("");
         try {
-          const res: any = await invoke("get_loader_versions", { loaderType: loader });
-          const vers = res.versions.map((v: any) => v.version);
+          const res = await invoke<{ versions: VersaoLoader[] }>("get_loader_versions", {
+            loaderType: loader,
+            minecraftVersion: version,
+          });
+          const vers = res.versions;
           setLoaderVersions(vers);
-          if (vers.length > 0) setLoaderVersion(vers[0]);
+          const recomendada = vers.find((v) => v.marcadores?.includes("recomendada")) || vers[0];
+          setLoaderVersion(recomendada?.version || "");
         } catch (error) {
           console.error("Erro ao carregar versões do loader:", error);
           setLoaderVersions([]);
+          setLoaderVersion("");
         } finally {
           setLoadingLoaderVersions(false);
         }
@@ -99,8 +118,11 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
 
   if (!isOpen) return null;
 
+  // Sem versão do loader (ex.: loader sem suporte à versão escolhida) não há o que instalar.
+  const faltaVersaoLoader = loader !== "vanilla" && (loadingLoaderVersions || !loaderVersion);
+
   const handleCreate = async () => {
-    if (!name.trim() || !version) return;
+    if (!name.trim() || !version || faltaVersaoLoader) return;
 
     const instanceId = name.toLowerCase().replace(/\s+/g, "_");
 
@@ -291,7 +313,12 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
                   onClick={() => setIsLoaderVersionOpen(!isLoaderVersionOpen)}
                   className="w-full bg-white/5 border border-white/10 rounded-lg px-3 py-2.5 text-sm text-left flex items-center justify-between hover:bg-white/10 transition-colors"
                 >
-                  <span>{loadingLoaderVersions ? "Carregando..." : loaderVersion || "Selecione..."}</span>
+                  <span>
+                    {loadingLoaderVersions
+                      ? "Carregando..."
+                      : loaderVersion ||
+                        (loaderVersions.length === 0 ? `Sem versões para ${version}` : "Selecione...")}
+                  </span>
                   <ChevronDown size={16} className={cn("text-white/40 transition-transform", isLoaderVersionOpen && "rotate-180")} />
                 </button>
 
@@ -305,17 +332,22 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
                     >
                       {loaderVersions.map((v) => (
                         <button
-                          key={v}
+                          key={v.version}
                           onClick={() => {
-                            setLoaderVersion(v);
+                            setLoaderVersion(v.version);
                             setIsLoaderVersionOpen(false);
                           }}
                           className={cn(
-                            "w-full px-3 py-2 text-sm text-left hover:bg-white/10 transition-colors",
-                            loaderVersion === v && "bg-emerald-500/20 text-emerald-400"
+                            "w-full px-3 py-2 text-sm text-left hover:bg-white/10 transition-colors flex items-center justify-between gap-2",
+                            loaderVersion === v.version && "bg-emerald-500/20 text-emerald-400"
                           )}
                         >
-                          {loader}-{v}
+                          <span>{loader}-{v.version}</span>
+                          {(v.marcadores?.length ?? 0) > 0 && (
+                            <span className="text-[10px] uppercase tracking-wide text-white/40">
+                              {v.marcadores!.map((m) => ROTULOS_MARCADOR[m]).join(" · ")}
+                            </span>
+                          )}
                         </button>
                       ))}
                     </motion.div>
@@ -336,7 +368,7 @@ export default function CreateInstanceModal({ isOpen, onClose }: CreateInstanceM
           </button>
           <button
             onClick={handleCreate}
-            disabled={!name.trim() || !version}
+            disabled={!name.trim() || !version || faltaVersaoLoader}
             className="px-5 py-2 rounded-lg text-sm font-bold bg-emerald-500 hover:bg-emerald-400 text-black transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
           >
             Create
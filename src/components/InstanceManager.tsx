@@ -28,6 +28,7 @@ import {
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { cn } from "../lib/utils";
+import { instalarConteudoComDependencias } from "../lib/instalacaoConteudo";
 
 interface InstanceManagerProps {
   instanceId: string;
@@ -50,6 +51,7 @@ interface InstanceDetails {
   created?: string;
   javaArgs?: string;
   mcArgs?: string;
+  javaPath?: string;
   width?: number;
   height?: number;
 }
@@ -297,6 +299,7 @@ export default function InstanceManager({
   const [logs, setLogs] = useState<LogFile[]>([]);
   const [selectedLog, setSelectedLog] = useState<string | null>(null);
   const [logContent, setLogContent] = useState("");
+  const [logProximoOffset, setLogProximoOffset] = useState<number | null>(null);
   const [showSettings, setShowSettings] = useState(false);
   const [showMoreMenu, setShowMoreMenu] = useState(false);
   const [updatingAll, setUpdatingAll] = useState(false);
@@ -313,6 +316,8 @@ export default function InstanceManager({
   const [isEditing, setIsEditing] = useState(false);
   const [editName, setEditName] = useState("");
   const [editJavaArgs, setEditJavaArgs] = useState("");
+  const [editJavaPath, setEditJavaPath] = useState("");
+  const [javasDetectados, setJavasDetectados] = useState<{ path: string; version: string }[]>([]);
   const [editWidth, setEditWidth] = useState("");
   const [editHeight, setEditHeight] = useState("");
   const [saving, setSaving] = useState(false);
@@ -381,6 +386,7 @@ export default function InstanceManager({
       // Preencher campos de edição
       setEditName(details.name);
       setEditJavaArgs(details.javaArgs || "");
+      setEditJavaPath(details.javaPath || "");
       setEditWidth(details.width?.toString() || "854");
       setEditHeight(details.height?.toString() || "480");
     } catch (error) {
@@ -877,11 +883,32 @@ export default function InstanceManager({
     }
   };
 
+  const lerPaginaLog = (filePath: string, offset: number) =>
+    invoke<{ proximoOffset?: number | null; linhas: { texto: string }[] }>("read_log_page", {
+      instanceId,
+      filePath,
+      offset,
+    });
+
   const viewLog = async (filePath: string) => {
     try {
-      const content: string = await invoke("get_log_content", { instanceId, filePath });
-      setLogContent(content);
       setSelectedLog(filePath);
+      setLogContent("");
+      const pagina = await lerPaginaLog(filePath, 0);
+      setLogContent(pagina.linhas.map((linha) => linha.texto).join("\n"));
+      setLogProximoOffset(pagina.proximoOffset ?? null);
+    } catch (error) {
+      console.error("Erro ao ler log:", error);
+    }
+  };
+
+  const carregarMaisLog = async () => {
+    if (!selectedLog || logProximoOffset === null) return;
+    try {
+      const pagina = await lerPaginaLog(selectedLog, logProximoOffset);
+      const texto = pagina.linhas.map((linha) => linha.texto).join("\n");
+      setLogContent((anterior) => (anterior && texto ? `${anterior}\n${texto}` : anterior + texto));
+      setLogProximoOffset(pagina.proximoOffset ?? null);
     } catch (error) {
       console.error("Erro ao ler log:", error);
     }
@@ -950,121 +977,44 @@ export default function InstanceManager({
         shaders: "shader",
       };
       const tipoProjeto = typeMap[activeFilter];
+      const plataforma = browseSource === "curseforge" ? "curseforge" : "modrinth";
 
-      if (browseSource === "curseforge") {
-        if (tipoProjeto === "mod") {
-          await invoke("install_mod", {
-            instanceId,
-            modInfo: {
-              id: item.id,
-              name: item.title,
-              description: item.description,
-              author: item.author,
-              version: item.latest_version || "latest",
-              download_url: "",
-              file_name: item.file_name || "",
-              platform: "curseforge",
-              dependencies: [],
-            },
-          });
-        } else {
-          await invoke("install_curseforge_project_file", {
-            instanceId,
-            projectType: tipoProjeto,
-            projectId: item.id,
-          });
-        }
-
-        const cacheConteudo = lerCacheConteudoInstalado();
-        const tipoProjetoCache = tipoProjeto as TipoProjetoCache;
-        const nomeArquivoCache = item.file_name || item.slug || item.id;
-        definirRegistroCacheConteudo(cacheConteudo, instanceId, tipoProjetoCache, nomeArquivoCache, {
-          name: item.title,
-          author: item.author,
-          icon: item.icon_url,
-          projectId: item.id,
-          source: "curseforge",
-          projectType: tipoProjetoCache,
-          latestVersion: item.latest_version,
-          updateAvailable: false,
-        });
-        salvarCacheConteudoInstalado(cacheConteudo);
+      // O backend escolhe a versão compatível com o Minecraft e o loader da instância
+      // e inclui as dependências obrigatórias no plano.
+      const resultado = await instalarConteudoComDependencias({
+        instanceId,
+        platform: plataforma,
+        projectId: item.id,
+        projectType: tipoProjeto,
+      });
+      if (!resultado) return;
 
+      const principal = resultado.principal;
+      if (!principal) {
+        alert(`${item.title} já está instalado nesta instância.`);
         await loadInstalledContent(activeFilter);
         return;
       }
 
-      // Determinar o loader correto para filtrar versões
-      const loaderType = instanceDetails.loaderType?.toLowerCase() || "";
-      const loadersSuportados = ["fabric", "forge", "quilt", "neoforge"];
-      const params = new URLSearchParams();
-      params.set("game_versions", JSON.stringify([instanceDetails.version]));
-      if (activeFilter === "mods" && loadersSuportados.includes(loaderType)) {
-        params.set("loaders", JSON.stringify([loaderType]));
-      }
-      
-      // Buscar versões compatíveis com versão do MC E loader
-      const versionsRes = await fetch(
-        `https://api.modrinth.com/v2/project/${item.id}/version?${params.toString()}`
-      );
-      const versions = await versionsRes.json();
-
-      if (versions.length === 0) {
-        const alvoCompat = loaderType ? `${loaderType} ${instanceDetails.version}` : instanceDetails.version;
-        alert(`Nenhuma versão compatível com ${alvoCompat}`);
-        setInstalling(null);
-        return;
-      }
-
-      const version = versions[0];
-      const file = version.files.find((f: any) => f.primary) || version.files[0];
-
-      if (!file) {
-        alert("Arquivo não encontrado");
-        setInstalling(null);
-        return;
-      }
-
-      if (tipoProjeto === "mod") {
-        await invoke("install_mod", {
-          instanceId,
-          modInfo: {
-            id: item.id,
-            name: item.title,
-            description: item.description,
-            author: item.author,
-            version: version.version_number,
-            download_url: file.url,
-            file_name: file.filename,
-            platform: "modrinth",
-            dependencies: [],
-          },
-        });
-      } else {
-        await invoke("install_project_file", {
-          instanceId,
-          projectType: tipoProjeto,
-          downloadUrl: file.url,
-          fileName: file.filename,
-        });
-      }
-
       const cacheConteudo = lerCacheConteudoInstalado();
       const tipoProjetoCache = tipoProjeto as TipoProjetoCache;
-      const nomeArquivoCache = file.filename || item.file_name || item.slug || item.id;
+      const nomeArquivoCache = principal.fileName || item.file_name || item.slug || item.id;
       definirRegistroCacheConteudo(cacheConteudo, instanceId, tipoProjetoCache, nomeArquivoCache, {
         name: item.title,
         author: item.author,
         icon: item.icon_url,
         projectId: item.id,
-        source: "modrinth",
+        source: plataforma,
         projectType: tipoProjetoCache,
-        latestVersion: version.version_number,
+        latestVersion: principal.versaoNome || item.latest_version,
         updateAvailable: false,
         updateFileName: undefined,
         updateDownloadUrl: undefined,
         atualizacaoVerificadaEm: Date.now(),
       });
+      if (principal.substitui && principal.substitui !== nomeArquivoCache) {
+        removerRegistroCacheConteudo(cacheConteudo, instanceId, tipoProjetoCache, principal.substitui);
+      }
       salvarCacheConteudoInstalado(cacheConteudo);
 
       await loadInstalledContent(activeFilter);
@@ -1208,85 +1158,24 @@ export default function InstanceManager({
     );
 
     try {
-      if (item.source === "curseforge") {
-        if (tipoProjeto === "mod") {
-          await invoke("install_mod", {
-            instanceId,
-            modInfo: {
-              id: item.projectId,
-              name: item.name,
-              description: "",
-              author: item.author,
-              version: item.latestVersion || "latest",
-              download_url: "",
-              file_name: "",
-              platform: "curseforge",
-              dependencies: [],
-            },
-          });
-        } else {
-          await invoke("install_curseforge_project_file", {
-            instanceId,
-            projectType: tipoProjeto,
-            projectId: item.projectId,
-          });
-        }
-      } else {
-        let downloadUrl = item.updateDownloadUrl;
-        let fileName = item.updateFileName;
-        let latestVersion = item.latestVersion;
-
-        if (!downloadUrl || !fileName) {
-          const params = new URLSearchParams();
-          params.set("game_versions", JSON.stringify([instanceDetails?.version || ""]));
-          if (tipoProjeto === "mod") {
-            const loaderAtual = (instanceDetails?.loaderType || "").toLowerCase();
-            if (["fabric", "forge", "quilt", "neoforge"].includes(loaderAtual)) {
-              params.set("loaders", JSON.stringify([loaderAtual]));
-            }
-          }
-
-          const resposta = await fetch(
-            `https://api.modrinth.com/v2/project/${item.projectId}/version?${params.toString()}`
-          );
-          const versoes = await resposta.json();
-          const versaoAlvo = Array.isArray(versoes) ? versoes[0] : null;
-          const arquivoAlvo =
-            versaoAlvo?.files?.find((f: any) => f?.primary) || versaoAlvo?.files?.[0];
-          if (!arquivoAlvo?.url || !arquivoAlvo?.filename) {
-            throw new Error("Nenhuma atualização compatível encontrada.");
-          }
-          downloadUrl = String(arquivoAlvo.url);
-          fileName = String(arquivoAlvo.filename);
-          latestVersion = String(versaoAlvo?.version_number || latestVersion || "");
-        }
-
-        if (tipoProjeto === "mod") {
-          await invoke("install_mod", {
-            instanceId,
-            modInfo: {
-              id: item.projectId,
-              name: item.name,
-              description: "",
-              author: item.author,
-              version: latestVersion || item.version || "latest",
-              download_url: downloadUrl,
-              file_name: fileName,
-              platform: "modrinth",
-              dependencies: [],
-            },
-          });
-        } else {
-          await invoke("install_project_file", {
-            instanceId,
-            projectType: tipoProjeto,
-            downloadUrl,
-            fileName,
-          });
-        }
+      const resultado = await instalarConteudoComDependencias({
+        instanceId,
+        platform: item.source === "curseforge" ? "curseforge" : "modrinth",
+        projectId: item.projectId,
+        projectType: tipoProjeto,
+      });
+      if (!resultado) {
+        atualizarListaPorTipo(tipoProjeto, (prev) =>
+          prev.map((m) => (m.fileName === item.fileName ? { ...m, updating: false } : m))
+        );
+        return;
       }
 
-      if (item.updateFileName && item.updateFileName !== item.fileName) {
+      // O plano já remove a versão anterior quando o lockfile conhece o projeto;
+      // conteúdo antigo sem registro é removido aqui.
+      const principal = resultado.principal;
+      const arquivoAtual = item.fileName.replace(/\.disabled$/, "");
+      if (principal && !principal.substitui && principal.fileName !== arquivoAtual) {
         await removerConteudoInstalado(item, filtro);
       }
 
@@ -1381,6 +1270,7 @@ export default function InstanceManager({
       await invoke("update_instance_settings", {
         instanceId: idAtual,
         javaArgs: editJavaArgs,
+        javaPath: editJavaPath,
         mcArgs: instanceDetails.mcArgs,
         width: Number.isFinite(largura) ? largura : undefined,
         height: Number.isFinite(altura) ? altura : undefined,
@@ -1712,7 +1602,15 @@ export default function InstanceManager({
                 {/* Settings Button */}
                 <div className="relative">
                   <button 
-                    onClick={() => { setShowSettings(!showSettings); setShowMoreMenu(false); }}
+                    onClick={() => {
+                      setShowSettings(!showSettings);
+                      setShowMoreMenu(false);
+                      if (!showSettings && javasDetectados.length === 0) {
+                        invoke<{ path: string; version: string }[]>("detect_java_installations")
+                          .then(setJavasDetectados)
+                          .catch((erro) => console.warn("Falha ao detectar Java:", erro));
+                      }
+                    }}
                     className="p-2.5 rounded-xl bg-white/5 hover:bg-white/10 transition-colors"
                   >
                     <Settings size={18} className="text-white/60" />
@@ -1736,6 +1634,26 @@ export default function InstanceManager({
                           />
                         </div>
                         
+                        {/* Java da instância */}
+                        <div>
+                          <label className="text-xs text-white/40 block mb-1">Java</label>
+                          <input
+                            type="text"
+                            list="javas-instancia"
+                            value={editJavaPath}
+                            onChange={(e) => setEditJavaPath(e.target.value)}
+                            placeholder="Automático (configuração global)"
+                            className="w-full bg-white/5 border border-white/10 rounded-lg px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-emerald-500"
+                          />
+                          <datalist id="javas-instancia">
+                            {javasDetectados.map((java) => (
+                              <option key={java.path} value={java.path}>
+                                Java {java.version}
+                              </option>
+                            ))}
+                          </datalist>
+                        </div>
+
                         {/* Resolução */}
                         <div>
                           <label className="text-xs text-white/40 block mb-1">Resolução</label>
@@ -2400,6 +2318,14 @@ export default function InstanceManager({
                     <pre className="text-xs text-white/70 font-mono whitespace-pre-wrap">
                       {logContent || "Carregando..."}
                     </pre>
+                    {logProximoOffset !== null && (
+                      <button
+                        onClick={carregarMaisLog}
+                        className="mt-3 text-xs text-white/40 hover:text-white px-2 py-1 rounded hover:bg-white/10"
+                      >
+                        Carregar mais
+                      </button>
+                    )}
                   </div>
                 </>
               ) : (
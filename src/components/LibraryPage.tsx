@@ -786,7 +786,17 @@ export default function LibraryPage({
         ? "Modrinth"
         : launcher === "curseforge"
           ? "CurseForge"
-          : launcher;
+          : launcher === "multimc"
+            ? "MultiMC"
+            : launcher === "atlauncher"
+              ? "ATLauncher"
+              : launcher === "gdlauncher"
+                ? "GDLauncher"
+                : launcher === "technic"
+                  ? "Technic"
+                  : launcher === "vanilla"
+                    ? "Minecraft Launcher"
+                    : launcher;
 
   return (
     <div className="space-y-4">
@@ -13,6 +13,9 @@ import {
   Loader2,
 } from "../iconesPixelados";
 import { cn } from "../lib/utils";
+import { instalarConteudoComDependencias } from "../lib/instalacaoConteudo";
+import { lerModpack, normalizarLoaderModpack, type LoaderModpack } from "../lib/instalacaoModpack";
+import { useEscolhaArquivosOpcionais } from "./SeletorArquivosOpcionais";
 import type { Instance } from "../hooks/useLauncher";
 import {
   addCreatingInstance,
@@ -75,7 +78,13 @@ interface CompatibilidadeInstancia {
 }
 
 interface LoaderVersionsResponse {
-  versions: Array<{ version: string; stable?: boolean }>;
+  versions: VersaoLoader[];
+}
+
+interface VersaoLoader {
+  version: string;
+  stable?: boolean;
+  marcadores?: Array<"recomendada" | "maisRecente" | "beta">;
 }
 
 interface DadosInstalacaoModpackCurseforge {
@@ -117,7 +126,7 @@ interface ProjetoDetalhePaginaProps {
   rotuloAcao?: string;
 }
 
-const ORDEM_LOADER_MODPACK = ["fabric", "forge", "neoforge"] as const;
+const ORDEM_LOADER_MODPACK = ["fabric", "forge", "neoforge", "quilt"] as const;
 
 function urlHttpsSegura(valor?: string): string | undefined {
   if (!valor) return undefined;
@@ -295,10 +304,12 @@ function gerarIdInstancia(nomeInstancia: string): string {
 
 function escolherVersaoLoaderIdeal(
   loader: string,
-  versoes: Array<{ version: string; stable?: boolean }>,
+  versoes: VersaoLoader[],
   versaoMinecraft: string
 ): string | null {
   if (versoes.length === 0) return null;
+  const recomendada = versoes.find((item) => item.marcadores?.includes("recomendada"));
+  if (recomendada) return recomendada.version;
   if (loader === "forge" || loader === "neoforge") {
     const compativel = versoes.find(
       (item) =>
@@ -309,16 +320,6 @@ function escolherVersaoLoaderIdeal(
   return versoes.find((item) => item.stable !== false)?.version || versoes[0].version;
 }
 
-function normalizarLoaderModpack(
-  loader: string | null | undefined
-): "vanilla" | "fabric" | "forge" | "neoforge" {
-  const valor = String(loader || "")
-    .toLowerCase()
-    .trim();
-  if (valor === "fabric" || valor === "forge" || valor === "neoforge") return valor;
-  return "vanilla";
-}
-
 function montarUrlProjeto(projeto: ProjetoConteudo): string {
   if (projeto.source === "modrinth") {
     return `https://modrinth.com/${projeto.project_type}/${projeto.slug}`;
@@ -459,6 +460,7 @@ export default function ProjetoDetalheModal({
   const [instalando, setInstalando] = useState(false);
   const [sucesso, setSucesso] = useState(false);
   const [erro, setErro] = useState<string | null>(null);
+  const { escolherArquivosOpcionais, dialogo: dialogoOpcionais } = useEscolhaArquivosOpcionais();
 
   useEffect(() => {
     let cancelado = false;
@@ -630,7 +632,6 @@ export default function ProjetoDetalheModal({
         const arquivo = escolherArquivoIdeal(versao, "modpack");
         if (!arquivo) return false;
         const loaders = (versao.loaders || []).map((item) => item.toLowerCase());
-        if (loaders.includes("quilt")) return false;
         if (loaders.length === 0) return true;
         return ORDEM_LOADER_MODPACK.some((loader) => loaders.includes(loader));
       }) || null
@@ -653,7 +654,7 @@ export default function ProjetoDetalheModal({
       try {
         let nomeBaseInstancia = projetoExibicao.title;
         let versaoMinecraft = "";
-        let loaderSelecionado: "vanilla" | "fabric" | "forge" | "neoforge" = "vanilla";
+        let loaderSelecionado: LoaderModpack = "vanilla";
         let arquivoModpackUrl = "";
         let arquivoModpackNome = "";
         let arquivoModpackHash: string | null = null;
@@ -699,6 +700,16 @@ export default function ProjetoDetalheModal({
           versaoId = dadosCurseforge.versaoModpack || "";
         }
 
+        // O índice do pacote define a versão do Minecraft e o loader exatos.
+        const resumoModpack = await lerModpack(arquivoModpackUrl);
+        const arquivosOpcionais = await escolherArquivosOpcionais(resumoModpack.arquivosOpcionais);
+        if (arquivosOpcionais === null) {
+          setInstalando(false);
+          return;
+        }
+        if (resumoModpack.versaoMinecraft) versaoMinecraft = resumoModpack.versaoMinecraft;
+        if (resumoModpack.loader) loaderSelecionado = normalizarLoaderModpack(resumoModpack.loader);
+
         const nomeInstancia = gerarNomeInstanciaDisponivel(
           nomeBaseInstancia,
           instancias.map((item) => item.name)
@@ -718,14 +729,15 @@ export default function ProjetoDetalheModal({
         };
         addCreatingInstance(criandoInstancia);
 
-        let loaderVersion: string | undefined;
-        if (loaderSelecionado !== "vanilla") {
+        let loaderVersion = resumoModpack.versaoLoader || undefined;
+        if (loaderSelecionado !== "vanilla" && !loaderVersion) {
           updateCreatingInstance(idOverlayCriacao, {
             progress: 8,
             message: "Buscando versão ideal do loader...",
           });
           const respostaLoader = await invoke<LoaderVersionsResponse>("get_loader_versions", {
             loaderType: loaderSelecionado,
+            minecraftVersion: versaoMinecraft,
           });
           loaderVersion =
             escolherVersaoLoaderIdeal(
@@ -771,6 +783,8 @@ export default function ProjetoDetalheModal({
           instanceId: idInstancia,
           downloadUrl: arquivoModpackUrl,
           fileName: arquivoModpackNome,
+          arquivosOpcionais,
+          novaInstancia: true,
         });
         completeCreatingInstance(idOverlayCriacao);
         setInstalando(false);
@@ -795,29 +809,13 @@ export default function ProjetoDetalheModal({
       setErro(null);
       setInstalando(true);
       try {
-        if (projeto.project_type === "mod") {
-          await invoke("install_mod", {
-            instanceId: instanciaAlvo.id,
-            modInfo: {
-              id: projeto.id,
-              name: projetoExibicao.title,
-              description: projetoExibicao.description,
-              author: projetoExibicao.author,
-              version: "latest",
-              download_url: "",
-              file_name: "",
-              platform: "curseforge",
-              dependencies: [],
-            },
-          });
-        } else {
-          await invoke("install_curseforge_project_file", {
-            instanceId: instanciaAlvo.id,
-            projectType: projeto.project_type,
-            projectId: projeto.id,
-          });
-        }
-        setSucesso(true);
+        const resultado = await instalarConteudoComDependencias({
+          instanceId: instanciaAlvo.id,
+          platform: "curseforge",
+          projectId: projeto.id,
+          projectType: projeto.project_type,
+        });
+        if (resultado) setSucesso(true);
       } catch (e) {
         setErro(extrairMensagemErro(e, "Erro ao instalar projeto CurseForge."));
       } finally {
@@ -834,30 +832,14 @@ export default function ProjetoDetalheModal({
     setErro(null);
     setInstalando(true);
     try {
-      if (projeto.project_type === "mod") {
-        await invoke("install_mod", {
-          instanceId: compatibilidadeSelecionada.instancia.id,
-          modInfo: {
-            id: projeto.id,
-            name: projetoExibicao.title,
-            description: projetoExibicao.description,
-            author: projetoExibicao.author,
-            version: compatibilidadeSelecionada.versaoIdeal.version_number,
-            download_url: compatibilidadeSelecionada.arquivoIdeal.url,
-            file_name: compatibilidadeSelecionada.arquivoIdeal.filename,
-            platform: "modrinth",
-            dependencies: [],
-          },
-        });
-      } else {
-        await invoke("install_project_file", {
-          instanceId: compatibilidadeSelecionada.instancia.id,
-          projectType: projeto.project_type,
-          downloadUrl: compatibilidadeSelecionada.arquivoIdeal.url,
-          fileName: compatibilidadeSelecionada.arquivoIdeal.filename,
-        });
-      }
-      setSucesso(true);
+      const resultado = await instalarConteudoComDependencias({
+        instanceId: compatibilidadeSelecionada.instancia.id,
+        platform: "modrinth",
+        projectId: projeto.id,
+        projectType: projeto.project_type,
+        versionId: compatibilidadeSelecionada.versaoIdeal.id,
+      });
+      if (resultado) setSucesso(true);
       setInstalando(false);
     } catch (e) {
       setErro(extrairMensagemErro(e, "Erro ao instalar projeto."));
@@ -1161,6 +1143,7 @@ export default function ProjetoDetalheModal({
           </aside>
         </div>
       </section>
+      {dialogoOpcionais}
     </div>
   );
 }
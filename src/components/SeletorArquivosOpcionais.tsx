@@ -0,0 +1,115 @@
+import { useCallback, useRef, useState } from "react";
+import { motion } from "framer-motion";
+import type { ArquivoOpcionalModpack } from "../lib/instalacaoModpack";
+
+type ResolverEscolha = (escolhidos: string[] | null) => void;
+
+function formatarTamanho(bytes?: number | null): string {
+  if (!bytes) return "";
+  if (bytes >= 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${Math.max(1, Math.round(bytes / 1024))} KB`;
+}
+
+/**
+ * Pergunta quais arquivos opcionais do modpack instalar. `escolherArquivosOpcionais`
+ * resolve com os caminhos marcados, ou `null` se o usuário cancelar; `dialogo`
+ * precisa ser renderizado pelo componente que usa o hook.
+ */
+export function useEscolhaArquivosOpcionais() {
+  const [arquivos, setArquivos] = useState<ArquivoOpcionalModpack[] | null>(null);
+  const [marcados, setMarcados] = useState<Set<string>>(new Set());
+  const resolverRef = useRef<ResolverEscolha | null>(null);
+
+  const escolherArquivosOpcionais = useCallback(
+    (opcionais: ArquivoOpcionalModpack[]): Promise<string[] | null> => {
+      if (opcionais.length === 0) return Promise.resolve([]);
+      return new Promise((resolver) => {
+        resolverRef.current = resolver;
+        setMarcados(new Set(opcionais.map((arquivo) => arquivo.caminho)));
+        setArquivos(opcionais);
+      });
+    },
+    []
+  );
+
+  const encerrar = (escolhidos: string[] | null) => {
+    resolverRef.current?.(escolhidos);
+    resolverRef.current = null;
+    setArquivos(null);
+  };
+
+  const alternar = (caminho: string) => {
+    setMarcados((anterior) => {
+      const proximo = new Set(anterior);
+      if (proximo.has(caminho)) {
+        proximo.delete(caminho);
+      } else {
+        proximo.add(caminho);
+      }
+      return proximo;
+    });
+  };
+
+  const dialogo = arquivos ? (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      className="fixed inset-0 z-[90] flex items-center justify-center bg-black/70 p-4"
+      onClick={() => encerrar(null)}
+    >
+      <div
+        role="dialog"
+        aria-modal="true"
+        aria-labelledby="titulo-opcionais-modpack"
+        className="w-full max-w-md border border-white/12 bg-[#151516] shadow-2xl"
+        onClick={(evento) => evento.stopPropagation()}
+      >
+        <div className="border-b border-white/8 px-4 py-3">
+          <p
+            id="titulo-opcionais-modpack"
+            className="text-xs font-black uppercase tracking-wide text-white/85"
+          >
+            Arquivos opcionais
+          </p>
+          <p className="mt-1 text-[11px] text-white/45">
+            Este modpack traz conteúdo opcional. Desmarque o que não quiser instalar.
+          </p>
+        </div>
+        <div className="max-h-72 space-y-1 overflow-y-auto px-4 py-3">
+          {arquivos.map((arquivo) => (
+            <label
+              key={arquivo.caminho}
+              className="flex cursor-pointer items-center gap-2 px-1 py-1 text-xs text-white/75 hover:bg-white/5"
+            >
+              <input
+                type="checkbox"
+                checked={marcados.has(arquivo.caminho)}
+                onChange={() => alternar(arquivo.caminho)}
+              />
+              <span className="min-w-0 flex-1 truncate" title={arquivo.caminho}>
+                {arquivo.nome}
+              </span>
+              <span className="text-[10px] text-white/35">{formatarTamanho(arquivo.tamanho)}</span>
+            </label>
+          ))}
+        </div>
+        <div className="flex justify-end gap-2 border-t border-white/8 px-4 py-3">
+          <button
+            onClick={() => encerrar(null)}
+            className="px-3 py-2 text-[10px] font-bold uppercase tracking-wide text-white/45 hover:text-white/75"
+          >
+            Cancelar
+          </button>
+          <button
+            onClick={() => encerrar(Array.from(marcados))}
+            className="border border-emerald-300 bg-emerald-500 px-3 py-2 text-[10px] font-bold uppercase tracking-wide text-black hover:bg-emerald-400"
+          >
+            Instalar
+          </button>
+        </div>
+      </div>
+    </motion.div>
+  ) : null;
+
+  return { escolherArquivosOpcionais, dialogo };
+}
@@ -13,6 +13,8 @@ import {
   removeCreatingInstance,
 } from '../stores/creatingInstances';
 import { cn } from '../lib/utils';
+import { lerModpack, normalizarLoaderModpack } from '../lib/instalacaoModpack';
+import { useEscolhaArquivosOpcionais } from './SeletorArquivosOpcionais';
 import { EsqueletoSocial } from './EsqueletoCarregamento';
 import { ListaAmigosAgrupada } from './social/ListaAmigosAgrupada';
 import { PainelChatSocial } from './social/PainelChatSocial';
@@ -204,7 +206,13 @@ interface VersaoModrinth {
 }
 
 interface LoaderVersionsResponse {
-  versions: Array<{ version: string; stable?: boolean }>;
+  versions: VersaoLoader[];
+}
+
+interface VersaoLoader {
+  version: string;
+  stable?: boolean;
+  marcadores?: Array<'recomendada' | 'maisRecente' | 'beta'>;
 }
 
 interface InstanciaResumo {
@@ -331,10 +339,12 @@ function escolherVersaoMinecraftIdeal(gameVersions: string[]): string | null {
 
 function escolherVersaoLoaderIdeal(
   loader: string,
-  versoes: Array<{ version: string; stable?: boolean }>,
+  versoes: VersaoLoader[],
   versaoMinecraft: string
 ): string | null {
   if (versoes.length === 0) return null;
+  const recomendada = versoes.find((item) => item.marcadores?.includes('recomendada'));
+  if (recomendada) return recomendada.version;
   if (loader === 'forge' || loader === 'neoforge') {
     const compativel = versoes.find(
       (item) =>
@@ -409,6 +419,7 @@ export default function SocialSidebar({
     atividadeAtual: null,
   });
   const [mensagemSync, setMensagemSync] = useState<string | null>(null);
+  const { escolherArquivosOpcionais, dialogo: dialogoOpcionais } = useEscolhaArquivosOpcionais();
   const [processandoAtividade, setProcessandoAtividade] = useState(false);
 
   const sessaoRef = useRef<SessaoSocial | null>(null);
@@ -1536,16 +1547,25 @@ export default function SocialSidebar({
         throw new Error('Arquivo da versao exata nao encontrado.');
       }
 
-      const versaoMinecraft = atividade.versaoMinecraft || escolherVersaoMinecraftIdeal(versao.game_versions || []);
+      // Minecraft e loader saem do índice do pacote; a atividade é só um fallback.
+      const resumoModpack = await lerModpack(arquivo.url);
+      const arquivosOpcionais = await escolherArquivosOpcionais(resumoModpack.arquivosOpcionais);
+      if (arquivosOpcionais === null) {
+        setMensagemSync(null);
+        return;
+      }
+
+      const versaoMinecraft =
+        resumoModpack.versaoMinecraft ||
+        atividade.versaoMinecraft ||
+        escolherVersaoMinecraftIdeal(versao.game_versions || []);
       if (!versaoMinecraft) {
         throw new Error('Nao foi possivel determinar versao Minecraft exata.');
       }
 
-      const loaderCandidato = (atividade.loader || versao.loaders?.[0] || 'vanilla').toLowerCase();
-      const loaderNormalizado =
-        loaderCandidato === 'fabric' || loaderCandidato === 'forge' || loaderCandidato === 'neoforge'
-          ? loaderCandidato
-          : 'vanilla';
+      const loaderNormalizado = normalizarLoaderModpack(
+        resumoModpack.loader || atividade.loader || versao.loaders?.[0]
+      );
 
       const instancias = await invoke<InstanciaResumo[]>('get_instances');
       const nomeBase = atividade.modpackNome || atividade.instanciaNome || 'Instancia social';
@@ -1555,10 +1575,11 @@ export default function SocialSidebar({
       );
       const idInstancia = gerarIdInstancia(nomeInstancia);
 
-      let loaderVersion: string | undefined;
-      if (loaderNormalizado !== 'vanilla') {
+      let loaderVersion = resumoModpack.versaoLoader || undefined;
+      if (loaderNormalizado !== 'vanilla' && !loaderVersion) {
         const respostaLoader = await invoke<LoaderVersionsResponse>('get_loader_versions', {
           loaderType: loaderNormalizado,
+          minecraftVersion: versaoMinecraft,
         });
         loaderVersion =
           escolherVersaoLoaderIdeal(loaderNormalizado, respostaLoader.versions || [], versaoMinecraft) || undefined;
@@ -1596,6 +1617,8 @@ export default function SocialSidebar({
         instanceId: idInstancia,
         downloadUrl: arquivo.url,
         fileName: arquivo.filename || `${idInstancia}.mrpack`,
+        arquivosOpcionais,
+        novaInstancia: true,
       });
 
       setMensagemSync('Modpack exato instalado com sucesso.');
@@ -1751,6 +1774,7 @@ export default function SocialSidebar({
         formatarTempoRelativo={tempoRelativo}
         rotuloStatus={rotuloStatus}
       />
+      {dialogoOpcionais}
     </aside>
   );
 }
@@ -0,0 +1,119 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type PlataformaConteudo = "modrinth" | "curseforge";
+
+export interface ItemPlanoInstalacao {
+    plataforma: PlataformaConteudo;
+    projectId: string;
+    nome: string;
+    versaoId: string;
+    versaoNome: string;
+    fileName: string;
+    downloadUrl: string;
+    sha1?: string | null;
+    tamanho?: number | null;
+    tipoConteudo: string;
+    motivo: "solicitado" | "dependencia";
+    requeridoPor?: string | null;
+    substitui?: string | null;
+}
+
+export interface PlanoInstalacao {
+    instanceId: string;
+    versaoMinecraft: string;
+    loader?: string | null;
+    itens: ItemPlanoInstalacao[];
+    jaInstalados: { plataforma: PlataformaConteudo; projectId: string; nome: string; arquivo: string }[];
+    incompatibilidades: {
+        plataforma: PlataformaConteudo;
+        projectId: string;
+        nome: string;
+        declaradoPor: string;
+        arquivoInstalado?: string | null;
+    }[];
+    naoResolvidas: string[];
+    bytesTotais: number;
+}
+
+export interface PedidoInstalacaoConteudo {
+    instanceId: string;
+    platform: PlataformaConteudo;
+    projectId: string;
+    projectType?: string;
+    versionId?: string | null;
+}
+
+export interface ResultadoInstalacaoConteudo {
+    plano: PlanoInstalacao;
+    arquivos: string[];
+    /** Item pedido pelo usuário, quando precisou ser baixado. */
+    principal: ItemPlanoInstalacao | null;
+}
+
+function formatarTamanho(bytes: number): string {
+    if (bytes >= 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+    if (bytes >= 1024) return `${Math.round(bytes / 1024)} KB`;
+    return `${bytes} B`;
+}
+
+function resumoPlano(plano: PlanoInstalacao): string {
+    const linhas: string[] = [];
+    linhas.push(`Serão baixados ${plano.itens.length} arquivo(s) (${formatarTamanho(plano.bytesTotais)}):`);
+    for (const item of plano.itens) {
+        const origem = item.requeridoPor ? ` — requerido por ${item.requeridoPor}` : "";
+        linhas.push(`• ${item.nome} ${item.versaoNome}${origem}`);
+    }
+    if (plano.jaInstalados.length > 0) {
+        linhas.push("", "Já instalados:");
+        for (const item of plano.jaInstalados) linhas.push(`• ${item.nome} (${item.arquivo})`);
+    }
+    if (plano.naoResolvidas.length > 0) {
+        linhas.push("", "Dependências não resolvidas:");
+        for (const motivo of plano.naoResolvidas) linhas.push(`• ${motivo}`);
+    }
+    if (plano.incompatibilidades.length > 0) {
+        linhas.push("", "Incompatibilidades:");
+        for (const conflito of plano.incompatibilidades) {
+            const instalado = conflito.arquivoInstalado ? ` (instalado: ${conflito.arquivoInstalado})` : "";
+            linhas.push(`• ${conflito.nome} é incompatível com ${conflito.declaradoPor}${instalado}`);
+        }
+    }
+    linhas.push("", "Deseja continuar?");
+    return linhas.join("\n");
+}
+
+/**
+ * Resolve as dependências do projeto, pede confirmação quando o plano vai além do
+ * próprio arquivo e instala tudo. Retorna `null` se o usuário cancelar.
+ */
+export async function instalarConteudoComDependencias(
+    pedido: PedidoInstalacaoConteudo,
+): Promise<ResultadoInstalacaoConteudo | null> {
+    const plano = await invoke<PlanoInstalacao>("resolver_plano_instalacao", {
+        instanceId: pedido.instanceId,
+        platform: pedido.platform,
+        projectId: pedido.projectId,
+        projectType: pedido.projectType ?? null,
+        versionId: pedido.versionId ?? null,
+    });
+
+    if (plano.itens.length === 0) {
+        return { plano, arquivos: [], principal: null };
+    }
+
+    const precisaConfirmar =
+        plano.itens.length > 1 ||
+        plano.incompatibilidades.length > 0 ||
+        plano.naoResolvidas.length > 0;
+    if (precisaConfirmar && !confirm(resumoPlano(plano))) {
+        return null;
+    }
+
+    const arquivos = await invoke<string[]>("executar_plano_instalacao", {
+        instanceId: pedido.instanceId,
+        plano,
+        ignorarIncompatibilidades: plano.incompatibilidades.length > 0,
+    });
+    const principal = plano.itens.find((item) => item.motivo === "solicitado") ?? null;
+    return { plano, arquivos, principal };
+}
@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ArquivoOpcionalModpack {
+    caminho: string;
+    nome: string;
+    tamanho?: number | null;
+}
+
+/** Retorno de `ler_modpack`: dependências do índice e arquivos opcionais. */
+export interface ResumoModpack {
+    nome?: string | null;
+    versao?: string | null;
+    versaoMinecraft?: string | null;
+    loader?: string | null;
+    versaoLoader?: string | null;
+    totalArquivos: number;
+    arquivosOpcionais: ArquivoOpcionalModpack[];
+}
+
+export type LoaderModpack = "vanilla" | "fabric" | "forge" | "neoforge" | "quilt";
+
+export function normalizarLoaderModpack(loader: string | null | undefined): LoaderModpack {
+    const valor = String(loader || "").toLowerCase().trim();
+    if (valor === "fabric" || valor === "forge" || valor === "neoforge" || valor === "quilt") {
+        return valor;
+    }
+    return "vanilla";
+}
+
+/** Baixa o pacote (reaproveitado depois por `install_modpack_files`) e lê o índice. */
+export function lerModpack(downloadUrl: string): Promise<ResumoModpack> {
+    return invoke<ResumoModpack>("ler_modpack", { downloadUrl });
+}
@@ -1,4 +1,4 @@
-import React, { useState, useEffect } from 'react'
+import React, { useState, useEffect, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import type { GameInstance, ContextMenuTypeOrNull, Version, LogEntry } from '../../types'
 
@@ -11,6 +11,49 @@ interface LogsProps {
   installed: boolean
 }
 
+interface LinhaLog {
+  offset: number
+  nivel?: string | null
+  texto: string
+}
+
+interface PaginaLog {
+  offsetInicial: number
+  proximoOffset?: number | null
+  tamanhoArquivo: number
+  linhasLidas: number
+  linhas: LinhaLog[]
+}
+
+const niveisPorFiltro: Record<string, string[]> = {
+  error: ['ERROR', 'FATAL'],
+  warn: ['WARN'],
+  info: ['INFO'],
+  debug: ['DEBUG'],
+  trace: ['TRACE']
+}
+
+// O backend compara o nível exato da linha; linhas sem nível ("Comment") só
+// aparecem quando nenhum filtro de nível é enviado.
+const niveisSelecionados = (filtros: Record<string, boolean>): string[] | null => {
+  if (Object.values(filtros).every(Boolean)) return null
+  return Object.entries(niveisPorFiltro)
+    .filter(([chave]) => filtros[chave])
+    .flatMap(([, niveis]) => niveis)
+}
+
+const escaparRegex = (texto: string) => texto.replace(/[.*+?^${}()|[\]\\]/g, '\\$&')
+
+const paraEntradas = (pagina: PaginaLog): LogEntry[] =>
+  pagina.linhas.map((linha) => ({
+    id: linha.offset,
+    text: linha.texto,
+    level: linha.nivel ? linha.nivel.toLowerCase() : 'comment'
+  }))
+
+// Posição a partir da qual a próxima leitura continua.
+const fimDaPagina = (pagina: PaginaLog) => pagina.proximoOffset ?? pagina.tamanhoArquivo
+
 const Logs: React.FC<LogsProps> = ({ instance }) => {
   const [logs, setLogs] = useState<any[]>([])
   const [selectedLogIndex, setSelectedLogIndex] = useState(0)
@@ -23,8 +66,13 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
     debug: true,
     trace: true
   })
+  const [linhas, setLinhas] = useState<LogEntry[]>([])
+  const [proximoOffset, setProximoOffset] = useState<number | null>(null)
+  const [carregandoPagina, setCarregandoPagina] = useState(false)
+  const [erroFiltro, setErroFiltro] = useState<string | null>(null)
   const [copied, setCopied] = useState(false)
   const [loading, setLoading] = useState(true)
+  const fimLido = useRef(0)
 
   useEffect(() => {
     const loadLogs = async () => {
@@ -34,24 +82,12 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
           instanceId: instance.id
         })
 
-        if (logFiles.length > 0) {
-          // Carregar conteúdo do primeiro arquivo de log
-          const logContent = await invoke('get_log_content', {
-            instanceId: instance.id,
-            filePath: logFiles[0].path
-          })
-
-          const logsData = logFiles.map((file: any, index: number) => ({
-            filename: file.filename,
-            path: file.path,
-            stdout: index === 0 ? logContent : '',
-            live: file.filename === 'latest.log'
-          }))
-
-          setLogs(logsData)
-        } else {
-          setLogs([])
-        }
+        setLogs(logFiles.map((file: any) => ({
+          filename: file.filename,
+          path: file.path,
+          live: file.filename === 'latest.log'
+        })))
+        setSelectedLogIndex(0)
       } catch (error) {
         console.error('Erro ao carregar logs:', error)
         setLogs([])
@@ -64,6 +100,50 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
   }, [instance.id])
 
   const logSelecionado = logs[selectedLogIndex]
+  const niveis = niveisSelecionados(levelFilters)
+  const chaveNiveis = niveis ? niveis.join(',') : ''
+  const filtroRegex = searchFilter.trim() ? escaparRegex(searchFilter.trim()) : null
+
+  const lerPagina = (offset: number) =>
+    invoke<PaginaLog>('read_log_page', {
+      instanceId: instance.id,
+      filePath: logSelecionado.path,
+      offset,
+      niveis,
+      filtroRegex
+    })
+
+  // Recomeça do início sempre que o arquivo ou os filtros mudam.
+  useEffect(() => {
+    setLinhas([])
+    setProximoOffset(null)
+    fimLido.current = 0
+    if (!logSelecionado) return
+
+    let cancelado = false
+    const atraso = window.setTimeout(async () => {
+      setCarregandoPagina(true)
+      try {
+        const pagina = await lerPagina(0)
+        if (cancelado) return
+        setErroFiltro(null)
+        setLinhas(paraEntradas(pagina))
+        setProximoOffset(pagina.proximoOffset ?? null)
+        fimLido.current = fimDaPagina(pagina)
+      } catch (error) {
+        if (cancelado) return
+        console.error('Erro ao carregar conteúdo do log:', error)
+        setErroFiltro(String(error))
+      } finally {
+        if (!cancelado) setCarregandoPagina(false)
+      }
+    }, 250)
+
+    return () => {
+      cancelado = true
+      window.clearTimeout(atraso)
+    }
+  }, [instance.id, logSelecionado?.path, chaveNiveis, filtroRegex])
 
   useEffect(() => {
     if (!logSelecionado?.live) {
@@ -71,74 +151,59 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
     }
 
     const intervalo = window.setInterval(async () => {
+      // Enquanto houver páginas pendentes, quem avança é o "Carregar mais".
+      if (proximoOffset !== null) return
       try {
-        const conteudo = await invoke<string>('get_log_content', {
-          instanceId: instance.id,
-          filePath: logSelecionado.path
-        })
-        setLogs((anteriores) => {
-          const atualizado = [...anteriores]
-          if (!atualizado[selectedLogIndex]) return anteriores
-          atualizado[selectedLogIndex] = {
-            ...atualizado[selectedLogIndex],
-            stdout: conteudo
-          }
-          return atualizado
-        })
+        const pagina = await lerPagina(fimLido.current)
+        if (pagina.tamanhoArquivo < fimLido.current) {
+          // O jogo reiniciou e recriou o latest.log.
+          const inicio = await lerPagina(0)
+          setLinhas(paraEntradas(inicio))
+          setProximoOffset(inicio.proximoOffset ?? null)
+          fimLido.current = fimDaPagina(inicio)
+          return
+        }
+        if (pagina.linhas.length > 0) {
+          setLinhas((anteriores) => [...anteriores, ...paraEntradas(pagina)])
+        }
+        setProximoOffset(pagina.proximoOffset ?? null)
+        fimLido.current = fimDaPagina(pagina)
       } catch (erro) {
         console.error('Erro ao atualizar log ao vivo:', erro)
       }
     }, 2000)
 
     return () => window.clearInterval(intervalo)
-  }, [instance.id, selectedLogIndex, logSelecionado?.live, logSelecionado?.path])
-
-
-  const loadLogContent = async (index: number) => {
-    if (logs[index] && !logs[index].stdout) {
-      try {
-        const content = await invoke<string>('get_log_content', {
-          instanceId: instance.id,
-          filePath: logs[index].path
-        })
+  }, [instance.id, logSelecionado?.live, logSelecionado?.path, chaveNiveis, filtroRegex, proximoOffset])
 
-        setLogs(prev => {
-          const updated = [...prev]
-          updated[index].stdout = content
-          return updated
-        })
-      } catch (error) {
-        console.error('Erro ao carregar conteúdo do log:', error)
-      }
+  const carregarMais = async () => {
+    if (proximoOffset === null || carregandoPagina) return
+    setCarregandoPagina(true)
+    try {
+      const pagina = await lerPagina(proximoOffset)
+      setLinhas((anteriores) => [...anteriores, ...paraEntradas(pagina)])
+      setProximoOffset(pagina.proximoOffset ?? null)
+      fimLido.current = fimDaPagina(pagina)
+    } catch (error) {
+      console.error('Erro ao carregar conteúdo do log:', error)
+    } finally {
+      setCarregandoPagina(false)
     }
   }
 
-  const handleLogChange = async (index: number) => {
+  const handleLogChange = (index: number) => {
     setSelectedLogIndex(index)
-    await loadLogContent(index)
   }
 
   const levels = ['Comment', 'Error', 'Warn', 'Info', 'Debug', 'Trace']
 
-  const processedLogs: LogEntry[] = []
-  const linhasBrutas = (logs[selectedLogIndex]?.stdout || '')
-    .split('\n')
-    .map((texto: string, indice: number) => ({
-      id: indice,
-      text: texto,
-      level: 'info',
-    }))
-
-  const displayProcessedLogs: LogEntry[] = (processedLogs.length > 0 ? processedLogs : linhasBrutas).filter((log: LogEntry) => {
-    if (!log.level) return true
-    if (!levelFilters[log.level.toLowerCase()]) return false
-    if (searchFilter && !log.text.toLowerCase().includes(searchFilter.toLowerCase())) return false
-    return true
-  })
+  // Nível e busca já são aplicados pelo `read_log_page`.
+  const displayProcessedLogs: LogEntry[] = linhas
+  const textoCarregado = () => linhas.map((log) => log.text).join('\n')
 
   const copyLog = async () => {
     if (logs[selectedLogIndex]) {
-      await navigator.clipboard.writeText(logs[selectedLogIndex].stdout || '')
+      await navigator.clipboard.writeText(textoCarregado())
       setCopied(true)
       setTimeout(() => setCopied(false), 2000)
     }
@@ -146,7 +211,7 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
 
   const share = async () => {
     if (logs[selectedLogIndex]) {
-      const conteudo = logs[selectedLogIndex].stdout || ''
+      const conteudo = textoCarregado()
       try {
         if (typeof navigator.share === 'function') {
           await navigator.share({
@@ -180,16 +245,8 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
 
   const clearLiveLog = () => {
     if (!logs[selectedLogIndex]?.live) return
-
-    setLogs((anteriores) => {
-      const atualizado = [...anteriores]
-      if (!atualizado[selectedLogIndex]) return anteriores
-      atualizado[selectedLogIndex] = {
-        ...atualizado[selectedLogIndex],
-        stdout: ''
-      }
-      return atualizado
-    })
+    // Só limpa a tela; a leitura ao vivo continua de onde parou.
+    setLinhas([])
   }
 
   if (loading) {
@@ -348,7 +405,23 @@ const Logs: React.FC<LogsProps> = ({ instance }) => {
           ))
         ) : (
           <div style={{ textAlign: 'center', padding: '32px' }}>
-            Nenhum log encontrado ou carregando...
+            {erroFiltro || (carregandoPagina ? 'Carregando...' : 'Nenhum log encontrado.')}
+          </div>
+        )}
+        {proximoOffset !== null && (
+          <div style={{ textAlign: 'center', padding: '8px' }}>
+            <button
+              onClick={carregarMais}
+              disabled={carregandoPagina}
+              style={{
+                padding: '8px 16px',
+                border: '1px solid #ccc',
+                borderRadius: '4px',
+                cursor: 'pointer'
+              }}
+            >
+              {carregandoPagina ? 'Carregando...' : 'Carregar mais'}
+            </button>
           </div>
         )}
       </div>
@@ -1,6 +1,7 @@
 import React, { useState, useEffect } from 'react'
 import dayjs from 'dayjs'
 import { invoke } from '@tauri-apps/api/core'
+import { instalarConteudoComDependencias } from '../../lib/instalacaoConteudo'
 import type { GameInstance, ContextMenuTypeOrNull, Version, ProjectListEntry } from '../../types'
 
 interface ModsProps {
@@ -196,60 +197,13 @@ const Mods: React.FC<ModsProps> = ({ instance }) => {
 
   const installMod = async (mod: ProjectListEntry) => {
     try {
-      let downloadUrl = ''
-      let fileName = mod.file_name || ''
-
-      if (activePlatform === 'modrinth') {
-        const gameVersion = instance.version || instance.game_version
-        if (!gameVersion) {
-          alert('A instância não possui versão do Minecraft definida.')
-          return
-        }
-
-        const loaderAtual = (instance.loader_type || instance.loader || '').toLowerCase()
-        const loadersSuportados = ['fabric', 'forge', 'quilt', 'neoforge']
-        const params = new URLSearchParams()
-        params.set('game_versions', JSON.stringify([gameVersion]))
-        if (loadersSuportados.includes(loaderAtual)) {
-          params.set('loaders', JSON.stringify([loaderAtual]))
-        }
-
-        const versionsRes = await fetch(
-          `https://api.modrinth.com/v2/project/${mod.id}/version?${params.toString()}`
-        )
-        const versions = await versionsRes.json()
-
-        if (!Array.isArray(versions) || versions.length === 0) {
-          alert(`Nenhuma versão compatível com ${loaderAtual || 'loader atual'} ${gameVersion}`)
-          return
-        }
-
-        const version = versions[0]
-        const file = version.files?.find((f: any) => f.primary) || version.files?.[0]
-
-        if (!file?.url) {
-          alert('Arquivo compatível não encontrado no Modrinth.')
-          return
-        }
-
-        downloadUrl = file.url
-        fileName = file.filename || fileName
-      }
-
-      await invoke('install_mod', {
+      const resultado = await instalarConteudoComDependencias({
         instanceId: instance.id,
-        modInfo: {
-          id: mod.id,
-          name: mod.name,
-          description: '',
-          author: mod.author?.name || 'Desconhecido',
-          version: mod.version,
-          download_url: downloadUrl,
-          file_name: fileName,
-          platform: activePlatform === 'modrinth' ? 'modrinth' : 'curseforge',
-          dependencies: []
-        }
+        platform: activePlatform === 'modrinth' ? 'modrinth' : 'curseforge',
+        projectId: mod.id,
+        projectType: 'mod'
       })
+      if (!resultado) return
 
       await recarregarModsInstalados()
       setSearchResults([])